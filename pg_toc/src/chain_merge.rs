@@ -0,0 +1,154 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+
+use zip::ZipArchive;
+
+use super::*;
+use super::zip_dump::copy_entry;
+use super::zip_dump::list_entries;
+
+// "teSection" enum value in pg_dump
+const SECTION_POST_DATA: i64 = 4;
+
+// Data of the tables that are not included into an incremental or differential
+// backup is copied from the earlier backups, table is specified as (schema, table,
+// backup path). Incremental dumps have no "TABLE DATA" entries for such tables,
+// entries are added to the TOC with new dump IDs and depend on the "TABLE" entries
+// of the dump. Differential dumps keep the entries, only their data files are copied.
+// Tables that already have data in the dump are skipped, so merging into the same
+// directory twice is a no-op.
+pub fn merge_chain_data<F: FnMut(&str)>(dump_dir: &Path, tables: &[(String, String, PathBuf)], mut throttle: Throttle,
+                                        mut listener: F) -> Result<usize, io::Error> {
+    let toc_path = dump_dir.join("toc.dat");
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    let mut next_id = toc.entries.iter().map(|en| en.dump_id).max().unwrap_or(0) + 1;
+
+    let mut by_source: BTreeMap<&PathBuf, Vec<(&str, &str)>> = BTreeMap::new();
+    for (schema, table, source) in tables.iter() {
+        if let Some(en) = toc.find_entry("TABLE DATA", schema, table) {
+            if has_data_file(dump_dir, en) {
+                continue;
+            }
+        }
+        by_source.entry(source).or_default().push((schema.as_str(), table.as_str()));
+    }
+
+    let mut merged = Vec::new();
+    let mut overlaid = 0;
+    for (source, source_tables) in by_source.iter() {
+        if !source.exists() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                "Backup with table data of the dump not found: {}", source.to_string_lossy())));
+        }
+        let summary = DumpSummary::read_path(source)?;
+        let mut copies = Vec::new();
+        for (schema, table) in source_tables.iter() {
+            let data_en = summary.entries.iter().find(|en| {
+                en.desc_is("TABLE DATA") && en.namespace_is(schema) && en.tag_is(table)
+            });
+            let (data_en, data_path) = match data_en.and_then(|en| summary.data_files.get(&en.dump_id).map(|path| (en, path))) {
+                Some(found) => found,
+                None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                    "Table data not found in backup: {}, table: {}.{}", source.to_string_lossy(), schema, table)))
+            };
+            // "NNNN.dat.gz" on disk when compression is enabled
+            let compressed = data_path.to_string_lossy().ends_with(".gz");
+            let gz_name = |filename: &str| if compressed {
+                format!("{}.gz", filename)
+            } else {
+                filename.to_string()
+            };
+            if let Some(en) = toc.find_entry("TABLE DATA", schema, table) {
+                match &en.filename {
+                    Some(filename) if !filename.is_empty() => copies.push((data_path.clone(), gz_name(filename))),
+                    _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                        "Data file name not found in TOC, table: {}.{}", schema, table)))
+                }
+                overlaid += 1;
+                continue;
+            }
+            let table_id = match toc.find_entry("TABLE", schema, table) {
+                Some(en) => en.dump_id,
+                None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                    "Table not found in incremental backup: {}.{}", schema, table)))
+            };
+            let filename = format!("{}.dat", next_id);
+            let mut en = data_en.clone();
+            en.dump_id = next_id;
+            en.deps = vec!(table_id.to_string());
+            en.filename = Some(filename.clone());
+            merged.push(en);
+            copies.push((data_path.clone(), gz_name(&filename)));
+            next_id += 1;
+        }
+        copy_data_files(source, &copies, dump_dir, &mut throttle, &mut listener)?;
+    }
+
+    // placed before indexes and constraints, so these are created after the data is loaded
+    let count = merged.len();
+    if count > 0 {
+        let pos = toc.entries.iter()
+            .position(|en| SECTION_POST_DATA == en.section)
+            .unwrap_or(toc.entries.len());
+        toc.entries.splice(pos..pos, merged);
+        toc.write_file(&toc_path)?;
+    }
+    Ok(count + overlaid)
+}
+
+fn has_data_file(dump_dir: &Path, en: &TocEntry) -> bool {
+    match &en.filename {
+        Some(name) if !name.is_empty() => {
+            dump_dir.join(name).exists() || dump_dir.join(format!("{}.gz", name)).exists()
+        },
+        _ => false
+    }
+}
+
+// source paths point to files on disk for unzipped dumps and to entries for zip files
+fn copy_data_files<F: FnMut(&str)>(backup_path: &Path, copies: &[(PathBuf, String)], dest_dir: &Path,
+                                   throttle: &mut Throttle, listener: &mut F) -> Result<(), io::Error> {
+    if backup_path.is_dir() {
+        for (src, dest_name) in copies.iter() {
+            listener(&src.to_string_lossy());
+            let mut reader = File::open(src)?;
+            let mut writer = ThrottledWriter::new(File::create(dest_dir.join(dest_name))?, throttle);
+            io::copy(&mut reader, &mut writer)?;
+        }
+        return Ok(());
+    }
+    let file = File::open(backup_path)?;
+    let mut archive = ZipArchive::new(BufReader::new(file))?;
+    let entries = list_entries(&mut archive)?;
+    for (src, dest_name) in copies.iter() {
+        let idx = match entries.iter().find(|(_, path)| path == src) {
+            Some((idx, _)) => *idx,
+            None => return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                "Data file not found in zip file: {}, entry: {}", backup_path.to_string_lossy(), src.to_string_lossy())))
+        };
+        let mut zf = archive.by_index(idx)?;
+        listener(zf.name());
+        copy_entry(&mut zf, &dest_dir.join(dest_name), throttle)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,225 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use super::TocArchive;
+
+// Table data files in directory-format dumps are referenced in TOC as "NNNN.dat",
+// with compression enabled the actual file on disk is "NNNN.dat.gz".
+// File is scanned first and is left untouched when no rows are changed.
+pub(super) fn rewrite_data_file<F>(dump_dir: &Path, filename: &str, mut rewrite_row: F) -> Result<(), io::Error>
+where
+    F: FnMut(&mut Vec<String>)
+{
+    let gz_path = dump_dir.join(format!("{}.gz", filename));
+    let (path, compressed) = if gz_path.exists() {
+        (gz_path, true)
+    } else {
+        (dump_dir.join(filename), false)
+    };
+    let src_file = File::open(&path)?;
+    let changed = if compressed {
+        has_changed_rows(BufReader::new(GzDecoder::new(src_file)), &mut rewrite_row)?
+    } else {
+        has_changed_rows(BufReader::new(src_file), &mut rewrite_row)?
+    };
+    if !changed {
+        return Ok(());
+    }
+
+    let rewritten_path = PathBuf::from(format!("{}.rewritten", path.to_string_lossy()));
+    let src_file = File::open(&path)?;
+    let dest_file = BufWriter::new(File::create(&rewritten_path)?);
+    if compressed {
+        let reader = BufReader::new(GzDecoder::new(src_file));
+        let mut writer = GzEncoder::new(dest_file, Compression::new(6));
+        rewrite_rows(reader, &mut writer, rewrite_row)?;
+        writer.finish()?.flush()?;
+    } else {
+        let reader = BufReader::new(src_file);
+        let mut writer = dest_file;
+        rewrite_rows(reader, &mut writer, rewrite_row)?;
+        writer.flush()?;
+    }
+    fs::rename(&rewritten_path, &path)?;
+    Ok(())
+}
+
+pub(super) fn read_first_row(dump_dir: &Path, filename: &str) -> Result<Option<Vec<String>>, io::Error> {
+    let gz_path = dump_dir.join(format!("{}.gz", filename));
+    if gz_path.exists() {
+        read_first_row_from(File::open(&gz_path)?, true)
+    } else {
+        read_first_row_from(File::open(dump_dir.join(filename))?, false)
+    }
+}
+
+pub(super) fn read_first_row_from<R: Read>(reader: R, compressed: bool) -> Result<Option<Vec<String>>, io::Error> {
+    let line = if compressed {
+        read_row(&mut BufReader::new(GzDecoder::new(reader)))?
+    } else {
+        read_row(&mut BufReader::new(reader))?
+    };
+    match line {
+        // end-of-data marker
+        Some((line, _)) if "\\." == line => Ok(None),
+        Some((line, _)) => Ok(Some(line.split('\t').map(|fi| fi.to_string()).collect())),
+        None => Ok(None)
+    }
+}
+
+// stops reading on the first changed row
+fn has_changed_rows<R: BufRead, F>(mut reader: R, rewrite_row: &mut F) -> Result<bool, io::Error>
+where
+    F: FnMut(&mut Vec<String>)
+{
+    while let Some((line, _)) = read_row(&mut reader)? {
+        let orig: Vec<String> = line.split('\t').map(|fi| fi.to_string()).collect();
+        let mut fields = orig.clone();
+        rewrite_row(&mut fields);
+        if fields != orig {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn read_row<R: BufRead>(reader: &mut R) -> Result<Option<(String, bool)>, io::Error> {
+    let mut buf = Vec::new();
+    let len = reader.read_until(b'\n', &mut buf)?;
+    if 0 == len {
+        return Ok(None);
+    }
+    let has_newline = buf.ends_with(b"\n");
+    if has_newline {
+        buf.pop();
+    }
+    match String::from_utf8(buf) {
+        Ok(st) => Ok(Some((st, has_newline))),
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Invalid data file row, message: {}", e)))
+    }
+}
+
+fn rewrite_rows<R: BufRead, W: Write, F>(mut reader: R, writer: &mut W, mut rewrite_row: F) -> Result<(), io::Error>
+where
+    F: FnMut(&mut Vec<String>)
+{
+    while let Some((line, has_newline)) = read_row(&mut reader)? {
+        let mut fields: Vec<String> = line.split('\t').map(|fi| fi.to_string()).collect();
+        rewrite_row(&mut fields);
+        writer.write_all(fields.join("\t").as_bytes())?;
+        if has_newline {
+            writer.write_all(b"\n")?;
+        }
+    }
+    Ok(())
+}
+
+// text format of COPY, non-ASCII characters are written as is
+pub(super) fn copy_escape(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => res.push_str("\\\\"),
+            '\t' => res.push_str("\\t"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            _ => res.push(ch)
+        }
+    }
+    res
+}
+
+pub(super) fn copy_stmt_columns(copy_stmt: &str) -> Vec<String> {
+    let start = match copy_stmt.find('(') {
+        Some(idx) => idx + 1,
+        None => return Vec::new()
+    };
+    let end = match copy_stmt[start..].find(')') {
+        Some(idx) => start + idx,
+        None => return Vec::new()
+    };
+    copy_stmt[start..end].split(',')
+        .map(|col| col.trim().trim_matches('"').to_string())
+        .collect()
+}
+
+// Copies "toc.dat" and catalog data files of an unzipped dump,
+// the copy is used to check the TOC rewrite without touching the source.
+pub fn copy_dump_catalog(dump_dir: &Path, dest_dir: &Path) -> Result<(), io::Error> {
+    if dest_dir.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!(
+            "Destination directory already exists: {}", dest_dir.to_string_lossy())));
+    }
+    fs::create_dir_all(dest_dir)?;
+    fs::copy(dump_dir.join("toc.dat"), dest_dir.join("toc.dat"))?;
+    let toc = TocArchive::read_file(&dump_dir.join("toc.dat"))?;
+    for en in toc.entries.iter() {
+        if !(en.desc_is("TABLE DATA") && en.namespace_is("sys")) {
+            continue;
+        }
+        let filename = match &en.filename {
+            Some(name) if !name.is_empty() => name,
+            _ => continue
+        };
+        // "NNNN.dat.gz" on disk when compression is enabled
+        for name in [filename.clone(), format!("{}.gz", filename)] {
+            let src = dump_dir.join(&name);
+            if src.exists() {
+                fs::copy(&src, dest_dir.join(&name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Work copy of an unzipped dump that can be rewritten without touching the
+// source: "toc.dat" and catalog data files are copied, other files are hard-linked,
+// or copied when the file system does not support links. Returns copied bytes
+// of the files that could not be linked.
+pub fn link_dump_dir(dump_dir: &Path, dest_dir: &Path) -> Result<u64, io::Error> {
+    copy_dump_catalog(dump_dir, dest_dir)?;
+    let mut copied_bytes = 0;
+    for en_res in fs::read_dir(dump_dir)? {
+        let en = en_res?;
+        if !en.file_type()?.is_file() {
+            continue;
+        }
+        let dest = dest_dir.join(en.file_name());
+        if dest.exists() {
+            continue;
+        }
+        if fs::hard_link(en.path(), &dest).is_err() {
+            copied_bytes += fs::copy(en.path(), &dest)?;
+        }
+    }
+    Ok(copied_bytes)
+}
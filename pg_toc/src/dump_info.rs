@@ -0,0 +1,206 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use zip::ZipArchive;
+
+use super::*;
+use super::data_file::copy_stmt_columns;
+use super::data_file::read_first_row;
+use super::data_file::read_first_row_from;
+use super::zip_dump::list_entries;
+
+// Contents of a directory-format dump as seen by the TOC code,
+// data file map points from dump ID to the actual file on disk
+// (or to the entry path inside the archive for zipped dumps).
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct DumpSummary {
+    pub original_dbname: Option<String>,
+    pub header: TocHeader,
+    pub entries: Vec<TocEntry>,
+    pub data_files: BTreeMap<i64, PathBuf>,
+}
+
+impl DumpSummary {
+    pub fn read(dump_dir: &Path) -> Result<Self, io::Error> {
+        let toc = TocArchive::read_file(&dump_dir.join("toc.dat"))?;
+        let original_dbname = read_original_dbname(dump_dir, &toc)?;
+        let mut data_files = BTreeMap::new();
+        for en in toc.entries.iter() {
+            let filename = match &en.filename {
+                Some(name) if !name.is_empty() => name,
+                _ => continue
+            };
+            // "NNNN.dat.gz" on disk when compression is enabled
+            let gz_path = dump_dir.join(format!("{}.gz", filename));
+            let path = if gz_path.exists() {
+                gz_path
+            } else {
+                dump_dir.join(filename)
+            };
+            data_files.insert(en.dump_id, path);
+        }
+        Ok(Self {
+            original_dbname,
+            header: toc.header,
+            entries: toc.entries,
+            data_files,
+        })
+    }
+
+    // Unzipped dump directory or zip file, tar and plain SQL backups are not supported.
+    pub fn read_path(path: &Path) -> Result<Self, io::Error> {
+        if path.is_dir() {
+            return Self::read(path);
+        }
+        let is_zip = match path.extension() {
+            Some(ext) => ext.eq_ignore_ascii_case("zip"),
+            None => false
+        };
+        if !is_zip {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+                "Only zip files and unzipped dump directories are supported: {}", path.to_string_lossy())));
+        }
+        Self::read_zip(path)
+    }
+
+    // Reads the TOC directly from the zip file without extracting the dump.
+    pub fn read_zip(zip_path: &Path) -> Result<Self, io::Error> {
+        let file = File::open(zip_path)?;
+        let mut archive = ZipArchive::new(BufReader::new(file))?;
+        let entries = list_entries(&mut archive)?;
+        let root = match entries.iter().find_map(|(_, path)| path.components().next()) {
+            Some(comp) => PathBuf::from(comp.as_os_str()),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Zip file is empty: {}", zip_path.to_string_lossy())))
+        };
+        let find_idx = |rel: &Path| entries.iter().find(|(_, path)| path == rel).map(|(idx, _)| *idx);
+        let toc_idx = match find_idx(&root.join("toc.dat")) {
+            Some(idx) => idx,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Zip file does not contain a dump, 'toc.dat' not found: {}", zip_path.to_string_lossy())))
+        };
+        let toc = TocArchive::read(BufReader::new(archive.by_index(toc_idx)?))?;
+
+        let mut data_files = BTreeMap::new();
+        for en in toc.entries.iter() {
+            let filename = match &en.filename {
+                Some(name) if !name.is_empty() => name,
+                _ => continue
+            };
+            let gz_rel = root.join(format!("{}.gz", filename));
+            let rel = if find_idx(&gz_rel).is_some() {
+                gz_rel
+            } else {
+                root.join(filename)
+            };
+            data_files.insert(en.dump_id, rel);
+        }
+
+        let mut original_dbname = None;
+        if let Some((filename, idx)) = dbname_column(&toc) {
+            let gz_rel = root.join(format!("{}.gz", filename));
+            let found = match find_idx(&gz_rel) {
+                Some(file_idx) => Some((file_idx, true)),
+                None => find_idx(&root.join(filename)).map(|file_idx| (file_idx, false))
+            };
+            if let Some((file_idx, compressed)) = found {
+                if let Some(row) = read_first_row_from(archive.by_index(file_idx)?, compressed)? {
+                    original_dbname = row.get(idx).cloned();
+                }
+            }
+        }
+
+        Ok(Self {
+            original_dbname,
+            header: toc.header,
+            entries: toc.entries,
+            data_files,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, io::Error> {
+        serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+// Reads a file that is stored next to "toc.dat" in an unzipped dump directory
+// or in a zip file, other backup formats do not contain such files.
+pub fn read_dump_file(path: &Path, name: &str) -> Result<Option<String>, io::Error> {
+    if path.is_dir() {
+        let file_path = path.join(name);
+        if !file_path.is_file() {
+            return Ok(None);
+        }
+        return fs::read_to_string(file_path).map(Some);
+    }
+    let is_zip = match path.extension() {
+        Some(ext) => ext.eq_ignore_ascii_case("zip"),
+        None => false
+    };
+    if !is_zip {
+        return Ok(None);
+    }
+    let file = File::open(path)?;
+    let mut archive = ZipArchive::new(BufReader::new(file))?;
+    let entries = list_entries(&mut archive)?;
+    let root = match entries.iter().find_map(|(_, path)| path.components().next()) {
+        Some(comp) => PathBuf::from(comp.as_os_str()),
+        None => return Ok(None)
+    };
+    let rel = root.join(name);
+    let idx = match entries.iter().find(|(_, path)| *path == rel) {
+        Some((idx, _)) => *idx,
+        None => return Ok(None)
+    };
+    let mut text = String::new();
+    archive.by_index(idx)?.read_to_string(&mut text)?;
+    Ok(Some(text))
+}
+
+// Logical name of the dumped Babelfish database as stored in "sys.babelfish_sysdatabases",
+// dump contains a single row in this table.
+pub(super) fn read_original_dbname(dump_dir: &Path, toc: &TocArchive) -> Result<Option<String>, io::Error> {
+    let (filename, idx) = match dbname_column(toc) {
+        Some(found) => found,
+        None => return Ok(None)
+    };
+    let row = match read_first_row(dump_dir, filename)? {
+        Some(row) => row,
+        None => return Ok(None)
+    };
+    Ok(row.get(idx).cloned())
+}
+
+// data file name and "name" column index of "sys.babelfish_sysdatabases"
+fn dbname_column(toc: &TocArchive) -> Option<(&str, usize)> {
+    let en = toc.find_entry("TABLE DATA", "sys", "babelfish_sysdatabases")?;
+    let (filename, copy_stmt) = match (&en.filename, &en.copy_stmt) {
+        (Some(filename), Some(copy_stmt)) if !filename.is_empty() => (filename, copy_stmt),
+        _ => return None
+    };
+    let idx = copy_stmt_columns(copy_stmt).iter().position(|col| "name" == col)?;
+    Some((filename.as_str(), idx))
+}
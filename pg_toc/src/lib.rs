@@ -0,0 +1,53 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod chain_merge;
+mod data_file;
+mod dump_info;
+mod migration_mode;
+mod owner_mapping;
+mod schema_mapping;
+mod tar_dump;
+mod throttle;
+mod toc_archive;
+mod zip_dump;
+
+pub use chain_merge::merge_chain_data;
+pub use data_file::copy_dump_catalog;
+pub use data_file::link_dump_dir;
+pub use dump_info::DumpSummary;
+pub use dump_info::read_dump_file;
+pub use migration_mode::convert_to_single_db;
+pub use migration_mode::dump_migration_mode;
+pub use migration_mode::rename_multi_db;
+pub use migration_mode::rename_single_db;
+pub use migration_mode::MULTI_DB;
+pub use migration_mode::SINGLE_DB;
+pub use owner_mapping::remap_owners;
+pub use schema_mapping::remap_schemas;
+pub use schema_mapping::rename_user_schemas;
+pub use schema_mapping::user_schemas;
+pub use tar_dump::extract_tar_dump;
+pub use throttle::Throttle;
+pub use throttle::ThrottledWriter;
+pub use toc_archive::TocArchive;
+pub use toc_archive::TocEntry;
+pub use toc_archive::TocHeader;
+pub use zip_dump::check_zip_dump;
+pub use zip_dump::extract_zip_catalog;
+pub use zip_dump::extract_zip_dump;
+pub use zip_dump::extracted_dump_size;
+pub use zip_dump::verify_zip;
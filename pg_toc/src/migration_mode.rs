@@ -0,0 +1,211 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::path::Path;
+
+use super::*;
+use super::data_file::copy_escape;
+use super::data_file::copy_stmt_columns;
+use super::data_file::rewrite_data_file;
+use super::dump_info::read_original_dbname;
+use super::schema_mapping::apply_renames;
+use super::schema_mapping::detect_schema_prefix;
+use super::schema_mapping::SchemaRename;
+
+// values of "babelfishpg_tsql.migration_mode" setting
+pub const SINGLE_DB: &str = "single-db";
+pub const MULTI_DB: &str = "multi-db";
+
+// catalog columns that contain the logical DB name
+const DBNAME_COLUMNS: [(&str, &str); 2] = [
+    ("babelfish_sysdatabases", "name"),
+    ("babelfish_authid_user_ext", "database_name"),
+];
+
+// roles that are not prefixed with DB name in single-db mode
+const SINGLE_DB_ROLES: [&str; 2] = ["dbo", "db_owner"];
+
+// schemas and roles created with every DB in multi-db mode, prefixed with DB name
+const MULTI_DB_SCHEMAS: [&str; 2] = ["dbo", "guest"];
+const MULTI_DB_ROLES: [&str; 3] = ["dbo", "db_owner", "guest"];
+
+// "dbo" schema is stored as "dbo" in single-db mode and as "mydb_dbo"
+// in multi-db mode, None when the dump has no "dbo" schema.
+pub fn dump_migration_mode(dump_dir: &Path) -> Result<Option<&'static str>, io::Error> {
+    let toc = TocArchive::read_file(&dump_dir.join("toc.dat"))?;
+    for en in toc.entries.iter() {
+        if !en.desc_is("SCHEMA") {
+            continue;
+        }
+        if en.tag_is("dbo") {
+            return Ok(Some(SINGLE_DB));
+        }
+        if let Some(tag) = &en.tag {
+            if tag.ends_with("_dbo") {
+                return Ok(Some(MULTI_DB));
+            }
+        }
+    }
+    Ok(None)
+}
+
+// In single-db mode schema and role names do not include the DB name,
+// so only the logical DB name stored in the Babelfish catalog is changed.
+pub fn rename_single_db(dump_dir: &Path, from: &str, to: &str) -> Result<(), io::Error> {
+    let toc = TocArchive::read_file(&dump_dir.join("toc.dat"))?;
+    rename_logical_db(dump_dir, &toc, from, to)
+}
+
+// Multi-db mode: "olddb_dbo" and "olddb_guest" schemas and "olddb_dbo", "olddb_db_owner"
+// and "olddb_guest" roles get the prefix of the target DB, logical DB name in the catalog
+// is changed too. Other schemas keep the old prefix and are moved by "rename_user_schemas".
+pub fn rename_multi_db(dump_dir: &Path, to: &str) -> Result<(), io::Error> {
+    let toc_path = dump_dir.join("toc.dat");
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    let orig_prefix = detect_schema_prefix(&toc);
+    if orig_prefix.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            "Dump was not taken in multi-db migration mode, 'dbo' schema with DB name prefix not found"));
+    }
+    // Postgres folds only ASCII letters in identifiers
+    let prefix = format!("{}_", to.to_ascii_lowercase());
+    if prefix != orig_prefix {
+        let renames: Vec<SchemaRename> = MULTI_DB_SCHEMAS.iter().map(|name| SchemaRename {
+            logical_from: String::new(),
+            logical_to: String::new(),
+            physical_from: format!("{}{}", orig_prefix, name),
+            physical_to: format!("{}{}", prefix, name),
+        }).collect();
+        // ACL statements of the roles with the same names as the schemas are updated here too
+        apply_renames(dump_dir, &mut toc, &renames)?;
+        let roles: Vec<(String, String)> = MULTI_DB_ROLES.iter()
+            .map(|role| (format!("{}{}", orig_prefix, role), format!("{}{}", prefix, role)))
+            .collect();
+        remap_owners(dump_dir, &roles)?;
+        rename_catalog_roles(dump_dir, &toc, &roles)?;
+    }
+    // prefix is the lower-case DB name when the catalog row cannot be read
+    let from = match read_original_dbname(dump_dir, &toc)? {
+        Some(name) => name,
+        None => orig_prefix[..orig_prefix.len() - 1].to_string()
+    };
+    rename_logical_db(dump_dir, &toc, &from, to)
+}
+
+fn rename_logical_db(dump_dir: &Path, toc: &TocArchive, from: &str, to: &str) -> Result<(), io::Error> {
+    let from_escaped = copy_escape(from);
+    let to_escaped = copy_escape(to);
+    for (table, column) in DBNAME_COLUMNS.iter() {
+        let en = match toc.find_entry("TABLE DATA", "sys", table) {
+            Some(en) => en,
+            None => continue
+        };
+        let (filename, copy_stmt) = match (&en.filename, &en.copy_stmt) {
+            (Some(filename), Some(copy_stmt)) if !filename.is_empty() => (filename, copy_stmt),
+            _ => continue
+        };
+        let idx = match copy_stmt_columns(copy_stmt).iter().position(|col| col == column) {
+            Some(idx) => idx,
+            None => continue
+        };
+        rewrite_data_file(dump_dir, filename, |fields| {
+            // DB names are case-insensitive in T-SQL
+            if idx < fields.len() && fields[idx].to_lowercase() == from_escaped.to_lowercase() {
+                fields[idx] = to_escaped.clone();
+            }
+        })?;
+    }
+    Ok(())
+}
+
+// role names in "sys.babelfish_authid_user_ext"
+fn rename_catalog_roles(dump_dir: &Path, toc: &TocArchive, roles: &[(String, String)]) -> Result<(), io::Error> {
+    let en = match toc.find_entry("TABLE DATA", "sys", "babelfish_authid_user_ext") {
+        Some(en) => en,
+        None => return Ok(())
+    };
+    let (filename, copy_stmt) = match (&en.filename, &en.copy_stmt) {
+        (Some(filename), Some(copy_stmt)) if !filename.is_empty() => (filename, copy_stmt),
+        _ => return Ok(())
+    };
+    let idx = match copy_stmt_columns(copy_stmt).iter().position(|col| "rolname" == col) {
+        Some(idx) => idx,
+        None => return Ok(())
+    };
+    let roles_escaped: Vec<(String, String)> = roles.iter()
+        .map(|(from, to)| (copy_escape(from), copy_escape(to)))
+        .collect();
+    rewrite_data_file(dump_dir, filename, |fields| {
+        if idx >= fields.len() {
+            return;
+        }
+        if let Some((_, to)) = roles_escaped.iter().find(|(from, _)| *from == fields[idx]) {
+            fields[idx] = to.clone();
+        }
+    })
+}
+
+// Converts a dump taken in multi-db mode for restoring into a server in single-db
+// mode: "mydb_dbo", "mydb_guest" and other schemas lose the DB name prefix, "mydb_dbo"
+// and "mydb_db_owner" roles become "dbo" and "db_owner", other roles keep the prefix.
+// Logical DB name in the catalog is not changed, returns the removed prefix.
+pub fn convert_to_single_db(dump_dir: &Path) -> Result<String, io::Error> {
+    let toc_path = dump_dir.join("toc.dat");
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    let prefix = detect_schema_prefix(&toc);
+    if prefix.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            "Dump was not taken in multi-db migration mode, 'dbo' schema with DB name prefix not found"));
+    }
+    let mut schemas: Vec<String> = Vec::new();
+    for en in toc.entries.iter() {
+        if !en.desc_is("SCHEMA") {
+            continue;
+        }
+        if let Some(name) = en.tag.as_ref().and_then(|tag| tag.strip_prefix(&prefix)) {
+            if !schemas.iter().any(|sc| sc == name) {
+                schemas.push(name.to_string());
+            }
+        }
+    }
+    let renames: Vec<SchemaRename> = schemas.iter().map(|name| SchemaRename {
+        logical_from: String::new(),
+        logical_to: String::new(),
+        physical_from: format!("{}{}", prefix, name),
+        physical_to: name.clone(),
+    }).collect();
+    apply_renames(dump_dir, &mut toc, &renames)?;
+
+    // Schema rename also changes role names in ACL statements, names of the
+    // roles that keep the prefix are restored there.
+    let mut owners: Vec<(String, String)> = SINGLE_DB_ROLES.iter()
+        .map(|role| (format!("{}{}", prefix, role), role.to_string()))
+        .collect();
+    for name in schemas.iter() {
+        if !SINGLE_DB_ROLES.contains(&name.as_str()) {
+            owners.push((name.clone(), format!("{}{}", prefix, name)));
+        }
+    }
+    remap_owners(dump_dir, &owners)?;
+
+    // role names in Babelfish catalog
+    let roles: Vec<(String, String)> = SINGLE_DB_ROLES.iter()
+        .map(|role| (format!("{}{}", prefix, role), role.to_string()))
+        .collect();
+    rename_catalog_roles(dump_dir, &toc, &roles)?;
+    Ok(prefix)
+}
@@ -0,0 +1,83 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::path::Path;
+
+use super::*;
+use super::schema_mapping::is_identifier_char;
+use super::schema_mapping::needs_quoting;
+use super::schema_mapping::quote_identifier;
+
+// role names in ACL statements follow one of these
+const ROLE_KEYWORDS: [&str; 5] = ["TO ", "FROM ", "FOR ROLE ", "AUTHORIZATION ", "GRANTED BY "];
+
+// Owners are stored in TOC as plain role names, "ALTER ... OWNER TO" statements
+// are generated from them by pg_restore. Grantees in ACL and DEFAULT ACL entries
+// are SQL text and only role positions are replaced there, so tables that happen
+// to have the same name as the role are left as is.
+pub fn remap_owners(dump_dir: &Path, mapping: &Vec<(String, String)>) -> Result<(), io::Error> {
+    if mapping.is_empty() {
+        return Ok(());
+    }
+    let toc_path = dump_dir.join("toc.dat");
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    for en in toc.entries.iter_mut() {
+        let is_acl = en.desc_is("ACL") || en.desc_is("DEFAULT ACL");
+        for (from, to) in mapping {
+            if en.owner.as_deref() == Some(from.as_str()) {
+                en.owner = Some(to.clone());
+            }
+            if is_acl {
+                en.defn = en.defn.as_ref().map(|st| replace_role(st, from, to));
+                en.drop_stmt = en.drop_stmt.as_ref().map(|st| replace_role(st, from, to));
+            }
+        }
+    }
+    toc.write_file(&toc_path)
+}
+
+fn sql_role_name(name: &str) -> String {
+    if needs_quoting(name) {
+        quote_identifier(name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn replace_role(text: &str, from: &str, to: &str) -> String {
+    let from_sql = sql_role_name(from);
+    let to_sql = sql_role_name(to);
+    let mut res = String::with_capacity(text.len());
+    let mut last = 0;
+    for (idx, _) in text.match_indices(&from_sql) {
+        if idx < last {
+            continue;
+        }
+        let before_ok = ROLE_KEYWORDS.iter().any(|kw| text[..idx].ends_with(kw));
+        let after_ok = match text[idx + from_sql.len()..].chars().next() {
+            Some(ch) => !is_identifier_char(ch) && '"' != ch,
+            None => true
+        };
+        if before_ok && after_ok {
+            res.push_str(&text[last..idx]);
+            res.push_str(&to_sql);
+            last = idx + from_sql.len();
+        }
+    }
+    res.push_str(&text[last..]);
+    res
+}
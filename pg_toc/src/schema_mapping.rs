@@ -0,0 +1,286 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::path::Path;
+
+use super::*;
+use super::data_file::copy_escape;
+use super::data_file::copy_stmt_columns;
+use super::data_file::rewrite_data_file;
+
+enum SchemaNameKind {
+    // "{dbname}_{schema}" in multi-db mode
+    Physical,
+    // schema name as seen from T-SQL
+    Logical,
+}
+
+struct CatalogColumn {
+    table: &'static str,
+    column: &'static str,
+    kind: SchemaNameKind,
+}
+
+const CATALOG_COLUMNS: [CatalogColumn; 6] = [
+    CatalogColumn { table: "babelfish_namespace_ext", column: "nspname", kind: SchemaNameKind::Physical },
+    CatalogColumn { table: "babelfish_namespace_ext", column: "orig_name", kind: SchemaNameKind::Logical },
+    CatalogColumn { table: "babelfish_function_ext", column: "nspname", kind: SchemaNameKind::Physical },
+    CatalogColumn { table: "babelfish_view_def", column: "schema_name", kind: SchemaNameKind::Logical },
+    CatalogColumn { table: "babelfish_extended_properties", column: "schema_name", kind: SchemaNameKind::Logical },
+    CatalogColumn { table: "babelfish_authid_user_ext", column: "default_schema_name", kind: SchemaNameKind::Logical },
+];
+
+// logical names are empty when only the physical name is changed
+pub(super) struct SchemaRename {
+    pub(super) logical_from: String,
+    pub(super) logical_to: String,
+    pub(super) physical_from: String,
+    pub(super) physical_to: String,
+}
+
+pub fn remap_schemas(dump_dir: &Path, mapping: &Vec<(String, String)>) -> Result<(), io::Error> {
+    if mapping.is_empty() {
+        return Ok(());
+    }
+    let toc_path = dump_dir.join("toc.dat");
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    let prefix = detect_schema_prefix(&toc);
+    let mut renames = Vec::new();
+    for (from, to) in mapping {
+        for reserved in ["dbo", "guest"] {
+            if from.to_lowercase() == reserved || to.to_lowercase() == reserved {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+                    "Schema '{}' cannot be remapped", reserved)));
+            }
+        }
+        renames.push(SchemaRename {
+            logical_from: from.clone(),
+            logical_to: to.clone(),
+            // Postgres folds only ASCII letters in identifiers
+            physical_from: format!("{}{}", prefix, from.to_ascii_lowercase()),
+            physical_to: format!("{}{}", prefix, to.to_ascii_lowercase()),
+        });
+    }
+    apply_renames(dump_dir, &mut toc, &renames)
+}
+
+// Multi-db mode: schema name prefix of the dumped DB and the names of T-SQL
+// schemas other than "dbo" and "guest", physical names of these schemas
+// are not updated by the DB name rewrite.
+pub fn user_schemas(dump_dir: &Path) -> Result<(String, Vec<String>), io::Error> {
+    let toc = TocArchive::read_file(&dump_dir.join("toc.dat"))?;
+    let prefix = detect_schema_prefix(&toc);
+    let mut schemas = Vec::new();
+    if prefix.is_empty() {
+        return Ok((prefix, schemas));
+    }
+    for en in toc.entries.iter() {
+        if !en.desc_is("SCHEMA") {
+            continue;
+        }
+        if let Some(tag) = &en.tag {
+            if let Some(name) = tag.strip_prefix(&prefix) {
+                if "dbo" != name && "guest" != name && !schemas.iter().any(|sc| sc == name) {
+                    schemas.push(name.to_string());
+                }
+            }
+        }
+    }
+    Ok((prefix, schemas))
+}
+
+// Called after the DB name rewrite, moves schemas returned by "user_schemas"
+// to the prefix of the target DB, T-SQL names of the schemas are not changed.
+pub fn rename_user_schemas(dump_dir: &Path, orig_prefix: &str, schemas: &[String]) -> Result<(), io::Error> {
+    if schemas.is_empty() {
+        return Ok(());
+    }
+    let toc_path = dump_dir.join("toc.dat");
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    let prefix = detect_schema_prefix(&toc);
+    if prefix == orig_prefix {
+        return Ok(());
+    }
+    let renames: Vec<SchemaRename> = schemas.iter().map(|name| SchemaRename {
+        logical_from: String::new(),
+        logical_to: String::new(),
+        physical_from: format!("{}{}", orig_prefix, name),
+        physical_to: format!("{}{}", prefix, name),
+    }).collect();
+    apply_renames(dump_dir, &mut toc, &renames)
+}
+
+pub(super) fn apply_renames(dump_dir: &Path, toc: &mut TocArchive, renames: &Vec<SchemaRename>) -> Result<(), io::Error> {
+    let toc_path = dump_dir.join("toc.dat");
+    for en in toc.entries.iter_mut() {
+        rewrite_entry(en, renames);
+    }
+    toc.write_file(&toc_path)?;
+
+    // values in data files are escaped
+    let copy_renames: Vec<SchemaRename> = renames.iter().map(|rn| SchemaRename {
+        logical_from: copy_escape(&rn.logical_from),
+        logical_to: copy_escape(&rn.logical_to),
+        physical_from: copy_escape(&rn.physical_from),
+        physical_to: copy_escape(&rn.physical_to),
+    }).collect();
+    for en in toc.entries.iter() {
+        if !(en.desc_is("TABLE DATA") && en.namespace_is("sys")) {
+            continue;
+        }
+        let filename = match &en.filename {
+            Some(name) if !name.is_empty() => name,
+            _ => continue
+        };
+        let columns = match &en.copy_stmt {
+            Some(stmt) => copy_stmt_columns(stmt),
+            None => continue
+        };
+        let mut targets = Vec::new();
+        for cc in CATALOG_COLUMNS.iter() {
+            if !en.tag_is(cc.table) {
+                continue;
+            }
+            if let Some(idx) = columns.iter().position(|col| col == cc.column) {
+                targets.push((idx, &cc.kind));
+            }
+        }
+        if targets.is_empty() {
+            continue;
+        }
+        rewrite_data_file(dump_dir, filename, |fields| {
+            for (idx, kind) in targets.iter() {
+                if *idx >= fields.len() {
+                    continue;
+                }
+                for rn in copy_renames.iter() {
+                    match kind {
+                        SchemaNameKind::Physical => if fields[*idx] == rn.physical_from {
+                            fields[*idx] = rn.physical_to.clone();
+                        },
+                        SchemaNameKind::Logical => if !rn.logical_from.is_empty() &&
+                                fields[*idx].to_lowercase() == rn.logical_from.to_lowercase() {
+                            fields[*idx] = rn.logical_to.clone();
+                        }
+                    }
+                }
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+// multi-db mode: "dbo" schema of "mydb" is stored as "mydb_dbo"
+pub(super) fn detect_schema_prefix(toc: &TocArchive) -> String {
+    for en in toc.entries.iter() {
+        if !en.desc_is("SCHEMA") {
+            continue;
+        }
+        if let Some(tag) = &en.tag {
+            if tag.ends_with("_dbo") {
+                return tag[..tag.len() - "dbo".len()].to_string();
+            }
+        }
+    }
+    String::new()
+}
+
+fn rewrite_entry(en: &mut TocEntry, renames: &Vec<SchemaRename>) {
+    // tag of a SCHEMA entry is a plain name, ACL and COMMENT tags are SQL: SCHEMA "my schema"
+    let tag_is_schema = en.desc_is("SCHEMA");
+    let tag_is_schema_ref = en.desc_is("ACL") || en.desc_is("COMMENT");
+    for rn in renames {
+        let from = &rn.physical_from;
+        let to = &rn.physical_to;
+        if en.namespace_is(from) {
+            en.namespace = Some(to.clone());
+        }
+        if tag_is_schema && en.tag_is(from) {
+            en.tag = Some(to.clone());
+        }
+        if tag_is_schema_ref {
+            en.tag = replace_identifier_opt(&en.tag, from, to);
+        }
+        en.defn = replace_identifier_opt(&en.defn, from, to);
+        en.drop_stmt = replace_identifier_opt(&en.drop_stmt, from, to);
+        en.copy_stmt = replace_identifier_opt(&en.copy_stmt, from, to);
+    }
+}
+
+fn replace_identifier_opt(text: &Option<String>, from: &str, to: &str) -> Option<String> {
+    text.as_ref().map(|st| replace_identifier(st, from, to))
+}
+
+pub(super) fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || '_' == ch || '$' == ch
+}
+
+// non-ASCII letters can be used in identifiers without quotes
+pub(super) fn needs_quoting(name: &str) -> bool {
+    let first_ok = match name.chars().next() {
+        Some(ch) => ch.is_ascii_lowercase() || '_' == ch || !ch.is_ascii(),
+        None => false
+    };
+    !first_ok || !name.chars().all(|ch| {
+        ch.is_ascii_lowercase() || ch.is_ascii_digit() || '_' == ch || '$' == ch || !ch.is_ascii()
+    })
+}
+
+pub(super) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Replaces schema name in SQL text, names with spaces, dots or upper-case
+// letters are quoted by pg_dump and are quoted in the replacement too.
+pub(super) fn replace_identifier(text: &str, from: &str, to: &str) -> String {
+    let quoted = text.replace(&quote_identifier(from), &quote_identifier(to));
+    if needs_quoting(from) {
+        return quoted;
+    }
+    let to_sql = if needs_quoting(to) {
+        quote_identifier(to)
+    } else {
+        to.to_string()
+    };
+    replace_plain_identifier(&quoted, from, &to_sql)
+}
+
+fn replace_plain_identifier(text: &str, from: &str, to: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut last = 0;
+    for (idx, _) in text.match_indices(from) {
+        if idx < last {
+            continue;
+        }
+        let before_ok = match text[..idx].chars().next_back() {
+            Some(ch) => !is_identifier_char(ch) && '"' != ch,
+            None => true
+        };
+        let after_ok = match text[idx + from.len()..].chars().next() {
+            Some(ch) => !is_identifier_char(ch) && '"' != ch,
+            None => true
+        };
+        if before_ok && after_ok {
+            res.push_str(&text[last..idx]);
+            res.push_str(to);
+            last = idx + from.len();
+        }
+    }
+    res.push_str(&text[last..]);
+    res
+}
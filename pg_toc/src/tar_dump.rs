@@ -0,0 +1,123 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+use super::*;
+use super::toc_archive::FORMAT_DIRECTORY;
+
+const BLOCK_SIZE: usize = 512;
+
+// Tar-format dumps contain the same "toc.dat" and "NNNN.dat" members as
+// directory-format ones, only the format byte in TOC header differs, so
+// after extraction the dump can be processed as a directory one.
+pub fn extract_tar_dump<F>(tar_path: &Path, dest_dir: &Path, mut listener: F) -> Result<(), io::Error>
+where
+    F: FnMut(&str)
+{
+    if dest_dir.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!(
+            "Extraction directory already exists: {}", dest_dir.to_string_lossy())));
+    }
+    fs::create_dir_all(dest_dir)?;
+    if let Err(e) = extract_members(tar_path, dest_dir, &mut listener) {
+        let _ = fs::remove_dir_all(dest_dir);
+        return Err(e);
+    }
+
+    let toc_path = dest_dir.join("toc.dat");
+    if !toc_path.is_file() {
+        let _ = fs::remove_dir_all(dest_dir);
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Tar archive does not contain a dump, 'toc.dat' not found: {}", tar_path.to_string_lossy())));
+    }
+    let mut toc = TocArchive::read_file(&toc_path)?;
+    toc.header.format = FORMAT_DIRECTORY;
+    toc.write_file(&toc_path)?;
+    Ok(())
+}
+
+fn extract_members<F>(tar_path: &Path, dest_dir: &Path, listener: &mut F) -> Result<(), io::Error>
+where
+    F: FnMut(&str)
+{
+    let mut reader = BufReader::new(File::open(tar_path)?);
+    let mut header = [0u8; BLOCK_SIZE];
+    loop {
+        reader.read_exact(&mut header)?;
+        if header.iter().all(|b| 0 == *b) {
+            break;
+        }
+        let name = header_name(&header)?;
+        let size = header_size(&header)?;
+        // regular file, members of other types are not written by pg_dump
+        let typeflag = header[156];
+        if b'0' == typeflag || 0 == typeflag {
+            listener(&name);
+            let mut writer = BufWriter::new(File::create(dest_dir.join(&name))?);
+            let copied = io::copy(&mut (&mut reader).take(size), &mut writer)?;
+            if copied != size {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!(
+                    "Unexpected end of tar archive, member: {}", name)));
+            }
+            writer.flush()?;
+        } else {
+            io::copy(&mut (&mut reader).take(size), &mut io::sink())?;
+        }
+        let padding = (BLOCK_SIZE as u64 - size % BLOCK_SIZE as u64) % BLOCK_SIZE as u64;
+        io::copy(&mut (&mut reader).take(padding), &mut io::sink())?;
+    }
+    Ok(())
+}
+
+fn header_name(header: &[u8]) -> Result<String, io::Error> {
+    let name_bytes: Vec<u8> = header[..100].iter().take_while(|b| 0 != **b).cloned().collect();
+    let name = String::from_utf8_lossy(&name_bytes).to_string();
+    // pg_dump writes all members at the top level
+    if name.is_empty() || name.contains('/') || name.contains('\\') || name.contains("..") {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Unexpected member in tar archive: {}", name)));
+    }
+    Ok(name)
+}
+
+fn header_size(header: &[u8]) -> Result<u64, io::Error> {
+    let field = &header[124..136];
+    // large sizes are stored as base-256 number with the high bit set
+    if 0 != (field[0] & 0x80) {
+        let mut res: u64 = (field[0] & 0x7f) as u64;
+        for b in field[1..].iter() {
+            res = (res << 8) | (*b as u64);
+        }
+        return Ok(res);
+    }
+    let st: String = field.iter()
+        .take_while(|b| 0 != **b)
+        .map(|b| *b as char)
+        .collect();
+    match u64::from_str_radix(st.trim(), 8) {
+        Ok(size) => Ok(size),
+        Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Invalid member size in tar archive: {}", st)))
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+// Limits the rate of the data written by zip, unzip and volume copying,
+// the caller is put to sleep when it gets ahead of the rate. Rate is measured
+// over short windows, so the time spent waiting for pg_dump is not turned
+// into a burst of writes afterwards.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    bytes_per_sec: u64,
+    window_start: Option<Instant>,
+    window_bytes: u64,
+}
+
+impl Throttle {
+    pub fn new(mb_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: mb_per_sec * 1024 * 1024,
+            window_start: None,
+            window_bytes: 0,
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.bytes_per_sec > 0
+    }
+
+    pub fn consume(&mut self, len: usize) {
+        if !self.is_enabled() {
+            return;
+        }
+        let start = match self.window_start {
+            Some(start) if start.elapsed() < WINDOW => start,
+            _ => {
+                let now = Instant::now();
+                self.window_start = Some(now);
+                self.window_bytes = 0;
+                now
+            }
+        };
+        self.window_bytes += len as u64;
+        let due = Duration::from_secs_f64(self.window_bytes as f64 / self.bytes_per_sec as f64);
+        let elapsed = start.elapsed();
+        if due > elapsed {
+            thread::sleep(due - elapsed);
+        }
+    }
+}
+
+pub struct ThrottledWriter<'a, W: Write> {
+    inner: W,
+    throttle: &'a mut Throttle,
+}
+
+impl<'a, W: Write> ThrottledWriter<'a, W> {
+    pub fn new(inner: W, throttle: &'a mut Throttle) -> Self {
+        Self {
+            inner,
+            throttle,
+        }
+    }
+}
+
+impl<'a, W: Write> Write for ThrottledWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.throttle.consume(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
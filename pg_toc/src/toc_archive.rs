@@ -0,0 +1,469 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+const MAGIC: &[u8] = b"PGDMP";
+// archive versions written by pg_dump from PostgreSQL 13 to 17:
+// 1.14 - 13, 14 and 15, 1.15 - 16, 1.16 - 17
+const MIN_VERSION: u8 = 14;
+const MAX_VERSION: u8 = 16;
+// compression algorithm byte replaced compression level int in header
+const VERSION_COMPRESSION_ALGORITHM: u8 = 15;
+// relkind int added to entries after table access method
+const VERSION_RELKIND: u8 = 16;
+pub(super) const FORMAT_TAR: u8 = 3;
+pub(super) const FORMAT_DIRECTORY: u8 = 5;
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct TocHeader {
+    pub vmaj: u8,
+    pub vmin: u8,
+    pub vrev: u8,
+    pub int_size: u8,
+    pub off_size: u8,
+    pub format: u8,
+    // compression level before version 1.15, compression algorithm after
+    pub compression: i64,
+    pub timestamp: Vec<i64>,
+    pub dbname: Option<String>,
+    pub server_version: Option<String>,
+    pub pg_dump_version: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct TocEntry {
+    pub dump_id: i64,
+    pub had_dumper: i64,
+    pub table_oid: Option<String>,
+    pub oid: Option<String>,
+    pub tag: Option<String>,
+    pub desc: Option<String>,
+    pub section: i64,
+    pub defn: Option<String>,
+    pub drop_stmt: Option<String>,
+    pub copy_stmt: Option<String>,
+    pub namespace: Option<String>,
+    pub tablespace: Option<String>,
+    pub tableam: Option<String>,
+    // only present in version 1.16 and later
+    pub relkind: i64,
+    pub owner: Option<String>,
+    pub with_oids: Option<String>,
+    pub deps: Vec<String>,
+    pub filename: Option<String>,
+}
+
+impl TocHeader {
+    // pg_dump writes "struct tm" fields: sec, min, hour, mday, mon, year, isdst
+    pub fn dump_time(&self) -> Option<String> {
+        if self.timestamp.len() < 6 {
+            return None;
+        }
+        let ts = &self.timestamp;
+        Some(format!("{:04}-{:02}-{:02} {:02}:{:02}", ts[5] + 1900, ts[4] + 1, ts[3], ts[2], ts[1]))
+    }
+}
+
+impl TocEntry {
+    pub fn desc_is(&self, desc: &str) -> bool {
+        match &self.desc {
+            Some(st) => st == desc,
+            None => false
+        }
+    }
+
+    pub fn namespace_is(&self, namespace: &str) -> bool {
+        match &self.namespace {
+            Some(st) => st == namespace,
+            None => false
+        }
+    }
+
+    pub fn tag_is(&self, tag: &str) -> bool {
+        match &self.tag {
+            Some(st) => st == tag,
+            None => false
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TocArchive {
+    pub header: TocHeader,
+    pub entries: Vec<TocEntry>,
+    pub trailer: Vec<u8>,
+}
+
+impl TocArchive {
+    pub fn read_file(toc_path: &Path) -> Result<Self, io::Error> {
+        let file = File::open(toc_path)?;
+        Self::read(BufReader::new(file))
+    }
+
+    // reader is consumed to the end, bytes after the entries are kept as trailer
+    pub fn read<R: Read>(reader: R) -> Result<Self, io::Error> {
+        let mut reader = TocReader {
+            reader,
+            int_size: 0,
+            vmin: 0
+        };
+        let header = reader.read_header()?;
+        let count = reader.read_int()?;
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let en = reader.read_entry()?;
+            entries.push(en);
+        }
+        let mut trailer = Vec::new();
+        reader.reader.read_to_end(&mut trailer)?;
+        Ok(Self {
+            header,
+            entries,
+            trailer
+        })
+    }
+
+    pub fn write_file(&self, toc_path: &Path) -> Result<(), io::Error> {
+        let rewritten_path_st = format!("{}.rewritten", toc_path.to_string_lossy());
+        let rewritten_path = Path::new(&rewritten_path_st);
+        {
+            let file = File::create(rewritten_path)?;
+            self.write(BufWriter::new(file))?;
+        }
+        fs::rename(rewritten_path, toc_path)?;
+        Ok(())
+    }
+
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), io::Error> {
+        let mut writer = TocWriter {
+            writer,
+            int_size: self.header.int_size as usize,
+            vmin: self.header.vmin
+        };
+        writer.write_header(&self.header)?;
+        writer.write_int(self.entries.len() as i64)?;
+        for en in self.entries.iter() {
+            writer.write_entry(en)?;
+        }
+        writer.writer.write_all(&self.trailer)?;
+        writer.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn find_entry(&self, desc: &str, namespace: &str, tag: &str) -> Option<&TocEntry> {
+        self.entries.iter().find(|en| {
+            en.desc_is(desc) && en.namespace_is(namespace) && en.tag_is(tag)
+        })
+    }
+}
+
+struct TocReader<R: Read> {
+    reader: R,
+    int_size: usize,
+    vmin: u8,
+}
+
+impl<R: Read> TocReader<R> {
+    fn read_byte(&mut self) -> Result<u8, io::Error> {
+        let mut buf = [0u8; 1];
+        self.reader.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_int(&mut self) -> Result<i64, io::Error> {
+        let sign = self.read_byte()?;
+        let mut res: i64 = 0;
+        for i in 0..self.int_size {
+            let bv = self.read_byte()? as i64;
+            res += bv << (8 * i);
+        }
+        if sign != 0 {
+            res = -res;
+        }
+        Ok(res)
+    }
+
+    fn read_str(&mut self) -> Result<Option<String>, io::Error> {
+        let len = self.read_int()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.reader.read_exact(&mut buf)?;
+        match String::from_utf8(buf) {
+            Ok(st) => Ok(Some(st)),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Invalid string in TOC file, message: {}", e)))
+        }
+    }
+
+    fn read_header(&mut self) -> Result<TocHeader, io::Error> {
+        let mut magic = [0u8; 5];
+        self.reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                "Invalid TOC file, magic header not found"));
+        }
+        let vmaj = self.read_byte()?;
+        let vmin = self.read_byte()?;
+        let vrev = self.read_byte()?;
+        if !(1 == vmaj && (MIN_VERSION..=MAX_VERSION).contains(&vmin)) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Unsupported TOC version: {}.{}.{}, supported versions: 1.{} - 1.{}",
+                vmaj, vmin, vrev, MIN_VERSION, MAX_VERSION)));
+        }
+        self.vmin = vmin;
+        let int_size = self.read_byte()?;
+        if !(1..=8).contains(&int_size) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Unsupported TOC int size: {}", int_size)));
+        }
+        self.int_size = int_size as usize;
+        let off_size = self.read_byte()?;
+        let format = self.read_byte()?;
+        if !(FORMAT_DIRECTORY == format || FORMAT_TAR == format) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Unsupported archive format: {}, only directory and tar formats are supported", format)));
+        }
+        let compression = if vmin >= VERSION_COMPRESSION_ALGORITHM {
+            self.read_byte()? as i64
+        } else {
+            self.read_int()?
+        };
+        let mut timestamp = Vec::new();
+        for _ in 0..7 {
+            timestamp.push(self.read_int()?);
+        }
+        let dbname = self.read_str()?;
+        let server_version = self.read_str()?;
+        let pg_dump_version = self.read_str()?;
+        Ok(TocHeader {
+            vmaj,
+            vmin,
+            vrev,
+            int_size,
+            off_size,
+            format,
+            compression,
+            timestamp,
+            dbname,
+            server_version,
+            pg_dump_version,
+        })
+    }
+
+    fn read_entry(&mut self) -> Result<TocEntry, io::Error> {
+        let dump_id = self.read_int()?;
+        let had_dumper = self.read_int()?;
+        let table_oid = self.read_str()?;
+        let oid = self.read_str()?;
+        let tag = self.read_str()?;
+        let desc = self.read_str()?;
+        let section = self.read_int()?;
+        let defn = self.read_str()?;
+        let drop_stmt = self.read_str()?;
+        let copy_stmt = self.read_str()?;
+        let namespace = self.read_str()?;
+        let tablespace = self.read_str()?;
+        let tableam = self.read_str()?;
+        let relkind = if self.vmin >= VERSION_RELKIND {
+            self.read_int()?
+        } else {
+            0
+        };
+        let owner = self.read_str()?;
+        let with_oids = self.read_str()?;
+        let mut deps = Vec::new();
+        while let Some(dep) = self.read_str()? {
+            deps.push(dep);
+        }
+        let filename = self.read_str()?;
+        Ok(TocEntry {
+            dump_id,
+            had_dumper,
+            table_oid,
+            oid,
+            tag,
+            desc,
+            section,
+            defn,
+            drop_stmt,
+            copy_stmt,
+            namespace,
+            tablespace,
+            tableam,
+            relkind,
+            owner,
+            with_oids,
+            deps,
+            filename,
+        })
+    }
+}
+
+struct TocWriter<W: Write> {
+    writer: W,
+    int_size: usize,
+    vmin: u8,
+}
+
+impl<W: Write> TocWriter<W> {
+    fn write_byte(&mut self, byte: u8) -> Result<(), io::Error> {
+        self.writer.write_all(&[byte])
+    }
+
+    fn write_int(&mut self, val: i64) -> Result<(), io::Error> {
+        let sign = if val < 0 { 1u8 } else { 0u8 };
+        self.write_byte(sign)?;
+        let mut abs = val.unsigned_abs();
+        for _ in 0..self.int_size {
+            self.write_byte((abs & 0xFF) as u8)?;
+            abs >>= 8;
+        }
+        Ok(())
+    }
+
+    fn write_str(&mut self, st: &Option<String>) -> Result<(), io::Error> {
+        match st {
+            Some(st) => {
+                self.write_int(st.len() as i64)?;
+                self.writer.write_all(st.as_bytes())
+            },
+            None => self.write_int(-1)
+        }
+    }
+
+    fn write_header(&mut self, header: &TocHeader) -> Result<(), io::Error> {
+        self.writer.write_all(MAGIC)?;
+        self.write_byte(header.vmaj)?;
+        self.write_byte(header.vmin)?;
+        self.write_byte(header.vrev)?;
+        self.write_byte(header.int_size)?;
+        self.write_byte(header.off_size)?;
+        self.write_byte(header.format)?;
+        if self.vmin >= VERSION_COMPRESSION_ALGORITHM {
+            self.write_byte(header.compression as u8)?;
+        } else {
+            self.write_int(header.compression)?;
+        }
+        for val in header.timestamp.iter() {
+            self.write_int(*val)?;
+        }
+        self.write_str(&header.dbname)?;
+        self.write_str(&header.server_version)?;
+        self.write_str(&header.pg_dump_version)?;
+        Ok(())
+    }
+
+    fn write_entry(&mut self, en: &TocEntry) -> Result<(), io::Error> {
+        self.write_int(en.dump_id)?;
+        self.write_int(en.had_dumper)?;
+        self.write_str(&en.table_oid)?;
+        self.write_str(&en.oid)?;
+        self.write_str(&en.tag)?;
+        self.write_str(&en.desc)?;
+        self.write_int(en.section)?;
+        self.write_str(&en.defn)?;
+        self.write_str(&en.drop_stmt)?;
+        self.write_str(&en.copy_stmt)?;
+        self.write_str(&en.namespace)?;
+        self.write_str(&en.tablespace)?;
+        self.write_str(&en.tableam)?;
+        if self.vmin >= VERSION_RELKIND {
+            self.write_int(en.relkind)?;
+        }
+        self.write_str(&en.owner)?;
+        self.write_str(&en.with_oids)?;
+        for dep in en.deps.iter() {
+            self.write_str(&Some(dep.clone()))?;
+        }
+        self.write_str(&None)?;
+        self.write_str(&en.filename)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_toc(vmin: u8) -> TocArchive {
+        TocArchive {
+            header: TocHeader {
+                vmaj: 1,
+                vmin,
+                vrev: 0,
+                int_size: 4,
+                off_size: 8,
+                format: FORMAT_DIRECTORY,
+                timestamp: vec!(0; 7),
+                dbname: Some("test".to_string()),
+                ..Default::default()
+            },
+            entries: vec!(TocEntry {
+                dump_id: 3000,
+                had_dumper: 1,
+                tag: Some("tab1".to_string()),
+                desc: Some("TABLE DATA".to_string()),
+                namespace: Some("test_dbo".to_string()),
+                owner: Some("test_db_owner".to_string()),
+                copy_stmt: Some("COPY test_dbo.tab1 (id) FROM stdin;\n".to_string()),
+                deps: vec!("2000".to_string()),
+                filename: Some("3000.dat".to_string()),
+                ..Default::default()
+            }),
+            trailer: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for vmin in MIN_VERSION..=MAX_VERSION {
+            let mut written = Vec::new();
+            test_toc(vmin).write(&mut written).unwrap();
+            let toc = TocArchive::read(written.as_slice()).unwrap();
+            assert_eq!(toc.header.vmin, vmin);
+            assert_eq!(toc.header.dbname.as_deref(), Some("test"));
+            let en = toc.find_entry("TABLE DATA", "test_dbo", "tab1").unwrap();
+            assert_eq!(en.owner.as_deref(), Some("test_db_owner"));
+            assert_eq!(en.deps, vec!("2000".to_string()));
+            let mut rewritten = Vec::new();
+            toc.write(&mut rewritten).unwrap();
+            assert_eq!(rewritten, written);
+        }
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let mut written = Vec::new();
+        test_toc(MAX_VERSION).write(&mut written).unwrap();
+        written[6] = MAX_VERSION + 1;
+        let err = TocArchive::read(written.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        written[0] = b'X';
+        assert!(TocArchive::read(written.as_slice()).is_err());
+    }
+}
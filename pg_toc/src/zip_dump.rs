@@ -0,0 +1,261 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use zip::ZipArchive;
+use zip::read::ZipFile;
+
+use super::*;
+
+// Dump is extracted in a single pass: "toc.dat" and data files of Babelfish
+// catalog tables (namespace "sys") are extracted first and passed to the
+// specified rewrite callback, remaining data files are extracted after that
+// as-is. Catalog tables are small, so TOC errors are reported before the
+// bulk of the archive is unpacked.
+pub fn extract_zip_dump<F, R>(zip_path: &Path, dest_parent_dir: &Path, throttle: Throttle, listener: F, rewrite: R) -> Result<PathBuf, io::Error>
+where
+    F: FnMut(&str),
+    R: FnOnce(&Path) -> Result<(), io::Error>
+{
+    extract_zip(zip_path, dest_parent_dir, throttle, listener, rewrite, true)
+}
+
+// Only "toc.dat" and catalog data files are extracted, this is enough
+// to check the TOC rewrite without unpacking the bulk of the data.
+pub fn extract_zip_catalog<F>(zip_path: &Path, dest_parent_dir: &Path, listener: F) -> Result<PathBuf, io::Error>
+where
+    F: FnMut(&str)
+{
+    extract_zip(zip_path, dest_parent_dir, Throttle::unlimited(), listener, |_| Ok(()), false)
+}
+
+fn extract_zip<F, R>(zip_path: &Path, dest_parent_dir: &Path, mut throttle: Throttle, mut listener: F, rewrite: R, with_data: bool) -> Result<PathBuf, io::Error>
+where
+    F: FnMut(&str),
+    R: FnOnce(&Path) -> Result<(), io::Error>
+{
+    let file = File::open(zip_path)?;
+    let mut archive = ZipArchive::new(BufReader::new(file))?;
+    let entries = list_entries(&mut archive)?;
+    let root = match entries.iter().find_map(|(_, path)| path.components().next()) {
+        Some(comp) => PathBuf::from(comp.as_os_str()),
+        None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Zip file is empty: {}", zip_path.to_string_lossy())))
+    };
+    let toc_rel = root.join("toc.dat");
+    let toc_idx = match entries.iter().find(|(_, path)| *path == toc_rel) {
+        Some((idx, _)) => *idx,
+        None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Zip file does not contain a dump, 'toc.dat' not found: {}", zip_path.to_string_lossy())))
+    };
+    let dest_dir = dest_parent_dir.join(&root);
+    if dest_dir.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!(
+            "Extraction directory already exists: {}", dest_dir.to_string_lossy())));
+    }
+    fs::create_dir_all(&dest_dir)?;
+    if let Err(e) = extract_entries(&mut archive, &entries, &root, toc_idx, dest_parent_dir, &mut throttle, &mut listener, rewrite, with_data) {
+        let _ = fs::remove_dir_all(&dest_dir);
+        return Err(e);
+    }
+    Ok(dest_dir)
+}
+
+// Only the central directory is read, so a wrong file is rejected without
+// unpacking anything: zip must contain "toc.dat" and at least one data file
+// inside a single root directory.
+pub fn check_zip_dump(zip_path: &Path) -> Result<(), io::Error> {
+    let not_backup = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, format!(
+        "Not a WiltonDB backup, {}: {}", msg, zip_path.to_string_lossy()));
+    let file = File::open(zip_path)?;
+    let mut archive = match ZipArchive::new(BufReader::new(file)) {
+        Ok(archive) => archive,
+        Err(e) => return Err(not_backup(&format!("error reading zip file: {}", e)))
+    };
+    let entries = list_entries(&mut archive)?;
+    let root = match entries.iter().find_map(|(_, path)| path.components().next()) {
+        Some(comp) => PathBuf::from(comp.as_os_str()),
+        None => return Err(not_backup("zip file is empty"))
+    };
+    if !entries.iter().any(|(_, path)| *path == root.join("toc.dat")) {
+        return Err(not_backup("'toc.dat' not found"));
+    }
+    let has_data = entries.iter().any(|(_, path)| {
+        let in_root = path.parent().map(|parent| parent == root).unwrap_or(false);
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        in_root && "toc.dat" != name && (name.ends_with(".dat") || name.ends_with(".dat.gz"))
+    });
+    if !has_data {
+        return Err(not_backup("no data files found"));
+    }
+    Ok(())
+}
+
+// Every entry is read to the end, zip reader checks CRC of the entry
+// data at the end of the stream, nothing is written to disk.
+pub fn verify_zip(zip_path: &Path) -> Result<usize, io::Error> {
+    let file = File::open(zip_path)?;
+    let mut archive = ZipArchive::new(BufReader::new(file))?;
+    for idx in 0..archive.len() {
+        let mut zf = archive.by_index(idx)?;
+        match io::copy(&mut zf, &mut io::sink()) {
+            Ok(read) if read != zf.size() => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Zip entry size mismatch: {}, expected: {}, read: {}", zf.name(), zf.size(), read))),
+            Ok(_) => { },
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Zip entry is damaged: {}, message: {}", zf.name(), e)))
+        }
+    }
+    Ok(archive.len())
+}
+
+// Space needed to extract the dump: uncompressed size of all entries plus
+// the largest of the TOC and catalog data files, the TOC rewrite writes
+// one file at a time next to the original and then replaces it.
+pub fn extracted_dump_size(zip_path: &Path) -> Result<u64, io::Error> {
+    let file = File::open(zip_path)?;
+    let mut archive = ZipArchive::new(BufReader::new(file))?;
+    let entries = list_entries(&mut archive)?;
+    let mut sizes = HashMap::new();
+    for (idx, path) in entries.iter() {
+        let size = archive.by_index(*idx)?.size();
+        sizes.insert(path.clone(), size);
+    }
+    let total: u64 = sizes.values().sum();
+
+    let summary = DumpSummary::read_zip(zip_path)?;
+    let mut rewritten: u64 = entries.iter()
+        .filter(|(_, path)| path.file_name().map(|name| "toc.dat" == name).unwrap_or(false))
+        .filter_map(|(_, path)| sizes.get(path))
+        .copied()
+        .max()
+        .unwrap_or(0);
+    for en in summary.entries.iter() {
+        if !(en.desc_is("TABLE DATA") && en.namespace_is("sys")) {
+            continue;
+        }
+        if let Some(size) = summary.data_files.get(&en.dump_id).and_then(|path| sizes.get(path)) {
+            rewritten = rewritten.max(*size);
+        }
+    }
+    Ok(total + rewritten)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn extract_entries<F, R>(archive: &mut ZipArchive<BufReader<File>>, entries: &[(usize, PathBuf)], root: &Path,
+                         toc_idx: usize, dest_parent_dir: &Path, throttle: &mut Throttle, listener: &mut F, rewrite: R, with_data: bool) -> Result<(), io::Error>
+where
+    F: FnMut(&str),
+    R: FnOnce(&Path) -> Result<(), io::Error>
+{
+    let dest_dir = dest_parent_dir.join(root);
+
+    // TOC
+    let mut extracted = HashSet::new();
+    extract_entry(archive, toc_idx, dest_parent_dir, throttle, listener)?;
+    extracted.insert(toc_idx);
+
+    // catalog tables
+    let toc = TocArchive::read_file(&dest_dir.join("toc.dat"))?;
+    let mut catalog_files = HashSet::new();
+    for en in toc.entries.iter() {
+        if !(en.desc_is("TABLE DATA") && en.namespace_is("sys")) {
+            continue;
+        }
+        if let Some(name) = &en.filename {
+            if !name.is_empty() {
+                // "NNNN.dat.gz" on disk when compression is enabled
+                catalog_files.insert(root.join(name));
+                catalog_files.insert(root.join(format!("{}.gz", name)));
+            }
+        }
+    }
+    for (idx, path) in entries.iter() {
+        if catalog_files.contains(path) {
+            extract_entry(archive, *idx, dest_parent_dir, throttle, listener)?;
+            extracted.insert(*idx);
+        }
+    }
+    rewrite(&dest_dir)?;
+    if !with_data {
+        return Ok(());
+    }
+
+    // remaining data
+    for (idx, _) in entries.iter() {
+        if !extracted.contains(idx) {
+            extract_entry(archive, *idx, dest_parent_dir, throttle, listener)?;
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn list_entries(archive: &mut ZipArchive<BufReader<File>>) -> Result<Vec<(usize, PathBuf)>, io::Error> {
+    let mut res = Vec::new();
+    for idx in 0..archive.len() {
+        let zf = archive.by_index(idx)?;
+        match zf.enclosed_name() {
+            Some(path) => res.push((idx, path.to_path_buf())),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Invalid entry name in zip file: {}", zf.name())))
+        }
+    }
+    Ok(res)
+}
+
+fn extract_entry<F: FnMut(&str)>(archive: &mut ZipArchive<BufReader<File>>, idx: usize, dest_parent_dir: &Path,
+                                 throttle: &mut Throttle, listener: &mut F) -> Result<(), io::Error> {
+    let mut zf = archive.by_index(idx)?;
+    let path = match zf.enclosed_name() {
+        Some(path) => dest_parent_dir.join(path),
+        None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Invalid entry name in zip file: {}", zf.name())))
+    };
+    listener(zf.name());
+    if zf.is_dir() {
+        fs::create_dir_all(&path)?;
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    copy_entry(&mut zf, &path, throttle)?;
+    Ok(())
+}
+
+// Sizes of the entries over 4 GB are read from Zip64 extra fields, archives
+// repacked by tools without Zip64 support have such sizes truncated to 32 bits,
+// these entries are reported instead of being extracted partially.
+pub(super) fn copy_entry(zf: &mut ZipFile, dest: &Path, throttle: &mut Throttle) -> Result<u64, io::Error> {
+    let mut writer = BufWriter::new(ThrottledWriter::new(File::create(dest)?, throttle));
+    let copied = io::copy(zf, &mut writer)?;
+    writer.flush()?;
+    if copied != zf.size() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Zip entry size mismatch: {}, expected: {}, extracted: {}", zf.name(), zf.size(), copied)));
+    }
+    Ok(copied)
+}
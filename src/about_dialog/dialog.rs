@@ -1,61 +1,61 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-use nwg::EventData;
-
-#[derive(Default)]
-pub struct AboutDialog {
-    pub(super) c: AboutDialogControls,
-
-    args: AboutDialogArgs,
-}
-
-impl AboutDialog {
-}
-
-impl ui::PopupDialog<AboutDialogArgs, ()> for AboutDialog {
-    fn popup(args: AboutDialogArgs) -> ui::PopupJoinHandle<()> {
-        let join_handle = thread::spawn(move || {
-            let data = Self {
-                args,
-                ..Default::default()
-            };
-            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
-            nwg::dispatch_thread_events();
-            dialog.result()
-        });
-        ui::PopupJoinHandle::from(join_handle)
-    }
-
-    fn init(&mut self) {
-        ui::shake_window(&self.c.window);
-    }
-
-    fn result(&mut self) -> () {
-        ()
-    }
-
-    fn close(&mut self, _: nwg::EventData) {
-        self.args.notify_parent();
-        self.c.window.set_visible(false);
-        nwg::stop_thread_dispatch();
-    }
-
-    fn on_resize(&mut self, _: EventData) {
-        self.c.update_tab_order();
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct AboutDialog {
+    pub(super) c: AboutDialogControls,
+
+    args: AboutDialogArgs,
+}
+
+impl AboutDialog {
+}
+
+impl ui::PopupDialog<AboutDialogArgs, ()> for AboutDialog {
+    fn popup(args: AboutDialogArgs) -> ui::PopupJoinHandle<()> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> () {
+        ()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
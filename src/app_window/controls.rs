@@ -1,319 +1,1195 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-const COLOR_WHITE: [u8; 3] = [255, 255, 255];
-
-#[derive(Default)]
-pub(super) struct AppWindowControls {
-    layout: AppWindowLayout,
-
-    pub(super) font_normal: nwg::Font,
-    pub(super) font_small: nwg::Font,
-
-    pub(super) icon: nwg::Icon,
-    pub(super) window: nwg::Window,
-
-    pub(super) file_menu: nwg::Menu,
-    pub(super) file_connect_menu_item: nwg::MenuItem,
-    pub(super) file_exit_menu_item: nwg::MenuItem,
-    pub(super) help_menu: nwg::Menu,
-    pub(super) help_about_menu_item: nwg::MenuItem,
-    pub(super) help_website_menu_item: nwg::MenuItem,
-
-    pub(super) tabs_container: nwg::TabsContainer,
-    pub(super) backup_tab: nwg::Tab,
-    pub(super) restore_tab: nwg::Tab,
-
-    pub(super) backup_dbname_label: nwg::Label,
-    pub(super) backup_dbname_combo: nwg::ComboBox<String>,
-    pub(super) backup_dbname_reload_button: nwg::Button,
-    pub(super) backup_dest_dir_label: nwg::Label,
-    pub(super) backup_dest_dir_input: nwg::TextInput,
-    pub(super) backup_dest_dir_button: nwg::Button,
-    pub(super) backup_dest_dir_chooser: nwg::FileDialog,
-    pub(super) backup_filename_label: nwg::Label,
-    pub(super) backup_filename_input: nwg::TextInput,
-    pub(super) backup_run_button: nwg::Button,
-    pub(super) backup_close_button: nwg::Button,
-
-    pub(super) restore_src_file_label: nwg::Label,
-    pub(super) restore_src_file_input: nwg::TextInput,
-    pub(super) restore_src_file_button: nwg::Button,
-    pub(super) restore_src_file_chooser: nwg::FileDialog,
-    pub(super) restore_bbf_db_label: nwg::Label,
-    pub(super) restore_bbf_db_input: nwg::TextInput,
-    pub(super) restore_dbname_label: nwg::Label,
-    pub(super) restore_dbname_input: nwg::TextInput,
-    pub(super) restore_run_button: nwg::Button,
-    pub(super) restore_close_button: nwg::Button,
-
-    pub(super) status_bar: nwg::StatusBar,
-
-    pub(super) about_notice: ui::SyncNotice,
-    pub(super) connect_notice: ui::SyncNotice,
-    pub(super) load_notice: ui::SyncNotice,
-    pub(super) backup_dialog_notice: ui::SyncNotice,
-    pub(super) restore_dialog_notice: ui::SyncNotice,
-}
-
-impl ui::Controls for AppWindowControls {
-    fn build(&mut self) -> Result<(), nwg::NwgError> {
-        // fonts
-        nwg::Font::builder()
-            .size(ui::font_size_builder()
-                .normal()
-                .build())
-            .build(&mut self.font_normal)?;
-        nwg::Font::builder()
-            .size(ui::font_size_builder()
-                .small()
-                .build())
-            .build(&mut self.font_small)?;
-
-        // window
-
-        nwg::Icon::builder()
-            .source_embed(Some(&nwg::EmbedResource::load(None)
-                .expect("Error loading embedded resource")))
-            .source_embed_id(2)
-            .build(&mut self.icon)?;
-
-        nwg::Window::builder()
-            .size((520, 320))
-            .icon(Some(&self.icon))
-            .center(true)
-            .title("WiltonDB Backup Tool")
-            .build(&mut self.window)?;
-
-        // menu
-
-        nwg::Menu::builder()
-            .parent(&self.window)
-            .text("File")
-            .build(&mut self.file_menu)?;
-        nwg::MenuItem::builder()
-            .parent(&self.file_menu)
-            .text("DB Connection")
-            .build(&mut self.file_connect_menu_item)?;
-        nwg::MenuItem::builder()
-            .parent(&self.file_menu)
-            .text("Exit")
-            .build(&mut self.file_exit_menu_item)?;
-
-        nwg::Menu::builder()
-            .parent(&self.window)
-            .text("Help")
-            .build(&mut self.help_menu)?;
-        nwg::MenuItem::builder()
-            .parent(&self.help_menu)
-            .text("About")
-            .build(&mut self.help_about_menu_item)?;
-        nwg::MenuItem::builder()
-            .parent(&self.help_menu)
-            .text("Website")
-            .build(&mut self.help_website_menu_item)?;
-
-        // tabs
-
-        nwg::TabsContainer::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.tabs_container)?;
-        nwg::Tab::builder()
-            .text("Backup")
-            .parent(&self.tabs_container)
-            .build(&mut self.backup_tab)?;
-        nwg::Tab::builder()
-            .text("Restore")
-            .parent(&self.tabs_container)
-            .build(&mut self.restore_tab)?;
-
-        // backup form
-
-        nwg::Label::builder()
-            .text("Database:")
-            .font(Some(&self.font_normal))
-            .background_color(Some(COLOR_WHITE))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_dbname_label)?;
-        nwg::ComboBox::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_dbname_combo)?;
-        nwg::Button::builder()
-            .text("Reload")
-            .font(Some(&self.font_normal))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_dbname_reload_button)?;
-
-        nwg::Label::builder()
-            .text("Destination dir.:")
-            .font(Some(&self.font_normal))
-            .background_color(Some(COLOR_WHITE))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_dest_dir_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .text(&std::env::var("USERPROFILE").unwrap_or(String::new()))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_dest_dir_input)?;
-        nwg::Button::builder()
-            .text("Choose")
-            .font(Some(&self.font_normal))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_dest_dir_button)?;
-        nwg::FileDialog::builder()
-            .title("Choose destination directory")
-            .action(nwg::FileDialogAction::OpenDirectory)
-            .build(&mut self.backup_dest_dir_chooser)?;
-        nwg::Label::builder()
-            .text("Backup file name:")
-            .font(Some(&self.font_normal))
-            .background_color(Some(COLOR_WHITE))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_filename_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_filename_input)?;
-
-        // backup buttons
-
-        nwg::Button::builder()
-            .text("Run Backup")
-            .font(Some(&self.font_normal))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_run_button)?;
-        nwg::Button::builder()
-            .text("Close")
-            .font(Some(&self.font_normal))
-            .parent(&self.backup_tab)
-            .build(&mut self.backup_close_button)?;
-
-        // restore form
-
-        nwg::Label::builder()
-            .text("Backup file:")
-            .font(Some(&self.font_normal))
-            .background_color(Some(COLOR_WHITE))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_src_file_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_src_file_input)?;
-        nwg::Button::builder()
-            .text("Choose")
-            .font(Some(&self.font_normal))
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_src_file_button)?;
-        nwg::FileDialog::builder()
-            .title("Choose backup file")
-            .action(nwg::FileDialogAction::Open)
-            .build(&mut self.restore_src_file_chooser)?;
-        nwg::Label::builder()
-            .text("Postgres DB name:")
-            .font(Some(&self.font_normal))
-            .background_color(Some(COLOR_WHITE))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_bbf_db_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .text("")
-            .readonly(true)
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_bbf_db_input)?;
-        nwg::Label::builder()
-            .text("Restore into DB:")
-            .font(Some(&self.font_normal))
-            .background_color(Some(COLOR_WHITE))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_dbname_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_dbname_input)?;
-
-        // restore buttons
-
-        nwg::Button::builder()
-            .text("Run Restore")
-            .font(Some(&self.font_normal))
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_run_button)?;
-        nwg::Button::builder()
-            .text("Close")
-            .font(Some(&self.font_normal))
-            .parent(&self.restore_tab)
-            .build(&mut self.restore_close_button)?;
-
-        // other
-
-        nwg::StatusBar::builder()
-            .parent(&self.window)
-            .font(Some(&self.font_small))
-            .build(&mut self.status_bar)?;
-
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.about_notice)?;
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.connect_notice)?;
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.load_notice)?;
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.backup_dialog_notice)?;
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.restore_dialog_notice)?;
-
-        self.layout.build(&self)?;
-
-        Ok(())
-    }
-
-    fn update_tab_order(&self) {
-        ui::tab_order_builder()
-            .control(&self.backup_dbname_combo)
-            .control(&self.backup_dbname_reload_button)
-            .control(&self.backup_dest_dir_input)
-            .control(&self.backup_dest_dir_button)
-            .control(&self.backup_filename_input)
-            .control(&self.backup_run_button)
-            .control(&self.backup_close_button)
-            .build();
-
-        ui::tab_order_builder()
-            .control(&self.restore_src_file_input)
-            .control(&self.restore_src_file_button)
-            .control(&self.restore_bbf_db_input)
-            .control(&self.restore_dbname_input)
-            .control(&self.restore_run_button)
-            .control(&self.restore_close_button)
-            .build();
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+const COLOR_WHITE: [u8; 3] = [255, 255, 255];
+
+#[derive(Default)]
+pub(super) struct AppWindowControls {
+    layout: AppWindowLayout,
+
+    pub(super) font_normal: nwg::Font,
+    pub(super) font_small: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) file_menu: nwg::Menu,
+    pub(super) file_connect_menu_item: nwg::MenuItem,
+    pub(super) file_copy_menu_item: nwg::MenuItem,
+    pub(super) file_clone_menu_item: nwg::MenuItem,
+    pub(super) file_migrate_menu_item: nwg::MenuItem,
+    pub(super) file_email_menu_item: nwg::MenuItem,
+    pub(super) file_toc_json_menu_item: nwg::MenuItem,
+    pub(super) file_ddl_menu_item: nwg::MenuItem,
+    pub(super) file_settings_export_menu_item: nwg::MenuItem,
+    pub(super) file_settings_import_menu_item: nwg::MenuItem,
+    pub(super) file_exit_menu_item: nwg::MenuItem,
+    pub(super) queue_menu: nwg::Menu,
+    pub(super) queue_add_menu_item: nwg::MenuItem,
+    pub(super) queue_show_menu_item: nwg::MenuItem,
+    pub(super) help_menu: nwg::Menu,
+    pub(super) help_about_menu_item: nwg::MenuItem,
+    pub(super) help_logs_menu_item: nwg::MenuItem,
+    pub(super) help_website_menu_item: nwg::MenuItem,
+
+    pub(super) tray: nwg::TrayNotification,
+    pub(super) tray_menu: nwg::Menu,
+    pub(super) tray_show_menu_item: nwg::MenuItem,
+    pub(super) tray_backup_menu_item: nwg::MenuItem,
+    pub(super) tray_exit_menu_item: nwg::MenuItem,
+    pub(super) scheduler_timer: nwg::AnimationTimer,
+
+    pub(super) tabs_container: nwg::TabsContainer,
+    pub(super) backup_tab: nwg::Tab,
+    pub(super) restore_tab: nwg::Tab,
+    pub(super) catalog_tab: nwg::Tab,
+    pub(super) export_tab: nwg::Tab,
+    pub(super) physical_tab: nwg::Tab,
+
+    pub(super) backup_dbname_label: nwg::Label,
+    pub(super) backup_dbname_combo: nwg::ComboBox<String>,
+    pub(super) backup_dbname_filter_input: nwg::TextInput,
+    pub(super) backup_dbname_reload_button: nwg::Button,
+    pub(super) backup_dbname_list_button: nwg::Button,
+    pub(super) backup_bbf_db_label: nwg::Label,
+    pub(super) backup_bbf_db_input: nwg::TextInput,
+    pub(super) backup_restore_script_checkbox: nwg::CheckBox,
+    pub(super) backup_dest_dir_label: nwg::Label,
+    pub(super) backup_dest_dir_input: nwg::TextInput,
+    pub(super) backup_dest_dir_button: nwg::Button,
+    pub(super) backup_dest_dir_chooser: nwg::FileDialog,
+    pub(super) backup_format_label: nwg::Label,
+    pub(super) backup_format_combo: nwg::ComboBox<String>,
+    pub(super) backup_globals_checkbox: nwg::CheckBox,
+    pub(super) backup_mode_combo: nwg::ComboBox<String>,
+    pub(super) backup_filename_label: nwg::Label,
+    pub(super) backup_filename_input: nwg::TextInput,
+    pub(super) backup_auto_increment_checkbox: nwg::CheckBox,
+    pub(super) backup_throttle_combo: nwg::ComboBox<String>,
+    pub(super) backup_retention_label: nwg::Label,
+    pub(super) backup_retention_combo: nwg::ComboBox<String>,
+    pub(super) backup_retention_input: nwg::TextInput,
+    pub(super) backup_volume_combo: nwg::ComboBox<String>,
+    pub(super) backup_help_box: nwg::TextBox,
+    pub(super) backup_forecast_label: nwg::Label,
+    pub(super) backup_help_button: nwg::Button,
+    pub(super) backup_schedule_button: nwg::Button,
+    pub(super) backup_run_button: nwg::Button,
+    pub(super) backup_close_button: nwg::Button,
+
+    pub(super) restore_src_file_label: nwg::Label,
+    pub(super) restore_src_file_input: nwg::TextInput,
+    pub(super) restore_src_file_button: nwg::Button,
+    pub(super) restore_src_file_chooser: nwg::FileDialog,
+    pub(super) restore_src_dir_button: nwg::Button,
+    pub(super) restore_src_dir_chooser: nwg::FileDialog,
+    pub(super) toc_json_file_chooser: nwg::FileDialog,
+    pub(super) ddl_file_chooser: nwg::FileDialog,
+    pub(super) settings_export_file_chooser: nwg::FileDialog,
+    pub(super) settings_import_file_chooser: nwg::FileDialog,
+    pub(super) restore_bbf_db_label: nwg::Label,
+    pub(super) restore_bbf_db_input: nwg::TextInput,
+    pub(super) restore_dbname_label: nwg::Label,
+    pub(super) restore_dbname_input: nwg::TextInput,
+    pub(super) restore_dbname_suggest_button: nwg::Button,
+    pub(super) restore_src_info_label: nwg::Label,
+    pub(super) restore_src_info_input: nwg::TextInput,
+    pub(super) restore_help_box: nwg::TextBox,
+    pub(super) restore_forecast_label: nwg::Label,
+    pub(super) restore_help_button: nwg::Button,
+    pub(super) restore_options_button: nwg::Button,
+    pub(super) restore_validate_button: nwg::Button,
+    pub(super) restore_run_button: nwg::Button,
+    pub(super) restore_run_tooltip: nwg::Tooltip,
+    pub(super) restore_close_button: nwg::Button,
+
+    pub(super) catalog_dir_label: nwg::Label,
+    pub(super) catalog_dir_input: nwg::TextInput,
+    pub(super) catalog_dir_button: nwg::Button,
+    pub(super) catalog_dir_chooser: nwg::FileDialog,
+    pub(super) catalog_scan_button: nwg::Button,
+    pub(super) catalog_list_view: nwg::ListView,
+    pub(super) catalog_age_label: nwg::Label,
+    pub(super) catalog_age_input: nwg::TextInput,
+    pub(super) catalog_delete_older_button: nwg::Button,
+    pub(super) catalog_recycle_checkbox: nwg::CheckBox,
+    pub(super) catalog_status_label: nwg::Label,
+    pub(super) catalog_restore_button: nwg::Button,
+    pub(super) catalog_verify_button: nwg::Button,
+    pub(super) catalog_delete_button: nwg::Button,
+    pub(super) catalog_close_button: nwg::Button,
+
+    pub(super) export_dbname_label: nwg::Label,
+    pub(super) export_dbname_combo: nwg::ComboBox<String>,
+    pub(super) export_tables_button: nwg::Button,
+    pub(super) export_tables_view: nwg::ListView,
+    pub(super) export_dest_dir_label: nwg::Label,
+    pub(super) export_dest_dir_input: nwg::TextInput,
+    pub(super) export_dest_dir_button: nwg::Button,
+    pub(super) export_dest_dir_chooser: nwg::FileDialog,
+    pub(super) export_status_label: nwg::Label,
+    pub(super) export_select_all_button: nwg::Button,
+    pub(super) export_import_button: nwg::Button,
+    pub(super) export_import_file_chooser: nwg::FileDialog,
+    pub(super) export_bacpac_button: nwg::Button,
+    pub(super) export_run_button: nwg::Button,
+    pub(super) export_close_button: nwg::Button,
+
+    pub(super) physical_dest_dir_label: nwg::Label,
+    pub(super) physical_dest_dir_input: nwg::TextInput,
+    pub(super) physical_dest_dir_button: nwg::Button,
+    pub(super) physical_dest_dir_chooser: nwg::FileDialog,
+    pub(super) physical_format_label: nwg::Label,
+    pub(super) physical_format_combo: nwg::ComboBox<String>,
+    pub(super) physical_filename_label: nwg::Label,
+    pub(super) physical_filename_input: nwg::TextInput,
+    pub(super) physical_wal_dir_label: nwg::Label,
+    pub(super) physical_wal_dir_input: nwg::TextInput,
+    pub(super) physical_wal_dir_button: nwg::Button,
+    pub(super) physical_wal_dir_chooser: nwg::FileDialog,
+    pub(super) physical_archive_status_label: nwg::Label,
+    pub(super) physical_archive_enable_button: nwg::Button,
+    pub(super) physical_archive_status_button: nwg::Button,
+    pub(super) physical_pitr_button: nwg::Button,
+    pub(super) physical_help_box: nwg::TextBox,
+    pub(super) physical_run_button: nwg::Button,
+    pub(super) physical_close_button: nwg::Button,
+
+    pub(super) status_bar: nwg::StatusBar,
+
+    pub(super) about_notice: ui::SyncNotice,
+    pub(super) connect_notice: ui::SyncNotice,
+    pub(super) copy_connect_notice: ui::SyncNotice,
+    pub(super) copy_notice: ui::SyncNotice,
+    pub(super) clone_notice: ui::SyncNotice,
+    pub(super) migrate_notice: ui::SyncNotice,
+    pub(super) load_notice: ui::SyncNotice,
+    pub(super) backup_dialog_notice: ui::SyncNotice,
+    pub(super) restore_dialog_notice: ui::SyncNotice,
+    pub(super) restore_validate_notice: ui::SyncNotice,
+    pub(super) restore_ddl_notice: ui::SyncNotice,
+    pub(super) globals_notice: ui::SyncNotice,
+    pub(super) db_list_notice: ui::SyncNotice,
+    pub(super) restore_options_notice: ui::SyncNotice,
+    pub(super) preflight_notice: ui::SyncNotice,
+    pub(super) share_credentials_notice: ui::SyncNotice,
+    pub(super) schedule_notice: ui::SyncNotice,
+    pub(super) email_notice: ui::SyncNotice,
+    pub(super) logs_notice: ui::SyncNotice,
+    pub(super) instance_notice: ui::SyncNoticeValue<String>,
+    pub(super) src_info_notice: ui::SyncNoticeValue<String>,
+    pub(super) catalog_scan_notice: ui::SyncNotice,
+    pub(super) catalog_verify_notice: ui::SyncNotice,
+    pub(super) export_tables_notice: ui::SyncNotice,
+    pub(super) export_dialog_notice: ui::SyncNotice,
+    pub(super) import_dialog_notice: ui::SyncNotice,
+    pub(super) wal_archive_notice: ui::SyncNotice,
+    pub(super) pitr_notice: ui::SyncNotice,
+    pub(super) pitr_restore_notice: ui::SyncNotice,
+    pub(super) scheduled_backup_notice: ui::SyncNotice,
+    pub(super) queue_notice: ui::SyncNotice,
+    pub(super) job_queue_notice: ui::SyncNotice,
+}
+
+impl ui::Controls for AppWindowControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        // fonts
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .small()
+                .build())
+            .build(&mut self.font_small)?;
+
+        // window
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((600, 350))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("WiltonDB Backup Tool")
+            .accept_files(true)
+            .build(&mut self.window)?;
+
+        // menu
+
+        nwg::Menu::builder()
+            .parent(&self.window)
+            .text("File")
+            .build(&mut self.file_menu)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("DB Connection")
+            .build(&mut self.file_connect_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Copy DB to another server")
+            .build(&mut self.file_copy_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Clone DB on this server")
+            .build(&mut self.file_clone_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Migrate from SQL Server")
+            .build(&mut self.file_migrate_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Email notifications")
+            .build(&mut self.file_email_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Export backup TOC as JSON")
+            .build(&mut self.file_toc_json_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Export schema DDL script")
+            .build(&mut self.file_ddl_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Export settings")
+            .build(&mut self.file_settings_export_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Import settings")
+            .build(&mut self.file_settings_import_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.file_menu)
+            .text("Exit")
+            .build(&mut self.file_exit_menu_item)?;
+
+        nwg::Menu::builder()
+            .parent(&self.window)
+            .text("Queue")
+            .build(&mut self.queue_menu)?;
+        nwg::MenuItem::builder()
+            .parent(&self.queue_menu)
+            .text("Add backup to queue")
+            .build(&mut self.queue_add_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.queue_menu)
+            .text("Show queue")
+            .build(&mut self.queue_show_menu_item)?;
+
+        nwg::Menu::builder()
+            .parent(&self.window)
+            .text("Help")
+            .build(&mut self.help_menu)?;
+        nwg::MenuItem::builder()
+            .parent(&self.help_menu)
+            .text("About")
+            .build(&mut self.help_about_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.help_menu)
+            .text("Open Logs")
+            .build(&mut self.help_logs_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.help_menu)
+            .text("Website")
+            .build(&mut self.help_website_menu_item)?;
+
+        // tray
+
+        nwg::TrayNotification::builder()
+            .parent(&self.window)
+            .icon(Some(&self.icon))
+            .tip(Some("WiltonDB Backup Tool"))
+            .visible(false)
+            .build(&mut self.tray)?;
+        nwg::Menu::builder()
+            .parent(&self.window)
+            .popup(true)
+            .build(&mut self.tray_menu)?;
+        nwg::MenuItem::builder()
+            .parent(&self.tray_menu)
+            .text("Open")
+            .build(&mut self.tray_show_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.tray_menu)
+            .text("Quick Backup")
+            .build(&mut self.tray_backup_menu_item)?;
+        nwg::MenuItem::builder()
+            .parent(&self.tray_menu)
+            .text("Exit")
+            .build(&mut self.tray_exit_menu_item)?;
+        nwg::AnimationTimer::builder()
+            .parent(&self.window)
+            .interval(std::time::Duration::from_secs(15))
+            .active(true)
+            .build(&mut self.scheduler_timer)?;
+
+        // tabs
+
+        nwg::TabsContainer::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.tabs_container)?;
+        nwg::Tab::builder()
+            .text("Backup")
+            .parent(&self.tabs_container)
+            .build(&mut self.backup_tab)?;
+        nwg::Tab::builder()
+            .text("Restore")
+            .parent(&self.tabs_container)
+            .build(&mut self.restore_tab)?;
+        nwg::Tab::builder()
+            .text("Catalog")
+            .parent(&self.tabs_container)
+            .build(&mut self.catalog_tab)?;
+        nwg::Tab::builder()
+            .text("Export")
+            .parent(&self.tabs_container)
+            .build(&mut self.export_tab)?;
+        nwg::Tab::builder()
+            .text("Physical")
+            .parent(&self.tabs_container)
+            .build(&mut self.physical_tab)?;
+
+        // backup form
+
+        nwg::Label::builder()
+            .text("Database:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dbname_label)?;
+        nwg::ComboBox::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dbname_combo)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Filter"))
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dbname_filter_input)?;
+        nwg::Button::builder()
+            .text("Reload")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dbname_reload_button)?;
+        nwg::Button::builder()
+            .text("Sizes")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dbname_list_button)?;
+        nwg::Label::builder()
+            .text("Postgres DB name:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_bbf_db_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .text("")
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_bbf_db_input)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Restore script")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_restore_script_checkbox)?;
+
+        nwg::Label::builder()
+            .text("Destination dir.:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dest_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .text(&std::env::var("USERPROFILE").unwrap_or(String::new()))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dest_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_dest_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose destination directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.backup_dest_dir_chooser)?;
+        nwg::Label::builder()
+            .text("Format:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_format_label)?;
+        nwg::ComboBox::builder()
+            .collection(BackupFormat::all().iter().map(|fmt| fmt.label().to_string()).collect())
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_format_combo)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Include logins")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_globals_checkbox)?;
+        nwg::ComboBox::builder()
+            .collection(BackupMode::all().iter().map(|mode| mode.label().to_string()).collect())
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_mode_combo)?;
+        nwg::Label::builder()
+            .text("Backup file name:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_filename_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_filename_input)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Add _1, _2 if exists")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_auto_increment_checkbox)?;
+        nwg::ComboBox::builder()
+            .collection(throttle_presets().iter().map(|(_, label)| label.to_string()).collect())
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_throttle_combo)?;
+        nwg::Label::builder()
+            .text("Retention:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_retention_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                "Keep all backups".to_string(),
+                "Keep N most recent backups".to_string(),
+                "Delete backups older than N days".to_string(),
+            ))
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_retention_combo)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_retention_input)?;
+        nwg::ComboBox::builder()
+            .collection(volume_size_presets().iter().map(|(_, label)| label.to_string()).collect())
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_volume_combo)?;
+
+        nwg::TextBox::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .flags(nwg::TextBoxFlags::VSCROLL | nwg::TextBoxFlags::AUTOVSCROLL)
+            .readonly(true)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_help_box)?;
+
+        // backup buttons
+
+        nwg::Label::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_forecast_label)?;
+        nwg::Button::builder()
+            .text("Help")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_help_button)?;
+        nwg::Button::builder()
+            .text("Schedule")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_schedule_button)?;
+        nwg::Button::builder()
+            .text("Run Backup")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_run_button)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.backup_tab)
+            .build(&mut self.backup_close_button)?;
+
+        // restore form
+
+        nwg::Label::builder()
+            .text("Backup file:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_src_file_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_src_file_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_src_file_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose backup file")
+            .action(nwg::FileDialogAction::Open)
+            .build(&mut self.restore_src_file_chooser)?;
+        nwg::Button::builder()
+            .text("Directory")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_src_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose unzipped backup directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.restore_src_dir_chooser)?;
+        nwg::FileDialog::builder()
+            .title("Save backup TOC as JSON")
+            .action(nwg::FileDialogAction::Save)
+            .filters("JSON(*.json)|Any(*.*)")
+            .build(&mut self.toc_json_file_chooser)?;
+        nwg::FileDialog::builder()
+            .title("Save schema DDL script")
+            .action(nwg::FileDialogAction::Save)
+            .filters("SQL(*.sql)|Any(*.*)")
+            .build(&mut self.ddl_file_chooser)?;
+        nwg::FileDialog::builder()
+            .title("Export settings")
+            .action(nwg::FileDialogAction::Save)
+            .filters("JSON(*.json)|Any(*.*)")
+            .build(&mut self.settings_export_file_chooser)?;
+        nwg::FileDialog::builder()
+            .title("Import settings")
+            .action(nwg::FileDialogAction::Open)
+            .filters("JSON(*.json)|Any(*.*)")
+            .build(&mut self.settings_import_file_chooser)?;
+        nwg::Label::builder()
+            .text("Postgres DB name:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_bbf_db_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .text("")
+            .readonly(true)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_bbf_db_input)?;
+        nwg::Label::builder()
+            .text("Restore into DB:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_dbname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_dbname_input)?;
+        nwg::Button::builder()
+            .text("Use suggested")
+            .font(Some(&self.font_normal))
+            .enabled(false)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_dbname_suggest_button)?;
+        nwg::Label::builder()
+            .text("Original DB:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_src_info_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .readonly(true)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_src_info_input)?;
+
+        nwg::TextBox::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .flags(nwg::TextBoxFlags::VSCROLL | nwg::TextBoxFlags::AUTOVSCROLL)
+            .readonly(true)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_help_box)?;
+
+        // restore buttons
+
+        nwg::Label::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_forecast_label)?;
+        nwg::Button::builder()
+            .text("Help")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_help_button)?;
+        nwg::Button::builder()
+            .text("Advanced")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_options_button)?;
+        nwg::Button::builder()
+            .text("Validate only")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_validate_button)?;
+        nwg::Button::builder()
+            .text("Run Restore")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_run_button)?;
+        nwg::Tooltip::builder()
+            .register(&self.restore_run_button, "")
+            .register(&self.restore_dbname_input, "")
+            .build(&mut self.restore_run_tooltip)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.restore_tab)
+            .build(&mut self.restore_close_button)?;
+
+        // catalog form
+
+        nwg::Label::builder()
+            .text("Backup folder:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose backup folder")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.catalog_dir_chooser)?;
+        nwg::Button::builder()
+            .text("Scan")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_scan_button)?;
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_list_view)?;
+        self.catalog_list_view.insert_column("File");
+        self.catalog_list_view.insert_column("Database");
+        self.catalog_list_view.insert_column("Dumped");
+        self.catalog_list_view.insert_column("Size");
+        self.catalog_list_view.insert_column("Server");
+        self.catalog_list_view.set_column_width(0, 150);
+        self.catalog_list_view.set_column_width(1, 100);
+        self.catalog_list_view.set_column_width(2, 120);
+        self.catalog_list_view.set_column_width(3, 70);
+        self.catalog_list_view.set_column_width(4, 120);
+        self.catalog_list_view.set_headers_enabled(true);
+        nwg::Label::builder()
+            .text("Older than, days:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_age_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_age_input)?;
+        nwg::Button::builder()
+            .text("Delete older")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_delete_older_button)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Checked)
+            .text("Use recycle bin")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_recycle_checkbox)?;
+
+        // catalog buttons
+
+        nwg::Label::builder()
+            .text("")
+            .flags(nwg::LabelFlags::VISIBLE | nwg::LabelFlags::ELIPSIS)
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_status_label)?;
+        nwg::Button::builder()
+            .text("Restore")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_restore_button)?;
+        nwg::Button::builder()
+            .text("Verify")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_verify_button)?;
+        nwg::Button::builder()
+            .text("Delete")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_delete_button)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.catalog_tab)
+            .build(&mut self.catalog_close_button)?;
+
+        // export form
+
+        nwg::Label::builder()
+            .text("Database:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.export_tab)
+            .build(&mut self.export_dbname_label)?;
+        nwg::ComboBox::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_dbname_combo)?;
+        nwg::Button::builder()
+            .text("Load tables")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_tables_button)?;
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.export_tab)
+            .build(&mut self.export_tables_view)?;
+        self.export_tables_view.insert_column("Schema");
+        self.export_tables_view.insert_column("Table");
+        self.export_tables_view.insert_column("Rows (estimate)");
+        self.export_tables_view.set_column_width(0, 120);
+        self.export_tables_view.set_column_width(1, 220);
+        self.export_tables_view.set_column_width(2, 120);
+        self.export_tables_view.set_headers_enabled(true);
+        nwg::Label::builder()
+            .text("Destination dir.:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.export_tab)
+            .build(&mut self.export_dest_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .text(&std::env::var("USERPROFILE").unwrap_or(String::new()))
+            .parent(&self.export_tab)
+            .build(&mut self.export_dest_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_dest_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose destination directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.export_dest_dir_chooser)?;
+
+        // export buttons
+
+        nwg::Label::builder()
+            .text("")
+            .flags(nwg::LabelFlags::VISIBLE | nwg::LabelFlags::ELIPSIS)
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.export_tab)
+            .build(&mut self.export_status_label)?;
+        nwg::Button::builder()
+            .text("Select all")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_select_all_button)?;
+        nwg::Button::builder()
+            .text("Import CSV")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_import_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose CSV file to import")
+            .action(nwg::FileDialogAction::Open)
+            .filters("CSV(*.csv)|Any(*.*)")
+            .build(&mut self.export_import_file_chooser)?;
+        nwg::Button::builder()
+            .text("Export BACPAC")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_bacpac_button)?;
+        nwg::Button::builder()
+            .text("Export")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_run_button)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.export_tab)
+            .build(&mut self.export_close_button)?;
+
+        // physical backup form
+
+        nwg::Label::builder()
+            .text("Destination dir.:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_dest_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .text(&std::env::var("USERPROFILE").unwrap_or(String::new()))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_dest_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_dest_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose destination directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.physical_dest_dir_chooser)?;
+        nwg::Label::builder()
+            .text("Format:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_format_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                BackupFormat::Zip.label().to_string(),
+                BackupFormat::Directory.label().to_string(),
+            ))
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_format_combo)?;
+        nwg::Label::builder()
+            .text("Backup file name:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_filename_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .text(&BackupFormat::Zip.backup_name("wiltondb_physical"))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_filename_input)?;
+        nwg::Label::builder()
+            .text("WAL archive dir.:")
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_wal_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_wal_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_wal_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose WAL archive directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.physical_wal_dir_chooser)?;
+        nwg::Label::builder()
+            .text("")
+            .flags(nwg::LabelFlags::VISIBLE | nwg::LabelFlags::ELIPSIS)
+            .font(Some(&self.font_normal))
+            .background_color(Some(COLOR_WHITE))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_archive_status_label)?;
+        nwg::Button::builder()
+            .text("Enable archiving")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_archive_enable_button)?;
+        nwg::Button::builder()
+            .text("Archive status")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_archive_status_button)?;
+        nwg::Button::builder()
+            .text("Point-in-time restore")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_pitr_button)?;
+        nwg::TextBox::builder()
+            .text(&[
+                "Physical backup copies the whole WiltonDB instance (all databases, logins and settings) using pg_basebackup.",
+                "",
+                "The user must have REPLICATION privilege and a \"replication\" entry must be allowed in pg_hba.conf on the server.",
+                "",
+                "Backup contains \"base.tar.gz\" and \"pg_wal.tar.gz\" archives, to restore it stop the server, extract \"base.tar.gz\" into an empty data directory and \"pg_wal.tar.gz\" into its \"pg_wal\" subdirectory.",
+                "",
+                "WAL archiving copies every completed WAL file into the WAL archive directory, the directory must be specified as a path on the server machine that is writable by the server service account. Enabling archiving requires superuser privileges and a server restart.",
+                "",
+                "Point-in-time restore lays down a physical backup into a new data directory on this machine and configures the server to replay archived WAL up to the chosen time on its first start.",
+            ].join("\r\n"))
+            .font(Some(&self.font_normal))
+            .flags(nwg::TextBoxFlags::VSCROLL | nwg::TextBoxFlags::AUTOVSCROLL)
+            .readonly(true)
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_help_box)?;
+
+        // physical backup buttons
+
+        nwg::Button::builder()
+            .text("Run Backup")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_run_button)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.physical_tab)
+            .build(&mut self.physical_close_button)?;
+
+        // other
+
+        nwg::StatusBar::builder()
+            .parent(&self.window)
+            .font(Some(&self.font_small))
+            .build(&mut self.status_bar)?;
+
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.about_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.connect_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.copy_connect_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.copy_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.clone_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.migrate_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.load_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.backup_dialog_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.restore_dialog_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.restore_validate_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.restore_ddl_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.globals_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.db_list_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.restore_options_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.preflight_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.share_credentials_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.schedule_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.email_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.logs_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.instance_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.src_info_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.catalog_scan_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.catalog_verify_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.export_tables_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.export_dialog_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.import_dialog_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.wal_archive_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.pitr_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.pitr_restore_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.scheduled_backup_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.queue_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.job_queue_notice)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.backup_dbname_combo)
+            .control(&self.backup_dbname_filter_input)
+            .control(&self.backup_dbname_reload_button)
+            .control(&self.backup_dbname_list_button)
+            .control(&self.backup_bbf_db_input)
+            .control(&self.backup_restore_script_checkbox)
+            .control(&self.backup_dest_dir_input)
+            .control(&self.backup_dest_dir_button)
+            .control(&self.backup_format_combo)
+            .control(&self.backup_globals_checkbox)
+            .control(&self.backup_mode_combo)
+            .control(&self.backup_filename_input)
+            .control(&self.backup_auto_increment_checkbox)
+            .control(&self.backup_throttle_combo)
+            .control(&self.backup_retention_combo)
+            .control(&self.backup_retention_input)
+            .control(&self.backup_volume_combo)
+            .control(&self.backup_help_box)
+            .control(&self.backup_help_button)
+            .control(&self.backup_schedule_button)
+            .control(&self.backup_run_button)
+            .control(&self.backup_close_button)
+            .build();
+
+        ui::tab_order_builder()
+            .control(&self.restore_src_file_input)
+            .control(&self.restore_src_file_button)
+            .control(&self.restore_src_dir_button)
+            .control(&self.restore_bbf_db_input)
+            .control(&self.restore_dbname_input)
+            .control(&self.restore_dbname_suggest_button)
+            .control(&self.restore_src_info_input)
+            .control(&self.restore_help_box)
+            .control(&self.restore_help_button)
+            .control(&self.restore_options_button)
+            .control(&self.restore_validate_button)
+            .control(&self.restore_run_button)
+            .control(&self.restore_close_button)
+            .build();
+
+        ui::tab_order_builder()
+            .control(&self.catalog_dir_input)
+            .control(&self.catalog_dir_button)
+            .control(&self.catalog_scan_button)
+            .control(&self.catalog_list_view)
+            .control(&self.catalog_age_input)
+            .control(&self.catalog_delete_older_button)
+            .control(&self.catalog_recycle_checkbox)
+            .control(&self.catalog_restore_button)
+            .control(&self.catalog_verify_button)
+            .control(&self.catalog_delete_button)
+            .control(&self.catalog_close_button)
+            .build();
+
+        ui::tab_order_builder()
+            .control(&self.export_dbname_combo)
+            .control(&self.export_tables_button)
+            .control(&self.export_tables_view)
+            .control(&self.export_dest_dir_input)
+            .control(&self.export_dest_dir_button)
+            .control(&self.export_select_all_button)
+            .control(&self.export_import_button)
+            .control(&self.export_bacpac_button)
+            .control(&self.export_run_button)
+            .control(&self.export_close_button)
+            .build();
+    }
+}
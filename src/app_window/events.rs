@@ -1,131 +1,551 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-#[derive(Default)]
-pub(super) struct AppWindowEvents {
-    pub(super) events: Vec<ui::Event<AppWindow>>
-}
-
-impl ui::Events<AppWindowControls> for AppWindowEvents {
-    fn build(&mut self, c: &AppWindowControls) -> Result<(), nwg::NwgError> {
-        ui::event_builder()
-            .control(&c.window)
-            .event(nwg::Event::OnWindowClose)
-            .handler(AppWindow::close)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.window)
-            .event(nwg::Event::OnResizeEnd)
-            .handler(AppWindow::on_resize)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.file_connect_menu_item)
-            .event(nwg::Event::OnMenuItemSelected)
-            .handler(AppWindow::open_connect_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.file_exit_menu_item)
-            .event(nwg::Event::OnMenuItemSelected)
-            .handler(AppWindow::close)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.help_about_menu_item)
-            .event(nwg::Event::OnMenuItemSelected)
-            .handler(AppWindow::open_about_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.help_website_menu_item)
-            .event(nwg::Event::OnMenuItemSelected)
-            .handler(AppWindow::open_website)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.backup_dbname_combo)
-            .event(nwg::Event::OnComboxBoxSelection)
-            .handler(AppWindow::on_dbname_changed)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.backup_dbname_reload_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::open_load_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.backup_dest_dir_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::choose_dest_dir)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.backup_run_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::open_backup_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.backup_close_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::close)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.restore_src_file_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::choose_src_file)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.restore_run_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::open_restore_command_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.restore_close_button)
-            .event(nwg::Event::OnButtonClick)
-            .handler(AppWindow::close)
-            .build(&mut self.events)?;
-
-        ui::event_builder()
-            .control(&c.about_notice.notice)
-            .event(nwg::Event::OnNotice)
-            .handler(AppWindow::await_about_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.connect_notice.notice)
-            .event(nwg::Event::OnNotice)
-            .handler(AppWindow::await_connect_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.load_notice.notice)
-            .event(nwg::Event::OnNotice)
-            .handler(AppWindow::await_load_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.backup_dialog_notice.notice)
-            .event(nwg::Event::OnNotice)
-            .handler(AppWindow::await_backup_dialog)
-            .build(&mut self.events)?;
-        ui::event_builder()
-            .control(&c.restore_dialog_notice.notice)
-            .event(nwg::Event::OnNotice)
-            .handler(AppWindow::await_restore_command_dialog)
-            .build(&mut self.events)?;
-
-        Ok(())
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct AppWindowEvents {
+    pub(super) events: Vec<ui::Event<AppWindow>>
+}
+
+impl ui::Events<AppWindowControls> for AppWindowEvents {
+    fn build(&mut self, c: &AppWindowControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(AppWindow::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(AppWindow::on_resize)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowMinimize)
+            .handler(AppWindow::minimize_to_tray)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnFileDrop)
+            .handler(AppWindow::on_file_drop)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.file_connect_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_connect_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_copy_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_copy_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_clone_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_clone_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_migrate_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_migrate_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_email_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_email_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_toc_json_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::export_toc_json)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_ddl_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_restore_ddl_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_settings_export_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::export_settings)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_settings_import_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::import_settings)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.file_exit_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::exit)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.queue_add_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::enqueue_backup)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.queue_show_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_queue_dialog)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.help_about_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_about_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.help_logs_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_logs_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.help_website_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::open_website)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.tray)
+            .event(nwg::Event::OnContextMenu)
+            .handler(AppWindow::show_tray_menu)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.tray)
+            .event(nwg::Event::OnMousePress(nwg::MousePressEvent::MousePressLeftUp))
+            .handler(AppWindow::show_window)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.tray_show_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::show_window)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.tray_backup_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::quick_backup)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.tray_exit_menu_item)
+            .event(nwg::Event::OnMenuItemSelected)
+            .handler(AppWindow::exit)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.scheduler_timer)
+            .event(nwg::Event::OnTimerTick)
+            .handler(AppWindow::on_scheduler_tick)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.backup_dbname_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(AppWindow::on_dbname_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_dbname_input)
+            .event(nwg::Event::OnTextInput)
+            .handler(AppWindow::on_restore_dbname_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_dbname_suggest_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::use_suggested_restore_dbname)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_dbname_filter_input)
+            .event(nwg::Event::OnTextInput)
+            .handler(AppWindow::on_dbname_filter_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_dbname_reload_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_load_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_dbname_list_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_db_list_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_dest_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_dest_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_dest_dir_input)
+            .event(nwg::Event::OnTextInput)
+            .handler(AppWindow::on_dest_dir_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_format_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(AppWindow::on_format_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_retention_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(AppWindow::on_retention_mode_changed)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.backup_help_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::toggle_help)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_schedule_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_schedule_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_run_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_backup_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::close)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.restore_src_file_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_src_file)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_src_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_src_dir)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.restore_help_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::toggle_help)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_options_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_restore_options_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_validate_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_restore_validate_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_run_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_restore_command_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::close)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.catalog_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_catalog_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_scan_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::scan_catalog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_list_view)
+            .event(nwg::Event::OnListViewDoubleClick)
+            .handler(AppWindow::restore_catalog_entry)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_delete_older_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::delete_older_catalog_entries)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_restore_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::restore_catalog_entry)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_verify_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::verify_catalog_entry)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_delete_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::delete_catalog_entry)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::close)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.export_dbname_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(AppWindow::load_export_tables)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_tables_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::load_export_tables)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_dest_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_export_dest_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_select_all_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::select_all_export_tables)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_import_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_import_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_bacpac_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_bacpac_export_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_run_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_export_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::close)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.physical_dest_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_physical_dest_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_format_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(AppWindow::on_physical_format_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_wal_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::choose_wal_archive_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_archive_enable_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::enable_wal_archiving)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_archive_status_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::load_wal_archive_status)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_pitr_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_pitr_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_run_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::open_physical_backup_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.physical_close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(AppWindow::close)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.about_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_about_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.connect_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_connect_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.copy_connect_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_copy_connect_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.copy_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_copy_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.clone_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_clone_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.migrate_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_migrate_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.load_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_load_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.backup_dialog_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_backup_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_dialog_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_restore_command_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_validate_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_restore_validate_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_ddl_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_restore_ddl_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.globals_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_globals_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.db_list_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_db_list_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.restore_options_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_restore_options_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.preflight_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_preflight_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.share_credentials_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_share_credentials_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.schedule_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_schedule_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.email_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_email_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.logs_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_logs_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.instance_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_instance_started)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.src_info_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_src_info_loaded)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_scan_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_catalog_scanned)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.catalog_verify_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_catalog_verified)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_tables_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_export_tables_loaded)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.export_dialog_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_export_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.wal_archive_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_wal_archive_complete)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.pitr_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_pitr_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.pitr_restore_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_pitr_restore_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.import_dialog_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_import_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.scheduled_backup_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_scheduled_backup)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.queue_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::await_queue_dialog)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.job_queue_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(AppWindow::on_job_queue_changed)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
@@ -1,255 +1,931 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-#[derive(Default)]
-pub(super) struct AppWindowLayout {
-    tabs_container_layout: nwg::FlexboxLayout,
-
-    backup_tab_layout: nwg::FlexboxLayout,
-    backup_dbname_layout: nwg::FlexboxLayout,
-    backup_dest_dir_layout: nwg::FlexboxLayout,
-    backup_filename_layout: nwg::FlexboxLayout,
-    backup_spacer_layout: nwg::FlexboxLayout,
-    backup_buttons_layout: nwg::FlexboxLayout,
-
-    restore_tab_layout: nwg::FlexboxLayout,
-    restore_src_dir_layout: nwg::FlexboxLayout,
-    restore_bbf_db_layout: nwg::FlexboxLayout,
-    restore_dbname_layout: nwg::FlexboxLayout,
-    restore_spacer_layout: nwg::FlexboxLayout,
-    restore_buttons_layout: nwg::FlexboxLayout,
-}
-
-impl ui::Layout<AppWindowControls> for AppWindowLayout {
-
-    // backup
-
-    fn build(&self, c: &AppWindowControls) -> Result<(), nwg::NwgError> {
-        nwg::FlexboxLayout::builder()
-            .parent(&c.backup_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.backup_dbname_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.backup_dbname_combo)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .child(&c.backup_dbname_reload_button)
-            .child_size(ui::size_builder()
-                .width_button_normal()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.backup_dbname_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.backup_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.backup_dest_dir_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.backup_dest_dir_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .child(&c.backup_dest_dir_button)
-            .child_size(ui::size_builder()
-                .width_button_normal()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.backup_dest_dir_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.backup_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.backup_filename_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.backup_filename_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.backup_filename_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.backup_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .build_partial(&self.backup_spacer_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.backup_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .justify_content(ui::JustifyContent::FlexEnd)
-            .auto_spacing(None)
-            .child(&c.backup_run_button)
-            .child_size(ui::size_builder()
-                .width_button_wide()
-                .height_button()
-                .build())
-            .child(&c.backup_close_button)
-            .child_size(ui::size_builder()
-                .width_button_normal()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.backup_buttons_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.backup_tab)
-            .flex_direction(ui::FlexDirection::Column)
-            .child_layout(&self.backup_dbname_layout)
-            .child_layout(&self.backup_dest_dir_layout)
-            .child_layout(&self.backup_filename_layout)
-            .child_layout(&self.backup_spacer_layout)
-            .child_flex_grow(1.0)
-            .child_layout(&self.backup_buttons_layout)
-            .build(&self.backup_tab_layout)?;
-
-        // restore
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.restore_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.restore_src_file_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.restore_src_file_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .child(&c.restore_src_file_button)
-            .child_size(ui::size_builder()
-                .width_button_normal()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.restore_src_dir_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.restore_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.restore_bbf_db_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.restore_bbf_db_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.restore_bbf_db_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.restore_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.restore_dbname_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.restore_dbname_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.restore_dbname_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.restore_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .build_partial(&self.restore_spacer_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.restore_tab)
-            .flex_direction(ui::FlexDirection::Row)
-            .justify_content(ui::JustifyContent::FlexEnd)
-            .auto_spacing(None)
-            .child(&c.restore_run_button)
-            .child_size(ui::size_builder()
-                .width_button_wide()
-                .height_button()
-                .build())
-            .child(&c.restore_close_button)
-            .child_size(ui::size_builder()
-                .width_button_normal()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.restore_buttons_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.restore_tab)
-            .flex_direction(ui::FlexDirection::Column)
-            .child_layout(&self.restore_src_dir_layout)
-            .child_layout(&self.restore_bbf_db_layout)
-            .child_layout(&self.restore_dbname_layout)
-            .child_layout(&self.restore_spacer_layout)
-            .child_flex_grow(1.0)
-            .child_layout(&self.restore_buttons_layout)
-            .build(&self.restore_tab_layout)?;
-
-        // tabs container
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Column)
-            .child(&c.tabs_container)
-            .child_margin(ui::margin_builder()
-                .start_default()
-                .top_default()
-                .end_default()
-                .bottom_pt(30)
-                .build())
-            .build(&self.tabs_container_layout)?;
-
-        Ok(())
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct AppWindowLayout {
+    tabs_container_layout: nwg::FlexboxLayout,
+
+    backup_tab_layout: nwg::FlexboxLayout,
+    backup_dbname_layout: nwg::FlexboxLayout,
+    backup_bbf_db_layout: nwg::FlexboxLayout,
+    backup_dest_dir_layout: nwg::FlexboxLayout,
+    backup_format_layout: nwg::FlexboxLayout,
+    backup_filename_layout: nwg::FlexboxLayout,
+    backup_retention_layout: nwg::FlexboxLayout,
+    backup_spacer_layout: nwg::FlexboxLayout,
+    backup_buttons_layout: nwg::FlexboxLayout,
+
+    restore_tab_layout: nwg::FlexboxLayout,
+    restore_src_dir_layout: nwg::FlexboxLayout,
+    restore_bbf_db_layout: nwg::FlexboxLayout,
+    restore_dbname_layout: nwg::FlexboxLayout,
+    restore_src_info_layout: nwg::FlexboxLayout,
+    restore_spacer_layout: nwg::FlexboxLayout,
+    restore_buttons_layout: nwg::FlexboxLayout,
+
+    catalog_tab_layout: nwg::FlexboxLayout,
+    catalog_dir_layout: nwg::FlexboxLayout,
+    catalog_list_layout: nwg::FlexboxLayout,
+    catalog_cleanup_layout: nwg::FlexboxLayout,
+    catalog_buttons_layout: nwg::FlexboxLayout,
+
+    export_tab_layout: nwg::FlexboxLayout,
+    export_dbname_layout: nwg::FlexboxLayout,
+    export_tables_layout: nwg::FlexboxLayout,
+    export_dest_dir_layout: nwg::FlexboxLayout,
+    export_buttons_layout: nwg::FlexboxLayout,
+
+    physical_tab_layout: nwg::FlexboxLayout,
+    physical_dest_dir_layout: nwg::FlexboxLayout,
+    physical_format_layout: nwg::FlexboxLayout,
+    physical_filename_layout: nwg::FlexboxLayout,
+    physical_wal_dir_layout: nwg::FlexboxLayout,
+    physical_archive_layout: nwg::FlexboxLayout,
+    physical_spacer_layout: nwg::FlexboxLayout,
+    physical_buttons_layout: nwg::FlexboxLayout,
+}
+
+impl ui::Layout<AppWindowControls> for AppWindowLayout {
+
+    // backup
+
+    fn build(&self, c: &AppWindowControls) -> Result<(), nwg::NwgError> {
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_dbname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.backup_dbname_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_dbname_filter_input)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_dbname_reload_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_dbname_list_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_dbname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_bbf_db_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.backup_bbf_db_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_restore_script_checkbox)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_bbf_db_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_dest_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.backup_dest_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_dest_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_dest_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_format_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.backup_format_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_globals_checkbox)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_mode_combo)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_format_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_filename_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.backup_filename_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_auto_increment_checkbox)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_throttle_combo)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_filename_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_retention_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.backup_retention_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_retention_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_volume_combo)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_retention_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.backup_help_box)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_auto()
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.backup_spacer_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.backup_forecast_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_button()
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.backup_help_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_schedule_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_run_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.backup_close_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.backup_buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.backup_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.backup_dbname_layout)
+            .child_layout(&self.backup_bbf_db_layout)
+            .child_layout(&self.backup_dest_dir_layout)
+            .child_layout(&self.backup_format_layout)
+            .child_layout(&self.backup_filename_layout)
+            .child_layout(&self.backup_retention_layout)
+            .child_layout(&self.backup_spacer_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.backup_buttons_layout)
+            .build(&self.backup_tab_layout)?;
+
+        // restore
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.restore_src_file_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.restore_src_file_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.restore_src_file_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.restore_src_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.restore_src_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.restore_bbf_db_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.restore_bbf_db_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.restore_bbf_db_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.restore_dbname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.restore_dbname_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.restore_dbname_suggest_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.restore_dbname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.restore_src_info_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.restore_src_info_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.restore_src_info_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.restore_help_box)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_auto()
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.restore_spacer_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.restore_forecast_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_button()
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.restore_help_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.restore_options_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.restore_validate_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.restore_run_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.restore_close_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.restore_buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.restore_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.restore_src_dir_layout)
+            .child_layout(&self.restore_bbf_db_layout)
+            .child_layout(&self.restore_dbname_layout)
+            .child_layout(&self.restore_src_info_layout)
+            .child_layout(&self.restore_spacer_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.restore_buttons_layout)
+            .build(&self.restore_tab_layout)?;
+
+        // catalog
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.catalog_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.catalog_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.catalog_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.catalog_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.catalog_scan_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.catalog_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.catalog_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.catalog_list_view)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_auto()
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.catalog_list_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.catalog_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.catalog_age_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.catalog_age_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.catalog_delete_older_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.catalog_recycle_checkbox)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.catalog_cleanup_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.catalog_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.catalog_status_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_button()
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.catalog_restore_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.catalog_verify_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.catalog_delete_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.catalog_close_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.catalog_buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.catalog_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.catalog_dir_layout)
+            .child_layout(&self.catalog_list_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.catalog_cleanup_layout)
+            .child_layout(&self.catalog_buttons_layout)
+            .build(&self.catalog_tab_layout)?;
+
+        // export
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.export_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.export_dbname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.export_dbname_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.export_tables_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.export_dbname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.export_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.export_tables_view)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_auto()
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.export_tables_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.export_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.export_dest_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.export_dest_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.export_dest_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.export_dest_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.export_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.export_status_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_button()
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.export_select_all_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.export_import_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.export_bacpac_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.export_run_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.export_close_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.export_buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.export_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.export_dbname_layout)
+            .child_layout(&self.export_tables_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.export_dest_dir_layout)
+            .child_layout(&self.export_buttons_layout)
+            .build(&self.export_tab_layout)?;
+
+        // physical
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.physical_dest_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.physical_dest_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.physical_dest_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.physical_dest_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.physical_format_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.physical_format_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.physical_format_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.physical_filename_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.physical_filename_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.physical_filename_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.physical_wal_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.physical_wal_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.physical_wal_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.physical_wal_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.physical_archive_status_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_button()
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.physical_archive_enable_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.physical_archive_status_button)
+            .child_size(ui::size_builder()
+                .width_button_wide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.physical_pitr_button)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.physical_archive_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.physical_help_box)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_auto()
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.physical_spacer_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.physical_run_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child(&c.physical_close_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.physical_buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.physical_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.physical_dest_dir_layout)
+            .child_layout(&self.physical_format_layout)
+            .child_layout(&self.physical_filename_layout)
+            .child_layout(&self.physical_wal_dir_layout)
+            .child_layout(&self.physical_archive_layout)
+            .child_layout(&self.physical_spacer_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.physical_buttons_layout)
+            .build(&self.physical_tab_layout)?;
+
+        // tabs container
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Column)
+            .child(&c.tabs_container)
+            .child_margin(ui::margin_builder()
+                .start_default()
+                .top_default()
+                .end_default()
+                .bottom_pt(30)
+                .build())
+            .build(&self.tabs_container_layout)?;
+
+        Ok(())
+    }
+}
@@ -1,49 +1,139 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-mod controls;
-mod events;
-mod layout;
-mod nui;
-mod window;
-
-use crate::*;
-use nwg_ui as ui;
-use ui::Controls;
-use ui::Events;
-use ui::Layout;
-use ui::PopupDialog;
-
-use about_dialog::AboutDialog;
-use about_dialog::AboutDialogArgs;
-use common::PgConnConfig;
-use backup_dialog::BackupDialog;
-use backup_dialog::BackupDialogArgs;
-use backup_dialog::BackupDialogResult;
-use connect_dialog::ConnectDialog;
-use connect_dialog::ConnectDialogArgs;
-use connect_dialog::ConnectDialogResult;
-use load_dbnames_dialog::LoadDbnamesDialog;
-use load_dbnames_dialog::LoadDbnamesDialogArgs;
-use load_dbnames_dialog::LoadDbnamesDialogResult;
-use restore_dialog::RestoreDialog;
-use restore_dialog::RestoreDialogArgs;
-use restore_dialog::RestoreDialogResult;
-
-pub(self) use controls::AppWindowControls;
-pub(self) use events::AppWindowEvents;
-use layout::AppWindowLayout;
-pub use window::AppWindow;
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod controls;
+mod events;
+mod layout;
+mod nui;
+mod result;
+mod window;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+use crate::*;
+use nwg_ui as ui;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupDialog;
+
+use about_dialog::AboutDialog;
+use about_dialog::AboutDialogArgs;
+use common::labels;
+use common::bring_to_foreground;
+use common::listen_for_instances;
+use common::AppSettings;
+use common::BackupFormat;
+use common::BackupMode;
+use common::BackupJob;
+use common::CatalogEntry;
+use common::CronSchedule;
+use common::delete_backup;
+use common::format_disk_size;
+use common::GLOBALS_FILE_NAME;
+use common::JobQueue;
+use common::LogTail;
+use common::long_path;
+use common::PgConnConfig;
+use common::probe_dir_writable;
+use common::probe_path_readable;
+use common::RetentionMode;
+use common::RetentionPolicy;
+use common::RunKind;
+use common::RunRecord;
+use common::scan_backup_dir;
+use common::select_older_than;
+use common::ServerInfo;
+use common::SilentProgress;
+use common::share_accessible;
+use common::unc_share_root;
+use common::throttle_presets;
+use common::validate_db_name;
+use common::volume_path;
+use common::volume_set_base;
+use common::volume_size_presets;
+use backup_dialog::BackupDialog;
+use backup_dialog::BackupDialogArgs;
+use backup_dialog::BackupDialogResult;
+use backup_dialog::BackupResult;
+use backup_dialog::PgDumpArgs;
+use clone_dialog::CloneDialog;
+use clone_dialog::CloneDialogArgs;
+use clone_dialog::CloneDialogResult;
+use connect_dialog::ConnectDialog;
+use connect_dialog::ConnectDialogArgs;
+use connect_dialog::ConnectDialogResult;
+use copy_dialog::CopyDialog;
+use copy_dialog::CopyDialogArgs;
+use copy_dialog::CopyDialogResult;
+use db_list_dialog::DbListDialog;
+use db_list_dialog::DbListDialogArgs;
+use db_list_dialog::DbListDialogResult;
+use email_dialog::EmailDialog;
+use email_dialog::EmailDialogArgs;
+use email_dialog::EmailDialogResult;
+use export_dialog::ExportDialog;
+use export_dialog::ExportDialogArgs;
+use export_dialog::ExportDialogResult;
+use export_dialog::ExportTable;
+use globals_dialog::GlobalsDialog;
+use globals_dialog::GlobalsDialogArgs;
+use globals_dialog::GlobalsDialogResult;
+use import_dialog::ImportDialog;
+use import_dialog::ImportDialogArgs;
+use import_dialog::ImportDialogResult;
+use load_dbnames_dialog::LoadDbnamesDialog;
+use load_dbnames_dialog::LoadDbnamesDialogArgs;
+use load_dbnames_dialog::LoadDbnamesDialogResult;
+use log_viewer_dialog::LogViewerDialog;
+use log_viewer_dialog::LogViewerDialogArgs;
+use migrate_dialog::MigrateDialog;
+use migrate_dialog::MigrateDialogArgs;
+use migrate_dialog::MigrateDialogResult;
+use pg_toc::DumpSummary;
+use pitr_dialog::PitrDialog;
+use pitr_dialog::PitrDialogArgs;
+use pitr_dialog::PitrDialogResult;
+use preflight_dialog::PreflightDialog;
+use preflight_dialog::PreflightDialogArgs;
+use preflight_dialog::PreflightDialogResult;
+use queue_dialog::QueueDialog;
+use queue_dialog::QueueDialogArgs;
+use restore_dialog::RestoreDialog;
+use restore_dialog::RestoreDialogArgs;
+use restore_dialog::RestoreDialogResult;
+use restore_dialog::RestoreOptions;
+use restore_options_dialog::RestoreOptionsDialog;
+use restore_options_dialog::RestoreOptionsDialogArgs;
+use restore_options_dialog::RestoreOptionsDialogResult;
+use schedule_dialog::ScheduleDialog;
+use schedule_dialog::ScheduleDialogArgs;
+use schedule_dialog::ScheduleDialogResult;
+use share_credentials_dialog::ShareCredentialsDialog;
+use share_credentials_dialog::ShareCredentialsDialogArgs;
+use share_credentials_dialog::ShareCredentialsDialogResult;
+
+pub(self) use controls::AppWindowControls;
+pub(self) use events::AppWindowEvents;
+use layout::AppWindowLayout;
+use result::CatalogScanResult;
+use result::CatalogVerifyResult;
+use result::ExportTablesResult;
+use result::WalArchiveResult;
+pub use window::AppWindow;
@@ -0,0 +1,103 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct CatalogScanResult {
+    pub(super) entries: Vec<CatalogEntry>,
+    pub(super) error: String,
+}
+
+impl CatalogScanResult {
+    pub(super) fn success(entries: Vec<CatalogEntry>) -> Self {
+        Self {
+            entries,
+            error: String::new(),
+        }
+    }
+
+    pub(super) fn failure(error: String) -> Self {
+        Self {
+            error,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct CatalogVerifyResult {
+    pub(super) file_name: String,
+    pub(super) success: bool,
+    pub(super) message: String,
+}
+
+impl CatalogVerifyResult {
+    pub(super) fn new(file_name: &str, res: Result<String, std::io::Error>) -> Self {
+        let (success, message) = match res {
+            Ok(message) => (true, message),
+            Err(e) => (false, e.to_string())
+        };
+        Self {
+            file_name: file_name.to_string(),
+            success,
+            message,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct ExportTablesResult {
+    pub(super) tables: Vec<ExportTable>,
+    pub(super) error: String,
+}
+
+impl ExportTablesResult {
+    pub(super) fn success(tables: Vec<ExportTable>) -> Self {
+        Self {
+            tables,
+            error: String::new(),
+        }
+    }
+
+    pub(super) fn failure(error: String) -> Self {
+        Self {
+            error,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct WalArchiveResult {
+    pub(super) title: String,
+    pub(super) message: String,
+    pub(super) error: String,
+}
+
+impl WalArchiveResult {
+    pub(super) fn new(title: &str, res: Result<String, String>) -> Self {
+        let (message, error) = match res {
+            Ok(message) => (message, String::new()),
+            Err(e) => (String::new(), e)
+        };
+        Self {
+            title: title.to_string(),
+            message,
+            error,
+        }
+    }
+}
@@ -1,234 +1,2125 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use std::path::Path;
-use std::os::windows::process::CommandExt;
-use std::process::Command;
-use std::process::Stdio;
-
-use super::*;
-
-const CREATE_NO_WINDOW: u32 = 0x08000000;
-
-#[derive(Default)]
-pub struct AppWindow {
-    pub(super) c: AppWindowControls,
-
-    pg_conn_config: PgConnConfig,
-
-    about_dialog_join_handle: ui::PopupJoinHandle<()>,
-    connect_dialog_join_handle: ui::PopupJoinHandle<ConnectDialogResult>,
-    load_join_handle: ui::PopupJoinHandle<LoadDbnamesDialogResult>,
-    backup_dialog_join_handle: ui::PopupJoinHandle<BackupDialogResult>,
-    restore_dialog_join_handle: ui::PopupJoinHandle<RestoreDialogResult>,
-}
-
-impl AppWindow {
-
-    pub fn new() -> Self {
-        Default::default()
-    }
-
-    pub(super) fn init(&mut self) {
-        self.pg_conn_config.hostname = String::from("localhost");
-        self.pg_conn_config.port = 5432;
-        self.pg_conn_config.username = String::from("wilton");
-        self.pg_conn_config.connect_db = String::from("wilton");
-        self.pg_conn_config.enable_tls = true;
-        self.pg_conn_config.accept_invalid_tls = true;
-
-        self.set_status_bar_dbconn_label("none");
-        self.open_connect_dialog(nwg::EventData::NoData);
-    }
-
-    pub(super) fn close(&mut self, _: nwg::EventData) {
-        self.c.window.set_visible(false);
-        nwg::stop_thread_dispatch();
-    }
-
-    pub(super) fn open_about_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(false);
-        let args = AboutDialogArgs::new(&self.c.about_notice);
-        self.about_dialog_join_handle = AboutDialog::popup(args);
-    }
-
-    pub(super) fn await_about_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.about_notice.receive();
-        let _ = self.about_dialog_join_handle.join();
-    }
-
-    pub(super) fn open_connect_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(false);
-        let args = ConnectDialogArgs::new(&self.c.connect_notice, self.pg_conn_config.clone());
-        self.connect_dialog_join_handle = ConnectDialog::popup(args);
-    }
-
-    pub(super) fn await_connect_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.connect_notice.receive();
-        let res = self.connect_dialog_join_handle.join();
-        if !res.cancelled {
-            self.set_dbnames(&res.dbnames, &res.bbf_db);
-            self.pg_conn_config = res.pg_conn_config;
-            let sbar_label = format!(
-                "{}:{}", &self.pg_conn_config.hostname, &self.pg_conn_config.port);
-            self.set_status_bar_dbconn_label(&sbar_label);
-        }
-    }
-
-    pub(super) fn open_load_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(false);
-        let pcc = self.pg_conn_config.clone();
-        let args = LoadDbnamesDialogArgs::new(&self.c.load_notice, pcc);
-        self.load_join_handle = LoadDbnamesDialog::popup(args);
-    }
-
-    pub(super) fn await_load_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.load_notice.receive();
-        let res = self.load_join_handle.join();
-        if res.success {
-            self.set_dbnames(&res.dbnames, &res.bbf_db);
-        }
-    }
-
-    pub(super) fn open_backup_dialog(&mut self, _: nwg::EventData) {
-        let dbname = match self.c.backup_dbname_combo.selection_string() {
-            Some(name) => name,
-            None => return
-        };
-        let bbf_db = self.c.restore_bbf_db_input.text();
-        let dir = self.c.backup_dest_dir_input.text();
-        let filename = self.c.backup_filename_input.text();
-        let dest_path = Path::new(&dir).join(&filename);
-        let mut go_on = true;
-        if dest_path.exists() {
-            let dest_path_st = dest_path.to_string_lossy().to_string();
-            go_on = ui::message_box_warning_yn(&format!(
-                "Destination file already exists:\r\n{}\r\n\r\nWould you like to overwrite it?", dest_path_st));
-        }
-        if go_on {
-            self.c.window.set_enabled(false);
-            let args = BackupDialogArgs::new(
-                &self.c.backup_dialog_notice, &self.pg_conn_config,  &dbname, &bbf_db, &dir, &filename);
-            self.backup_dialog_join_handle = BackupDialog::popup(args);
-        }
-    }
-
-    pub(super) fn await_backup_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.backup_dialog_notice.receive();
-        let _ = self.backup_dialog_join_handle.join();
-    }
-
-    pub(super) fn open_restore_command_dialog(&mut self, _: nwg::EventData) {
-        let pcc = &self.pg_conn_config;
-        let zipfile = self.c.restore_src_file_input.text();
-        let dbname = self.c.restore_dbname_input.text();
-        let bbf_db = self.c.restore_bbf_db_input.text();
-        self.c.window.set_enabled(false);
-        let args = RestoreDialogArgs::new(
-            &self.c.restore_dialog_notice, &pcc,
-            &zipfile, &dbname, &bbf_db);
-        self.restore_dialog_join_handle = RestoreDialog::popup(args);
-    }
-
-    pub(super) fn await_restore_command_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.restore_dialog_notice.receive();
-        let _ = self.restore_dialog_join_handle.join();
-    }
-
-    pub(super) fn open_website(&mut self, _: nwg::EventData) {
-        let _ = Command::new("cmd")
-            .arg("/c")
-            .arg("start")
-            .arg("https://wiltondb.com")
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .creation_flags(CREATE_NO_WINDOW)
-            .status();
-    }
-
-    pub(super) fn on_resize(&mut self, _: nwg::EventData) {
-        self.c.update_tab_order();
-    }
-
-    pub(super) fn choose_dest_dir(&mut self, _: nwg::EventData) {
-        if let Ok(d) = std::env::current_dir() {
-            if let Some(d) = d.to_str() {
-                let _ = self.c.backup_dest_dir_chooser.set_default_folder(d);
-            }
-        }
-
-        if self.c.backup_dest_dir_chooser.run(Some(&self.c.window)) {
-            self.c.backup_dest_dir_input.set_text("");
-            if let Ok(directory) = self.c.backup_dest_dir_chooser.get_selected_item() {
-                let dir = directory.to_string_lossy().to_string();
-                self.c.backup_dest_dir_input.set_text(&dir);
-            }
-        }
-    }
-
-    pub(super) fn choose_src_file(&mut self, _: nwg::EventData) {
-        if let Ok(d) = std::env::current_dir() {
-            if let Some(d) = d.to_str() {
-                let _ = self.c.restore_src_file_chooser.set_default_folder(d);
-            }
-        }
-
-        if self.c.restore_src_file_chooser.run(Some(&self.c.window)) {
-            self.c.restore_src_file_input.set_text("");
-            if let Ok(file) = self.c.restore_src_file_chooser.get_selected_item() {
-                let fpath_st = file.to_string_lossy().to_string();
-                self.c.restore_src_file_input.set_text(&fpath_st);
-                if let Some(filename) = Path::new(&file).file_name() {
-                    let name_st = filename.to_string_lossy().to_string();
-                    let ext = match Path::new(&file).extension() {
-                        Some(ext) => format!(".{}", ext.to_string_lossy().to_string()),
-                        None => "".to_string()
-                    };
-                    let dbname: String = name_st.chars().take(name_st.len() - ext.len()).collect();
-                    self.c.restore_dbname_input.set_text(&dbname);
-                }
-            }
-        }
-    }
-
-    pub(super) fn on_dbname_changed(&mut self, _: nwg::EventData) {
-        if let Some(name) = &self.c.backup_dbname_combo.selection_string() {
-            let filename = format!("{}.zip", name);
-            self.c.backup_filename_input.set_text(&filename);
-        }
-    }
-
-    fn set_dbnames(&mut self, dbnames_all: &Vec<String>, bbf_db: &str) {
-        let mut dbnames: Vec<String> = dbnames_all.iter().filter(|name| {
-            !vec!("master", "msdb", "tempdb").contains(&name.as_str())
-        }).map(|name| name.clone()).collect();
-        dbnames.sort();
-        self.c.backup_dbname_combo.set_collection(dbnames);
-        self.c.backup_dbname_combo.set_selection(Some(0));
-        self.on_dbname_changed(nwg::EventData::NoData);
-        self.c.restore_bbf_db_input.set_text(bbf_db);
-    }
-
-    fn set_status_bar_dbconn_label(&self, text: &str) {
-        self.c.status_bar.set_text(0, &format!("  DB connection: {}", text));
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::process::Stdio;
+
+use super::*;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+const HELP_PANEL_HEIGHT: u32 = 220;
+// run history name for whole-instance backups
+const PHYSICAL_BACKUP_NAME: &str = "(instance)";
+
+#[derive(Default)]
+pub struct AppWindow {
+    pub(super) c: AppWindowControls,
+
+    pg_conn_config: PgConnConfig,
+    settings: AppSettings,
+    restore_options: RestoreOptions,
+    help_expanded: bool,
+    startup_src_file: Option<String>,
+    scheduler_last_minute: i64,
+    scheduled_running: Vec<String>,
+    scheduled_results: Arc<Mutex<Vec<(String, BackupResult, RunRecord)>>>,
+    job_queue: JobQueue,
+    // DB name and backup path of the running operation dialogs, main window stays
+    // enabled while they run, runs are recorded to history when dialogs are closed
+    running_backup: Option<(String, PathBuf)>,
+    running_restore: Option<(String, PathBuf)>,
+    // DB name and Babelfish DB of the copy source, set while target connection
+    // or clone name is chosen
+    copy_source: Option<(String, String)>,
+    // runs of queued jobs, recorded to history on the main thread
+    queue_results: Arc<Mutex<Vec<RunRecord>>>,
+    // operation that is started after the pre-flight check
+    preflight_kind: RunKind,
+    // access problems found when the location is selected, shown instead of the forecast
+    backup_access_warning: String,
+    restore_access_warning: String,
+    // all DB names on the server, used to suggest a free name for restore
+    server_dbnames: Vec<String>,
+    // server has no Babelfish catalog, DB names are physical Postgres DBs
+    plain_pg: bool,
+    // combo box shows the names matching the filter input
+    backup_dbnames: Vec<String>,
+    restore_dbname_suggestion: Option<String>,
+    // restore is blocked while the target name is invalid
+    restore_dbname_error: String,
+    // logins from "globals.sql" are restored in the next run
+    restore_globals: bool,
+    // backups listed on the "Catalog" tab, in list view order
+    catalog_entries: Vec<CatalogEntry>,
+    // catalog buttons are disabled while the folder is scanned or a backup is verified
+    catalog_busy: bool,
+    // tables listed on the "Export" tab, in list view order, and the DB they belong to
+    export_tables: Vec<ExportTable>,
+    export_dbname: String,
+    export_busy: bool,
+    // table and CSV file of the import, set while the column mapping is chosen
+    import_target: Option<(ExportTable, String)>,
+
+    about_dialog_join_handle: ui::PopupJoinHandle<()>,
+    connect_dialog_join_handle: ui::PopupJoinHandle<ConnectDialogResult>,
+    copy_connect_dialog_join_handle: ui::PopupJoinHandle<ConnectDialogResult>,
+    copy_dialog_join_handle: ui::PopupJoinHandle<CopyDialogResult>,
+    clone_dialog_join_handle: ui::PopupJoinHandle<CloneDialogResult>,
+    migrate_dialog_join_handle: ui::PopupJoinHandle<MigrateDialogResult>,
+    load_join_handle: ui::PopupJoinHandle<LoadDbnamesDialogResult>,
+    backup_dialog_join_handle: ui::PopupJoinHandle<BackupDialogResult>,
+    restore_dialog_join_handle: ui::PopupJoinHandle<RestoreDialogResult>,
+    globals_dialog_join_handle: ui::PopupJoinHandle<GlobalsDialogResult>,
+    db_list_dialog_join_handle: ui::PopupJoinHandle<DbListDialogResult>,
+    restore_validate_dialog_join_handle: ui::PopupJoinHandle<RestoreDialogResult>,
+    restore_ddl_dialog_join_handle: ui::PopupJoinHandle<RestoreDialogResult>,
+    restore_options_dialog_join_handle: ui::PopupJoinHandle<RestoreOptionsDialogResult>,
+    preflight_dialog_join_handle: ui::PopupJoinHandle<PreflightDialogResult>,
+    share_credentials_dialog_join_handle: ui::PopupJoinHandle<ShareCredentialsDialogResult>,
+    schedule_dialog_join_handle: ui::PopupJoinHandle<ScheduleDialogResult>,
+    email_dialog_join_handle: ui::PopupJoinHandle<EmailDialogResult>,
+    queue_dialog_join_handle: ui::PopupJoinHandle<()>,
+    logs_dialog_join_handle: ui::PopupJoinHandle<()>,
+    catalog_scan_join_handle: ui::PopupJoinHandle<CatalogScanResult>,
+    catalog_verify_join_handle: ui::PopupJoinHandle<CatalogVerifyResult>,
+    export_tables_join_handle: ui::PopupJoinHandle<ExportTablesResult>,
+    export_dialog_join_handle: ui::PopupJoinHandle<ExportDialogResult>,
+    import_dialog_join_handle: ui::PopupJoinHandle<ImportDialogResult>,
+    wal_archive_join_handle: ui::PopupJoinHandle<WalArchiveResult>,
+    pitr_dialog_join_handle: ui::PopupJoinHandle<PitrDialogResult>,
+    pitr_restore_dialog_join_handle: ui::PopupJoinHandle<RestoreDialogResult>,
+}
+
+impl AppWindow {
+
+    pub fn new(startup_src_file: Option<String>) -> Self {
+        Self {
+            startup_src_file,
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn init(&mut self) {
+        self.pg_conn_config.hostname = String::from("localhost");
+        self.pg_conn_config.port = 5432;
+        self.pg_conn_config.username = String::from("wilton");
+        self.pg_conn_config.connect_db = String::from("wilton");
+        self.pg_conn_config.enable_tls = true;
+        self.pg_conn_config.accept_invalid_tls = true;
+
+        self.settings = match AppSettings::load() {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::error!("{}", e);
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+                AppSettings::default()
+            }
+        };
+        self.pg_conn_config.tls_ca_file = self.settings.tls_ca_file.clone();
+        let queue_sender = self.c.job_queue_notice.sender();
+        self.job_queue = JobQueue::new(move || queue_sender.send());
+        self.on_dest_dir_changed(nwg::EventData::NoData);
+        self.c.backup_help_box.set_text(&help_text(labels::BACKUP_HELP));
+        self.c.restore_help_box.set_text(&help_text(labels::RESTORE_HELP));
+        self.set_status_bar_dbconn_label("none");
+        self.on_restore_dbname_changed(nwg::EventData::NoData);
+        self.settings_to_controls();
+        listen_for_instances(self.c.instance_notice.sender());
+        if let Some(file) = self.startup_src_file.take() {
+            self.open_src_file_arg(&file);
+        }
+        self.open_connect_dialog(nwg::EventData::NoData);
+    }
+
+    fn settings_to_controls(&mut self) {
+        let checked = |flag: bool| if flag {
+            nwg::CheckBoxState::Checked
+        } else {
+            nwg::CheckBoxState::Unchecked
+        };
+        self.c.backup_auto_increment_checkbox.set_check_state(checked(self.settings.backup_auto_increment));
+        self.c.backup_globals_checkbox.set_check_state(checked(self.settings.backup_include_globals));
+        self.c.backup_restore_script_checkbox.set_check_state(checked(self.settings.backup_restore_script));
+        self.c.backup_mode_combo.set_selection(Some(self.settings.backup_mode.index()));
+        let volume_idx = volume_size_presets().iter()
+            .position(|(size, _)| *size == self.settings.backup_volume_size_mb)
+            .unwrap_or(0);
+        self.c.backup_volume_combo.set_selection(Some(volume_idx));
+        let throttle_idx = throttle_presets().iter()
+            .position(|(rate, _)| *rate == self.settings.throttle_mb_per_sec)
+            .unwrap_or(0);
+        self.c.backup_throttle_combo.set_selection(Some(throttle_idx));
+        self.c.tray.set_visibility(self.has_builtin_jobs());
+        self.c.catalog_recycle_checkbox.set_check_state(checked(!self.settings.catalog_delete_permanently));
+        if self.settings.catalog_dir.is_empty() {
+            self.c.catalog_dir_input.set_text(&self.c.backup_dest_dir_input.text());
+        } else {
+            self.c.catalog_dir_input.set_text(&self.settings.catalog_dir);
+            self.scan_catalog(nwg::EventData::NoData);
+        }
+    }
+
+    pub(super) fn close(&mut self, _: nwg::EventData) {
+        if self.has_builtin_jobs() {
+            self.c.window.set_visible(false);
+            self.show_tray_message("WiltonDB Backup Tool", "Scheduled backups will keep running in background");
+        } else {
+            self.exit(nwg::EventData::NoData);
+        }
+    }
+
+    pub(super) fn exit(&mut self, _: nwg::EventData) {
+        if !self.scheduled_running.is_empty() {
+            let go_on = ui::message_box_warning_yn(&format!(
+                "Scheduled backup is running:\r\n{}\r\n\r\nWould you like to exit anyway?",
+                self.scheduled_running.join(", ")));
+            if !go_on {
+                return;
+            }
+        }
+        let mut running = Vec::new();
+        if let Some((dbname, _)) = &self.running_backup {
+            running.push(format!("Backup '{}'", dbname));
+        }
+        if let Some((dbname, _)) = &self.running_restore {
+            running.push(format!("Restore '{}'", dbname));
+        }
+        if !running.is_empty() {
+            let go_on = ui::message_box_warning_yn(&format!(
+                "Operation is running:\r\n{}\r\n\r\nWould you like to exit anyway?",
+                running.join(", ")));
+            if !go_on {
+                return;
+            }
+        }
+        let queued = self.job_queue.active_jobs();
+        if !queued.is_empty() {
+            let go_on = ui::message_box_warning_yn(&format!(
+                "Queued jobs are not complete:\r\n{}\r\n\r\nWould you like to exit anyway?",
+                queued.join(", ")));
+            if !go_on {
+                return;
+            }
+        }
+        self.c.tray.set_visibility(false);
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    pub(super) fn minimize_to_tray(&mut self, _: nwg::EventData) {
+        self.c.window.set_visible(false);
+        self.c.tray.set_visibility(true);
+    }
+
+    pub(super) fn show_window(&mut self, _: nwg::EventData) {
+        self.c.window.set_visible(true);
+        self.c.window.restore();
+        self.c.tray.set_visibility(self.has_builtin_jobs());
+    }
+
+    pub(super) fn quick_backup(&mut self, _: nwg::EventData) {
+        self.show_window(nwg::EventData::NoData);
+        if !self.c.window.enabled() {
+            // other dialog is already open
+            return;
+        }
+        self.c.tabs_container.set_selected_tab(0);
+        self.open_backup_dialog(nwg::EventData::NoData);
+    }
+
+    pub(super) fn show_tray_menu(&mut self, _: nwg::EventData) {
+        let (x, y) = nwg::GlobalCursor::position();
+        self.c.tray_menu.popup(x, y);
+    }
+
+    pub(super) fn on_scheduler_tick(&mut self, _: nwg::EventData) {
+        let now_local = chrono::Local::now();
+        let minute = now_local.timestamp() / 60;
+        let now = now_local.naive_local();
+        if minute == self.scheduler_last_minute {
+            return;
+        }
+        self.scheduler_last_minute = minute;
+        let jobs: Vec<BackupJob> = self.settings.backup_jobs.iter()
+            .filter(|job| !job.cron.is_empty())
+            .cloned()
+            .collect();
+        for job in jobs {
+            let schedule = match CronSchedule::parse(&job.cron) {
+                Ok(schedule) => schedule,
+                Err(_) => continue
+            };
+            if schedule.matches(&now) && !self.scheduled_running.contains(&job.name) {
+                self.start_scheduled_backup(job);
+            }
+        }
+    }
+
+    pub(super) fn await_scheduled_backup(&mut self, _: nwg::EventData) {
+        self.c.scheduled_backup_notice.receive();
+        let finished: Vec<(String, BackupResult, RunRecord)> = match self.scheduled_results.lock() {
+            Ok(mut results) => results.drain(..).collect(),
+            Err(_) => return
+        };
+        for (name, res, record) in finished {
+            self.scheduled_running.retain(|running| running != &name);
+            self.settings.record_run(record);
+            // window may be hidden, failing to save history is not reported
+            let _ = self.settings.save();
+            if res.error.is_empty() {
+                self.show_tray_message("Backup complete", &format!(
+                    "Scheduled backup '{}' completed successfully", name));
+            } else {
+                log::error!("Scheduled backup '{}' failed: {}", name, res.error);
+                self.show_tray_message("Backup failed", &format!(
+                    "Scheduled backup '{}' failed: {}", name, res.error));
+            }
+        }
+        self.update_backup_forecast();
+    }
+
+    // backup with the current form values is run after previously queued jobs
+    pub(super) fn enqueue_backup(&mut self, _: nwg::EventData) {
+        let dbname = match self.c.backup_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => {
+                nwg::modal_error_message(&self.c.window, "Job queue", "DB name must be selected");
+                return;
+            }
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        if bbf_db.is_empty() && !self.plain_pg {
+            nwg::modal_error_message(&self.c.window, "Job queue", "Postgres DB name must be specified");
+            return;
+        }
+        let dir = self.c.backup_dest_dir_input.text();
+        let filename = self.c.backup_filename_input.text();
+        let format = self.selected_backup_format();
+        let retention = self.retention_policy_from_input(&dir);
+        let auto_increment = self.c.backup_auto_increment_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        let include_globals = self.c.backup_globals_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        let restore_script = self.c.backup_restore_script_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        let mode = self.selected_backup_mode();
+        let volume_size_mb = self.selected_volume_size();
+        let throttle_mb_per_sec = self.selected_throttle();
+        let pcc = self.pg_conn_config.clone();
+        let email = self.settings.email.clone();
+        let results = self.queue_results.clone();
+        let task_dbname = dbname.clone();
+        let task = Box::new(move || {
+            // name is resolved when the job starts, earlier jobs may have created the same file
+            let filename = if auto_increment {
+                format.unique_dest_name(Path::new(&dir), &filename)
+            } else {
+                filename
+            };
+            let pargs = PgDumpArgs::new(&task_dbname, &bbf_db, &dir, &filename, format, &retention)
+                .with_globals(include_globals)
+                .with_mode(mode)
+                .with_volume_size(volume_size_mb)
+                .with_throttle(throttle_mb_per_sec)
+                .with_restore_script(restore_script);
+            let start = Instant::now();
+            let tail = LogTail::new(&SilentProgress {});
+            let res = BackupDialog::run_backup(&tail, &pcc, &pargs);
+            BackupDialog::notify_by_email(&SilentProgress {}, &email, &pargs, &res, start.elapsed(), &tail.text());
+            let dest_path = pargs.dest_path();
+            let record = RunRecord::new(RunKind::Backup, &task_dbname, &dest_path, start.elapsed(), res.error.is_empty());
+            if let Ok(mut results) = results.lock() {
+                results.push(record);
+            }
+            if res.error.is_empty() {
+                Ok(format!("Backup file: {}", dest_path.to_string_lossy()))
+            } else {
+                Err(res.error)
+            }
+        });
+        if let Err(e) = self.job_queue.submit(&format!("Backup '{}'", dbname), task) {
+            nwg::modal_error_message(&self.c.window, "Job queue", &e.to_string());
+        }
+    }
+
+    pub(super) fn on_job_queue_changed(&mut self, _: nwg::EventData) {
+        self.c.job_queue_notice.receive();
+        let finished: Vec<RunRecord> = match self.queue_results.lock() {
+            Ok(mut results) => results.drain(..).collect(),
+            Err(_) => return
+        };
+        if !finished.is_empty() {
+            for record in finished {
+                self.settings.record_run(record);
+            }
+            // window may be hidden, failing to save history is not reported
+            let _ = self.settings.save();
+            self.update_backup_forecast();
+        }
+    }
+
+    pub(super) fn open_queue_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = QueueDialogArgs::new(&self.c.queue_notice, &self.job_queue);
+        self.queue_dialog_join_handle = QueueDialog::popup(args);
+    }
+
+    pub(super) fn await_queue_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.queue_notice.receive();
+        self.queue_dialog_join_handle.join();
+    }
+
+    pub(super) fn open_about_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = AboutDialogArgs::new(&self.c.about_notice);
+        self.about_dialog_join_handle = AboutDialog::popup(args);
+    }
+
+    pub(super) fn await_about_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.about_notice.receive();
+        let _ = self.about_dialog_join_handle.join();
+    }
+
+    pub(super) fn open_logs_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = LogViewerDialogArgs::new(&self.c.logs_notice);
+        self.logs_dialog_join_handle = LogViewerDialog::popup(args);
+    }
+
+    pub(super) fn await_logs_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.logs_notice.receive();
+        let _ = self.logs_dialog_join_handle.join();
+    }
+
+    pub(super) fn open_connect_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = ConnectDialogArgs::new(&self.c.connect_notice, self.pg_conn_config.clone());
+        self.connect_dialog_join_handle = ConnectDialog::popup(args);
+    }
+
+    pub(super) fn await_connect_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.connect_notice.receive();
+        let res = self.connect_dialog_join_handle.join();
+        if !res.cancelled {
+            self.pg_conn_config = res.pg_conn_config;
+            if self.settings.tls_ca_file != self.pg_conn_config.tls_ca_file {
+                self.settings.tls_ca_file = self.pg_conn_config.tls_ca_file.clone();
+                if let Err(e) = self.settings.save() {
+                    log::error!("{}", e);
+                }
+            }
+            let mut sbar_label = format!(
+                "{}:{}", &self.pg_conn_config.hostname, &self.pg_conn_config.port);
+            if let Some(info) = res.server_info {
+                if !info.migration_mode.is_empty() {
+                    sbar_label = format!("{} ({})", sbar_label, info.migration_mode);
+                }
+                self.save_profile_server_info(info);
+            }
+            if res.bbf_db.is_empty() {
+                sbar_label = format!("{} (plain Postgres)", sbar_label);
+            }
+            self.set_dbnames(&res.dbnames, &res.bbf_db);
+            self.set_status_bar_dbconn_label(&sbar_label);
+        }
+    }
+
+    // target connection is chosen with the regular connect dialog, current connection
+    // is not changed
+    pub(super) fn open_copy_dialog(&mut self, _: nwg::EventData) {
+        let dbname = match self.c.backup_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => {
+                nwg::modal_error_message(&self.c.window, "Copy DB", "DB name must be selected");
+                return;
+            }
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        if bbf_db.is_empty() && !self.plain_pg {
+            nwg::modal_error_message(&self.c.window, "Copy DB", "Postgres DB name must be specified");
+            return;
+        }
+        self.copy_source = Some((dbname, bbf_db));
+        self.c.window.set_enabled(false);
+        let args = ConnectDialogArgs::new(&self.c.copy_connect_notice, self.pg_conn_config.clone());
+        self.copy_connect_dialog_join_handle = ConnectDialog::popup(args);
+    }
+
+    pub(super) fn await_copy_connect_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.copy_connect_notice.receive();
+        let res = self.copy_connect_dialog_join_handle.join();
+        let (dbname, bbf_db) = match self.copy_source.take() {
+            Some(tup) => tup,
+            None => return
+        };
+        if res.cancelled {
+            return;
+        }
+        let dest = &res.pg_conn_config;
+        let src = &self.pg_conn_config;
+        if dest.hostname.to_lowercase() == src.hostname.to_lowercase() && dest.port == src.port {
+            nwg::modal_error_message(&self.c.window, "Copy DB",
+                "Target server must be different from the current one, use restore to copy DB on the same server");
+            return;
+        }
+        if res.dbnames.iter().any(|name| name.to_lowercase() == dbname.to_lowercase()) {
+            nwg::modal_error_message(&self.c.window, "Copy DB", &format!(
+                "Database with name '{}' already exists on the target server", &dbname));
+            return;
+        }
+        let work_dir = std::env::temp_dir().to_string_lossy().to_string();
+        let go_on = ui::message_box_warning_yn(&format!(
+            "DB '{}' will be copied\r\nfrom: {}:{}\r\nto: {}:{}\r\n\r\nTemp directory for the dump:\r\n{}\r\n\r\nWould you like to continue?",
+            &dbname, &src.hostname, src.port, &dest.hostname, dest.port, &work_dir));
+        if !go_on {
+            return;
+        }
+        self.c.window.set_enabled(false);
+        let args = CopyDialogArgs::new(&self.c.copy_notice, src, dest, &dbname, &bbf_db, &res.bbf_db, &work_dir);
+        self.copy_dialog_join_handle = CopyDialog::popup(args);
+    }
+
+    // DB is backed up into a temp dir and restored under the new name with the copy dialog
+    pub(super) fn open_clone_dialog(&mut self, _: nwg::EventData) {
+        let dbname = match self.c.backup_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => {
+                nwg::modal_error_message(&self.c.window, "Clone DB", "DB name must be selected");
+                return;
+            }
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        if bbf_db.is_empty() && !self.plain_pg {
+            nwg::modal_error_message(&self.c.window, "Clone DB", "Postgres DB name must be specified");
+            return;
+        }
+        self.copy_source = Some((dbname.clone(), bbf_db));
+        self.c.window.set_enabled(false);
+        let args = CloneDialogArgs::new(&self.c.clone_notice, &dbname);
+        self.clone_dialog_join_handle = CloneDialog::popup(args);
+    }
+
+    pub(super) fn await_clone_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.clone_notice.receive();
+        let res = self.clone_dialog_join_handle.join();
+        let (dbname, bbf_db) = match self.copy_source.take() {
+            Some(tup) => tup,
+            None => return
+        };
+        if res.cancelled {
+            return;
+        }
+        let work_dir = std::env::temp_dir().to_string_lossy().to_string();
+        self.c.window.set_enabled(false);
+        let args = CopyDialogArgs::clone_db(&self.c.copy_notice, &self.pg_conn_config,
+            &dbname, &res.dest_dbname, &bbf_db, &work_dir);
+        self.copy_dialog_join_handle = CopyDialog::popup(args);
+    }
+
+    // SQL Server connection and tools are chosen in the wizard, migration
+    // itself runs in the copy dialog
+    pub(super) fn open_migrate_dialog(&mut self, _: nwg::EventData) {
+        let work_dir = std::env::temp_dir().to_string_lossy().to_string();
+        self.c.window.set_enabled(false);
+        let args = MigrateDialogArgs::new(&self.c.migrate_notice, &self.pg_conn_config, &work_dir);
+        self.migrate_dialog_join_handle = MigrateDialog::popup(args);
+    }
+
+    pub(super) fn await_migrate_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.migrate_notice.receive();
+        let res = self.migrate_dialog_join_handle.join();
+        if res.cancelled {
+            return;
+        }
+        let dbname = &res.migration.target_dbname;
+        if self.server_dbnames.iter().any(|name| name.to_lowercase() == dbname.to_lowercase()) {
+            nwg::modal_error_message(&self.c.window, "Migrate from SQL Server", &format!(
+                "Database with name '{}' already exists on this server", dbname));
+            return;
+        }
+        self.c.window.set_enabled(false);
+        let args = CopyDialogArgs::migrate(&self.c.copy_notice, &self.pg_conn_config, res.migration);
+        self.copy_dialog_join_handle = CopyDialog::popup(args);
+    }
+
+    pub(super) fn await_copy_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.copy_notice.receive();
+        let _ = self.copy_dialog_join_handle.join();
+    }
+
+    pub(super) fn open_load_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let pcc = self.pg_conn_config.clone();
+        let args = LoadDbnamesDialogArgs::new(&self.c.load_notice, pcc);
+        self.load_join_handle = LoadDbnamesDialog::popup(args);
+    }
+
+    pub(super) fn await_load_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.load_notice.receive();
+        let res = self.load_join_handle.join();
+        if res.success {
+            self.set_dbnames(&res.dbnames, &res.bbf_db);
+        }
+    }
+
+    pub(super) fn open_db_list_dialog(&mut self, _: nwg::EventData) {
+        if self.plain_pg {
+            nwg::modal_error_message(&self.c.window, "Databases", "Database list is only available for Babelfish servers");
+            return;
+        }
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        if bbf_db.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Databases", "Postgres DB name must be specified");
+            return;
+        }
+        let selected = self.c.backup_dbname_combo.selection_string().unwrap_or_default();
+        self.c.window.set_enabled(false);
+        let args = DbListDialogArgs::new(&self.c.db_list_notice, &self.pg_conn_config, &bbf_db, &selected);
+        self.db_list_dialog_join_handle = DbListDialog::popup(args);
+    }
+
+    pub(super) fn await_db_list_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.db_list_notice.receive();
+        let res = self.db_list_dialog_join_handle.join();
+        if res.cancelled {
+            return;
+        }
+        // filter may hide the chosen DB
+        if !self.c.backup_dbname_combo.collection().contains(&res.dbname) {
+            self.c.backup_dbname_filter_input.set_text("");
+        }
+        self.filter_backup_dbnames(Some(&res.dbname));
+    }
+
+    pub(super) fn on_dbname_filter_changed(&mut self, _: nwg::EventData) {
+        let selected = self.c.backup_dbname_combo.selection_string();
+        self.filter_backup_dbnames(selected.as_deref());
+        self.set_export_dbnames();
+    }
+
+    // selection is kept when the selected name still matches the filter
+    fn filter_backup_dbnames(&mut self, selected: Option<&str>) {
+        let filter = self.c.backup_dbname_filter_input.text().trim().to_lowercase();
+        let dbnames: Vec<String> = self.backup_dbnames.iter()
+            .filter(|name| filter.is_empty() || name.to_lowercase().contains(&filter))
+            .cloned()
+            .collect();
+        let idx = match selected {
+            Some(sel) => dbnames.iter().position(|name| name == sel),
+            None => None
+        };
+        let empty = dbnames.is_empty();
+        self.c.backup_dbname_combo.set_collection(dbnames);
+        self.c.backup_dbname_combo.set_selection(if empty { None } else { Some(idx.unwrap_or(0)) });
+        self.on_dbname_changed(nwg::EventData::NoData);
+    }
+
+    pub(super) fn open_backup_dialog(&mut self, _: nwg::EventData) {
+        if let Some((running, _)) = &self.running_backup {
+            nwg::modal_info_message(&self.c.window, "Backup", &format!(
+                "Backup of '{}' is still running, next backup can be added to the queue from the Queue menu", running));
+            return;
+        }
+        let dbname = match self.c.backup_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => return
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        if bbf_db.is_empty() && !self.plain_pg {
+            nwg::modal_error_message(&self.c.window, "Backup", "Postgres DB name must be specified");
+            return;
+        }
+        let dir = self.c.backup_dest_dir_input.text();
+        if let Some(share) = unc_share_root(&dir) {
+            if !share_accessible(&share) {
+                self.c.window.set_enabled(false);
+                let args = ShareCredentialsDialogArgs::new(&self.c.share_credentials_notice, &share);
+                self.share_credentials_dialog_join_handle = ShareCredentialsDialog::popup(args);
+                return;
+            }
+        }
+        let mut filename = self.c.backup_filename_input.text();
+        let auto_increment = self.c.backup_auto_increment_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        if auto_increment {
+            filename = self.selected_backup_format().unique_dest_name(Path::new(&dir), &filename);
+            self.c.backup_filename_input.set_text(&filename);
+        }
+        let dest_path = Path::new(&dir).join(self.selected_backup_format().dest_name(&filename));
+        let mut go_on = true;
+        if dest_path.exists() || volume_path(&dest_path, 1).exists() {
+            let dest_path_st = dest_path.to_string_lossy().to_string();
+            go_on = ui::message_box_warning_yn(&format!(
+                "Destination file already exists:\r\n{}\r\n\r\nWould you like to overwrite it?", dest_path_st));
+        }
+        if go_on {
+            let retention = self.retention_policy_from_input(&dir);
+            self.settings.set_retention_policy(retention);
+            self.settings.backup_auto_increment = auto_increment;
+            self.settings.backup_include_globals = self.c.backup_globals_checkbox.check_state() == nwg::CheckBoxState::Checked;
+            self.settings.backup_restore_script = self.c.backup_restore_script_checkbox.check_state() == nwg::CheckBoxState::Checked;
+            self.settings.backup_mode = self.selected_backup_mode();
+            self.settings.backup_volume_size_mb = self.selected_volume_size();
+            self.settings.throttle_mb_per_sec = self.selected_throttle();
+            self.save_profile_bbf_db(&bbf_db);
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+            self.c.window.set_enabled(false);
+            let forecast = self.settings.forecast(RunKind::Backup, &dbname);
+            self.preflight_kind = RunKind::Backup;
+            let args = PreflightDialogArgs::backup(
+                &self.c.preflight_notice, &self.pg_conn_config, &bbf_db, &dir, self.selected_backup_format(), forecast);
+            self.preflight_dialog_join_handle = PreflightDialog::popup(args);
+        }
+    }
+
+    pub(super) fn await_share_credentials_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.share_credentials_notice.receive();
+        let res = self.share_credentials_dialog_join_handle.join();
+        if !res.cancelled {
+            self.open_backup_dialog(nwg::EventData::NoData);
+        }
+    }
+
+    fn start_backup_dialog(&mut self) {
+        let dbname = match self.c.backup_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => return
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        let dir = self.c.backup_dest_dir_input.text();
+        let filename = self.c.backup_filename_input.text();
+        let retention = self.settings.retention_policy(&dir);
+        let forecast = self.settings.forecast(RunKind::Backup, &dbname);
+        let args = BackupDialogArgs::new(
+            &self.c.backup_dialog_notice, &self.pg_conn_config,  &dbname, &bbf_db, &dir, &filename,
+            self.selected_backup_format(), &retention, forecast, &self.settings.email)
+            .with_globals(self.settings.backup_include_globals)
+            .with_mode(self.settings.backup_mode)
+            .with_volume_size(self.settings.backup_volume_size_mb)
+            .with_throttle(self.settings.throttle_mb_per_sec)
+            .with_restore_script(self.settings.backup_restore_script);
+        let dest_path = Path::new(&dir).join(self.selected_backup_format().dest_name(&filename));
+        self.running_backup = Some((dbname, dest_path));
+        self.backup_dialog_join_handle = BackupDialog::popup(args);
+    }
+
+    pub(super) fn await_backup_dialog(&mut self, _: nwg::EventData) {
+        self.c.backup_dialog_notice.receive();
+        let res = self.backup_dialog_join_handle.join();
+        if let Some((dbname, dest_path)) = self.running_backup.take() {
+            self.settings.record_run(RunRecord::new(RunKind::Backup, &dbname, &dest_path, res.duration, res.success));
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+        }
+        self.update_backup_forecast();
+    }
+
+    pub(super) fn open_schedule_dialog(&mut self, _: nwg::EventData) {
+        let dbname = match self.c.backup_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => return
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        let dir = self.c.backup_dest_dir_input.text();
+        let mut job = BackupJob::new("", &self.pg_conn_config, &bbf_db, &dbname, &dir, self.selected_backup_format());
+        job.include_globals = self.c.backup_globals_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        job.mode = self.selected_backup_mode();
+        job.volume_size_mb = self.selected_volume_size();
+        job.throttle_mb_per_sec = self.selected_throttle();
+        job.restore_script = self.c.backup_restore_script_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        self.c.window.set_enabled(false);
+        let args = ScheduleDialogArgs::new(&self.c.schedule_notice, &job);
+        self.schedule_dialog_join_handle = ScheduleDialog::popup(args);
+    }
+
+    pub(super) fn await_schedule_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.schedule_notice.receive();
+        let res = self.schedule_dialog_join_handle.join();
+        if !res.cancelled {
+            let dir = res.job.dest_dir.clone();
+            self.settings.set_backup_job(res.job);
+            self.settings.set_retention_policy(self.retention_policy_from_input(&dir));
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+            self.c.tray.set_visibility(self.has_builtin_jobs());
+        }
+    }
+
+    pub(super) fn open_email_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = EmailDialogArgs::new(&self.c.email_notice, &self.settings.email);
+        self.email_dialog_join_handle = EmailDialog::popup(args);
+    }
+
+    pub(super) fn await_email_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.email_notice.receive();
+        let res = self.email_dialog_join_handle.join();
+        if !res.cancelled {
+            self.settings.email = res.email;
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+        }
+    }
+
+    pub(super) fn open_restore_command_dialog(&mut self, _: nwg::EventData) {
+        if let Some((running, _)) = &self.running_restore {
+            nwg::modal_info_message(&self.c.window, "Restore", &format!(
+                "Restore of '{}' is still running", running));
+            return;
+        }
+        let src_path = self.c.restore_src_file_input.text();
+        let dbname = self.c.restore_dbname_input.text();
+        if let Some(existing) = self.single_db_conflict(&dbname) {
+            nwg::modal_error_message(&self.c.window, "Restore", &format!(
+                "DB server is in single-db migration mode and already has user database '{}'.\r\n\r\n\
+                Only one user database can exist in this mode, drop it first or restore\r\n\
+                into a DB server in multi-db mode.", existing));
+            return;
+        }
+        if self.restore_options.replace_existing {
+            let go_on = ui::message_box_warning_yn(&format!(
+                "If database '{}' exists, it will be DROPPED before restore and all its data will be lost.\r\n\r\nWould you like to continue?", &dbname));
+            if !go_on {
+                return;
+            }
+        }
+
+        // logins are offered with a preview before the preflight check,
+        // read errors are reported later by the restore itself
+        self.restore_globals = false;
+        if let Ok(Some(statements)) = pg_toc::read_dump_file(Path::new(&src_path), GLOBALS_FILE_NAME) {
+            self.c.window.set_enabled(false);
+            let args = GlobalsDialogArgs::new(&self.c.globals_notice, &statements);
+            self.globals_dialog_join_handle = GlobalsDialog::popup(args);
+            return;
+        }
+        self.open_restore_preflight_dialog();
+    }
+
+    pub(super) fn await_globals_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.globals_notice.receive();
+        let res = self.globals_dialog_join_handle.join();
+        if res.cancelled {
+            return;
+        }
+        self.restore_globals = res.restore_globals;
+        self.open_restore_preflight_dialog();
+    }
+
+    fn open_restore_preflight_dialog(&mut self) {
+        let src_path = self.c.restore_src_file_input.text();
+        let dbname = self.c.restore_dbname_input.text();
+        let bbf_db = self.c.restore_bbf_db_input.text();
+        let forecast = self.settings.forecast(RunKind::Restore, &dbname);
+        self.c.window.set_enabled(false);
+        self.preflight_kind = RunKind::Restore;
+        let args = PreflightDialogArgs::restore(
+            &self.c.preflight_notice, &self.pg_conn_config, &bbf_db, &src_path, forecast);
+        self.preflight_dialog_join_handle = PreflightDialog::popup(args);
+    }
+
+    pub(super) fn await_preflight_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.preflight_notice.receive();
+        let res = self.preflight_dialog_join_handle.join();
+        if !res.proceed {
+            return;
+        }
+        match self.preflight_kind {
+            RunKind::Backup => self.start_backup_dialog(),
+            RunKind::Restore => self.start_restore_dialog(),
+        }
+    }
+
+    fn start_restore_dialog(&mut self) {
+        let pcc = &self.pg_conn_config;
+        let zipfile = self.c.restore_src_file_input.text();
+        let dbname = self.c.restore_dbname_input.text();
+        let bbf_db = self.c.restore_bbf_db_input.text();
+        let forecast = self.settings.forecast(RunKind::Restore, &dbname);
+        let args = RestoreDialogArgs::new(
+            &self.c.restore_dialog_notice, &pcc,
+            &zipfile, &dbname, &bbf_db, &self.restore_options, forecast)
+            .with_globals(self.restore_globals)
+            .with_throttle(self.selected_throttle());
+        self.running_restore = Some((dbname, PathBuf::from(&zipfile)));
+        self.restore_dialog_join_handle = RestoreDialog::popup(args);
+    }
+
+    pub(super) fn await_restore_command_dialog(&mut self, _: nwg::EventData) {
+        self.c.restore_dialog_notice.receive();
+        let res = self.restore_dialog_join_handle.join();
+        if let Some((dbname, zip_path)) = self.running_restore.take() {
+            self.settings.record_run(RunRecord::new(RunKind::Restore, &dbname, &zip_path, res.duration, res.success));
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+        }
+        self.update_restore_forecast();
+        // restored DB is added to the backup list, skipped while another popup is open
+        if res.success && self.c.window.enabled() {
+            self.open_load_dialog(nwg::EventData::NoData);
+        }
+    }
+
+    // nothing is restored, so no preflight check and no run history
+    pub(super) fn open_restore_validate_dialog(&mut self, _: nwg::EventData) {
+        let src_path = self.c.restore_src_file_input.text();
+        if src_path.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Validate restore",
+                "Please choose a backup file or an unzipped backup directory first");
+            return;
+        }
+        let dbname = self.c.restore_dbname_input.text();
+        let bbf_db = self.c.restore_bbf_db_input.text();
+        self.c.window.set_enabled(false);
+        let args = RestoreDialogArgs::new(
+            &self.c.restore_validate_notice, &self.pg_conn_config,
+            &src_path, &dbname, &bbf_db, &self.restore_options, None)
+            .validate_only();
+        self.restore_validate_dialog_join_handle = RestoreDialog::popup(args);
+    }
+
+    pub(super) fn await_restore_validate_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.restore_validate_notice.receive();
+        let _ = self.restore_validate_dialog_join_handle.join();
+    }
+
+    // schema-only script for review, server is not used
+    pub(super) fn open_restore_ddl_dialog(&mut self, _: nwg::EventData) {
+        let src_path = self.c.restore_src_file_input.text();
+        if src_path.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Export DDL script",
+                "Please choose a backup file or an unzipped backup directory on the 'Restore' tab first");
+            return;
+        }
+        if !self.c.ddl_file_chooser.run(Some(&self.c.window)) {
+            return;
+        }
+        let mut dest = match self.c.ddl_file_chooser.get_selected_item() {
+            Ok(file) => file.to_string_lossy().to_string(),
+            Err(_) => return
+        };
+        if !dest.to_lowercase().ends_with(".sql") {
+            dest.push_str(".sql");
+        }
+        let dbname = self.c.restore_dbname_input.text();
+        let bbf_db = self.c.restore_bbf_db_input.text();
+        self.c.window.set_enabled(false);
+        let args = RestoreDialogArgs::new(
+            &self.c.restore_ddl_notice, &self.pg_conn_config,
+            &src_path, &dbname, &bbf_db, &self.restore_options, None)
+            .export_ddl(&dest);
+        self.restore_ddl_dialog_join_handle = RestoreDialog::popup(args);
+    }
+
+    pub(super) fn await_restore_ddl_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.restore_ddl_notice.receive();
+        let _ = self.restore_ddl_dialog_join_handle.join();
+    }
+
+    pub(super) fn open_restore_options_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = RestoreOptionsDialogArgs::new(&self.c.restore_options_notice, &self.restore_options);
+        self.restore_options_dialog_join_handle = RestoreOptionsDialog::popup(args);
+    }
+
+    pub(super) fn await_restore_options_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.restore_options_notice.receive();
+        let res = self.restore_options_dialog_join_handle.join();
+        if !res.cancelled {
+            self.restore_options = res.options;
+            self.on_restore_dbname_changed(nwg::EventData::NoData);
+        }
+    }
+
+    pub(super) fn choose_catalog_dir(&mut self, _: nwg::EventData) {
+        let dir = self.c.catalog_dir_input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = self.c.catalog_dir_chooser.set_default_folder(&dir);
+        }
+
+        if self.c.catalog_dir_chooser.run(Some(&self.c.window)) {
+            if let Ok(directory) = self.c.catalog_dir_chooser.get_selected_item() {
+                self.c.catalog_dir_input.set_text(&directory.to_string_lossy());
+                self.scan_catalog(nwg::EventData::NoData);
+            }
+        }
+    }
+
+    // every backup is opened to read its TOC, this may take a while on a network share
+    pub(super) fn scan_catalog(&mut self, _: nwg::EventData) {
+        if self.catalog_busy {
+            return;
+        }
+        let dir = self.c.catalog_dir_input.text().trim().to_string();
+        if dir.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Catalog", "Backup folder must be specified");
+            return;
+        }
+        if self.settings.catalog_dir != dir {
+            self.settings.catalog_dir = dir.clone();
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+        }
+        // age from the retention policy of this folder, if one is set on the "Backup" tab
+        let policy = self.settings.retention_policies.iter()
+            .find(|pol| pol.matches_dir(&dir) && RetentionMode::MaxAgeDays == pol.mode && pol.is_enabled());
+        if let Some(pol) = policy {
+            self.c.catalog_age_input.set_text(&pol.value.to_string());
+        }
+        self.catalog_entries.clear();
+        self.c.catalog_list_view.clear();
+        self.set_catalog_busy(true, "Scanning backup folder ...");
+        let sender = self.c.catalog_scan_notice.sender();
+        let join_handle = thread::spawn(move || {
+            let res = match scan_backup_dir(Path::new(&dir)) {
+                Ok(entries) => CatalogScanResult::success(entries),
+                Err(e) => CatalogScanResult::failure(format!(
+                    "Error scanning backup folder: {}, message: {}", dir, e))
+            };
+            sender.send();
+            res
+        });
+        self.catalog_scan_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    pub(super) fn on_catalog_scanned(&mut self, _: nwg::EventData) {
+        self.c.catalog_scan_notice.receive();
+        let res = self.catalog_scan_join_handle.join();
+        if !res.error.is_empty() {
+            self.set_catalog_busy(false, &res.error);
+            return;
+        }
+        self.catalog_entries = res.entries;
+        self.fill_catalog_list();
+        self.set_catalog_busy(false, &self.catalog_summary());
+    }
+
+    pub(super) fn restore_catalog_entry(&mut self, _: nwg::EventData) {
+        let en = match self.selected_catalog_entry() {
+            Some(en) => en,
+            None => return
+        };
+        self.c.tabs_container.set_selected_tab(1);
+        self.set_src_file(&en.path);
+    }
+
+    pub(super) fn verify_catalog_entry(&mut self, _: nwg::EventData) {
+        let en = match self.selected_catalog_entry() {
+            Some(en) => en,
+            None => return
+        };
+        self.set_catalog_busy(true, &format!("Verifying backup: {} ...", en.file_name()));
+        let sender = self.c.catalog_verify_notice.sender();
+        let join_handle = thread::spawn(move || {
+            let res = CatalogVerifyResult::new(&en.file_name(), en.verify());
+            sender.send();
+            res
+        });
+        self.catalog_verify_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    pub(super) fn on_catalog_verified(&mut self, _: nwg::EventData) {
+        self.c.catalog_verify_notice.receive();
+        let res = self.catalog_verify_join_handle.join();
+        self.set_catalog_busy(false, &self.catalog_summary());
+        if res.success {
+            nwg::modal_info_message(&self.c.window, "Verify backup", &format!(
+                "Backup is readable: {}\r\n{}", res.file_name, res.message));
+        } else {
+            nwg::modal_error_message(&self.c.window, "Verify backup", &format!(
+                "Backup is damaged: {}\r\n{}", res.file_name, res.message));
+        }
+    }
+
+    pub(super) fn delete_catalog_entry(&mut self, _: nwg::EventData) {
+        if self.catalog_busy {
+            return;
+        }
+        let entries: Vec<CatalogEntry> = self.c.catalog_list_view.selected_items().iter()
+            .filter_map(|idx| self.catalog_entries.get(*idx))
+            .cloned()
+            .collect();
+        if entries.is_empty() {
+            nwg::modal_info_message(&self.c.window, "Catalog", "Please select one or more backups in the list first");
+            return;
+        }
+        self.delete_catalog_entries("Delete backups", entries);
+    }
+
+    // most recent backup of each DB is kept, so a cleanup never removes the last copy
+    pub(super) fn delete_older_catalog_entries(&mut self, _: nwg::EventData) {
+        if self.catalog_busy {
+            return;
+        }
+        let days = match self.c.catalog_age_input.text().trim().parse::<u32>() {
+            Ok(days) if days > 0 => days,
+            _ => {
+                nwg::modal_error_message(&self.c.window, "Delete older backups",
+                    "Age in days must be a positive number");
+                return;
+            }
+        };
+        let entries = select_older_than(&self.catalog_entries, days, chrono::Local::now().timestamp());
+        if entries.is_empty() {
+            nwg::modal_info_message(&self.c.window, "Delete older backups", &format!(
+                "No backups older than {} days found, most recent backup of each DB is always kept", days));
+            return;
+        }
+        self.delete_catalog_entries("Delete older backups", entries);
+    }
+
+    fn delete_catalog_entries(&mut self, title: &str, entries: Vec<CatalogEntry>) {
+        if let Some((_, path)) = &self.running_restore {
+            if entries.iter().any(|en| &en.path == path) {
+                nwg::modal_error_message(&self.c.window, title,
+                    "Backup is being restored and cannot be deleted");
+                return;
+            }
+        }
+        let recycle = self.c.catalog_recycle_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        if self.settings.catalog_delete_permanently == recycle {
+            self.settings.catalog_delete_permanently = !recycle;
+            if let Err(e) = self.settings.save() {
+                nwg::modal_error_message(&self.c.window, "Settings", &e.to_string());
+            }
+        }
+        let total: u64 = entries.iter().map(|en| en.size).sum();
+        let mut names: Vec<String> = entries.iter().take(10).map(|en| en.file_name()).collect();
+        if entries.len() > names.len() {
+            names.push(format!("... and {} more", entries.len() - names.len()));
+        }
+        let go_on = ui::message_box_warning_yn(&format!(
+            "Backups will be {}, count: {}, total size: {}\r\n\r\n{}\r\n\r\nWould you like to continue?",
+            if recycle { "moved to the recycle bin" } else { "deleted permanently" },
+            entries.len(), format_disk_size(total), names.join("\r\n")));
+        if !go_on {
+            return;
+        }
+        let mut errors = Vec::new();
+        for en in entries.iter() {
+            match delete_backup(&en.path, recycle) {
+                Ok(_) => {
+                    log::info!("Backup deleted from catalog: {}, recycle bin: {}", en.path.to_string_lossy(), recycle);
+                    self.catalog_entries.retain(|existing| existing.path != en.path);
+                },
+                Err(e) => errors.push(format!("{}: {}", en.file_name(), e))
+            }
+        }
+        self.fill_catalog_list();
+        self.c.catalog_status_label.set_text(&self.catalog_summary());
+        if !errors.is_empty() {
+            nwg::modal_error_message(&self.c.window, title, &format!(
+                "Error deleting backups:\r\n{}", errors.join("\r\n")));
+        }
+    }
+
+    fn selected_catalog_entry(&self) -> Option<CatalogEntry> {
+        if self.catalog_busy {
+            return None;
+        }
+        match self.c.catalog_list_view.selected_item() {
+            Some(idx) if idx < self.catalog_entries.len() => Some(self.catalog_entries[idx].clone()),
+            _ => {
+                nwg::modal_info_message(&self.c.window, "Catalog", "Please select a backup in the list first");
+                None
+            }
+        }
+    }
+
+    // backups that cannot be read are listed with the error instead of the DB name
+    fn fill_catalog_list(&self) {
+        self.c.catalog_list_view.clear();
+        for en in self.catalog_entries.iter() {
+            let dbname = if en.is_valid() {
+                en.dbname.clone()
+            } else {
+                format!("Error: {}", en.error)
+            };
+            self.c.catalog_list_view.insert_items_row(None, &[
+                &en.file_name(), &dbname, &en.dump_time, &format_disk_size(en.size), &en.server]);
+        }
+    }
+
+    fn catalog_summary(&self) -> String {
+        let total: u64 = self.catalog_entries.iter().map(|en| en.size).sum();
+        format!("Backups: {}, total size: {}", self.catalog_entries.len(), format_disk_size(total))
+    }
+
+    fn set_catalog_busy(&mut self, busy: bool, status: &str) {
+        self.catalog_busy = busy;
+        self.c.catalog_status_label.set_text(status);
+        self.c.catalog_dir_button.set_enabled(!busy);
+        self.c.catalog_scan_button.set_enabled(!busy);
+        self.c.catalog_restore_button.set_enabled(!busy);
+        self.c.catalog_verify_button.set_enabled(!busy);
+        self.c.catalog_delete_button.set_enabled(!busy);
+        self.c.catalog_delete_older_button.set_enabled(!busy);
+    }
+
+    pub(super) fn load_export_tables(&mut self, _: nwg::EventData) {
+        if self.export_busy {
+            return;
+        }
+        let dbname = match self.c.export_dbname_combo.selection_string() {
+            Some(name) => name,
+            None => {
+                nwg::modal_error_message(&self.c.window, "Export", "DB name must be selected");
+                return;
+            }
+        };
+        if self.plain_pg {
+            nwg::modal_error_message(&self.c.window, "Export", "Export is only available for Babelfish databases");
+            return;
+        }
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        if bbf_db.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Export", "Postgres DB name must be specified on the \"Backup\" tab");
+            return;
+        }
+        self.export_tables.clear();
+        self.export_dbname = dbname.clone();
+        self.c.export_tables_view.clear();
+        self.set_export_busy(true, "Loading tables ...");
+        let sender = self.c.export_tables_notice.sender();
+        let pcc = self.pg_conn_config.clone();
+        let join_handle = thread::spawn(move || {
+            let res = match ExportDialog::load_tables(&pcc, &bbf_db, &dbname) {
+                Ok(tables) => ExportTablesResult::success(tables),
+                Err(e) => ExportTablesResult::failure(format!(
+                    "Error loading tables of DB: {}, message: {}", dbname, e))
+            };
+            sender.send();
+            res
+        });
+        self.export_tables_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    pub(super) fn on_export_tables_loaded(&mut self, _: nwg::EventData) {
+        self.c.export_tables_notice.receive();
+        let res = self.export_tables_join_handle.join();
+        if !res.error.is_empty() {
+            self.set_export_busy(false, &res.error);
+            return;
+        }
+        self.export_tables = res.tables;
+        for tb in self.export_tables.iter() {
+            self.c.export_tables_view.insert_items_row(None, &[&tb.schema, &tb.table, &tb.rows.to_string()]);
+        }
+        self.set_export_busy(false, &format!("Tables: {}", self.export_tables.len()));
+    }
+
+    pub(super) fn choose_export_dest_dir(&mut self, _: nwg::EventData) {
+        let dir = self.c.export_dest_dir_input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = self.c.export_dest_dir_chooser.set_default_folder(&dir);
+        }
+
+        if self.c.export_dest_dir_chooser.run(Some(&self.c.window)) {
+            if let Ok(directory) = self.c.export_dest_dir_chooser.get_selected_item() {
+                self.c.export_dest_dir_input.set_text(&directory.to_string_lossy());
+            }
+        }
+    }
+
+    pub(super) fn select_all_export_tables(&mut self, _: nwg::EventData) {
+        for idx in 0..self.export_tables.len() {
+            self.c.export_tables_view.select_item(idx, true);
+        }
+        self.c.export_tables_view.set_focus();
+    }
+
+    // selected tables are written to CSV files and zipped by the export dialog
+    pub(super) fn open_export_dialog(&mut self, _: nwg::EventData) {
+        if self.export_busy {
+            return;
+        }
+        let tables: Vec<ExportTable> = self.c.export_tables_view.selected_items().iter()
+            .filter_map(|idx| self.export_tables.get(*idx))
+            .cloned()
+            .collect();
+        if tables.is_empty() {
+            nwg::modal_info_message(&self.c.window, "Export", "Please select one or more tables in the list first");
+            return;
+        }
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        let dest_dir = self.c.export_dest_dir_input.text().trim().to_string();
+        if let Err(e) = probe_dir_writable(Path::new(&dest_dir)) {
+            nwg::modal_error_message(&self.c.window, "Export", &e.to_string());
+            return;
+        }
+        self.c.window.set_enabled(false);
+        let args = ExportDialogArgs::new(&self.c.export_dialog_notice, &self.pg_conn_config,
+            &self.export_dbname, &bbf_db, tables, &dest_dir);
+        self.export_dialog_join_handle = ExportDialog::popup(args);
+    }
+
+    pub(super) fn await_export_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.export_dialog_notice.receive();
+        let _ = self.export_dialog_join_handle.join();
+    }
+
+    // Experimental: all loaded tables of the DB are written into a single .bacpac
+    // package that can be imported into SQL Server or Azure SQL with SqlPackage.
+    pub(super) fn open_bacpac_export_dialog(&mut self, _: nwg::EventData) {
+        if self.export_busy {
+            return;
+        }
+        if self.export_tables.is_empty() {
+            nwg::modal_info_message(&self.c.window, "Export BACPAC", "Please load the list of tables first");
+            return;
+        }
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        let dest_dir = self.c.export_dest_dir_input.text().trim().to_string();
+        if let Err(e) = probe_dir_writable(Path::new(&dest_dir)) {
+            nwg::modal_error_message(&self.c.window, "Export BACPAC", &e.to_string());
+            return;
+        }
+        self.c.window.set_enabled(false);
+        let args = ExportDialogArgs::new(&self.c.export_dialog_notice, &self.pg_conn_config,
+            &self.export_dbname, &bbf_db, self.export_tables.clone(), &dest_dir).bacpac();
+        self.export_dialog_join_handle = ExportDialog::popup(args);
+    }
+
+    pub(super) fn choose_physical_dest_dir(&mut self, _: nwg::EventData) {
+        let dir = self.c.physical_dest_dir_input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = self.c.physical_dest_dir_chooser.set_default_folder(&dir);
+        }
+
+        if self.c.physical_dest_dir_chooser.run(Some(&self.c.window)) {
+            if let Ok(directory) = self.c.physical_dest_dir_chooser.get_selected_item() {
+                self.c.physical_dest_dir_input.set_text(&directory.to_string_lossy());
+            }
+        }
+    }
+
+    pub(super) fn on_physical_format_changed(&mut self, _: nwg::EventData) {
+        let filename = self.c.physical_filename_input.text();
+        if !filename.is_empty() {
+            let stem = BackupFormat::strip_extension(&filename);
+            self.c.physical_filename_input.set_text(&self.selected_physical_format().backup_name(&stem));
+        }
+    }
+
+    fn selected_physical_format(&self) -> BackupFormat {
+        BackupFormat::from_index(self.c.physical_format_combo.selection())
+    }
+
+    // Whole instance is backed up with pg_basebackup, preflight checks and
+    // retention are specific to logical backups and are not applied.
+    pub(super) fn open_physical_backup_dialog(&mut self, _: nwg::EventData) {
+        if let Some((running, _)) = &self.running_backup {
+            nwg::modal_info_message(&self.c.window, "Physical backup", &format!(
+                "Backup of '{}' is still running", running));
+            return;
+        }
+        let dir = self.c.physical_dest_dir_input.text().trim().to_string();
+        if let Err(e) = probe_dir_writable(Path::new(&dir)) {
+            nwg::modal_error_message(&self.c.window, "Physical backup", &e.to_string());
+            return;
+        }
+        let filename = self.c.physical_filename_input.text().trim().to_string();
+        if filename.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Physical backup", "Backup file name must be specified");
+            return;
+        }
+        let format = self.selected_physical_format();
+        let dest_path = Path::new(&dir).join(format.dest_name(&filename));
+        if dest_path.exists() {
+            let dest_path_st = dest_path.to_string_lossy().to_string();
+            let go_on = ui::message_box_warning_yn(&format!(
+                "Destination file already exists:\r\n{}\r\n\r\nWould you like to overwrite it?", dest_path_st));
+            if !go_on {
+                return;
+            }
+        }
+        let forecast = self.settings.forecast(RunKind::Backup, PHYSICAL_BACKUP_NAME);
+        let args = BackupDialogArgs::new(
+            &self.c.backup_dialog_notice, &self.pg_conn_config, PHYSICAL_BACKUP_NAME, "", &dir, &filename,
+            format, &RetentionPolicy::default(), forecast, &self.settings.email)
+            .with_throttle(self.selected_throttle())
+            .physical();
+        self.running_backup = Some((PHYSICAL_BACKUP_NAME.to_string(), dest_path));
+        self.backup_dialog_join_handle = BackupDialog::popup(args);
+    }
+
+    pub(super) fn choose_wal_archive_dir(&mut self, _: nwg::EventData) {
+        let dir = self.c.physical_wal_dir_input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = self.c.physical_wal_dir_chooser.set_default_folder(&dir);
+        }
+
+        if self.c.physical_wal_dir_chooser.run(Some(&self.c.window)) {
+            if let Ok(directory) = self.c.physical_wal_dir_chooser.get_selected_item() {
+                self.c.physical_wal_dir_input.set_text(&directory.to_string_lossy());
+            }
+        }
+    }
+
+    // "archive_command" is set with ALTER SYSTEM, the folder path is
+    // used by the server as is, so it is not checked on this machine.
+    pub(super) fn enable_wal_archiving(&mut self, _: nwg::EventData) {
+        let wal_dir = self.c.physical_wal_dir_input.text().trim().to_string();
+        if wal_dir.is_empty() {
+            nwg::modal_error_message(&self.c.window, "WAL archiving", "WAL archive directory must be specified");
+            return;
+        }
+        let go_on = ui::message_box_warning_yn(&format!(
+            "Server will be configured to copy WAL files into directory:\r\n{}\r\n\r\n\
+            Directory must exist on the server machine and must be writable by the server service account.\r\n\r\n\
+            Would you like to enable WAL archiving?", &wal_dir));
+        if !go_on {
+            return;
+        }
+        self.set_wal_archive_busy(true, "Enabling WAL archiving ...");
+        let sender = self.c.wal_archive_notice.sender();
+        let pcc = self.pg_conn_config.clone();
+        let join_handle = thread::spawn(move || {
+            let res = match pitr::enable_wal_archiving(&pcc, &wal_dir) {
+                Ok(true) => Ok("WAL archiving is configured, server restart is required to start archiving".to_string()),
+                Ok(false) => Ok("WAL archiving is configured".to_string()),
+                Err(e) => Err(format!("Error enabling WAL archiving: {}", e))
+            };
+            sender.send();
+            WalArchiveResult::new("WAL archiving", res)
+        });
+        self.wal_archive_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    pub(super) fn load_wal_archive_status(&mut self, _: nwg::EventData) {
+        let wal_dir = self.c.physical_wal_dir_input.text().trim().to_string();
+        self.set_wal_archive_busy(true, "Loading archive status ...");
+        let sender = self.c.wal_archive_notice.sender();
+        let pcc = self.pg_conn_config.clone();
+        let join_handle = thread::spawn(move || {
+            let res = match pitr::load_wal_archive_status(&pcc, &wal_dir) {
+                Ok(status) => Ok(status.describe()),
+                Err(e) => Err(format!("Error loading WAL archive status: {}", e))
+            };
+            sender.send();
+            WalArchiveResult::new("WAL archive status", res)
+        });
+        self.wal_archive_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    pub(super) fn on_wal_archive_complete(&mut self, _: nwg::EventData) {
+        self.c.wal_archive_notice.receive();
+        let res = self.wal_archive_join_handle.join();
+        if !res.error.is_empty() {
+            self.set_wal_archive_busy(false, &res.error);
+            nwg::modal_error_message(&self.c.window, &res.title, &res.error);
+            return;
+        }
+        self.set_wal_archive_busy(false, "");
+        nwg::modal_info_message(&self.c.window, &res.title, &res.message);
+    }
+
+    fn set_wal_archive_busy(&self, busy: bool, status: &str) {
+        self.c.physical_archive_enable_button.set_enabled(!busy);
+        self.c.physical_archive_status_button.set_enabled(!busy);
+        self.c.physical_archive_status_label.set_text(status);
+    }
+
+    pub(super) fn open_pitr_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let args = PitrDialogArgs::new(&self.c.pitr_notice,
+            &self.c.physical_wal_dir_input.text(), &self.c.physical_dest_dir_input.text());
+        self.pitr_dialog_join_handle = PitrDialog::popup(args);
+    }
+
+    // server is not used, the data directory is prepared on this machine
+    pub(super) fn await_pitr_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.pitr_notice.receive();
+        let res = self.pitr_dialog_join_handle.join();
+        if res.cancelled {
+            return;
+        }
+        self.c.window.set_enabled(false);
+        let args = RestoreDialogArgs::new(&self.c.pitr_restore_notice, &self.pg_conn_config,
+            &res.pitr.backup_path, "", "", &RestoreOptions::default(), None)
+            .point_in_time(res.pitr);
+        self.pitr_restore_dialog_join_handle = RestoreDialog::popup(args);
+    }
+
+    pub(super) fn await_pitr_restore_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.pitr_restore_notice.receive();
+        let _ = self.pitr_restore_dialog_join_handle.join();
+    }
+
+    // CSV file is imported into the selected table, column mapping is chosen in the
+    // import dialog and the data is copied with the export dialog in import mode
+    pub(super) fn open_import_dialog(&mut self, _: nwg::EventData) {
+        if self.export_busy {
+            return;
+        }
+        let selected = self.c.export_tables_view.selected_items();
+        let table = match selected.as_slice() {
+            [idx] if *idx < self.export_tables.len() => self.export_tables[*idx].clone(),
+            _ => {
+                nwg::modal_info_message(&self.c.window, "Import CSV", "Please select a single table in the list first");
+                return;
+            }
+        };
+        let dir = self.c.export_dest_dir_input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = self.c.export_import_file_chooser.set_default_folder(&dir);
+        }
+        if !self.c.export_import_file_chooser.run(Some(&self.c.window)) {
+            return;
+        }
+        let csv_file = match self.c.export_import_file_chooser.get_selected_item() {
+            Ok(file) => file.to_string_lossy().to_string(),
+            Err(_) => return
+        };
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        self.import_target = Some((table.clone(), csv_file.clone()));
+        self.c.window.set_enabled(false);
+        let args = ImportDialogArgs::new(&self.c.import_dialog_notice, &self.pg_conn_config, &bbf_db, &table, &csv_file);
+        self.import_dialog_join_handle = ImportDialog::popup(args);
+    }
+
+    pub(super) fn await_import_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.import_dialog_notice.receive();
+        let res = self.import_dialog_join_handle.join();
+        let (table, csv_file) = match self.import_target.take() {
+            Some(tup) => tup,
+            None => return
+        };
+        if res.cancelled {
+            return;
+        }
+        let bbf_db = self.c.backup_bbf_db_input.text().trim().to_string();
+        self.c.window.set_enabled(false);
+        let args = ExportDialogArgs::import(&self.c.export_dialog_notice, &self.pg_conn_config,
+            &bbf_db, &table, &csv_file, res.delimiter, res.columns);
+        self.export_dialog_join_handle = ExportDialog::popup(args);
+    }
+
+    fn set_export_busy(&mut self, busy: bool, status: &str) {
+        self.export_busy = busy;
+        self.c.export_status_label.set_text(status);
+        self.c.export_tables_button.set_enabled(!busy);
+        self.c.export_select_all_button.set_enabled(!busy);
+        self.c.export_import_button.set_enabled(!busy);
+        self.c.export_bacpac_button.set_enabled(!busy);
+        self.c.export_run_button.set_enabled(!busy);
+    }
+
+    pub(super) fn export_toc_json(&mut self, _: nwg::EventData) {
+        let src_path = self.c.restore_src_file_input.text();
+        if src_path.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Export TOC",
+                "Please choose a backup file or an unzipped backup directory on the 'Restore' tab first");
+            return;
+        }
+        let summary = match DumpSummary::read_path(Path::new(&src_path)) {
+            Ok(summary) => summary,
+            Err(e) => {
+                nwg::modal_error_message(&self.c.window, "Export TOC", &e.to_string());
+                return;
+            }
+        };
+        if !self.c.toc_json_file_chooser.run(Some(&self.c.window)) {
+            return;
+        }
+        let mut dest = match self.c.toc_json_file_chooser.get_selected_item() {
+            Ok(file) => file.to_string_lossy().to_string(),
+            Err(_) => return
+        };
+        if !dest.to_lowercase().ends_with(".json") {
+            dest.push_str(".json");
+        }
+        let res = summary.to_json().and_then(|json| fs::write(&dest, json));
+        match res {
+            Ok(_) => nwg::modal_info_message(&self.c.window, "Export TOC",
+                &format!("Backup TOC written to: {}", &dest)),
+            Err(e) => nwg::modal_error_message(&self.c.window, "Export TOC", &e.to_string())
+        };
+    }
+
+    pub(super) fn export_settings(&mut self, _: nwg::EventData) {
+        if !self.c.settings_export_file_chooser.run(Some(&self.c.window)) {
+            return;
+        }
+        let mut dest = match self.c.settings_export_file_chooser.get_selected_item() {
+            Ok(file) => file.to_string_lossy().to_string(),
+            Err(_) => return
+        };
+        if !dest.to_lowercase().ends_with(".json") {
+            dest.push_str(".json");
+        }
+        match self.settings.export_to_file(Path::new(&dest)) {
+            Ok(_) => nwg::modal_info_message(&self.c.window, "Export settings", &format!(
+                "Settings written to: {}\r\n\r\n\
+                DB passwords are not included, SMTP password needs to be entered again after import.", &dest)),
+            Err(e) => nwg::modal_error_message(&self.c.window, "Export settings", &e.to_string())
+        };
+    }
+
+    pub(super) fn import_settings(&mut self, _: nwg::EventData) {
+        if !self.c.settings_import_file_chooser.run(Some(&self.c.window)) {
+            return;
+        }
+        let src = match self.c.settings_import_file_chooser.get_selected_item() {
+            Ok(file) => file.to_string_lossy().to_string(),
+            Err(_) => return
+        };
+        let go_on = ui::message_box_warning_yn(&format!(
+            "Connection profiles, backup jobs and preferences will be REPLACED with the ones from file:\r\n{}\r\n\r\nWould you like to continue?", &src));
+        if !go_on {
+            return;
+        }
+        let res = self.settings.import_from_file(Path::new(&src))
+            .and_then(|_| self.settings.save());
+        if let Err(e) = res {
+            log::error!("{}", e);
+            nwg::modal_error_message(&self.c.window, "Import settings", &e.to_string());
+            return;
+        }
+        self.pg_conn_config.tls_ca_file = self.settings.tls_ca_file.clone();
+        self.on_dest_dir_changed(nwg::EventData::NoData);
+        self.settings_to_controls();
+        nwg::modal_info_message(&self.c.window, "Import settings", &format!(
+            "Settings imported from: {}\r\n\r\n\
+            Jobs run by Windows Task Scheduler need to be scheduled again on this machine.", &src));
+    }
+
+    pub(super) fn open_website(&mut self, _: nwg::EventData) {
+        let _ = Command::new("cmd")
+            .arg("/c")
+            .arg("start")
+            .arg("https://wiltondb.com")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .status();
+    }
+
+    pub(super) fn toggle_help(&mut self, _: nwg::EventData) {
+        self.help_expanded = !self.help_expanded;
+        let (width, height) = self.c.window.size();
+        if self.help_expanded {
+            self.c.window.set_size(width, height + HELP_PANEL_HEIGHT);
+        } else if height > HELP_PANEL_HEIGHT {
+            self.c.window.set_size(width, height - HELP_PANEL_HEIGHT);
+        }
+        let label = if self.help_expanded { "Hide help" } else { "Help" };
+        self.c.backup_help_button.set_text(label);
+        self.c.restore_help_button.set_text(label);
+        self.c.backup_help_box.set_visible(self.help_expanded);
+        self.c.restore_help_box.set_visible(self.help_expanded);
+        self.c.update_tab_order();
+    }
+
+    pub(super) fn on_resize(&mut self, _: nwg::EventData) {
+        self.c.update_tab_order();
+    }
+
+    pub(super) fn choose_dest_dir(&mut self, _: nwg::EventData) {
+        if let Ok(d) = std::env::current_dir() {
+            if let Some(d) = d.to_str() {
+                let _ = self.c.backup_dest_dir_chooser.set_default_folder(d);
+            }
+        }
+
+        if self.c.backup_dest_dir_chooser.run(Some(&self.c.window)) {
+            self.c.backup_dest_dir_input.set_text("");
+            if let Ok(directory) = self.c.backup_dest_dir_chooser.get_selected_item() {
+                let dir = directory.to_string_lossy().to_string();
+                self.c.backup_dest_dir_input.set_text(&dir);
+            }
+            self.probe_dest_dir();
+        }
+    }
+
+    pub(super) fn choose_src_file(&mut self, _: nwg::EventData) {
+        if let Ok(d) = std::env::current_dir() {
+            if let Some(d) = d.to_str() {
+                let _ = self.c.restore_src_file_chooser.set_default_folder(d);
+            }
+        }
+
+        if self.c.restore_src_file_chooser.run(Some(&self.c.window)) {
+            self.c.restore_src_file_input.set_text("");
+            if let Ok(file) = self.c.restore_src_file_chooser.get_selected_item() {
+                self.set_src_file(Path::new(&file));
+            }
+        }
+    }
+
+    pub(super) fn choose_src_dir(&mut self, _: nwg::EventData) {
+        if let Ok(d) = std::env::current_dir() {
+            if let Some(d) = d.to_str() {
+                let _ = self.c.restore_src_dir_chooser.set_default_folder(d);
+            }
+        }
+
+        if self.c.restore_src_dir_chooser.run(Some(&self.c.window)) {
+            self.c.restore_src_file_input.set_text("");
+            if let Ok(dir) = self.c.restore_src_dir_chooser.get_selected_item() {
+                self.set_src_file(Path::new(&dir));
+            }
+        }
+    }
+
+    pub(super) fn on_file_drop(&mut self, ed: nwg::EventData) {
+        let files = match ed {
+            nwg::EventData::OnFileDrop(drop) => drop.files(),
+            _ => return
+        };
+        let backup_file = files.iter().find(|f| {
+            let name = f.to_lowercase();
+            [".zip", ".tar", ".sql", ".sql.gz", ".001"].iter().any(|ext| name.ends_with(ext))
+        }).or_else(|| files.iter().find(|f| Path::new(f).is_dir()));
+        match backup_file {
+            Some(file) => {
+                self.c.tabs_container.set_selected_tab(1);
+                self.set_src_file(Path::new(file));
+            },
+            None => {
+                nwg::modal_error_message(&self.c.window, "Error",
+                    "Only backup files with '.zip', '.tar', '.sql' or '.sql.gz' extensions, first volumes ('.001') of split backups or unzipped backup directories can be restored");
+            }
+        }
+    }
+
+    // another copy of the app was launched, possibly with a file argument
+    pub(super) fn on_instance_started(&mut self, _: nwg::EventData) {
+        let file = self.c.instance_notice.receive();
+        self.show_window(nwg::EventData::NoData);
+        bring_to_foreground(&self.c.window);
+        if !file.is_empty() {
+            if !self.c.window.enabled() {
+                // other dialog is already open
+                return;
+            }
+            self.open_src_file_arg(&file);
+        }
+    }
+
+    // opened with "Open With..." from Explorer
+    fn open_src_file_arg(&mut self, file: &str) {
+        let path = Path::new(file);
+        if !path.exists() {
+            log::error!("Backup file not found: {}", file);
+            nwg::modal_error_message(&self.c.window, "Error",
+                &format!("Backup file not found: {}", file));
+            return;
+        }
+        self.c.tabs_container.set_selected_tab(1);
+        self.set_src_file(path);
+    }
+
+    fn set_src_file(&mut self, file: &Path) {
+        let fpath_st = file.to_string_lossy().to_string();
+        self.c.restore_src_file_input.set_text(&fpath_st);
+        self.probe_src_file();
+        self.load_src_info(&fpath_st);
+        // DB name is taken from the joined file name of a split backup
+        let base = volume_set_base(file);
+        let file = base.as_deref().unwrap_or(file);
+        if let Some(filename) = file.file_name() {
+            let name_st = filename.to_string_lossy().to_string();
+            let dbname: String = if BackupFormat::detect(file).is_plain_sql() {
+                // double extension ".sql.gz"
+                BackupFormat::strip_extension(&name_st)
+            } else if file.is_dir() {
+                name_st
+            } else {
+                match file.file_stem() {
+                    Some(stem) => stem.to_string_lossy().to_string(),
+                    None => name_st
+                }
+            };
+            self.c.restore_dbname_input.set_text(&dbname);
+        }
+    }
+
+    pub(super) fn on_dbname_changed(&mut self, _: nwg::EventData) {
+        if let Some(name) = &self.c.backup_dbname_combo.selection_string() {
+            let filename = self.selected_backup_format().backup_name(name);
+            self.c.backup_filename_input.set_text(&filename);
+        }
+        self.update_backup_forecast();
+    }
+
+    pub(super) fn on_format_changed(&mut self, _: nwg::EventData) {
+        let filename = self.c.backup_filename_input.text();
+        if !filename.is_empty() {
+            let stem = BackupFormat::strip_extension(&filename);
+            self.c.backup_filename_input.set_text(&self.selected_backup_format().backup_name(&stem));
+        }
+    }
+
+    fn selected_backup_format(&self) -> BackupFormat {
+        BackupFormat::from_index(self.c.backup_format_combo.selection())
+    }
+
+    fn selected_backup_mode(&self) -> BackupMode {
+        BackupMode::from_index(self.c.backup_mode_combo.selection())
+    }
+
+    fn selected_volume_size(&self) -> u64 {
+        self.c.backup_volume_combo.selection()
+            .and_then(|idx| volume_size_presets().get(idx).map(|(size, _)| *size))
+            .unwrap_or(0)
+    }
+
+    fn selected_throttle(&self) -> u64 {
+        self.c.backup_throttle_combo.selection()
+            .and_then(|idx| throttle_presets().get(idx).map(|(rate, _)| *rate))
+            .unwrap_or(0)
+    }
+
+    pub(super) fn on_restore_dbname_changed(&mut self, _: nwg::EventData) {
+        let dbname = self.c.restore_dbname_input.text().trim().to_string();
+        self.restore_dbname_error = match validate_db_name(&dbname) {
+            Ok(_) => String::new(),
+            Err(msg) => msg
+        };
+        self.c.restore_run_button.set_enabled(self.restore_dbname_error.is_empty());
+        self.c.restore_validate_button.set_enabled(self.restore_dbname_error.is_empty());
+        self.c.restore_run_tooltip.set_text(&self.c.restore_run_button.handle, &self.restore_dbname_error);
+        self.c.restore_run_tooltip.set_text(&self.c.restore_dbname_input.handle, &self.restore_dbname_error);
+        self.restore_dbname_suggestion = if !self.restore_dbname_error.is_empty() || self.restore_options.replace_existing {
+            None
+        } else {
+            self.suggest_restore_dbname(&dbname)
+        };
+        self.c.restore_dbname_suggest_button.set_enabled(self.restore_dbname_suggestion.is_some());
+        self.update_restore_forecast();
+    }
+
+    pub(super) fn use_suggested_restore_dbname(&mut self, _: nwg::EventData) {
+        if let Some(name) = self.restore_dbname_suggestion.clone() {
+            self.c.restore_dbname_input.set_text(&name);
+            self.on_restore_dbname_changed(nwg::EventData::NoData);
+        }
+    }
+
+    fn server_has_db(&self, dbname: &str) -> bool {
+        self.server_dbnames.iter().any(|name| name.to_lowercase() == dbname.to_lowercase())
+    }
+
+    // "name_2" ... "name_9" are tried first, then "name_restored_YYYYMMDD"
+    fn suggest_restore_dbname(&self, dbname: &str) -> Option<String> {
+        if dbname.is_empty() || !self.server_has_db(dbname) {
+            return None;
+        }
+        let mut candidates: Vec<String> = (2..10).map(|idx| format!("{}_{}", dbname, idx)).collect();
+        candidates.push(format!("{}_restored_{}", dbname, chrono::Local::now().format("%Y%m%d")));
+        candidates.into_iter().find(|name| !self.server_has_db(name))
+    }
+
+    pub(super) fn on_dest_dir_changed(&mut self, _: nwg::EventData) {
+        let dir = self.c.backup_dest_dir_input.text();
+        let policy = self.settings.retention_policy(&dir);
+        let idx = match policy.mode {
+            RetentionMode::KeepAll => 0,
+            RetentionMode::KeepLast => 1,
+            RetentionMode::MaxAgeDays => 2,
+        };
+        self.c.backup_retention_combo.set_selection(Some(idx));
+        if policy.is_enabled() {
+            self.c.backup_retention_input.set_text(&policy.value.to_string());
+        } else {
+            self.c.backup_retention_input.set_text("");
+        }
+        self.on_retention_mode_changed(nwg::EventData::NoData);
+        // network paths are checked only when chosen, typing a server name
+        // would otherwise block the window on each key press
+        if unc_share_root(&dir).is_some() {
+            self.backup_access_warning = String::new();
+            self.update_backup_forecast();
+        } else {
+            self.probe_dest_dir();
+        }
+    }
+
+    fn probe_dest_dir(&mut self) {
+        let dir = self.c.backup_dest_dir_input.text();
+        self.backup_access_warning = match probe_dir_writable(Path::new(&dir)) {
+            Ok(_) => String::new(),
+            Err(e) => e.to_string()
+        };
+        self.update_backup_forecast();
+    }
+
+    // TOC is read in background, large zip files may be on a network share
+    fn load_src_info(&mut self, src_path: &str) {
+        let path = PathBuf::from(src_path);
+        if volume_set_base(&path).is_some() {
+            self.c.restore_src_info_input.set_text("unknown, volumes of a split backup are joined before restore");
+            return;
+        }
+        if BackupFormat::detect(&path).is_plain_sql() || BackupFormat::Tar == BackupFormat::detect(&path) {
+            self.c.restore_src_info_input.set_text("unknown, backup format does not have a readable TOC");
+            return;
+        }
+        self.c.restore_src_info_input.set_text("Reading backup TOC ...");
+        let sender = self.c.src_info_notice.sender();
+        let src_path = src_path.to_string();
+        thread::spawn(move || {
+            let info = match DumpSummary::read_path(&path) {
+                Ok(summary) => format!("{}, dumped: {}",
+                    summary.original_dbname.as_deref().unwrap_or("unknown"),
+                    summary.header.dump_time().as_deref().unwrap_or("unknown")),
+                Err(e) => format!("Error reading backup TOC: {}", e)
+            };
+            // path is sent back to drop results for a previously chosen file
+            sender.send_value(format!("{}\n{}", src_path, info));
+        });
+    }
+
+    pub(super) fn on_src_info_loaded(&mut self, _: nwg::EventData) {
+        let msg = self.c.src_info_notice.receive();
+        let (path, info) = match msg.split_once('\n') {
+            Some(parts) => parts,
+            None => return
+        };
+        if path == self.c.restore_src_file_input.text() {
+            self.c.restore_src_info_input.set_text(info);
+        }
+    }
+
+    fn probe_src_file(&mut self) {
+        let src_path = self.c.restore_src_file_input.text();
+        let path = Path::new(&src_path);
+        // unzipped dump is rewritten in place, archives are unpacked next to them
+        let res = probe_path_readable(path).and_then(|_| match BackupFormat::detect(path) {
+            BackupFormat::Directory => probe_dir_writable(path),
+            BackupFormat::PlainSql => Ok(()),
+            _ => match path.parent() {
+                Some(dir) => probe_dir_writable(dir),
+                None => Ok(())
+            }
+        });
+        self.restore_access_warning = match res {
+            Ok(_) => String::new(),
+            Err(e) => e.to_string()
+        };
+        self.update_restore_forecast();
+    }
+
+    pub(super) fn on_retention_mode_changed(&mut self, _: nwg::EventData) {
+        let enabled = match self.c.backup_retention_combo.selection() {
+            Some(idx) => idx > 0,
+            None => false
+        };
+        self.c.backup_retention_input.set_enabled(enabled);
+    }
+
+    fn retention_policy_from_input(&self, dir: &str) -> RetentionPolicy {
+        let mode = match self.c.backup_retention_combo.selection() {
+            Some(1) => RetentionMode::KeepLast,
+            Some(2) => RetentionMode::MaxAgeDays,
+            _ => RetentionMode::KeepAll
+        };
+        let value = self.c.backup_retention_input.text().trim().parse::<u32>().unwrap_or(0);
+        RetentionPolicy {
+            dest_dir: dir.to_string(),
+            mode,
+            value
+        }
+    }
+
+    fn set_dbnames(&mut self, dbnames_all: &Vec<String>, bbf_db: &str) {
+        self.server_dbnames = dbnames_all.clone();
+        self.plain_pg = bbf_db.is_empty();
+        self.on_restore_dbname_changed(nwg::EventData::NoData);
+        let mut dbnames: Vec<String> = dbnames_all.iter().filter(|name| {
+            !vec!("master", "msdb", "tempdb").contains(&name.as_str())
+        }).map(|name| name.clone()).collect();
+        dbnames.sort();
+        self.backup_dbnames = dbnames;
+        // selection is kept when the list is reloaded after restore
+        let selected = self.c.backup_dbname_combo.selection_string();
+        self.filter_backup_dbnames(selected.as_deref());
+        self.c.restore_bbf_db_input.set_text(bbf_db);
+        let profile = self.settings.profile(&self.pg_conn_config);
+        // Babelfish DB override is not used with plain Postgres
+        if profile.backup_bbf_db.is_empty() || self.plain_pg {
+            self.c.backup_bbf_db_input.set_text(bbf_db);
+        } else {
+            self.c.backup_bbf_db_input.set_text(&profile.backup_bbf_db);
+        }
+    }
+
+    // tables list is kept only when the selected DB still exists
+    fn set_export_dbnames(&mut self) {
+        let selected = self.c.export_dbname_combo.selection_string();
+        let idx = match &selected {
+            Some(sel) => self.backup_dbnames.iter().position(|name| name == sel),
+            None => None
+        };
+        self.c.export_dbname_combo.set_collection(self.backup_dbnames.clone());
+        self.c.export_dbname_combo.set_selection(idx);
+        if idx.is_none() && !self.export_busy {
+            self.export_tables.clear();
+            self.c.export_tables_view.clear();
+            self.c.export_status_label.set_text("");
+        }
+    }
+
+    fn save_profile_bbf_db(&mut self, bbf_db: &str) {
+        let mut profile = self.settings.profile(&self.pg_conn_config);
+        // override is only stored when it differs from the detected DB
+        if bbf_db == self.c.restore_bbf_db_input.text() {
+            profile.backup_bbf_db = String::new();
+        } else {
+            profile.backup_bbf_db = bbf_db.to_string();
+        }
+        self.settings.set_profile(profile);
+    }
+
+    // loaded DB names are checked, restore itself checks the server again
+    fn single_db_conflict(&self, dbname: &str) -> Option<String> {
+        if !self.settings.profile(&self.pg_conn_config).server_info.is_single_db() {
+            return None;
+        }
+        self.backup_dbnames.iter()
+            .find(|name| name.to_lowercase() != dbname.to_lowercase())
+            .cloned()
+    }
+
+    fn save_profile_server_info(&mut self, info: ServerInfo) {
+        let mut profile = self.settings.profile(&self.pg_conn_config);
+        profile.server_info = info;
+        self.settings.set_profile(profile);
+        if let Err(e) = self.settings.save() {
+            log::error!("{}", e);
+        }
+    }
+
+    fn update_backup_forecast(&self) {
+        if !self.backup_access_warning.is_empty() {
+            self.c.backup_forecast_label.set_text(&format!("Warning: {}", self.backup_access_warning));
+            return;
+        }
+        let forecast = match self.c.backup_dbname_combo.selection_string() {
+            Some(dbname) => self.settings.forecast(RunKind::Backup, &dbname),
+            None => None
+        };
+        match forecast {
+            Some(fc) => self.c.backup_forecast_label.set_text(&fc.describe()),
+            None => self.c.backup_forecast_label.set_text("")
+        }
+    }
+
+    fn update_restore_forecast(&self) {
+        if !self.restore_access_warning.is_empty() {
+            self.c.restore_forecast_label.set_text(&format!("Warning: {}", self.restore_access_warning));
+            return;
+        }
+        let dbname = self.c.restore_dbname_input.text();
+        if !self.restore_dbname_error.is_empty() && !dbname.trim().is_empty() {
+            self.c.restore_forecast_label.set_text(&format!("Invalid DB name: {}", self.restore_dbname_error));
+            return;
+        }
+        if let Some(suggested) = &self.restore_dbname_suggestion {
+            self.c.restore_forecast_label.set_text(&format!(
+                "DB '{}' already exists, suggested name: {}", dbname.trim(), suggested));
+            return;
+        }
+        match self.settings.forecast(RunKind::Restore, &dbname) {
+            Some(fc) => self.c.restore_forecast_label.set_text(&fc.describe()),
+            None => self.c.restore_forecast_label.set_text("")
+        }
+    }
+
+    fn has_builtin_jobs(&self) -> bool {
+        self.settings.backup_jobs.iter().any(|job| !job.cron.is_empty())
+    }
+
+    fn start_scheduled_backup(&mut self, job: BackupJob) {
+        let pcc = &self.pg_conn_config;
+        // password entered in GUI is used when connection matches
+        let pcc = if pcc.hostname == job.hostname && pcc.port == job.port && pcc.username == job.username {
+            pcc.clone()
+        } else {
+            job.pg_conn_config()
+        };
+        let retention = self.settings.retention_policy(&job.dest_dir);
+        let filename = job.dest_filename();
+        let pargs = PgDumpArgs::new(&job.dbname, &job.bbf_db, &job.dest_dir, &filename, job.format, &retention)
+            .with_globals(job.include_globals)
+            .with_mode(job.mode)
+            .with_volume_size(job.volume_size_mb)
+            .with_throttle(job.throttle_mb_per_sec)
+            .with_restore_script(job.restore_script);
+        let name = job.name.clone();
+        let results = self.scheduled_results.clone();
+        let sender = self.c.scheduled_backup_notice.sender();
+        let dest_path = pargs.dest_path();
+        let dbname = job.dbname.clone();
+        let email = self.settings.email.clone();
+        thread::spawn(move || {
+            let start = Instant::now();
+            let tail = LogTail::new(&SilentProgress {});
+            let res = BackupDialog::run_backup(&tail, &pcc, &pargs);
+            BackupDialog::notify_by_email(&SilentProgress {}, &email, &pargs, &res, start.elapsed(), &tail.text());
+            let record = RunRecord::new(RunKind::Backup, &dbname, &dest_path, start.elapsed(), res.error.is_empty());
+            if let Ok(mut results) = results.lock() {
+                results.push((name, res, record));
+            }
+            sender.send();
+        });
+        self.scheduled_running.push(job.name);
+    }
+
+    fn show_tray_message(&self, title: &str, text: &str) {
+        let flags = nwg::TrayNotificationFlags::USER_ICON | nwg::TrayNotificationFlags::LARGE_ICON;
+        self.c.tray.show(text, Some(title), Some(flags), Some(&self.c.icon));
+    }
+
+    fn set_status_bar_dbconn_label(&self, text: &str) {
+        self.c.status_bar.set_text(0, &format!("  DB connection: {}", text));
+    }
+}
+
+fn help_text(text: &str) -> String {
+    text.replace("\r\n", "\n").replace("\n", "\r\n")
+}
@@ -1,58 +1,169 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-
-#[derive(Default, Clone)]
-pub struct PgDumpArgs {
-    pub(super) dbname: String,
-    pub(super) bbf_db: String,
-    pub(super) parent_dir: String,
-    pub(super) dest_filename: String,
-}
-
-#[derive(Default)]
-pub struct BackupDialogArgs {
-    pub(super) notice_sender:  ui::SyncNoticeSender,
-    pub(super) pg_conn_config: PgConnConfig,
-    pub(super) pg_dump_args: PgDumpArgs,
-}
-
-impl BackupDialogArgs {
-    pub fn new(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig, dbname: &str, bbf_db: &str, parent_dir: &str, dest_filename: &str) -> Self {
-        Self {
-            notice_sender: notice.sender(),
-            pg_conn_config: pg_conn_config.clone(),
-            pg_dump_args: PgDumpArgs {
-                dbname: dbname.to_string(),
-                bbf_db: bbf_db.to_string(),
-                parent_dir: parent_dir.to_string(),
-                dest_filename: dest_filename.to_string()
-            },
-        }
-    }
-
-    pub fn send_notice(&self) {
-        self.notice_sender.send()
-    }
-}
-
-impl ui::PopupArgs for BackupDialogArgs {
-    fn notify_parent(&self) {
-        self.notice_sender.send()
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+
+#[derive(Default, Clone)]
+pub struct PgDumpArgs {
+    pub(super) dbname: String,
+    // empty for plain Postgres servers, "dbname" is a physical DB then
+    pub(super) bbf_db: String,
+    pub(super) parent_dir: String,
+    pub(super) dest_filename: String,
+    pub(super) format: BackupFormat,
+    pub(super) retention: RetentionPolicy,
+    // logins and roles are dumped with pg_dumpall into "globals.sql"
+    pub(super) include_globals: bool,
+    // whole cluster is backed up with pg_basebackup, DB names are not used
+    pub(super) physical: bool,
+    // data not changed since the previous (or last full) backup in the same
+    // directory is not included, restore takes it from that backup
+    pub(super) mode: BackupMode,
+    // backup file is split into volumes of this size, zero - not split
+    pub(super) volume_size_mb: u64,
+    // zip and volume writes are limited to this rate, zero - no limit
+    pub(super) throttle_mb_per_sec: u64,
+    // PowerShell script for restoring without the GUI is written next to the backup
+    pub(super) restore_script: bool,
+}
+
+impl PgDumpArgs {
+    pub fn new(dbname: &str, bbf_db: &str, parent_dir: &str, dest_filename: &str, format: BackupFormat, retention: &RetentionPolicy) -> Self {
+        Self {
+            dbname: dbname.to_string(),
+            bbf_db: bbf_db.to_string(),
+            parent_dir: parent_dir.to_string(),
+            dest_filename: dest_filename.to_string(),
+            format,
+            retention: retention.clone(),
+            include_globals: false,
+            physical: false,
+            mode: BackupMode::Full,
+            volume_size_mb: 0,
+            throttle_mb_per_sec: 0,
+            restore_script: false,
+        }
+    }
+
+    pub fn with_globals(mut self, include_globals: bool) -> Self {
+        self.include_globals = include_globals;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: BackupMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_volume_size(mut self, volume_size_mb: u64) -> Self {
+        self.volume_size_mb = volume_size_mb;
+        self
+    }
+
+    pub fn with_throttle(mut self, throttle_mb_per_sec: u64) -> Self {
+        self.throttle_mb_per_sec = throttle_mb_per_sec;
+        self
+    }
+
+    pub fn with_restore_script(mut self, restore_script: bool) -> Self {
+        self.restore_script = restore_script;
+        self
+    }
+
+    pub(super) fn is_plain_pg(&self) -> bool {
+        self.bbf_db.is_empty()
+    }
+
+    // DB that pg_dump connects to
+    pub(super) fn physical_db(&self) -> &str {
+        if self.is_plain_pg() {
+            &self.dbname
+        } else {
+            &self.bbf_db
+        }
+    }
+
+    pub(super) fn throttle(&self) -> Throttle {
+        Throttle::new(self.throttle_mb_per_sec)
+    }
+
+    // backup file or directory that is created
+    pub fn dest_path(&self) -> PathBuf {
+        Path::new(&self.parent_dir).join(self.format.dest_name(&self.dest_filename))
+    }
+}
+
+#[derive(Default)]
+pub struct BackupDialogArgs {
+    pub(super) notice_sender:  ui::SyncNoticeSender,
+    pub(super) pg_conn_config: PgConnConfig,
+    pub(super) pg_dump_args: PgDumpArgs,
+    pub(super) forecast: Option<RunForecast>,
+    pub(super) email: EmailSettings,
+}
+
+impl BackupDialogArgs {
+    pub fn new(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig, dbname: &str, bbf_db: &str, parent_dir: &str, dest_filename: &str, format: BackupFormat, retention: &RetentionPolicy, forecast: Option<RunForecast>, email: &EmailSettings) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            pg_conn_config: pg_conn_config.clone(),
+            pg_dump_args: PgDumpArgs::new(dbname, bbf_db, parent_dir, dest_filename, format, retention),
+            forecast,
+            email: email.clone(),
+        }
+    }
+
+    pub fn with_globals(mut self, include_globals: bool) -> Self {
+        self.pg_dump_args.include_globals = include_globals;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: BackupMode) -> Self {
+        self.pg_dump_args.mode = mode;
+        self
+    }
+
+    pub fn with_volume_size(mut self, volume_size_mb: u64) -> Self {
+        self.pg_dump_args.volume_size_mb = volume_size_mb;
+        self
+    }
+
+    pub fn with_throttle(mut self, throttle_mb_per_sec: u64) -> Self {
+        self.pg_dump_args.throttle_mb_per_sec = throttle_mb_per_sec;
+        self
+    }
+
+    pub fn with_restore_script(mut self, restore_script: bool) -> Self {
+        self.pg_dump_args.restore_script = restore_script;
+        self
+    }
+
+    pub fn physical(mut self) -> Self {
+        self.pg_dump_args.physical = true;
+        self
+    }
+
+    pub fn send_notice(&self) {
+        self.notice_sender.send()
+    }
+}
+
+impl ui::PopupArgs for BackupDialogArgs {
+    fn notify_parent(&self) {
+        self.notice_sender.send()
+    }
+}
@@ -1,294 +1,1093 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use std::env;
-use std::ffi::OsStr;
-use std::fs;
-use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::os::windows::process::CommandExt;
-use std::path::Path;
-use std::time;
-
-use super::*;
-use crate::backup_dialog::args::PgDumpArgs;
-
-#[derive(Default)]
-pub struct BackupDialog {
-    pub(super) c: BackupDialogControls,
-
-    args: BackupDialogArgs,
-    command_join_handle: ui::PopupJoinHandle<BackupResult>,
-    dialog_result: BackupDialogResult,
-
-    progress_pending: Vec<String>,
-    progress_last_updated: u128,
-}
-
-impl BackupDialog {
-
-    pub(super) fn on_progress(&mut self, _: nwg::EventData) {
-        let msg = self.c.progress_notice.receive();
-        self.progress_pending.push(msg);
-        let now = time::SystemTime::now()
-            .duration_since(time::UNIX_EPOCH)
-            .unwrap_or(Duration::from_secs(0))
-            .as_millis();
-        if now - self.progress_last_updated > 100 {
-            let joined = self.progress_pending.join("\r\n");
-            self.progress_pending.clear();
-            self.progress_last_updated = now;
-            self.c.details_box.appendln(&joined);
-        }
-    }
-
-    pub(super) fn on_complete(&mut self, _: nwg::EventData) {
-        self.c.complete_notice.receive();
-        let res = self.command_join_handle.join();
-        let success = res.error.is_empty();
-        self.stop_progress_bar(success.clone());
-        if !success {
-            self.dialog_result = BackupDialogResult::failure();
-            self.c.label.set_text("Backup failed");
-            self.progress_pending.push(res.error);
-            self.c.copy_clipboard_button.set_enabled(true);
-            self.c.close_button.set_enabled(true);
-        } else {
-            self.dialog_result = BackupDialogResult::success();
-            self.c.label.set_text("Backup complete");
-            self.c.copy_clipboard_button.set_enabled(true);
-            self.c.close_button.set_enabled(true);
-        }
-        if self.progress_pending.len() > 0 {
-            let joined = self.progress_pending.join("\r\n");
-            self.c.details_box.appendln(&joined);
-            self.progress_pending.clear();
-        }
-    }
-
-    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
-        let text = self.c.details_box.text();
-        let _ = set_clipboard(formats::Unicode, &text);
-    }
-
-    fn stop_progress_bar(&self, success: bool) {
-        self.c.progress_bar.set_marquee(false, 0);
-        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
-        self.c.progress_bar.set_pos(1);
-        if !success {
-            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
-        }
-    }
-
-    fn run_command(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, pargs: &PgDumpArgs, dest_dir: &str) -> Result<(), io::Error> {
-        let cur_exe = env::current_exe()?;
-        let bin_dir = match cur_exe.parent() {
-            Some(path) => path,
-            None => { // cannot happen
-                let exe_st = cur_exe.to_str().unwrap_or("");
-                return Err(io::Error::new(io::ErrorKind::Other, format!(
-                    "Parent dir failure, exe path: {}", exe_st)))
-            }
-        };
-        let pg_dump_exe = bin_dir.join("pg_dump.exe");
-        let mut cmd = duct::cmd!(
-            pg_dump_exe,
-            "-v",
-            "-h", &pcc.hostname,
-            "-p", &pcc.port.to_string(),
-            "-U", &pcc.username,
-            "--bbf-database-name", &pargs.dbname,
-            "-F", "d",
-            "-Z", "6",
-            "-j", "4",
-            "-f", &dest_dir,
-            &pargs.bbf_db
-        )
-            .stdin_null()
-            .stderr_to_stdout()
-            .stdout_capture()
-            .before_spawn(|pcmd| {
-                // create no window
-                let _ = pcmd.creation_flags(0x08000000);
-                Ok(())
-            });
-        if !&pcc.use_pgpass_file {
-            cmd = cmd.env("PGPASSWORD", &pcc.password);
-        }
-        let reader = match cmd.reader() {
-            Ok(reader) => reader,
-            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "pg_dump process spawn failure: {}", e)))
-        };
-        let mut buf_reader = BufReader::new(&reader);
-        loop {
-            let mut buf = vec!();
-            match buf_reader.read_until(b'\n', &mut buf) {
-                Ok(len) => {
-                    if 0 == len {
-                        break;
-                    }
-                    if buf.len() >= 2 {
-                        let ln = String::from_utf8_lossy(&buf[0..buf.len() - 2]);
-                        progress.send_value(ln);
-                    }
-                },
-                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                    "pg_dump process failure: {}", e)))
-            };
-        };
-        match reader.try_wait() {
-            Ok(opt) => match opt {
-                Some(_) => { },
-                None => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                        "pg_dump process failure")))
-            },
-            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "pg_dump process failure: {}", e)))
-        }
-
-        Ok(())
-    }
-
-    fn zip_dest_directory(progress: &ui::SyncNoticeValueSender<String>, dest_dir: &str, filename: &str) -> Result<(), io::Error> {
-        let dest_dir_path = Path::new(dest_dir);
-        let parent_path = match dest_dir_path.parent() {
-            Some(path) => path,
-            None => return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!(
-                "Error accessing destination directory parent")))
-        };
-        let dest_dir_st = match dest_dir_path.to_str() {
-            Some(st) => st,
-            None => return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!(
-                "Error accessing destination directory")))
-        };
-        let dest_file_buf = parent_path.join(filename);
-        let dest_file_st = match dest_file_buf.to_str() {
-            Some(st) => st,
-            None => return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!(
-                "Error accessing destination file")))
-        };
-        let listener = |en: &str| {
-            progress.send_value(en);
-        };
-        if let Err(e) = zip_recurse::zip_directory_listen(dest_dir_st, dest_file_st, 0, listener) {
-            return Err(io::Error::new(io::ErrorKind::Other, e.to_string()))
-        };
-        std::fs::remove_dir_all(dest_dir_path)?;
-        Ok(())
-    }
-
-    fn prepare_dest_dir(dest_parent_dir: &str, dest_filename: &str) -> Result<(String, String), io::Error> {
-        let mut ext = Path::new(dest_filename).extension().unwrap_or(OsStr::new(""))
-            .to_str().unwrap_or("").to_string();
-        let mut filename = dest_filename.to_string();
-        if ext.is_empty() {
-            ext = "zip".to_string();
-            filename = format!("{}.{}", filename, ext);
-        }
-        let dirname: String = filename.chars().take(filename.len() - (ext.len() + 1)).collect();
-        let parent_dir_path = Path::new(dest_parent_dir);
-        let dir_path = parent_dir_path.join(dirname);
-        let dir_path_st = match dir_path.to_str() {
-            Some(st) => st.to_string(),
-            None => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "Error reading directory name")))
-        };
-        let _ = fs::remove_dir_all(&dir_path);
-        if dir_path.exists() {
-            return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!(
-                "Error removing directory: {}", dir_path_st)));
-        }
-        Ok((dir_path_st, filename))
-    }
-
-    fn run_backup(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, pargs: &PgDumpArgs) -> BackupResult {
-        progress.send_value("Running backup ...");
-
-        // ensure no dest dir
-        let (dest_dir, filename) = match Self::prepare_dest_dir(&pargs.parent_dir, &pargs.dest_filename) {
-            Ok(tup) => tup,
-            Err(e) => return BackupResult::failure(e.to_string())
-        };
-        let dest_file = Path::new(&pargs.parent_dir).join(Path::new(&filename)).to_string_lossy().to_string();
-        progress.send_value(format!("Backup file: {}", dest_file));
-
-        // spawn and wait
-        progress.send_value("Running pg_dump ....");
-        if let Err(e) = BackupDialog::run_command(progress, pcc, pargs, &dest_dir) {
-            return BackupResult::failure(e.to_string());
-        };
-
-        // zip results
-        progress.send_value("Zipping destination directory ....");
-        if let Err(e) = Self::zip_dest_directory(progress, &dest_dir, &filename) {
-            return BackupResult::failure(format!(
-                "Error zipping destination directory, path: {}, error: {}", &dest_dir, e));
-        };
-
-        progress.send_value("Backup complete");
-        BackupResult::success()
-    }
-}
-
-impl ui::PopupDialog<BackupDialogArgs, BackupDialogResult> for BackupDialog {
-    fn popup(args: BackupDialogArgs) -> ui::PopupJoinHandle<BackupDialogResult> {
-        let join_handle = thread::spawn(move || {
-            let data = Self {
-                args,
-                ..Default::default()
-            };
-            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
-            nwg::dispatch_thread_events();
-            dialog.result()
-        });
-        ui::PopupJoinHandle::from(join_handle)
-    }
-
-    fn init(&mut self) {
-        let complete_sender = self.c.complete_notice.sender();
-        let progress_sender = self.c.progress_notice.sender();
-        let pcc: PgConnConfig = self.args.pg_conn_config.clone();
-        let pargs = self.args.pg_dump_args.clone();
-        let join_handle = thread::spawn(move || {
-            let start = Instant::now();
-            let res = BackupDialog::run_backup(&progress_sender, &pcc, &pargs);
-            let remaining = 1000 - start.elapsed().as_millis() as i64;
-            if remaining > 0 {
-                thread::sleep(Duration::from_millis(remaining as u64));
-            }
-            complete_sender.send();
-            res
-        });
-        self.command_join_handle = ui::PopupJoinHandle::from(join_handle);
-    }
-
-    fn result(&mut self) -> BackupDialogResult {
-        self.dialog_result.clone()
-    }
-
-    fn close(&mut self, _: nwg::EventData) {
-        self.args.send_notice();
-        self.c.window.set_visible(false);
-        nwg::stop_thread_dispatch();
-    }
-
-    fn on_resize(&mut self, _: nwg::EventData) {
-        self.c.update_tab_order();
-    }
-}
-
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+
+use super::*;
+
+// longer list of tables to exclude does not fit into Windows command line
+const MAX_EXCLUDE_ARGS_LEN: usize = 24 * 1024;
+
+// table statistics are flushed by the backends with a delay of up to 10 seconds
+const STATS_FLUSH_DELAY: Duration = Duration::from_secs(12);
+
+// schemas of the backed up DB, "n" is "pg_namespace" and "$1" is the DB name
+const BBF_SCHEMAS_FILTER: &str = "
+    join sys.babelfish_namespace_ext ns on ns.nspname = n.nspname
+    join sys.babelfish_sysdatabases db on db.dbid = ns.dbid
+    where db.name = $1";
+
+// plain Postgres DB is dumped whole, only system schemas are skipped
+const PLAIN_SCHEMAS_FILTER: &str = "
+    where current_database() = $1
+    and n.nspname not in ('pg_catalog', 'information_schema')
+    and n.nspname not like 'pg\\_%'";
+
+#[derive(Default)]
+pub struct BackupDialog {
+    pub(super) c: BackupDialogControls,
+
+    args: BackupDialogArgs,
+    command_join_handle: ui::PopupJoinHandle<BackupResult>,
+    dialog_result: BackupDialogResult,
+
+    progress_buffer: ProgressBuffer,
+    details_log: DetailsLog,
+    taskbar_progress: Option<TaskbarProgress>,
+    started_at: Option<Instant>,
+    overrun_flagged: bool,
+}
+
+impl BackupDialog {
+
+    pub(super) fn on_progress(&mut self, _: nwg::EventData) {
+        let msg = self.c.progress_notice.receive();
+        if let Some(text) = self.progress_buffer.push(msg) {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_progress_tick(&mut self, _: nwg::EventData) {
+        if let Some(text) = self.progress_buffer.flush_if_due() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+        self.check_overrun();
+    }
+
+    fn check_overrun(&mut self) {
+        if self.overrun_flagged {
+            return;
+        }
+        if let (Some(forecast), Some(started)) = (&self.args.forecast, &self.started_at) {
+            if forecast.is_exceeded(started.elapsed()) {
+                self.overrun_flagged = true;
+                self.c.label.set_text(&format!(
+                    "Running backup ... taking longer than usual ({})", forecast.describe()));
+                self.c.progress_bar.set_state(nwg::ProgressBarState::Paused);
+            }
+        }
+    }
+
+    pub(super) fn on_complete(&mut self, _: nwg::EventData) {
+        self.c.complete_notice.receive();
+        let res = self.command_join_handle.join();
+        let success = res.error.is_empty();
+        let duration = match self.started_at {
+            Some(started) => started.elapsed(),
+            None => Duration::from_secs(0)
+        };
+        self.stop_progress_bar(success.clone());
+        if !success {
+            self.dialog_result = BackupDialogResult::failure(duration);
+            self.c.label.set_text("Backup failed");
+            log::error!("Backup failed: {}", res.error);
+            if let Some(text) = self.progress_buffer.push(res.error.clone()) {
+                self.details_log.append(&self.c.details_box, &text);
+            }
+            self.c.run_again_button.set_enabled(true);
+            self.c.copy_clipboard_button.set_enabled(true);
+            self.c.close_button.set_enabled(true);
+        } else {
+            self.dialog_result = BackupDialogResult::success(duration);
+            self.c.label.set_text("Backup complete");
+            self.c.open_folder_button.set_enabled(true);
+            self.c.run_again_button.set_enabled(true);
+            self.c.copy_clipboard_button.set_enabled(true);
+            self.c.close_button.set_enabled(true);
+        }
+        if window_in_background(&self.c.window) {
+            if success {
+                show_toast(&self.c.tray, &self.c.icon, "Backup complete", "Backup completed successfully");
+            } else {
+                show_toast(&self.c.tray, &self.c.icon, "Backup failed", &res.error);
+            }
+        }
+        self.c.progress_timer.stop();
+        if let Some(text) = self.progress_buffer.flush() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+        self.c.copy_command_button.set_enabled(self.details_log.has_commands());
+    }
+
+    pub(super) fn on_autoscroll_changed(&mut self, _: nwg::EventData) {
+        let paused = self.c.autoscroll_checkbox.check_state() != nwg::CheckBoxState::Checked;
+        self.details_log.set_paused(&self.c.details_box, paused);
+    }
+
+    // same arguments are used, output of the previous run is discarded
+    pub(super) fn run_again(&mut self, _: nwg::EventData) {
+        self.c.run_again_button.set_enabled(false);
+        self.c.open_folder_button.set_enabled(false);
+        self.c.copy_command_button.set_enabled(false);
+        self.c.copy_clipboard_button.set_enabled(false);
+        self.c.close_button.set_enabled(false);
+        self.c.label.set_text("Running backup ...");
+        self.c.details_box.set_text("");
+        self.details_log = DetailsLog::default();
+        self.c.autoscroll_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        self.overrun_flagged = false;
+        self.c.progress_bar.set_state(nwg::ProgressBarState::Normal);
+        self.c.progress_bar.set_pos(0);
+        self.c.progress_bar.add_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_marquee(true, 30);
+        self.c.progress_timer.start();
+        self.start_command();
+    }
+
+    // commands can be re-run from a terminal to reproduce a failure
+    pub(super) fn copy_command_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.commands_text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    // opens Explorer with the created backup selected
+    pub(super) fn open_folder(&mut self, _: nwg::EventData) {
+        let dest = self.args.pg_dump_args.dest_path().to_string_lossy().to_string();
+        if let Err(e) = Command::new("explorer.exe").raw_arg(format!("/select,\"{}\"", dest)).spawn() {
+            nwg::modal_error_message(&self.c.window, "Error",
+                &format!("Error opening folder: {}, message: {}", dest, e));
+        }
+    }
+
+    fn start_command(&mut self) {
+        self.started_at = Some(Instant::now());
+        if let Some(forecast) = &self.args.forecast {
+            self.c.label.set_text(&format!("Running backup ... ({})", forecast.describe()));
+        }
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.set_indeterminate();
+        }
+        let complete_sender = self.c.complete_notice.sender();
+        let progress_sender = self.c.progress_notice.sender();
+        let pcc: PgConnConfig = self.args.pg_conn_config.clone();
+        let pargs = self.args.pg_dump_args.clone();
+        let email = self.args.email.clone();
+        let join_handle = thread::spawn(move || {
+            let start = Instant::now();
+            let tail = LogTail::new(&progress_sender);
+            let res = BackupDialog::run_backup(&tail, &pcc, &pargs);
+            BackupDialog::notify_by_email(&progress_sender, &email, &pargs, &res, start.elapsed(), &tail.text());
+            let remaining = 1000 - start.elapsed().as_millis() as i64;
+            if remaining > 0 {
+                thread::sleep(Duration::from_millis(remaining as u64));
+            }
+            complete_sender.send();
+            res
+        });
+        self.command_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn stop_progress_bar(&self, success: bool) {
+        self.c.progress_bar.set_marquee(false, 0);
+        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_pos(1);
+        if !success {
+            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
+        }
+        if let Some(taskbar) = &self.taskbar_progress {
+            if success {
+                taskbar.set_complete();
+            } else {
+                taskbar.set_error();
+            }
+        }
+    }
+
+    fn run_command(progress: &impl ProgressSender, pcc: &PgConnConfig, pargs: &PgDumpArgs, dest: &str,
+                   snapshot: Option<&str>, exclude_data: &[String], streaming_zip: Option<&mut StreamingZip>) -> Result<(), io::Error> {
+        let port = pcc.client_port()?.to_string();
+        let mut args: Vec<&str> = vec!(
+            "-v",
+            "-h", &pcc.hostname,
+            "-p", &port,
+            "-U", &pcc.username,
+        );
+        if !pargs.is_plain_pg() {
+            args.push("--bbf-database-name");
+            args.push(&pargs.dbname);
+        }
+        args.extend(pargs.format.pg_dump_args());
+        if let Some(id) = snapshot {
+            args.push("--snapshot");
+            args.push(id);
+        }
+        for arg in exclude_data.iter() {
+            args.push(arg);
+        }
+        args.extend(vec!(
+            "-f", dest,
+            "-d", pargs.physical_db()
+        ));
+        Self::run_tool(progress, pcc, "pg_dump", args, streaming_zip)
+    }
+
+    // Babelfish pg_dumpall only dumps logins and roles related to the specified DB,
+    // all roles of the server are dumped from plain Postgres
+    fn run_pg_dumpall(progress: &impl ProgressSender, pcc: &PgConnConfig, pargs: &PgDumpArgs, dest: &str) -> Result<(), io::Error> {
+        let port = pcc.client_port()?.to_string();
+        let mut args: Vec<&str> = vec!(
+            "-v",
+            "-h", &pcc.hostname,
+            "-p", &port,
+            "-U", &pcc.username,
+            "--globals-only",
+            "--quote-all-identifiers",
+        );
+        if !pargs.is_plain_pg() {
+            args.push("--bbf-database-name");
+            args.push(&pargs.dbname);
+        }
+        args.extend(vec!(
+            "-l", pargs.physical_db(),
+            "-f", dest
+        ));
+        Self::run_tool(progress, pcc, "pg_dumpall", args, None)
+    }
+
+    fn schemas_filter(pargs: &PgDumpArgs) -> &'static str {
+        if pargs.is_plain_pg() {
+            PLAIN_SCHEMAS_FILTER
+        } else {
+            BBF_SCHEMAS_FILTER
+        }
+    }
+
+    // tar format with gzip, WAL needed for consistency is streamed into "pg_wal.tar.gz"
+    fn run_pg_basebackup(progress: &impl ProgressSender, pcc: &PgConnConfig, dest: &str,
+                         streaming_zip: Option<&mut StreamingZip>) -> Result<(), io::Error> {
+        let port = pcc.client_port()?.to_string();
+        Self::run_tool(progress, pcc, "pg_basebackup", vec!(
+            "-v",
+            "-P",
+            "-h", &pcc.hostname,
+            "-p", &port,
+            "-U", &pcc.username,
+            "-D", dest,
+            "-F", "t",
+            "-z",
+            "-X", "stream",
+            "-c", "fast"
+        ), streaming_zip)
+    }
+
+    fn run_tool(progress: &impl ProgressSender, pcc: &PgConnConfig, tool: &str, args: Vec<&str>,
+                mut streaming_zip: Option<&mut StreamingZip>) -> Result<(), io::Error> {
+        let cmd = pg_tool_cmd(progress, pcc, tool, args)?;
+        let reader = match cmd.reader() {
+            Ok(reader) => reader,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "{} process spawn failure: {}", tool, e)))
+        };
+        let mut buf_reader = BufReader::new(&reader);
+        loop {
+            let mut buf = vec!();
+            match buf_reader.read_until(b'\n', &mut buf) {
+                Ok(len) => {
+                    if 0 == len {
+                        break;
+                    }
+                    if buf.len() >= 2 {
+                        let ln = String::from_utf8_lossy(&buf[0..buf.len() - 2]);
+                        progress.send_value(ln);
+                    }
+                    if let Some(zip) = streaming_zip.as_mut() {
+                        zip.add_completed(|en| progress.send_value(en))?;
+                    }
+                },
+                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                    "{} process failure: {}", tool, e)))
+            };
+        };
+        match reader.try_wait() {
+            Ok(opt) => match opt {
+                Some(_) => { },
+                None => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                        "{} process failure", tool)))
+            },
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "{} process failure: {}", tool, e)))
+        }
+
+        Ok(())
+    }
+
+    fn prepare_dest_dir(dest_parent_dir: &str, dest_filename: &str, format: BackupFormat) -> Result<(String, String), io::Error> {
+        let filename = format.dest_name(dest_filename);
+        let dirname: String = match format {
+            BackupFormat::Zip => match Path::new(&filename).file_stem() {
+                Some(stem) => stem.to_string_lossy().to_string(),
+                None => filename.clone()
+            },
+            _ => filename.clone()
+        };
+        let parent_dir_path = Path::new(dest_parent_dir);
+        let dir_path = parent_dir_path.join(dirname);
+        let dir_path_st = match dir_path.to_str() {
+            Some(st) => st.to_string(),
+            None => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Error reading directory name")))
+        };
+        let _ = fs::remove_dir_all(long_path(&dir_path));
+        if long_path(&dir_path).exists() {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!(
+                "Error removing directory: {}", dir_path_st)));
+        }
+        Ok((dir_path_st, filename))
+    }
+
+    // Table sizes of the DB schemas, indexes are not included because they
+    // are not dumped, dump is usually smaller than this as data files are compressed.
+    fn estimate_dump_size(pcc: &PgConnConfig, pargs: &PgDumpArgs) -> Result<u64, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(pargs.physical_db())?;
+        let rs = client.query(&format!("
+            select coalesce(sum(pg_table_size(c.oid)), 0)::bigint as size
+            from pg_class c
+            join pg_namespace n on n.oid = c.relnamespace
+            {}
+            and c.relkind in ('r', 'm')", Self::schemas_filter(pargs)), &[&pargs.dbname])?;
+        let size: i64 = rs[0].get("size");
+        client.close()?;
+        Ok(size as u64)
+    }
+
+    // Estimation errors are reported as warnings, pg_dump still reports a clear
+    // error when the destination is not accessible.
+    fn check_disk_space(progress: &impl ProgressSender, pcc: &PgConnConfig, pargs: &PgDumpArgs) -> Result<(), io::Error> {
+        let retry = pcc.retry_policy();
+        let estimate = match retry.run("Backup size estimation", |en| progress.send_value(en), || Self::estimate_dump_size(pcc, pargs)) {
+            Ok(size) => size,
+            Err(e) => {
+                progress.send_value(format!("Warning: error estimating backup size: {}", e));
+                return Ok(());
+            }
+        };
+        let free = match free_disk_space(Path::new(&pargs.parent_dir)) {
+            Ok(free) => free,
+            Err(e) => {
+                progress.send_value(format!("Warning: error checking free disk space: {}", e));
+                return Ok(());
+            }
+        };
+        progress.send_value(format!("Database size: {}, free disk space: {}",
+            format_disk_size(estimate), format_disk_size(free)));
+        if free < estimate {
+            return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Not enough free disk space in destination directory: {}, free: {}, database size: {}",
+                &pargs.parent_dir, format_disk_size(free), format_disk_size(estimate))));
+        }
+        Ok(())
+    }
+
+    // UNC share that is not connected fails with a generic pg_dump error,
+    // so the destination is checked before pg_dump is started.
+    fn check_dest_dir(pargs: &PgDumpArgs) -> Result<(), io::Error> {
+        if let Some(share) = unc_share_root(&pargs.parent_dir) {
+            if !share_accessible(&share) {
+                return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                    "Network share is not accessible: {}", share)));
+            }
+        }
+        if !long_path(Path::new(&pargs.parent_dir)).is_dir() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                "Destination directory does not exist: {}", &pargs.parent_dir)));
+        }
+        Ok(())
+    }
+
+    fn prune_old_backups(progress: &impl ProgressSender, pargs: &PgDumpArgs, dest_file: &str) -> Result<(), io::Error> {
+        let expired = pargs.retention.select_expired(&pargs.dbname, Path::new(dest_file))?;
+        if expired.is_empty() {
+            progress.send_value("No old backups to delete");
+            return Ok(());
+        }
+        let referenced = Self::chain_referenced_backups(pargs, &expired)?;
+        for path in expired {
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            if referenced.contains(&name) {
+                progress.send_value(format!(
+                    "Keeping old backup, it contains table data of a later backup: {}", path.to_string_lossy()));
+                continue;
+            }
+            let removed = if path.is_dir() {
+                fs::remove_dir_all(long_path(&path))
+            } else if let Some(base) = volume_set_base(&path) {
+                remove_volumes(&base).map(|_| ())
+            } else {
+                fs::remove_file(long_path(&path))
+            };
+            match removed {
+                Ok(_) => progress.send_value(format!("Deleted old backup: {}", path.to_string_lossy())),
+                Err(e) => progress.send_value(format!(
+                    "Warning: error deleting old backup: {}, message: {}", path.to_string_lossy(), e))
+            }
+        }
+        Ok(())
+    }
+
+    // File names of the backups that contain table data of the incremental and differential
+    // backups that are kept, these are not deleted by the retention policy.
+    fn chain_referenced_backups(pargs: &PgDumpArgs, expired: &[PathBuf]) -> Result<HashSet<String>, io::Error> {
+        let expired_names: HashSet<String> = expired.iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect();
+        let mut res = HashSet::new();
+        for en in scan_backup_dir(Path::new(&pargs.parent_dir))? {
+            if expired_names.contains(&en.file_name()) || !en.is_valid() {
+                continue;
+            }
+            if let Ok(Some(manifest)) = BackupManifest::read_path(&en.path) {
+                res.extend(manifest.data_sources.values().cloned());
+            }
+        }
+        Ok(res)
+    }
+
+    // Stamps of the tables in the DB schemas, tables without
+    // statistics (e.g. after a server crash) get zero counters.
+    fn load_table_stamps(pcc: &PgConnConfig, pargs: &PgDumpArgs) -> Result<Vec<TableStamp>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(pargs.physical_db())?;
+        let rs = client.query(&format!("
+            select
+                n.nspname::text as schema_name,
+                c.relname::text as table_name,
+                c.relfilenode::bigint as relfilenode,
+                coalesce(s.n_tup_ins, 0)::bigint as n_tup_ins,
+                coalesce(s.n_tup_upd, 0)::bigint as n_tup_upd,
+                coalesce(s.n_tup_del, 0)::bigint as n_tup_del,
+                (select string_agg(a.attname || ' ' || pg_catalog.format_type(a.atttypid, a.atttypmod), ', ' order by a.attnum)
+                    from pg_catalog.pg_attribute a
+                    where a.attrelid = c.oid
+                    and a.attnum > 0
+                    and not a.attisdropped) as columns
+            from pg_class c
+            join pg_namespace n on n.oid = c.relnamespace
+            left join pg_catalog.pg_stat_user_tables s on s.relid = c.oid
+            {}
+            and c.relkind = 'r'
+            order by n.nspname, c.relname", Self::schemas_filter(pargs)), &[&pargs.dbname])?;
+        let mut res = Vec::new();
+        for row in rs.iter() {
+            let columns: Option<String> = row.get("columns");
+            res.push(TableStamp {
+                schema: row.get("schema_name"),
+                table: row.get("table_name"),
+                relfilenode: row.get("relfilenode"),
+                n_tup_ins: row.get("n_tup_ins"),
+                n_tup_upd: row.get("n_tup_upd"),
+                n_tup_del: row.get("n_tup_del"),
+                columns: columns.unwrap_or_default(),
+            });
+        }
+        client.close()?;
+        Ok(res)
+    }
+
+    // Newest backup of the same DB in the destination directory that has table
+    // stamps recorded, backups created by older versions are skipped.
+    fn find_base_backup(pargs: &PgDumpArgs) -> Result<Option<(String, BackupManifest)>, io::Error> {
+        for en in scan_backup_dir(Path::new(&pargs.parent_dir))? {
+            if !en.is_valid() || !en.dbname.eq_ignore_ascii_case(&pargs.dbname) {
+                continue;
+            }
+            let manifest = match BackupManifest::read_path(&en.path) {
+                Ok(Some(manifest)) => manifest,
+                _ => continue
+            };
+            if manifest.table_stamps.is_empty() {
+                continue;
+            }
+            // backups holding the data of unchanged tables must still be there
+            let sources_exist = manifest.data_sources.values().all(|name| {
+                long_path(&Path::new(&pargs.parent_dir).join(name)).exists()
+            });
+            if !sources_exist {
+                continue;
+            }
+            return Ok(Some((en.file_name(), manifest)));
+        }
+        Ok(None)
+    }
+
+    // Tables not changed since the base backup are mapped to the backup that holds
+    // their data, nothing is mapped when there is no base backup to chain to and
+    // the full backup is run instead.
+    fn plan_incremental(progress: &impl ProgressSender, pargs: &PgDumpArgs, stamps: &[TableStamp]) -> (String, BTreeMap<String, String>) {
+        let full = (String::new(), BTreeMap::new());
+        if pargs.format.is_single_file() {
+            progress.send_value("Warning: incremental mode is only supported for Zip and Directory backups, running full backup");
+            return full;
+        }
+        if stamps.is_empty() {
+            progress.send_value("Warning: table change stamps are not available, running full backup");
+            return full;
+        }
+        let (base_name, base) = match Self::find_base_backup(pargs) {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                progress.send_value("No earlier backup with change tracking found, running full backup");
+                return full;
+            },
+            Err(e) => {
+                progress.send_value(format!("Warning: error looking up earlier backups, running full backup: {}", e));
+                return full;
+            }
+        };
+        let sources = base.unchanged_tables(&base_name, stamps);
+        let args_len: usize = stamps.iter()
+            .filter(|st| sources.contains_key(&st.key()))
+            .map(|st| Self::exclude_data_arg(st).len() + 1)
+            .sum();
+        if args_len > MAX_EXCLUDE_ARGS_LEN {
+            progress.send_value(format!(
+                "Warning: too many unchanged tables to exclude: {}, running full backup", sources.len()));
+            return full;
+        }
+        progress.send_value(format!("Incremental backup, base: {}, changed tables: {}, unchanged tables: {}",
+            base_name, stamps.len() - sources.len(), sources.len()));
+        (base_name, sources)
+    }
+
+    // Statistics are not transactional and are flushed with a delay, so a change committed
+    // shortly before the snapshot may be missing from the stamps read before it. Stamps of
+    // the excluded tables are read again once the delay has passed, tables changed after
+    // the snapshot are reported too.
+    fn changed_excluded_tables(progress: &impl ProgressSender, pcc: &PgConnConfig, pargs: &PgDumpArgs, stamps: &[TableStamp],
+                               data_sources: &BTreeMap<String, String>, snapshot_at: Instant) -> Result<Vec<String>, PgAccessError> {
+        let elapsed = snapshot_at.elapsed();
+        if elapsed < STATS_FLUSH_DELAY {
+            let wait = STATS_FLUSH_DELAY - elapsed;
+            progress.send_value(format!("Waiting for table statistics to be flushed, seconds: {} ...", wait.as_secs() + 1));
+            thread::sleep(wait);
+        }
+        progress.send_value("Checking change stamps of the excluded tables ...");
+        let load = || Self::load_table_stamps(pcc, pargs);
+        let current = pcc.retry_policy().run("Reading table change stamps", |en| progress.send_value(en), load)?;
+        let res = stamps.iter()
+            .filter(|st| data_sources.contains_key(&st.key()))
+            .filter(|st| !current.contains(st))
+            .map(|st| st.key())
+            .collect();
+        Ok(res)
+    }
+
+    // quoted so that upper case letters and pattern characters are matched literally
+    fn exclude_data_arg(stamp: &TableStamp) -> String {
+        format!("--exclude-table-data=\"{}\".\"{}\"",
+            stamp.schema.replace('"', "\"\""), stamp.table.replace('"', "\"\""))
+    }
+
+    // transaction is kept open until pg_dump finishes, snapshot is only valid in the dumped DB
+    fn export_snapshot(pcc: &PgConnConfig, pargs: &PgDumpArgs) -> Result<(postgres::Client, String), PgAccessError> {
+        let mut client = pcc.open_connection_to_db(pargs.physical_db())?;
+        client.batch_execute("begin transaction isolation level repeatable read, read only")?;
+        let row = client.query_one("select pg_catalog.pg_export_snapshot() as snapshot_id", &[])?;
+        let id: String = row.get("snapshot_id");
+        Ok((client, id))
+    }
+
+    // Babelfish catalog tables ("sys" schema) are rewritten on restore and are not counted,
+    // server settings are recorded to be compared with the target server on restore.
+    // Checksums of the data files that are already zipped are taken from the zip.
+    fn write_manifest(client: &mut postgres::Client, dest_dir: &str, zipped: &BTreeMap<String, String>,
+                      mut manifest: BackupManifest) -> Result<usize, PgAccessError> {
+        let dir = long_path(Path::new(dest_dir));
+        let toc = pg_toc::TocArchive::read_file(&dir.join("toc.dat"))?;
+        let mut checksums = BTreeMap::new();
+        let mut tables = Vec::new();
+        for en in toc.entries.iter() {
+            let file = match &en.filename {
+                Some(name) if !name.is_empty() => Self::data_file_name(&dir, zipped, name),
+                _ => continue
+            };
+            let checksum = match zipped.get(&file) {
+                Some(checksum) => checksum.clone(),
+                None => sha256_file_hex(&dir.join(&file))?
+            };
+            checksums.insert(file.clone(), checksum);
+            if !en.desc_is("TABLE DATA") || en.namespace_is("sys") {
+                continue;
+            }
+            let (schema, table) = match (&en.namespace, &en.tag) {
+                (Some(schema), Some(table)) => (schema, table),
+                _ => continue
+            };
+            let row = client.query_one(&format!("select count(*) as cnt from \"{}\".\"{}\"",
+                schema.replace('"', "\"\""), table.replace('"', "\"\"")), &[])?;
+            tables.push(TableRowCount {
+                dump_id: en.dump_id,
+                schema: schema.clone(),
+                table: table.clone(),
+                rows: row.get("cnt"),
+                file,
+            });
+        }
+        let count = tables.len();
+        manifest.tables = tables;
+        manifest.checksums = checksums;
+        manifest.settings = babelfish_settings(client)?;
+        manifest.write(&dir)?;
+        Ok(count)
+    }
+
+    // "NNNN.dat.gz" on disk when compression is enabled
+    fn data_file_name(dir: &Path, zipped: &BTreeMap<String, String>, filename: &str) -> String {
+        let gz_name = format!("{}.gz", filename);
+        if zipped.contains_key(&gz_name) || dir.join(&gz_name).exists() {
+            gz_name
+        } else {
+            filename.to_string()
+        }
+    }
+
+    // Newest full backup of the same DB in the destination directory
+    // that has data file checksums recorded.
+    fn find_full_backup(pargs: &PgDumpArgs) -> Result<Option<(String, BackupManifest)>, io::Error> {
+        for en in scan_backup_dir(Path::new(&pargs.parent_dir))? {
+            if !en.is_valid() || !en.dbname.eq_ignore_ascii_case(&pargs.dbname) {
+                continue;
+            }
+            match BackupManifest::read_path(&en.path) {
+                Ok(Some(manifest)) if !manifest.is_chained() && !manifest.checksums.is_empty() => {
+                    return Ok(Some((en.file_name(), manifest)));
+                },
+                _ => continue
+            }
+        }
+        Ok(None)
+    }
+
+    // full backup is run when there is no full backup to compare with
+    fn plan_differential(progress: &impl ProgressSender, pargs: &PgDumpArgs) -> Option<(String, BackupManifest)> {
+        if pargs.format.is_single_file() {
+            progress.send_value("Warning: differential mode is only supported for Zip and Directory backups, running full backup");
+            return None;
+        }
+        match Self::find_full_backup(pargs) {
+            Ok(Some((name, manifest))) => {
+                progress.send_value(format!("Differential backup, base: {}", name));
+                Some((name, manifest))
+            },
+            Ok(None) => {
+                progress.send_value("No earlier full backup with checksums found, running full backup");
+                None
+            },
+            Err(e) => {
+                progress.send_value(format!("Warning: error looking up earlier backups, running full backup: {}", e));
+                None
+            }
+        }
+    }
+
+    // Data files that are the same as in the base full backup are removed from
+    // the dump, TOC entries are kept and restore copies these files from the base.
+    fn drop_unchanged_data(dest_dir: &str, base_name: &str, base: &BackupManifest) -> Result<usize, io::Error> {
+        let dir = long_path(Path::new(dest_dir));
+        let mut manifest = match BackupManifest::read(&dir)? {
+            Some(manifest) => manifest,
+            None => return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                "Backup manifest not found: {}", dest_dir)))
+        };
+        let mut sources = BTreeMap::new();
+        for tbl in manifest.tables.iter() {
+            let checksum = manifest.checksums.get(&tbl.file);
+            if checksum.is_none() || checksum != base.table_checksum(&tbl.schema, &tbl.table) {
+                continue;
+            }
+            sources.insert(table_key(&tbl.schema, &tbl.table), tbl.file.clone());
+        }
+        if sources.is_empty() {
+            return Ok(0);
+        }
+        for file in sources.values() {
+            fs::remove_file(dir.join(file))?;
+            manifest.checksums.remove(file);
+        }
+        let count = sources.len();
+        let sources = sources.into_iter().map(|(key, _)| (key, base_name.to_string())).collect();
+        manifest.with_base(base_name, sources).write(&dir)?;
+        Ok(count)
+    }
+
+    // Split backups are not listed by the backup catalog, so later incremental
+    // and differential backups cannot use them as a base.
+    fn split_backup_file(progress: &impl ProgressSender, pargs: &PgDumpArgs, dest_file: &str) -> Result<(), io::Error> {
+        if BackupFormat::Directory == pargs.format {
+            progress.send_value("Warning: unzipped directory cannot be split into volumes");
+            return Ok(());
+        }
+        progress.send_value(format!("Splitting backup file into volumes of {} MB ....", pargs.volume_size_mb));
+        let volume_size = pargs.volume_size_mb * 1024 * 1024;
+        let volumes = split_into_volumes(Path::new(dest_file), volume_size, pargs.throttle(), |en| progress.send_value(en))?;
+        if volumes.len() > 1 {
+            progress.send_value(format!("Backup file split into volumes: {}", volumes.len()));
+        } else {
+            progress.send_value("Backup file fits into a single volume, it is not split");
+        }
+        Ok(())
+    }
+
+    // whole cluster, archives are already compressed so they are stored into zip as is
+    fn run_physical_backup(progress: &impl ProgressSender, pcc: &PgConnConfig, pargs: &PgDumpArgs) -> BackupResult {
+        progress.send_value("Running physical backup ...");
+
+        if let Err(e) = Self::check_dest_dir(pargs) {
+            return BackupResult::failure(e.to_string());
+        }
+        let (dest_dir, filename) = match Self::prepare_dest_dir(&pargs.parent_dir, &pargs.dest_filename, pargs.format) {
+            Ok(tup) => tup,
+            Err(e) => return BackupResult::failure(e.to_string())
+        };
+        let dest_file = Path::new(&pargs.parent_dir).join(Path::new(&filename)).to_string_lossy().to_string();
+        progress.send_value(format!("Backup file: {}", dest_file));
+
+        let mut streaming_zip = if BackupFormat::Zip == pargs.format {
+            match StreamingZip::create(Path::new(&dest_dir), Path::new(&dest_file)) {
+                Ok(zip) => Some(zip.with_throttle(pargs.throttle())),
+                Err(e) => return BackupResult::failure(format!(
+                    "Error creating zip file, path: {}, error: {}", &dest_file, e))
+            }
+        } else {
+            None
+        };
+
+        progress.send_value("Running pg_basebackup ....");
+        if let Err(e) = Self::run_pg_basebackup(progress, pcc, &dest_dir, streaming_zip.as_mut()) {
+            if let Some(zip) = streaming_zip {
+                zip.abort();
+            }
+            return BackupResult::failure(e.to_string());
+        }
+
+        if let Some(zip) = streaming_zip.take() {
+            progress.send_value("Finishing zip file ....");
+            if let Err(e) = zip.finish(|en| progress.send_value(en)) {
+                return BackupResult::failure(format!(
+                    "Error zipping destination directory, path: {}, error: {}", &dest_dir, e));
+            }
+        } else {
+            progress.send_value(format!("Leaving destination directory unzipped: {}", &dest_dir));
+        }
+
+        progress.send_value("Backup complete");
+        BackupResult::success()
+    }
+
+    pub fn run_backup(progress: &impl ProgressSender, pcc: &PgConnConfig, pargs: &PgDumpArgs) -> BackupResult {
+        if pargs.physical {
+            return Self::run_physical_backup(progress, pcc, pargs);
+        }
+        progress.send_value("Running backup ...");
+
+        if let Err(e) = Self::check_dest_dir(pargs) {
+            return BackupResult::failure(e.to_string());
+        }
+        if let Err(e) = Self::check_disk_space(progress, pcc, pargs) {
+            return BackupResult::failure(e.to_string());
+        }
+
+        // Stamps are taken before the snapshot, changes made in between are
+        // dumped again by the next run instead of being missed by it, changes
+        // not yet visible in statistics are checked for after pg_dump.
+        let stamps = if pargs.format.is_single_file() {
+            Vec::new()
+        } else {
+            let load = || Self::load_table_stamps(pcc, pargs);
+            match pcc.retry_policy().run("Reading table change stamps", |en| progress.send_value(en), load) {
+                Ok(stamps) => stamps,
+                Err(e) => {
+                    progress.send_value(format!("Warning: error reading table change stamps: {}", e));
+                    Vec::new()
+                }
+            }
+        };
+        let (base_backup, mut data_sources) = match pargs.mode {
+            BackupMode::Incremental => Self::plan_incremental(progress, pargs, &stamps),
+            _ => (String::new(), BTreeMap::new())
+        };
+        let mut diff_base = match pargs.mode {
+            BackupMode::Differential => Self::plan_differential(progress, pargs),
+            _ => None
+        };
+
+        // ensure no dest dir, single file formats are written directly
+        let (dest_dir, filename) = if pargs.format.is_single_file() {
+            let filename = pargs.format.dest_name(&pargs.dest_filename);
+            let dest = Path::new(&pargs.parent_dir).join(&filename).to_string_lossy().to_string();
+            (dest, filename)
+        } else {
+            match Self::prepare_dest_dir(&pargs.parent_dir, &pargs.dest_filename, pargs.format) {
+                Ok(tup) => tup,
+                Err(e) => return BackupResult::failure(e.to_string())
+            }
+        };
+        let dest_file = Path::new(&pargs.parent_dir).join(Path::new(&filename)).to_string_lossy().to_string();
+        progress.send_value(format!("Backup file: {}", dest_file));
+
+        // data files are zipped while pg_dump is running, differential dumps
+        // are zipped after unchanged data files are removed
+        let mut streaming_zip = if BackupFormat::Zip == pargs.format && diff_base.is_none() {
+            match StreamingZip::create(Path::new(&dest_dir), Path::new(&dest_file)) {
+                Ok(zip) => Some(zip.with_throttle(pargs.throttle())),
+                Err(e) => return BackupResult::failure(format!(
+                    "Error creating zip file, path: {}, error: {}", &dest_file, e))
+            }
+        } else {
+            None
+        };
+
+        // row counts for the manifest are taken in the snapshot used by pg_dump
+        let mut snapshot = if pargs.format.is_single_file() {
+            None
+        } else {
+            let export = || Self::export_snapshot(pcc, pargs);
+            match pcc.retry_policy().run("Exporting snapshot", |en| progress.send_value(en), export) {
+                Ok(tup) => Some(tup),
+                Err(e) => {
+                    progress.send_value(format!("Warning: error exporting snapshot, row counts are not recorded: {}", e));
+                    None
+                }
+            }
+        };
+        let snapshot_id = snapshot.as_ref().map(|(_, id)| id.clone());
+        let snapshot_at = Instant::now();
+
+        // data of the unchanged tables can only be found on restore through the manifest
+        if snapshot.is_none() && (!data_sources.is_empty() || diff_base.is_some()) {
+            progress.send_value("Warning: manifest cannot be written without snapshot, running full backup");
+            data_sources.clear();
+            diff_base = None;
+        }
+        let exclude_data: Vec<String> = stamps.iter()
+            .filter(|st| data_sources.contains_key(&st.key()))
+            .map(Self::exclude_data_arg)
+            .collect();
+        let base_backup = if exclude_data.is_empty() {
+            String::new()
+        } else {
+            base_backup
+        };
+
+        // spawn and wait
+        progress.send_value("Running pg_dump ....");
+        if let Err(e) = BackupDialog::run_command(progress, pcc, pargs, &dest_dir, snapshot_id.as_deref(), &exclude_data, streaming_zip.as_mut()) {
+            if let Some(zip) = streaming_zip {
+                zip.abort();
+            }
+            return BackupResult::failure(e.to_string());
+        };
+
+        // data of the excluded tables must not have changed before the snapshot
+        if !exclude_data.is_empty() {
+            let changed = match Self::changed_excluded_tables(progress, pcc, pargs, &stamps, &data_sources, snapshot_at) {
+                Ok(changed) => changed,
+                Err(e) => vec!(format!("(error reading table change stamps: {})", e))
+            };
+            if !changed.is_empty() {
+                progress.send_value(format!(
+                    "Warning: tables excluded from incremental backup have changed: {}, running full backup",
+                    changed.join(", ")));
+                if let Some(zip) = streaming_zip {
+                    zip.abort();
+                }
+                if let Some((client, _)) = snapshot.take() {
+                    let _ = client.close();
+                }
+                return Self::run_backup(progress, pcc, &PgDumpArgs {
+                    mode: BackupMode::Full,
+                    ..pargs.clone()
+                });
+            }
+        }
+
+        // remaining data files are zipped first to record their checksums
+        if let Some(mut zip) = streaming_zip.take() {
+            if let Err(e) = zip.add_data_files(|en| progress.send_value(en)) {
+                zip.abort();
+                return BackupResult::failure(format!(
+                    "Error zipping destination directory, path: {}, error: {}", &dest_dir, e));
+            }
+            streaming_zip = Some(zip);
+        }
+
+        // manifest
+        if let Some((client, _)) = snapshot.as_mut() {
+            progress.send_value("Recording table row counts, checksums and server settings ....");
+            let zipped = streaming_zip.as_ref().map(|zip| zip.checksums().clone()).unwrap_or_default();
+            let server = format!("{}:{}", pcc.hostname, pcc.port);
+            let manifest = BackupManifest::new(&pargs.dbname, &server, Vec::new(), BTreeMap::new())
+                .with_mode(pargs.mode)
+                .with_stamps(stamps)
+                .with_base(&base_backup, data_sources);
+            match Self::write_manifest(client, &dest_dir, &zipped, manifest) {
+                Ok(count) => progress.send_value(format!("Row counts recorded, tables: {}", count)),
+                Err(e) if !exclude_data.is_empty() => {
+                    if let Some(zip) = streaming_zip {
+                        zip.abort();
+                    }
+                    return BackupResult::failure(format!("Error writing incremental backup manifest: {}", e));
+                },
+                Err(e) => {
+                    progress.send_value(format!("Warning: error recording row counts: {}", e));
+                    diff_base = None;
+                }
+            }
+        }
+        if let Some((client, _)) = snapshot.take() {
+            let _ = client.close();
+        }
+
+        // differential
+        if let Some((base_name, base)) = diff_base.as_ref() {
+            progress.send_value("Removing data files that are unchanged since the full backup ....");
+            match Self::drop_unchanged_data(&dest_dir, base_name, base) {
+                Ok(count) => progress.send_value(format!("Unchanged data files removed: {}", count)),
+                Err(e) => return BackupResult::failure(format!(
+                    "Error removing unchanged data files, path: {}, error: {}", &dest_dir, e))
+            }
+        }
+
+        // globals, added to the zip together with the manifest
+        if pargs.include_globals {
+            if pargs.format.is_single_file() {
+                progress.send_value("Warning: logins are only included into Zip and Directory backups");
+            } else {
+                progress.send_value("Running pg_dumpall ....");
+                let globals_file = Path::new(&dest_dir).join(GLOBALS_FILE_NAME).to_string_lossy().to_string();
+                if let Err(e) = Self::run_pg_dumpall(progress, pcc, pargs, &globals_file) {
+                    if let Some(zip) = streaming_zip {
+                        zip.abort();
+                    }
+                    return BackupResult::failure(e.to_string());
+                }
+            }
+        }
+
+        // zip results
+        match pargs.format {
+            BackupFormat::Zip => {
+                progress.send_value("Finishing zip file ....");
+                let zip = match streaming_zip.take() {
+                    Some(zip) => Ok(zip),
+                    None => StreamingZip::create(Path::new(&dest_dir), Path::new(&dest_file))
+                        .map(|zip| zip.with_throttle(pargs.throttle()))
+                };
+                if let Err(e) = zip.and_then(|zip| zip.finish(|en| progress.send_value(en))) {
+                    return BackupResult::failure(format!(
+                        "Error zipping destination directory, path: {}, error: {}", &dest_dir, e));
+                }
+            },
+            BackupFormat::Directory => {
+                progress.send_value(format!("Leaving destination directory unzipped: {}", &dest_dir));
+            },
+            BackupFormat::Tar | BackupFormat::PlainSql | BackupFormat::PlainSqlGzip => { }
+        }
+
+        // volumes
+        if pargs.volume_size_mb > 0 {
+            if let Err(e) = Self::split_backup_file(progress, pargs, &dest_file) {
+                return BackupResult::failure(format!(
+                    "Error splitting backup file into volumes, path: {}, error: {}", &dest_file, e));
+            }
+        }
+
+        // restore script, backup is usable without it
+        if pargs.restore_script {
+            match restore_script::write_restore_script(pcc, pargs) {
+                Ok(path) => progress.send_value(format!("Restore script written: {}", path.to_string_lossy())),
+                Err(e) => progress.send_value(format!("Warning: error writing restore script: {}", e))
+            }
+        }
+
+        // retention
+        if pargs.retention.is_enabled() {
+            progress.send_value("Applying retention policy ....");
+            if let Err(e) = Self::prune_old_backups(progress, pargs, &dest_file) {
+                progress.send_value(format!("Warning: error applying retention policy: {}", e));
+            }
+        }
+
+        progress.send_value("Backup complete");
+        BackupResult::success()
+    }
+
+    pub fn notify_by_email(progress: &impl ProgressSender, email: &EmailSettings, pargs: &PgDumpArgs,
+                           res: &BackupResult, duration: Duration, log_tail: &str) {
+        let success = res.error.is_empty();
+        if !email.should_notify(success) {
+            return;
+        }
+        let archive_path = pargs.dest_path();
+        let record = RunRecord::new(RunKind::Backup, &pargs.dbname, &archive_path, duration, success);
+        progress.send_value(format!("Sending email notification to: {} ...", email.to));
+        match email.send_backup_summary(&record, &archive_path, &res.error, log_tail) {
+            Ok(_) => progress.send_value("Email notification sent"),
+            Err(e) => progress.send_value(format!("Warning: error sending email notification: {}", e))
+        }
+    }
+}
+
+impl ui::PopupDialog<BackupDialogArgs, BackupDialogResult> for BackupDialog {
+    fn popup(args: BackupDialogArgs) -> ui::PopupJoinHandle<BackupDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.taskbar_progress = TaskbarProgress::new(&self.c.window);
+        self.start_command();
+    }
+
+    fn result(&mut self) -> BackupDialogResult {
+        self.dialog_result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.c.tray.set_visibility(false);
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.clear();
+        }
+        self.args.send_notice();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: nwg::EventData) {
+        self.c.update_tab_order();
+    }
+}
+
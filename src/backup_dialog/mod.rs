@@ -1,47 +1,85 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-mod args;
-mod controls;
-mod dialog;
-mod events;
-mod layout;
-mod nui;
-mod result;
-
-use std::thread;
-use std::time::Duration;
-use std::time::Instant;
-
-use clipboard_win::formats;
-use clipboard_win::set_clipboard;
-use nwg::NativeUi;
-
-use crate::*;
-use common::PgConnConfig;
-use nwg_ui as ui;
-use ui::Controls;
-use ui::Events;
-use ui::Layout;
-use ui::PopupDialog;
-
-pub use args::BackupDialogArgs;
-pub(self) use controls::BackupDialogControls;
-pub use dialog::BackupDialog;
-use events::BackupDialogEvents;
-use layout::BackupDialogLayout;
-pub use result::BackupDialogResult;
-use result::BackupResult;
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod args;
+mod controls;
+mod dialog;
+mod events;
+mod layout;
+mod nui;
+mod restore_script;
+mod result;
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use clipboard_win::formats;
+use clipboard_win::set_clipboard;
+use nwg::NativeUi;
+
+use crate::*;
+use common::babelfish_settings;
+use common::BackupFormat;
+use common::BackupManifest;
+use common::BackupMode;
+use common::DetailsLog;
+use common::EmailSettings;
+use common::format_disk_size;
+use common::free_disk_space;
+use common::GLOBALS_FILE_NAME;
+use common::LogTail;
+use common::long_path;
+use common::PgAccessError;
+use common::PgConnConfig;
+use common::pg_tool_cmd;
+use common::ProgressBuffer;
+use common::RunForecast;
+use common::RunKind;
+use common::RunRecord;
+use common::scan_backup_dir;
+use common::sha256_file_hex;
+use common::show_toast;
+use common::split_into_volumes;
+use common::TaskbarProgress;
+use pg_toc::Throttle;
+use common::window_in_background;
+use common::ProgressSender;
+use common::remove_volumes;
+use common::RetentionPolicy;
+use common::share_accessible;
+use common::StreamingZip;
+use common::table_key;
+use common::TableRowCount;
+use common::TableStamp;
+use common::unc_share_root;
+use common::volume_set_base;
+use nwg_ui as ui;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupDialog;
+
+pub use args::BackupDialogArgs;
+pub use args::PgDumpArgs;
+pub(self) use controls::BackupDialogControls;
+pub use dialog::BackupDialog;
+use events::BackupDialogEvents;
+use layout::BackupDialogLayout;
+pub use result::BackupDialogResult;
+pub use result::BackupResult;
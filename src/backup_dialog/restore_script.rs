@@ -0,0 +1,230 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::*;
+
+const SCRIPT_SUFFIX: &str = "_restore.ps1";
+
+const HEADER: &str = r#"# WiltonDB backup restore script, generated by WiltonDB Backup Tool
+# Backup: @FILE_TEXT@, DB: @DBNAME_TEXT@, created: @CREATED@
+#
+# Restores the backup without the GUI, check the settings below and run:
+#   powershell -ExecutionPolicy Bypass -File "@SCRIPT@"
+# Password is read from pgpass file or PGPASSWORD environment variable,
+# otherwise it is prompted for by each tool.
+
+# target server, defaults are the settings of the backed up server
+$PgHost = '@HOST@'
+$PgPort = '@PORT@'
+$PgUser = '@USER@'
+# @SERVER_DB_COMMENT@
+$ServerDb = '@SERVER_DB@'
+# name of the restored DB, must not exist on the target server
+$NewDbName = '@DBNAME@'
+# directory with pg_restore.exe and psql.exe (and wdb_backup.exe to restore
+# under a different name), empty - found on PATH
+$PgBin = ''
+"#;
+
+const COMMON: &str = r#"
+$ErrorActionPreference = 'Stop'
+$OrigDbName = '@DBNAME@'
+$Archive = Join-Path $PSScriptRoot '@FILE@'
+$DumpDir = Join-Path $PSScriptRoot '@DUMP_DIR@'
+$Conn = @('-h', $PgHost, '-p', $PgPort, '-U', $PgUser)
+
+function Get-Tool($Name) {
+    if ($PgBin) { Join-Path $PgBin "$Name.exe" } else { "$Name.exe" }
+}
+
+function Invoke-Tool($Name, [string[]] $ToolArgs) {
+    & (Get-Tool $Name) @ToolArgs
+    if ($LASTEXITCODE -ne 0) {
+        throw "$Name failed, exit code: $LASTEXITCODE"
+    }
+}
+
+# SQL is passed in a file, quotes in command line arguments are mangled by PowerShell
+function Invoke-Sql($Db, $Sql) {
+    $file = Join-Path $DumpDir 'restore_setup.sql'
+    [IO.File]::WriteAllText($file, $Sql)
+    Invoke-Tool 'psql' ($Conn + @('-X', '-v', 'ON_ERROR_STOP=1', '-d', $Db, '-f', $file))
+    Remove-Item $file
+}
+"#;
+
+const JOIN_VOLUMES: &str = r#"
+Write-Host 'Joining volumes ...'
+if (-not (Test-Path $Archive)) {
+    $volumes = Get-ChildItem -Path $PSScriptRoot -Filter '@FILE@.*' |
+        Where-Object { $_.Name -match '\.\d{3}$' } | Sort-Object Name
+    $out = [IO.File]::Create($Archive)
+    foreach ($vol in $volumes) {
+        $in = [IO.File]::OpenRead($vol.FullName)
+        $in.CopyTo($out)
+        $in.Close()
+    }
+    $out.Close()
+}
+"#;
+
+const UNZIP: &str = r#"
+Write-Host 'Unzipping backup ...'
+if (-not (Test-Path (Join-Path $DumpDir 'toc.dat'))) {
+    Expand-Archive -Path $Archive -DestinationPath $PSScriptRoot
+}
+"#;
+
+const UNTAR: &str = r#"
+Write-Host 'Extracting backup ...'
+if (-not (Test-Path (Join-Path $DumpDir 'toc.dat'))) {
+    New-Item -ItemType Directory -Force -Path $DumpDir | Out-Null
+    tar -xf $Archive -C $DumpDir
+    if ($LASTEXITCODE -ne 0) {
+        throw "tar failed, exit code: $LASTEXITCODE"
+    }
+}
+"#;
+
+const GLOBALS: &str = r#"
+# roles that already exist fail to be created, the script continues
+$Globals = Join-Path $DumpDir '@GLOBALS_FILE@'
+if (Test-Path $Globals) {
+    Write-Host 'Restoring logins ...'
+    $GlobalsArgs = $Conn + @('-X', '-d', $ServerDb, '-f', $Globals)
+    & (Get-Tool 'psql') @GlobalsArgs
+}
+"#;
+
+const BABELFISH_RESTORE: &str = r#"
+# DB name is stored in the dump TOC, it is updated with the TOC rewrite
+if ($NewDbName -ne $OrigDbName) {
+    Write-Host "Rewriting dump TOC, new DB name: $NewDbName ..."
+    Invoke-Tool 'wdb_backup' @('toc-rewrite', $DumpDir, '--new-name', $NewDbName)
+}
+
+# "dbo" and "db_owner" roles are not prefixed with DB name in single-db migration mode
+$SingleDbMode = $false
+$Prefix = $NewDbName.ToLower()
+$Owner = if ($SingleDbMode) { 'db_owner' } else { "${Prefix}_db_owner" }
+$Dbo = if ($SingleDbMode) { 'dbo' } else { "${Prefix}_dbo" }
+$Guest = "${Prefix}_guest"
+Write-Host 'Creating DB roles ...'
+Invoke-Sql $ServerDb @"
+DO `$`$
+DECLARE
+  r text;
+BEGIN
+  FOREACH r IN ARRAY ARRAY['$Owner', '$Dbo', '$Guest'] LOOP
+    IF NOT EXISTS (SELECT 1 FROM pg_catalog.pg_roles WHERE rolname = r) THEN
+      EXECUTE format('CREATE ROLE %I WITH NOSUPERUSER INHERIT NOCREATEROLE NOCREATEDB NOLOGIN NOREPLICATION NOBYPASSRLS', r);
+    END IF;
+  END LOOP;
+END
+`$`$;
+GRANT "$Owner" TO "$Dbo";
+GRANT "$Dbo" TO sysadmin;
+GRANT "$Guest" TO sysadmin;
+GRANT "$Guest" TO "$Owner";
+"@
+
+Write-Host 'Running pg_restore ...'
+Invoke-Tool 'pg_restore' ($Conn + @('-v', '-d', $ServerDb, '-F', 'd', '-j', '1', '--single-transaction', $DumpDir))
+Write-Host 'Restore complete'
+"#;
+
+const PLAIN_RESTORE: &str = r#"
+Write-Host "Creating DB: $NewDbName ..."
+Invoke-Sql $ServerDb "CREATE DATABASE `"$($NewDbName.Replace('"', '""'))`";"
+
+Write-Host 'Running pg_restore ...'
+Invoke-Tool 'pg_restore' ($Conn + @('-v', '-d', $NewDbName, '-F', 'd', '-j', '1', '--single-transaction', $DumpDir))
+Write-Host 'Restore complete'
+"#;
+
+// value for a single-quoted PowerShell string
+fn ps_quote(st: &str) -> String {
+    st.replace('\'', "''")
+}
+
+// Script file is written next to the backup with unzip, TOC rewrite and pg_restore
+// steps, so the backup can be restored on a machine without this app's GUI.
+// Incremental and differential backups depend on other backups and are not supported.
+pub(super) fn write_restore_script(pcc: &PgConnConfig, pargs: &PgDumpArgs) -> Result<PathBuf, io::Error> {
+    if BackupMode::Full != pargs.mode {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            "Restore script is only generated for full backups"));
+    }
+    let filename = pargs.format.dest_name(&pargs.dest_filename);
+    let (dump_dir, extract) = match pargs.format {
+        BackupFormat::Zip => match Path::new(&filename).file_stem() {
+            Some(stem) => (stem.to_string_lossy().to_string(), UNZIP),
+            None => (filename.clone(), UNZIP)
+        },
+        BackupFormat::Directory => (filename.clone(), ""),
+        BackupFormat::Tar => (BackupFormat::strip_extension(&filename), UNTAR),
+        BackupFormat::PlainSql | BackupFormat::PlainSqlGzip => return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            "Restore script is only generated for Zip, Directory and TAR backups"))
+    };
+    let script_name = format!("{}{}", dump_dir, SCRIPT_SUFFIX);
+    let script_path = Path::new(&pargs.parent_dir).join(&script_name);
+
+    let mut script = String::from(HEADER);
+    script.push_str(COMMON);
+    if pargs.volume_size_mb > 0 {
+        script.push_str(JOIN_VOLUMES);
+    }
+    script.push_str(extract);
+    if pargs.include_globals && !pargs.format.is_single_file() {
+        script.push_str(GLOBALS);
+    }
+    if pargs.is_plain_pg() {
+        script.push_str(PLAIN_RESTORE);
+    } else {
+        script.push_str(BABELFISH_RESTORE);
+    }
+    let (server_db_comment, server_db) = if pargs.is_plain_pg() {
+        ("existing DB to connect to for creating the restored one", pcc.connect_db.as_str())
+    } else {
+        ("Postgres DB that contains Babelfish databases", pargs.bbf_db.as_str())
+    };
+    let script = script
+        .replace("@SERVER_DB_COMMENT@", server_db_comment)
+        .replace("@FILE_TEXT@", &filename)
+        .replace("@DBNAME_TEXT@", &pargs.dbname)
+        .replace("@SERVER_DB@", &ps_quote(server_db))
+        .replace("@HOST@", &ps_quote(&pcc.hostname))
+        .replace("@PORT@", &pcc.port.to_string())
+        .replace("@USER@", &ps_quote(&pcc.username))
+        .replace("@DBNAME@", &ps_quote(&pargs.dbname))
+        .replace("@FILE@", &ps_quote(&filename))
+        .replace("@DUMP_DIR@", &ps_quote(&dump_dir))
+        .replace("@GLOBALS_FILE@", GLOBALS_FILE_NAME)
+        .replace("@SCRIPT@", &script_name)
+        .replace("@CREATED@", &chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+
+    // BOM is required for non-ASCII names in Windows PowerShell
+    let mut content = String::from("\u{feff}");
+    content.push_str(&script.replace('\n', "\r\n"));
+    fs::write(long_path(&script_path), content)?;
+    Ok(script_path)
+}
@@ -0,0 +1,214 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Timelike;
+
+use super::*;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+// Native format of "bcp -n": each value is preceded by a length prefix, prefix
+// size depends on the column type, all bits set in the prefix stand for NULL.
+// Fixed-size values of NOT NULL columns are written without a prefix.
+pub fn encode_value(out: &mut Vec<u8>, ty: &TsqlType, nullable: bool, value: Option<&str>) -> Result<(), String> {
+    let prefix = prefix_size(ty, nullable);
+    let data = match value {
+        Some(st) => encode_data(ty, st)?,
+        None if prefix > 0 => {
+            out.extend_from_slice(&vec![0xFF; prefix]);
+            return Ok(());
+        },
+        None => return Err(format!("NULL value in NOT NULL column of type: {}", &ty.name))
+    };
+    match prefix {
+        0 => {},
+        1 => out.push(data.len() as u8),
+        2 => out.extend_from_slice(&(data.len() as u16).to_le_bytes()),
+        _ => out.extend_from_slice(&(data.len() as u64).to_le_bytes())
+    }
+    out.extend_from_slice(&data);
+    Ok(())
+}
+
+fn prefix_size(ty: &TsqlType, nullable: bool) -> usize {
+    match ty.name.as_str() {
+        "char" | "varchar" | "nchar" | "nvarchar" | "binary" | "varbinary" => if ty.is_max { 8 } else { 2 },
+        "uniqueidentifier" | "decimal" | "date" | "time" | "datetime2" => 1,
+        _ => if nullable { 1 } else { 0 }
+    }
+}
+
+fn encode_data(ty: &TsqlType, st: &str) -> Result<Vec<u8>, String> {
+    let invalid = || format!("Invalid value for type: {}, value: '{}'", &ty.name, st);
+    let res = match ty.name.as_str() {
+        "tinyint" => st.parse::<u8>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "smallint" => st.parse::<i16>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "int" => st.parse::<i32>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "bigint" => st.parse::<i64>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "bit" => match st.to_lowercase().as_str() {
+            "1" | "t" | "true" => vec!(1),
+            "0" | "f" | "false" => vec!(0),
+            _ => return Err(invalid())
+        },
+        "real" => st.parse::<f32>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "float" => st.parse::<f64>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "money" => {
+            // high 4 bytes first
+            let val = parse_scaled(st, 4).ok_or_else(invalid)? as i64;
+            let mut res = ((val >> 32) as i32).to_le_bytes().to_vec();
+            res.extend_from_slice(&(val as u32).to_le_bytes());
+            res
+        },
+        "smallmoney" => (parse_scaled(st, 4).ok_or_else(invalid)? as i32).to_le_bytes().to_vec(),
+        "decimal" => {
+            let precision = ty.precision.unwrap_or(38);
+            let scale = ty.scale.unwrap_or(0);
+            let val = parse_scaled(st, scale as u32).ok_or_else(invalid)?;
+            let mut res = vec!(precision as u8, scale as u8, if val < 0 { 0 } else { 1 });
+            res.extend_from_slice(&val.unsigned_abs().to_le_bytes());
+            res
+        },
+        // default collation code page, characters outside of Latin-1 are replaced
+        "char" | "varchar" => st.chars()
+            .map(|ch| if (ch as u32) < 0x100 { ch as u32 as u8 } else { b'?' })
+            .collect(),
+        "nchar" | "nvarchar" => st.encode_utf16().flat_map(|unit| unit.to_le_bytes().to_vec()).collect(),
+        "binary" | "varbinary" => {
+            let hex = st.strip_prefix("0x").or_else(|| st.strip_prefix("\\x")).unwrap_or(st);
+            parse_hex(hex).ok_or_else(invalid)?
+        },
+        "uniqueidentifier" => {
+            let hex: String = st.chars().filter(|ch| ch.is_ascii_hexdigit()).collect();
+            let bytes = parse_hex(&hex).filter(|bytes| 16 == bytes.len()).ok_or_else(invalid)?;
+            // first three groups are little-endian
+            let mut res = Vec::with_capacity(16);
+            res.extend(bytes[0..4].iter().rev());
+            res.extend(bytes[4..6].iter().rev());
+            res.extend(bytes[6..8].iter().rev());
+            res.extend_from_slice(&bytes[8..16]);
+            res
+        },
+        "date" => {
+            let date = NaiveDate::parse_from_str(st, "%Y-%m-%d").map_err(|_| invalid())?;
+            date_bytes(&date)
+        },
+        "datetime" => {
+            let dt = parse_datetime(st).ok_or_else(invalid)?;
+            let mut days = (dt.date() - base_date()).num_days();
+            let mut ticks = (nanos_of_day(&dt.time()) * 300 + NANOS_PER_SEC / 2) / NANOS_PER_SEC;
+            if ticks >= 300 * 86400 {
+                days += 1;
+                ticks = 0;
+            }
+            let mut res = (days as i32).to_le_bytes().to_vec();
+            res.extend_from_slice(&(ticks as u32).to_le_bytes());
+            res
+        },
+        "smalldatetime" => {
+            let dt = parse_datetime(st).ok_or_else(invalid)?;
+            let days = (dt.date() - base_date()).num_days();
+            let minutes = (dt.time().num_seconds_from_midnight() + 30) / 60;
+            let mut res = (days as u16).to_le_bytes().to_vec();
+            res.extend_from_slice(&(minutes as u16).to_le_bytes());
+            res
+        },
+        "time" => {
+            let time = NaiveTime::parse_from_str(st, "%H:%M:%S%.f").map_err(|_| invalid())?;
+            time_bytes(&time, ty.scale.unwrap_or(7))
+        },
+        "datetime2" => {
+            let dt = parse_datetime(st).ok_or_else(invalid)?;
+            let mut res = time_bytes(&dt.time(), ty.scale.unwrap_or(7));
+            res.extend(date_bytes(&dt.date()));
+            res
+        },
+        _ => return Err(format!("Data type is not supported: {}", &ty.name))
+    };
+    Ok(res)
+}
+
+// decimal string to integer with the specified number of fractional digits,
+// extra digits are truncated
+fn parse_scaled(st: &str, scale: u32) -> Option<i128> {
+    let st = st.trim();
+    let (negative, digits) = match st.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, st.strip_prefix('+').unwrap_or(st))
+    };
+    let (int_part, frac_part) = match digits.find('.') {
+        Some(idx) => (&digits[..idx], &digits[idx + 1..]),
+        None => (digits, "")
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    let mut val: i128 = 0;
+    for ch in int_part.chars() {
+        val = val.checked_mul(10)?.checked_add(ch.to_digit(10)? as i128)?;
+    }
+    let mut frac = frac_part.chars();
+    for _ in 0..scale {
+        let digit = match frac.next() {
+            Some(ch) => ch.to_digit(10)?,
+            None => 0
+        };
+        val = val.checked_mul(10)?.checked_add(digit as i128)?;
+    }
+    Some(if negative { -val } else { val })
+}
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len()).step_by(2)
+        .map(|idx| u8::from_str_radix(&hex[idx..idx + 2], 16).ok())
+        .collect()
+}
+
+fn parse_datetime(st: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(st, "%Y-%m-%d %H:%M:%S%.f").ok()
+}
+
+fn base_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1900, 1, 1).expect("Invalid base date")
+}
+
+fn nanos_of_day(time: &NaiveTime) -> u64 {
+    time.num_seconds_from_midnight() as u64 * NANOS_PER_SEC + time.nanosecond() as u64
+}
+
+// days since 0001-01-01 in 3 bytes
+fn date_bytes(date: &NaiveDate) -> Vec<u8> {
+    let days = (date.num_days_from_ce() - 1) as u32;
+    days.to_le_bytes()[..3].to_vec()
+}
+
+// units of 10^-scale seconds since midnight, size depends on the scale
+fn time_bytes(time: &NaiveTime, scale: i32) -> Vec<u8> {
+    let scale = scale.max(0).min(7) as u32;
+    let units = nanos_of_day(time) / 10u64.pow(9 - scale);
+    let len = match scale {
+        0..=2 => 3,
+        3..=4 => 4,
+        _ => 5
+    };
+    units.to_le_bytes()[..len].to_vec()
+}
@@ -0,0 +1,278 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+const MODEL_NAMESPACE: &str = "http://schemas.microsoft.com/sqlserver/dac/Serialization/2012/02";
+const BUILTIN_SCHEMAS: [&str; 2] = ["dbo", "sys"];
+
+// T-SQL type of a column, length is in characters for (n)char/(n)varchar
+// and in bytes for binary types.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct TsqlType {
+    pub name: String,
+    pub length: Option<i32>,
+    pub is_max: bool,
+    pub precision: Option<i32>,
+    pub scale: Option<i32>,
+}
+
+impl TsqlType {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn sized(name: &str, args: &[i32]) -> Self {
+        match args.first() {
+            Some(len) if *len > 0 => Self {
+                name: name.to_string(),
+                length: Some(*len),
+                ..Default::default()
+            },
+            _ => Self {
+                name: name.to_string(),
+                is_max: true,
+                ..Default::default()
+            }
+        }
+    }
+
+    fn fixed(name: &str, args: &[i32]) -> Self {
+        Self {
+            name: name.to_string(),
+            length: Some(args.first().cloned().unwrap_or(1)),
+            ..Default::default()
+        }
+    }
+
+    fn scaled(name: &str, args: &[i32], default_scale: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            scale: Some(args.first().cloned().unwrap_or(default_scale)),
+            ..Default::default()
+        }
+    }
+
+    // Types are read with "format_type()", Babelfish types are in "sys" schema,
+    // for example: 'sys."varchar"(50)', 'sys.datetime', 'numeric(10,2)'.
+    pub fn from_pg(pg_type: &str) -> Option<Self> {
+        let st = pg_type.trim();
+        let st = st.strip_prefix("sys.").unwrap_or(st).replace('"', "");
+        let (name, args) = match st.find('(') {
+            Some(idx) => {
+                let args: Vec<i32> = st[idx + 1..].trim_end_matches(')').split(',')
+                    .filter_map(|arg| arg.trim().parse().ok())
+                    .collect();
+                (st[..idx].trim().to_lowercase(), args)
+            },
+            None => (st.to_lowercase(), Vec::new())
+        };
+        let res = match name.as_str() {
+            "tinyint" => Self::new("tinyint"),
+            "smallint" => Self::new("smallint"),
+            "integer" | "int" => Self::new("int"),
+            "bigint" => Self::new("bigint"),
+            "bit" | "boolean" => Self::new("bit"),
+            "real" => Self::new("real"),
+            "double precision" | "float" => Self::new("float"),
+            "money" => Self::new("money"),
+            "smallmoney" => Self::new("smallmoney"),
+            "numeric" | "decimal" => Self {
+                name: "decimal".to_string(),
+                precision: Some(args.get(0).cloned().unwrap_or(38)),
+                scale: Some(args.get(1).cloned().unwrap_or(if args.is_empty() { 10 } else { 0 })),
+                ..Default::default()
+            },
+            "char" | "bpchar" | "character" => Self::fixed("char", &args),
+            "nchar" => Self::fixed("nchar", &args),
+            "varchar" | "character varying" => Self::sized("varchar", &args),
+            "nvarchar" | "text" | "ntext" => Self::sized("nvarchar", &args),
+            "binary" => Self::fixed("binary", &args),
+            "varbinary" | "bytea" | "image" => Self::sized("varbinary", &args),
+            "uniqueidentifier" | "uuid" => Self::new("uniqueidentifier"),
+            "date" => Self::new("date"),
+            "datetime" => Self::new("datetime"),
+            "smalldatetime" => Self::new("smalldatetime"),
+            "datetime2" | "timestamp without time zone" | "timestamp" => Self::scaled("datetime2", &args, 6),
+            "time" | "time without time zone" => Self::scaled("time", &args, 6),
+            _ => return None
+        };
+        Some(res)
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct BacpacColumn {
+    pub name: String,
+    pub tsql_type: TsqlType,
+    pub nullable: bool,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct BacpacTable {
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<BacpacColumn>,
+    // constraint name and key columns
+    pub primary_key: Option<(String, Vec<String>)>,
+}
+
+impl BacpacTable {
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", bracket(&self.schema), bracket(&self.name))
+    }
+
+    // data files are named after the table, same as in packages written by SqlPackage
+    pub fn data_entry_name(&self) -> String {
+        format!("Data/{}.{}/TableData-000-00000.BCP", &self.schema, &self.name)
+    }
+}
+
+pub fn bracket(name: &str) -> String {
+    format!("[{}]", name.replace(']', "]]"))
+}
+
+pub fn xml_escape(st: &str) -> String {
+    st.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Only tables, columns and primary keys are included into the model,
+// other objects are expected to be recreated with scripts after the import.
+pub fn model_xml(tables: &[BacpacTable]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n");
+    xml.push_str(&format!(
+        "<DataSchemaModel FileFormatVersion=\"1.2\" SchemaVersion=\"2.9\" \
+        DspName=\"Microsoft.Data.Tools.Schema.Sql.Sql130DatabaseSchemaProvider\" \
+        CollationLcid=\"1033\" CollationCaseSensitive=\"False\" xmlns=\"{}\">\r\n", MODEL_NAMESPACE));
+    xml.push_str("  <Header>\r\n");
+    xml.push_str("    <CustomData Category=\"AnsiNulls\">\r\n");
+    xml.push_str("      <Metadata Name=\"AnsiNulls\" Value=\"True\" />\r\n");
+    xml.push_str("    </CustomData>\r\n");
+    xml.push_str("    <CustomData Category=\"QuotedIdentifier\">\r\n");
+    xml.push_str("      <Metadata Name=\"QuotedIdentifier\" Value=\"True\" />\r\n");
+    xml.push_str("    </CustomData>\r\n");
+    xml.push_str("  </Header>\r\n");
+    xml.push_str("  <Model>\r\n");
+    xml.push_str("    <Element Type=\"SqlDatabaseOptions\">\r\n");
+    xml.push_str("      <Property Name=\"Collation\" Value=\"SQL_Latin1_General_CP1_CI_AS\" />\r\n");
+    xml.push_str("    </Element>\r\n");
+
+    let mut schemas: Vec<&str> = tables.iter()
+        .map(|tb| tb.schema.as_str())
+        .filter(|schema| !BUILTIN_SCHEMAS.contains(schema))
+        .collect();
+    schemas.sort();
+    schemas.dedup();
+    for schema in schemas {
+        xml.push_str(&format!("    <Element Type=\"SqlSchema\" Name=\"{}\">\r\n", xml_escape(&bracket(schema))));
+        xml.push_str("      <Relationship Name=\"Authorizer\">\r\n");
+        xml.push_str("        <Entry>\r\n");
+        xml.push_str("          <References ExternalSource=\"BuiltIns\" Name=\"[dbo]\" />\r\n");
+        xml.push_str("        </Entry>\r\n");
+        xml.push_str("      </Relationship>\r\n");
+        xml.push_str("    </Element>\r\n");
+    }
+
+    for tb in tables.iter() {
+        let table_name = tb.full_name();
+        xml.push_str(&format!("    <Element Type=\"SqlTable\" Name=\"{}\">\r\n", xml_escape(&table_name)));
+        xml.push_str("      <Property Name=\"IsAnsiNullsOn\" Value=\"True\" />\r\n");
+        xml.push_str("      <Relationship Name=\"Columns\">\r\n");
+        for col in tb.columns.iter() {
+            let column_name = format!("{}.{}", &table_name, bracket(&col.name));
+            xml.push_str("        <Entry>\r\n");
+            xml.push_str(&format!("          <Element Type=\"SqlSimpleColumn\" Name=\"{}\">\r\n", xml_escape(&column_name)));
+            if !col.nullable {
+                xml.push_str("            <Property Name=\"IsNullable\" Value=\"False\" />\r\n");
+            }
+            xml.push_str("            <Relationship Name=\"TypeSpecifier\">\r\n");
+            xml.push_str("              <Entry>\r\n");
+            xml.push_str("                <Element Type=\"SqlTypeSpecifier\">\r\n");
+            let ty = &col.tsql_type;
+            if let Some(len) = ty.length {
+                xml.push_str(&format!("                  <Property Name=\"Length\" Value=\"{}\" />\r\n", len));
+            }
+            if ty.is_max {
+                xml.push_str("                  <Property Name=\"IsMax\" Value=\"True\" />\r\n");
+            }
+            if let Some(precision) = ty.precision {
+                xml.push_str(&format!("                  <Property Name=\"Precision\" Value=\"{}\" />\r\n", precision));
+            }
+            if let Some(scale) = ty.scale {
+                xml.push_str(&format!("                  <Property Name=\"Scale\" Value=\"{}\" />\r\n", scale));
+            }
+            xml.push_str("                  <Relationship Name=\"Type\">\r\n");
+            xml.push_str("                    <Entry>\r\n");
+            xml.push_str(&format!("                      <References ExternalSource=\"BuiltIns\" Name=\"[{}]\" />\r\n", &ty.name));
+            xml.push_str("                    </Entry>\r\n");
+            xml.push_str("                  </Relationship>\r\n");
+            xml.push_str("                </Element>\r\n");
+            xml.push_str("              </Entry>\r\n");
+            xml.push_str("            </Relationship>\r\n");
+            xml.push_str("          </Element>\r\n");
+            xml.push_str("        </Entry>\r\n");
+        }
+        xml.push_str("      </Relationship>\r\n");
+        xml.push_str("      <Relationship Name=\"Schema\">\r\n");
+        xml.push_str("        <Entry>\r\n");
+        xml.push_str(&schema_reference(&tb.schema));
+        xml.push_str("        </Entry>\r\n");
+        xml.push_str("      </Relationship>\r\n");
+        xml.push_str("    </Element>\r\n");
+
+        if let Some((pk_name, pk_columns)) = &tb.primary_key {
+            let constraint_name = format!("{}.{}", bracket(&tb.schema), bracket(pk_name));
+            xml.push_str(&format!("    <Element Type=\"SqlPrimaryKeyConstraint\" Name=\"{}\">\r\n", xml_escape(&constraint_name)));
+            xml.push_str("      <Relationship Name=\"ColumnSpecifications\">\r\n");
+            for col in pk_columns.iter() {
+                let column_name = format!("{}.{}", &table_name, bracket(col));
+                xml.push_str("        <Entry>\r\n");
+                xml.push_str("          <Element Type=\"SqlIndexedColumnSpecification\">\r\n");
+                xml.push_str("            <Relationship Name=\"Column\">\r\n");
+                xml.push_str("              <Entry>\r\n");
+                xml.push_str(&format!("                <References Name=\"{}\" />\r\n", xml_escape(&column_name)));
+                xml.push_str("              </Entry>\r\n");
+                xml.push_str("            </Relationship>\r\n");
+                xml.push_str("          </Element>\r\n");
+                xml.push_str("        </Entry>\r\n");
+            }
+            xml.push_str("      </Relationship>\r\n");
+            xml.push_str("      <Relationship Name=\"DefiningTable\">\r\n");
+            xml.push_str("        <Entry>\r\n");
+            xml.push_str(&format!("          <References Name=\"{}\" />\r\n", xml_escape(&table_name)));
+            xml.push_str("        </Entry>\r\n");
+            xml.push_str("      </Relationship>\r\n");
+            xml.push_str("    </Element>\r\n");
+        }
+    }
+
+    xml.push_str("  </Model>\r\n");
+    xml.push_str("</DataSchemaModel>\r\n");
+    xml
+}
+
+fn schema_reference(schema: &str) -> String {
+    if BUILTIN_SCHEMAS.contains(&schema) {
+        format!("          <References ExternalSource=\"BuiltIns\" Name=\"{}\" />\r\n", xml_escape(&bracket(schema)))
+    } else {
+        format!("          <References Name=\"{}\" />\r\n", xml_escape(&bracket(schema)))
+    }
+}
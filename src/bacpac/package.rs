@@ -0,0 +1,181 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::Local;
+use zip::CompressionMethod;
+use zip::ZipWriter;
+use zip::write::FileOptions;
+
+use crate::common::CsvReader;
+use crate::common::sha256_hex;
+
+use super::*;
+use super::bcp::encode_value;
+use super::model::model_xml;
+use super::model::xml_escape;
+
+const DAC_NAMESPACE: &str = "http://schemas.microsoft.com/sqlserver/dac/Serialization/2012/02";
+const ROWS_PER_CHUNK: usize = 1024;
+
+// Package is a zip file with "model.xml" describing the schema and one
+// native-format BCP data file per table, "Origin.xml" is written last because
+// it contains row counts and the checksum of the model.
+pub struct BacpacWriter {
+    zip: ZipWriter<BufWriter<File>>,
+    dbname: String,
+    started: String,
+    model_checksum: String,
+    table_rows: Vec<(String, u64)>,
+}
+
+impl BacpacWriter {
+    pub fn create(path: &Path, dbname: &str, tables: &[BacpacTable]) -> Result<Self, io::Error> {
+        let file = File::create(path)?;
+        let mut zip = ZipWriter::new(BufWriter::new(file));
+        let model = model_xml(tables);
+        let model_checksum = sha256_hex(model.as_bytes())?;
+        zip.start_file("model.xml", options())?;
+        zip.write_all(model.as_bytes())?;
+        zip.start_file("DacMetadata.xml", options())?;
+        zip.write_all(dac_metadata_xml(dbname).as_bytes())?;
+        Ok(Self {
+            zip,
+            dbname: dbname.to_string(),
+            started: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+            model_checksum,
+            table_rows: Vec::new(),
+        })
+    }
+
+    // Input is the output of "COPY ... TO STDOUT WITH (FORMAT csv)" with
+    // columns in the same order as in the table definition.
+    pub fn write_table_data<R: BufRead>(&mut self, table: &BacpacTable, reader: R) -> Result<u64, io::Error> {
+        self.zip.start_file(table.data_entry_name(), options())?;
+        let mut csv = CsvReader::new(reader, ',');
+        let mut buf = Vec::new();
+        let mut rows = 0u64;
+        while let Some(record) = csv.read_record()? {
+            if record.len() != table.columns.len() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                    "Invalid number of fields: {}, table: {}, expected: {}",
+                    record.len(), table.full_name(), table.columns.len())));
+            }
+            for (col, field) in table.columns.iter().zip(record.iter()) {
+                let value = if field.value.is_empty() && !field.quoted {
+                    None
+                } else {
+                    Some(field.value.as_str())
+                };
+                if let Err(e) = encode_value(&mut buf, &col.tsql_type, col.nullable, value) {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                        "{}, table: {}, column: {}", e, table.full_name(), &col.name)));
+                }
+            }
+            rows += 1;
+            if 0 == rows as usize % ROWS_PER_CHUNK {
+                self.zip.write_all(&buf)?;
+                buf.clear();
+            }
+        }
+        self.zip.write_all(&buf)?;
+        self.table_rows.push((table.full_name(), rows));
+        Ok(rows)
+    }
+
+    pub fn finish(mut self) -> Result<(), io::Error> {
+        let origin = origin_xml(&self.dbname, &self.started, &self.model_checksum, &self.table_rows);
+        self.zip.start_file("Origin.xml", options())?;
+        self.zip.write_all(origin.as_bytes())?;
+        self.zip.start_file("[Content_Types].xml", options())?;
+        self.zip.write_all(content_types_xml().as_bytes())?;
+        let mut writer = self.zip.finish()?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn options() -> FileOptions {
+    FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true)
+}
+
+fn dac_metadata_xml(dbname: &str) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n");
+    xml.push_str(&format!("<DacType xmlns=\"{}\">\r\n", DAC_NAMESPACE));
+    xml.push_str(&format!("  <Name>{}</Name>\r\n", xml_escape(dbname)));
+    xml.push_str("  <Version>1.0.0.0</Version>\r\n");
+    xml.push_str("</DacType>\r\n");
+    xml
+}
+
+fn origin_xml(dbname: &str, started: &str, model_checksum: &str, table_rows: &[(String, u64)]) -> String {
+    let total_rows: u64 = table_rows.iter().map(|(_, rows)| rows).sum();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n");
+    xml.push_str(&format!("<DacOrigin xmlns=\"{}\">\r\n", DAC_NAMESPACE));
+    xml.push_str("  <PackageProperties>\r\n");
+    xml.push_str("    <Version>3.1.0.0</Version>\r\n");
+    xml.push_str("    <ContainsExportedData>true</ContainsExportedData>\r\n");
+    xml.push_str("    <StreamVersions>\r\n");
+    xml.push_str("      <Version StreamName=\"Data\">2.0.0.0</Version>\r\n");
+    xml.push_str("      <Version StreamName=\"DeploymentContributors\">1.0.0.0</Version>\r\n");
+    xml.push_str("    </StreamVersions>\r\n");
+    xml.push_str("  </PackageProperties>\r\n");
+    xml.push_str("  <Operation>\r\n");
+    xml.push_str(&format!("    <Start>{}</Start>\r\n", started));
+    xml.push_str(&format!("    <End>{}</End>\r\n", Local::now().format("%Y-%m-%dT%H:%M:%S")));
+    xml.push_str("    <ProductName>WiltonDB Backup Tool</ProductName>\r\n");
+    xml.push_str(&format!("    <ProductVersion>{}</ProductVersion>\r\n", env!("CARGO_PKG_VERSION")));
+    xml.push_str(&format!("    <ProductSchema>{}</ProductSchema>\r\n", DAC_NAMESPACE));
+    xml.push_str("  </Operation>\r\n");
+    xml.push_str("  <Server>\r\n");
+    xml.push_str(&format!("    <DatabaseName>{}</DatabaseName>\r\n", xml_escape(dbname)));
+    xml.push_str("  </Server>\r\n");
+    xml.push_str("  <ExportStatistics>\r\n");
+    xml.push_str(&format!("    <SourceObjectCount>{}</SourceObjectCount>\r\n", table_rows.len()));
+    xml.push_str(&format!("    <TableRowCount>{}</TableRowCount>\r\n", total_rows));
+    xml.push_str("    <Tables>\r\n");
+    for (name, rows) in table_rows.iter() {
+        xml.push_str(&format!("      <Table Name=\"{}\" RowCount=\"{}\" />\r\n", xml_escape(name), rows));
+    }
+    xml.push_str("    </Tables>\r\n");
+    xml.push_str("  </ExportStatistics>\r\n");
+    xml.push_str("  <Checksums>\r\n");
+    xml.push_str(&format!("    <Checksum Uri=\"/model.xml\">{}</Checksum>\r\n", model_checksum));
+    xml.push_str("  </Checksums>\r\n");
+    xml.push_str("  <ModelSchemaVersion>2.9</ModelSchemaVersion>\r\n");
+    xml.push_str("</DacOrigin>\r\n");
+    xml
+}
+
+fn content_types_xml() -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n");
+    xml.push_str("<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\r\n");
+    xml.push_str("  <Default Extension=\"xml\" ContentType=\"text/xml\" />\r\n");
+    xml.push_str("  <Default Extension=\"BCP\" ContentType=\"application/octet-stream\" />\r\n");
+    xml.push_str("</Types>\r\n");
+    xml
+}
@@ -0,0 +1,244 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+pub(super) const COMMANDS: [&str; 5] = ["backup", "toc-rewrite", "toc-json", "--help", "/?"];
+
+pub(super) const USAGE: &str = "\
+Usage:
+  wdb_backup.exe backup --job <name> [options]
+  wdb_backup.exe backup --db <dbname> --dest-dir <dir> [options]
+  wdb_backup.exe toc-rewrite <dump-dir> --new-name <dbname>   Rename DB in an unzipped dump
+  wdb_backup.exe toc-json <dump-dir|file.zip> [--out <file.json>]   Export dump TOC entries as JSON
+  wdb_backup.exe [<file.zip>]   Open the GUI, optionally with a backup file selected for restore
+
+Options:
+  --job <name>        Use connection and database settings saved in a scheduled job
+  --host <hostname>   DB server hostname, default: localhost
+  --port <port>       DB server port, default: 5432
+  --user <username>   DB user name, default: wilton
+  --connect-db <db>   DB to connect to when checking the server, default: wilton
+  --bbf-db <db>       Postgres DB that contains Babelfish databases, default: wilton,
+                      empty value (\"\") for plain Postgres servers
+  --db <dbname>       Babelfish database to backup, or Postgres DB with empty --bbf-db
+  --dest-dir <dir>    Destination directory
+  --file <name>       Backup file name, default: <dbname>_<timestamp>.zip
+  --format <format>   Backup format: zip (default), dir (pg_dump directory, not zipped),
+                      tar (pg_dump tar archive), plain (SQL script)
+                      or plain-gz (gzip compressed SQL script)
+  --no-tls            Disable TLS
+  --restore-script    Write a PowerShell script for restoring without this tool next to the backup
+  --tls-ca-file <file>
+                      PEM file with CA certificates to verify the server certificate,
+                      default: Windows certificate store, invalid certificates accepted
+  --connect-timeout <sec>
+                      Connection timeout in seconds, default: 10
+  --read-timeout <sec>
+                      Dead server detection timeout in seconds, default: OS keepalive settings
+  --statement-timeout <sec>
+                      Timeout in seconds for SQL queries run by wdb_backup, default: no limit
+  --retries <count>   Repeats of the SQL checks failed with connection errors, default: 0
+  --ssh-host <host>   Connect through an SSH tunnel opened to this host, default: direct connection
+  --ssh-user <user>   SSH user name, default: current user
+  --ssh-key <file>    SSH private key file, default: user keys or ssh-agent
+  --new-name <name>   toc-rewrite: new Babelfish database name, dump directory is modified in place
+  --out <file>        toc-json: output file, default: print to console
+  --help              Show this message
+
+Password is read from pgpass file or from PGPASSWORD environment variable.";
+
+#[derive(Default, PartialEq)]
+pub(super) enum CliCommand {
+    #[default]
+    Backup,
+    TocRewrite,
+    TocJson,
+}
+
+#[derive(Default)]
+pub(super) struct CliArgs {
+    pub(super) command: CliCommand,
+    pub(super) help: bool,
+    pub(super) job: Option<String>,
+    pub(super) host: Option<String>,
+    pub(super) port: Option<u16>,
+    pub(super) user: Option<String>,
+    pub(super) connect_db: Option<String>,
+    pub(super) bbf_db: Option<String>,
+    pub(super) db: Option<String>,
+    pub(super) dest_dir: Option<String>,
+    pub(super) file: Option<String>,
+    pub(super) format: Option<BackupFormat>,
+    pub(super) no_tls: bool,
+    pub(super) restore_script: bool,
+    pub(super) tls_ca_file: Option<String>,
+    pub(super) connect_timeout: Option<u64>,
+    pub(super) read_timeout: Option<u64>,
+    pub(super) statement_timeout: Option<u64>,
+    pub(super) retries: Option<u32>,
+    pub(super) ssh_host: Option<String>,
+    pub(super) ssh_user: Option<String>,
+    pub(super) ssh_key: Option<String>,
+    pub(super) dump_path: Option<String>,
+    pub(super) new_name: Option<String>,
+    pub(super) out: Option<String>,
+}
+
+impl CliArgs {
+    pub(super) fn parse(args: &[String]) -> Result<Self, String> {
+        let mut res = Self::default();
+        let mut it = args.iter();
+        match it.next() {
+            Some(cmd) if "backup" == cmd => { },
+            Some(cmd) if "toc-rewrite" == cmd => res.command = CliCommand::TocRewrite,
+            Some(cmd) if "toc-json" == cmd => res.command = CliCommand::TocJson,
+            _ => {
+                res.help = true;
+                return Ok(res);
+            }
+        }
+        while let Some(name) = it.next() {
+            match name.as_str() {
+                "--help" | "/?" => res.help = true,
+                "--no-tls" => res.no_tls = true,
+                "--restore-script" => res.restore_script = true,
+                "--tls-ca-file" => res.tls_ca_file = Some(Self::value(name, it.next())?),
+                "--job" => res.job = Some(Self::value(name, it.next())?),
+                "--host" => res.host = Some(Self::value(name, it.next())?),
+                "--port" => {
+                    let val = Self::value(name, it.next())?;
+                    match val.parse::<u16>() {
+                        Ok(port) => res.port = Some(port),
+                        Err(_) => return Err(format!("Invalid port: {}", val))
+                    }
+                },
+                "--connect-timeout" => res.connect_timeout = Some(Self::timeout_value(name, it.next())?),
+                "--read-timeout" => res.read_timeout = Some(Self::timeout_value(name, it.next())?),
+                "--statement-timeout" => res.statement_timeout = Some(Self::timeout_value(name, it.next())?),
+                "--retries" => {
+                    let val = Self::value(name, it.next())?;
+                    match val.parse::<u32>() {
+                        Ok(retries) => res.retries = Some(retries),
+                        Err(_) => return Err(format!("Invalid retries count: {}", val))
+                    }
+                },
+                "--ssh-host" => res.ssh_host = Some(Self::value(name, it.next())?),
+                "--ssh-user" => res.ssh_user = Some(Self::value(name, it.next())?),
+                "--ssh-key" => res.ssh_key = Some(Self::value(name, it.next())?),
+                "--user" => res.user = Some(Self::value(name, it.next())?),
+                "--connect-db" => res.connect_db = Some(Self::value(name, it.next())?),
+                "--bbf-db" => res.bbf_db = Some(Self::value(name, it.next())?),
+                "--db" => res.db = Some(Self::value(name, it.next())?),
+                "--dest-dir" => res.dest_dir = Some(Self::value(name, it.next())?),
+                "--file" => res.file = Some(Self::value(name, it.next())?),
+                "--new-name" => res.new_name = Some(Self::value(name, it.next())?),
+                "--out" => res.out = Some(Self::value(name, it.next())?),
+                "--format" => {
+                    let val = Self::value(name, it.next())?;
+                    match BackupFormat::from_cli_name(&val) {
+                        Some(format) => res.format = Some(format),
+                        None => return Err(format!("Invalid format: {}", val))
+                    }
+                },
+                _ if CliCommand::Backup != res.command && res.dump_path.is_none() && !name.starts_with("--") => {
+                    res.dump_path = Some(name.clone())
+                },
+                _ => return Err(format!("Unsupported option: {}", name))
+            }
+        }
+        Ok(res)
+    }
+
+    pub(super) fn resolve_job(&self, settings: &AppSettings) -> Result<BackupJob, String> {
+        let mut job = match &self.job {
+            Some(name) => match settings.backup_job(name) {
+                Some(job) => job,
+                None => return Err(format!("Backup job not found, name: {}", name))
+            },
+            None => BackupJob {
+                hostname: "localhost".to_string(),
+                port: 5432,
+                username: "wilton".to_string(),
+                connect_db: "wilton".to_string(),
+                enable_tls: true,
+                accept_invalid_tls: true,
+                tls_ca_file: settings.tls_ca_file.clone(),
+                bbf_db: "wilton".to_string(),
+                ..Default::default()
+            }
+        };
+        if let Some(host) = &self.host { job.hostname = host.clone(); }
+        if let Some(port) = self.port { job.port = port; }
+        if let Some(user) = &self.user { job.username = user.clone(); }
+        if let Some(connect_db) = &self.connect_db { job.connect_db = connect_db.clone(); }
+        if let Some(bbf_db) = &self.bbf_db { job.bbf_db = bbf_db.clone(); }
+        if let Some(db) = &self.db { job.dbname = db.clone(); }
+        if let Some(dest_dir) = &self.dest_dir { job.dest_dir = dest_dir.clone(); }
+        if let Some(format) = self.format { job.format = format; }
+        if self.no_tls { job.enable_tls = false; }
+        if self.restore_script { job.restore_script = true; }
+        if let Some(ca_file) = &self.tls_ca_file { job.tls_ca_file = ca_file.clone(); }
+        if let Some(secs) = self.connect_timeout { job.connect_timeout_secs = secs; }
+        if let Some(secs) = self.read_timeout { job.read_timeout_secs = secs; }
+        if let Some(secs) = self.statement_timeout { job.statement_timeout_secs = secs; }
+        if let Some(retries) = self.retries { job.retries = retries; }
+        if let Some(ssh_host) = &self.ssh_host { job.ssh_host = ssh_host.clone(); }
+        if let Some(ssh_user) = &self.ssh_user { job.ssh_user = ssh_user.clone(); }
+        if let Some(ssh_key) = &self.ssh_key { job.ssh_key_file = ssh_key.clone(); }
+        if job.dbname.is_empty() {
+            return Err("Database name must be specified".to_string());
+        }
+        if job.dest_dir.is_empty() {
+            return Err("Destination directory must be specified".to_string());
+        }
+        Ok(job)
+    }
+
+    pub(super) fn resolve_toc_rewrite(&self) -> Result<(String, String), String> {
+        let dump_dir = match &self.dump_path {
+            Some(dir) => dir.clone(),
+            None => return Err("Dump directory must be specified".to_string())
+        };
+        let new_name = match &self.new_name {
+            Some(name) if !name.is_empty() => name.clone(),
+            _ => return Err("New database name must be specified".to_string())
+        };
+        Ok((dump_dir, new_name))
+    }
+
+    pub(super) fn resolve_toc_json(&self) -> Result<String, String> {
+        match &self.dump_path {
+            Some(path) => Ok(path.clone()),
+            None => Err("Dump directory or zip file must be specified".to_string())
+        }
+    }
+
+    fn value(name: &str, val: Option<&String>) -> Result<String, String> {
+        match val {
+            Some(val) => Ok(val.clone()),
+            None => Err(format!("Value not specified for option: {}", name))
+        }
+    }
+
+    fn timeout_value(name: &str, val: Option<&String>) -> Result<u64, String> {
+        let val = Self::value(name, val)?;
+        match val.parse::<u64>() {
+            Ok(secs) => Ok(secs),
+            Err(_) => Err(format!("Invalid timeout: {}", val))
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::io::Write;
+
+use winapi::um::wincon::AttachConsole;
+use winapi::um::wincon::ATTACH_PARENT_PROCESS;
+
+use super::*;
+
+pub(super) struct ConsoleProgress {}
+
+impl ConsoleProgress {
+    pub(super) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ProgressSender for ConsoleProgress {
+    fn send_value<T: Into<String>>(&self, value: T) {
+        let st = redact_secrets(&value.into());
+        log::info!("{}", st);
+        let stdout = io::stdout();
+        let mut lock = stdout.lock();
+        let _ = writeln!(lock, "{}", st);
+        let _ = lock.flush();
+    }
+}
+
+// GUI subsystem app has no console, output is only visible
+// when it is launched from a command prompt
+pub(super) fn attach_parent_console() {
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod cli_args;
+mod console_progress;
+
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::*;
+use backup_dialog::BackupDialog;
+use backup_dialog::PgDumpArgs;
+use common::AppSettings;
+use common::BackupFormat;
+use common::BackupJob;
+use common::LogTail;
+use common::long_path;
+use common::ProgressSender;
+use common::redact_secrets;
+use common::RunKind;
+use common::RunRecord;
+use common::SecretString;
+use pg_toc::DumpSummary;
+use restore_dialog::PgRestoreArgs;
+use restore_dialog::RestoreDialog;
+use restore_dialog::RestoreOptions;
+
+use cli_args::CliArgs;
+use cli_args::CliCommand;
+use console_progress::ConsoleProgress;
+
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    match args.get(1) {
+        Some(cmd) => cli_args::COMMANDS.contains(&cmd.as_str()),
+        None => false
+    }
+}
+
+pub fn run(args: &[String], env_password: Option<SecretString>) -> i32 {
+    console_progress::attach_parent_console();
+    let cargs = match CliArgs::parse(&args[1..]) {
+        Ok(cargs) => cargs,
+        Err(msg) => {
+            eprintln!("ERROR: {}\n\n{}", msg, cli_args::USAGE);
+            return 2;
+        }
+    };
+    if cargs.help {
+        println!("{}", cli_args::USAGE);
+        return 0;
+    }
+    let res = match cargs.command {
+        CliCommand::Backup => run_backup(&cargs, env_password),
+        CliCommand::TocRewrite => run_toc_rewrite(&cargs),
+        CliCommand::TocJson => run_toc_json(&cargs)
+    };
+    common::close_ssh_tunnels();
+    match res {
+        Ok(_) => 0,
+        Err(msg) => {
+            log::error!("{}", msg);
+            eprintln!("ERROR: {}", msg);
+            1
+        }
+    }
+}
+
+fn run_backup(cargs: &CliArgs, env_password: Option<SecretString>) -> Result<(), String> {
+    let mut settings = match AppSettings::load() {
+        Ok(settings) => settings,
+        Err(e) => return Err(e.to_string())
+    };
+    let job = cargs.resolve_job(&settings)?;
+    let mut pcc = job.pg_conn_config();
+    if let Some(password) = env_password {
+        pcc.password = password;
+        pcc.use_pgpass_file = false;
+    }
+    let retention = settings.retention_policy(&job.dest_dir);
+    let filename = match &cargs.file {
+        Some(file) => file.clone(),
+        None => job.dest_filename()
+    };
+    let pargs = PgDumpArgs::new(&job.dbname, &job.bbf_db, &job.dest_dir, &filename, job.format, &retention)
+        .with_globals(job.include_globals)
+        .with_mode(job.mode)
+        .with_volume_size(job.volume_size_mb)
+        .with_throttle(job.throttle_mb_per_sec)
+        .with_restore_script(job.restore_script);
+    let progress = ConsoleProgress::new();
+    progress.send_value(format!("Database: {}, connection: {}:{}", &job.dbname, &job.hostname, job.port));
+    let start = Instant::now();
+    let tail = LogTail::new(&progress);
+    let res = BackupDialog::run_backup(&tail, &pcc, &pargs);
+    BackupDialog::notify_by_email(&progress, &settings.email, &pargs, &res, start.elapsed(), &tail.text());
+    let dest_path = pargs.dest_path();
+    settings.record_run(RunRecord::new(RunKind::Backup, &job.dbname, &dest_path, start.elapsed(), res.error.is_empty()));
+    if let Err(e) = settings.save() {
+        progress.send_value(format!("WARNING: {}", e));
+    }
+    if !res.error.is_empty() {
+        return Err(res.error);
+    }
+    Ok(())
+}
+
+fn run_toc_rewrite(cargs: &CliArgs) -> Result<(), String> {
+    let (dump_dir, new_name) = cargs.resolve_toc_rewrite()?;
+    let dir = Path::new(&dump_dir);
+    if !dir.join("toc.dat").is_file() {
+        return Err(format!("Directory does not contain a dump, 'toc.dat' not found: {}", &dump_dir));
+    }
+    let progress = ConsoleProgress::new();
+    progress.send_value(format!("Dump directory: {}, new DB name: {}", &dump_dir, &new_name));
+    let ra = PgRestoreArgs::new(&dump_dir, &new_name, "", &RestoreOptions::default());
+    if let Err(e) = RestoreDialog::rewrite_dump(&progress, &ra, &long_path(dir)) {
+        return Err(e.to_string());
+    }
+    progress.send_value("TOC rewrite complete");
+    Ok(())
+}
+
+fn run_toc_json(cargs: &CliArgs) -> Result<(), String> {
+    let dump_path = cargs.resolve_toc_json()?;
+    let json = match DumpSummary::read_path(Path::new(&dump_path)).and_then(|summary| summary.to_json()) {
+        Ok(json) => json,
+        Err(e) => return Err(e.to_string())
+    };
+    match &cargs.out {
+        Some(out) => {
+            if let Err(e) = fs::write(out, json) {
+                return Err(format!("Error writing file: {}, message: {}", out, e));
+            }
+            println!("TOC written to: {}", out);
+        },
+        None => println!("{}", json)
+    }
+    Ok(())
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct CloneDialogControls {
+    layout: CloneDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) src_dbname_label: nwg::Label,
+    pub(super) src_dbname_input: nwg::TextInput,
+    pub(super) dest_dbname_label: nwg::Label,
+    pub(super) dest_dbname_input: nwg::TextInput,
+
+    pub(super) clone_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for CloneDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((400, 130))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Clone DB")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Source DB:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.src_dbname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .readonly(true)
+            .parent(&self.window)
+            .build(&mut self.src_dbname_input)?;
+        nwg::Label::builder()
+            .text("New DB name:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.dest_dbname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.dest_dbname_input)?;
+
+        nwg::Button::builder()
+            .text("Clone")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.clone_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.dest_dbname_input)
+            .control(&self.clone_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct CloneDialog {
+    pub(super) c: CloneDialogControls,
+
+    args: CloneDialogArgs,
+    result: CloneDialogResult,
+}
+
+impl CloneDialog {
+    pub(super) fn clone_db(&mut self, _: nwg::EventData) {
+        let dest_dbname = self.c.dest_dbname_input.text().trim().to_string();
+        if let Err(msg) = validate_db_name(&dest_dbname) {
+            nwg::modal_error_message(&self.c.window, "Clone DB", &msg);
+            return;
+        }
+        if dest_dbname.to_lowercase() == self.args.dbname.to_lowercase() {
+            nwg::modal_error_message(&self.c.window, "Clone DB", "New DB name must be different from the source one");
+            return;
+        }
+        self.result = CloneDialogResult::new(&dest_dbname);
+        self.close(nwg::EventData::NoData);
+    }
+}
+
+impl ui::PopupDialog<CloneDialogArgs, CloneDialogResult> for CloneDialog {
+    fn popup(args: CloneDialogArgs) -> ui::PopupJoinHandle<CloneDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.c.src_dbname_input.set_text(&self.args.dbname);
+        self.c.dest_dbname_input.set_text(&format!("{}_copy", &self.args.dbname));
+        self.result = CloneDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> CloneDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
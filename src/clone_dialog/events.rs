@@ -0,0 +1,50 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct CloneDialogEvents {
+    pub(super) events: Vec<ui::Event<CloneDialog>>
+}
+
+impl ui::Events<CloneDialogControls> for CloneDialogEvents {
+    fn build(&mut self, c: &CloneDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(CloneDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(CloneDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.clone_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(CloneDialog::clone_db)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.cancel_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(CloneDialog::close)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
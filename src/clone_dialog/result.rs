@@ -0,0 +1,37 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[derive(Default, Clone)]
+pub struct CloneDialogResult {
+    pub cancelled: bool,
+    pub dest_dbname: String,
+}
+
+impl CloneDialogResult {
+    pub fn new(dest_dbname: &str) -> Self {
+        Self {
+            cancelled: false,
+            dest_dbname: dest_dbname.to_string(),
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self {
+            cancelled: true,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+use super::long_path;
+
+const PROBE_FILE_NAME: &str = ".wdb_backup_write_test";
+
+// Creates and removes a small file, access rights on network shares and
+// folders protected by "Controlled folder access" are not reliably
+// reported by the directory attributes.
+pub fn probe_dir_writable(dir: &Path) -> Result<(), io::Error> {
+    let dir_st = dir.to_string_lossy().to_string();
+    if dir_st.is_empty() || !long_path(dir).is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+            "Directory does not exist: {}", dir_st)));
+    }
+    let probe = long_path(&dir.join(PROBE_FILE_NAME));
+    if let Err(e) = File::create(&probe) {
+        return Err(io::Error::new(e.kind(), format!(
+            "Cannot write to directory: {}, message: {}", dir_st, e)));
+    }
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+// Reads the first byte of a file or lists a directory.
+pub fn probe_path_readable(path: &Path) -> Result<(), io::Error> {
+    let path_st = path.to_string_lossy().to_string();
+    let lpath = long_path(path);
+    let res = if lpath.is_dir() {
+        fs::read_dir(&lpath).map(|_| ())
+    } else {
+        File::open(&lpath).and_then(|mut file| {
+            let mut buf = [0u8; 1];
+            file.read(&mut buf).map(|_| ())
+        })
+    };
+    match res {
+        Ok(_) => Ok(()),
+        Err(e) => Err(io::Error::new(e.kind(), format!(
+            "Cannot read: {}, message: {}", path_st, e)))
+    }
+}
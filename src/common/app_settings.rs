@@ -0,0 +1,188 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::*;
+
+const MAX_RUN_HISTORY: usize = 500;
+const DEFAULT_OVERRUN_FACTOR: f64 = 1.5;
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub backup_jobs: Vec<BackupJob>,
+    pub profiles: Vec<ConnProfile>,
+    // CA certificates file from the last DB connection, empty - Windows certificate store
+    pub tls_ca_file: String,
+    pub retention_policies: Vec<RetentionPolicy>,
+    pub run_history: Vec<RunRecord>,
+    // runs taking longer than forecast multiplied by this factor are flagged
+    pub forecast_overrun_factor: f64,
+    pub email: EmailSettings,
+    // existing backup is kept and "_N" suffix is added to the new one
+    pub backup_auto_increment: bool,
+    // logins and roles are added to the backup
+    pub backup_include_globals: bool,
+    // full, incremental or differential
+    pub backup_mode: BackupMode,
+    // backup file is split into volumes of this size, zero - not split
+    pub backup_volume_size_mb: u64,
+    // zip, unzip and volume writes are limited to this rate, zero - no limit
+    pub throttle_mb_per_sec: u64,
+    // script for restoring without this app is generated next to the backup
+    pub backup_restore_script: bool,
+    // folder listed on the "Catalog" tab
+    pub catalog_dir: String,
+    // backups deleted from the catalog are not moved to the recycle bin
+    pub catalog_delete_permanently: bool,
+}
+
+impl AppSettings {
+    pub fn load() -> Result<Self, io::Error> {
+        let path = Self::settings_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::read_file(&path)
+    }
+
+    pub fn save(&self) -> Result<(), io::Error> {
+        let path = Self::settings_file_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.write_file(&path)
+    }
+
+    // Writes profiles, jobs and preferences for moving them to another machine,
+    // run history is not exported and SMTP password is dropped because
+    // it is encrypted with DPAPI for the current Windows user only
+    pub fn export_to_file(&self, path: &Path) -> Result<(), io::Error> {
+        let mut exported = self.clone();
+        exported.run_history.clear();
+        exported.email.password_protected.clear();
+        exported.write_file(path)
+    }
+
+    // Replaces current settings with the exported ones, local run history
+    // is kept, as well as SMTP password when the same SMTP account is imported
+    pub fn import_from_file(&mut self, path: &Path) -> Result<(), io::Error> {
+        let mut imported = Self::read_file(path)?;
+        imported.run_history = mem::take(&mut self.run_history);
+        let same_smtp = imported.email.smtp_host.to_lowercase() == self.email.smtp_host.to_lowercase() &&
+            imported.email.username == self.email.username;
+        if imported.email.password_protected.is_empty() && same_smtp {
+            imported.email.password_protected = self.email.password_protected.clone();
+        }
+        *self = imported;
+        Ok(())
+    }
+
+    fn read_file(path: &Path) -> Result<Self, io::Error> {
+        let json = fs::read_to_string(path)?;
+        match serde_json::from_str(&json) {
+            Ok(settings) => Ok(settings),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Error reading settings file: {}, message: {}", path.to_string_lossy(), e)))
+        }
+    }
+
+    fn write_file(&self, path: &Path) -> Result<(), io::Error> {
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(json) => json,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Error serializing settings, message: {}", e)))
+        };
+        fs::write(path, json)
+    }
+
+    pub fn app_data_dir() -> Result<PathBuf, io::Error> {
+        match std::env::var("LOCALAPPDATA") {
+            Ok(dir) => Ok(PathBuf::from(dir).join("wdb_backup")),
+            Err(_) => Err(io::Error::new(io::ErrorKind::NotFound,
+                "Local application data directory not found, env var: 'LOCALAPPDATA'"))
+        }
+    }
+
+    fn settings_file_path() -> Result<PathBuf, io::Error> {
+        Ok(Self::app_data_dir()?.join("settings.json"))
+    }
+
+    pub fn retention_policy(&self, dest_dir: &str) -> RetentionPolicy {
+        match self.retention_policies.iter().find(|rp| rp.matches_dir(dest_dir)) {
+            Some(rp) => rp.clone(),
+            None => RetentionPolicy::keep_all(dest_dir)
+        }
+    }
+
+    pub fn set_retention_policy(&mut self, policy: RetentionPolicy) {
+        self.retention_policies.retain(|rp| !rp.matches_dir(&policy.dest_dir));
+        if policy.is_enabled() {
+            self.retention_policies.push(policy);
+        }
+    }
+
+    pub fn profile(&self, pcc: &PgConnConfig) -> ConnProfile {
+        match self.profiles.iter().find(|prof| prof.matches(pcc)) {
+            Some(prof) => prof.clone(),
+            None => ConnProfile::new(pcc)
+        }
+    }
+
+    pub fn set_profile(&mut self, profile: ConnProfile) {
+        let pos = self.profiles.iter().position(|prof| {
+            prof.matches_key(&profile.hostname, profile.port, &profile.username)
+        });
+        match pos {
+            Some(idx) => self.profiles[idx] = profile,
+            None => self.profiles.push(profile)
+        }
+    }
+
+    pub fn backup_job(&self, name: &str) -> Option<BackupJob> {
+        self.backup_jobs.iter().find(|job| job.name.to_lowercase() == name.to_lowercase()).cloned()
+    }
+
+    pub fn set_backup_job(&mut self, job: BackupJob) {
+        self.backup_jobs.retain(|existing| existing.name.to_lowercase() != job.name.to_lowercase());
+        self.backup_jobs.push(job);
+    }
+
+    pub fn record_run(&mut self, record: RunRecord) {
+        self.run_history.push(record);
+        if self.run_history.len() > MAX_RUN_HISTORY {
+            let excess = self.run_history.len() - MAX_RUN_HISTORY;
+            self.run_history.drain(..excess);
+        }
+    }
+
+    pub fn forecast(&self, kind: RunKind, dbname: &str) -> Option<RunForecast> {
+        let factor = if self.forecast_overrun_factor > 1.0 {
+            self.forecast_overrun_factor
+        } else {
+            DEFAULT_OVERRUN_FACTOR
+        };
+        RunForecast::from_history(&self.run_history, kind, dbname, factor)
+    }
+}
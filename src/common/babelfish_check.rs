@@ -0,0 +1,54 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+
+use postgres::Client;
+
+use super::*;
+
+// Babelfish settings are registered only when babelfishpg_tsql
+// library is loaded by the server
+pub fn babelfish_enabled(client: &mut Client) -> Result<bool, PgAccessError> {
+    let rs = client.query(
+        "select 1 from pg_settings where name = 'babelfishpg_tsql.database_name'", &[])?;
+    Ok(!rs.is_empty())
+}
+
+pub fn babelfish_catalog_exists(client: &mut Client) -> Result<bool, PgAccessError> {
+    let rs = client.query(
+        "select to_regclass('sys.babelfish_sysdatabases') is not null as catalog_exists", &[])?;
+    let exists: bool = rs[0].get("catalog_exists");
+    Ok(exists)
+}
+
+// Babelfish server settings including escape hatches, sp_configure
+// options are stored in the same settings
+pub fn babelfish_settings(client: &mut Client) -> Result<BTreeMap<String, String>, PgAccessError> {
+    let rs = client.query("
+        select name, setting
+        from pg_catalog.pg_settings
+        where name like 'babelfishpg\\_tsql.%'
+        or name like 'babelfishpg\\_tds.%'
+        order by name", &[])?;
+    let mut res = BTreeMap::new();
+    for row in rs.iter() {
+        let name: String = row.get("name");
+        let setting: Option<String> = row.get("setting");
+        res.insert(name, setting.unwrap_or_default());
+    }
+    Ok(res)
+}
@@ -0,0 +1,289 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+use pg_toc::DumpSummary;
+
+use super::*;
+use super::backup_manifest::MANIFEST_FILE_NAME;
+use super::recycle_bin::move_to_recycle_bin;
+
+const CATALOG_INDEX_FILE_NAME: &str = "catalog_index.json";
+
+// Backup found in the catalog folder, metadata is read from the TOC and
+// from the manifest, backups that cannot be read are listed with an error
+// so they can still be deleted.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatalogEntry {
+    pub path: PathBuf,
+    pub dbname: String,
+    pub dump_time: String,
+    pub size: u64,
+    pub modified: i64,
+    pub server: String,
+    pub error: String,
+}
+
+impl CatalogEntry {
+    pub fn read(path: &Path) -> Self {
+        let (size, modified) = match path_size(path) {
+            Ok(found) => found,
+            Err(_) => (0, 0)
+        };
+        let mut res = Self {
+            path: path.to_path_buf(),
+            size,
+            modified,
+            ..Default::default()
+        };
+        let summary = match DumpSummary::read_path(path) {
+            Ok(summary) => summary,
+            Err(e) => {
+                res.error = e.to_string();
+                return res;
+            }
+        };
+        res.dbname = summary.original_dbname.unwrap_or_default();
+        res.dump_time = summary.header.dump_time().unwrap_or_default();
+        // backups created by older versions do not have a manifest
+        res.server = match pg_toc::read_dump_file(path, MANIFEST_FILE_NAME) {
+            Ok(Some(json)) => match BackupManifest::parse(&json, path) {
+                Ok(manifest) if !manifest.server.is_empty() => manifest.server,
+                _ => String::new()
+            },
+            _ => String::new()
+        };
+        if res.server.is_empty() {
+            if let Some(version) = &summary.header.server_version {
+                res.server = format!("PostgreSQL {}", version);
+            }
+        }
+        res
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.is_empty()
+    }
+
+    // All zip entries are read to the end to check their CRC, for
+    // unzipped dumps the TOC is read and all data files must be present,
+    // except the ones taken from earlier backups, checksums recorded in
+    // the manifest are compared with the files.
+    pub fn verify(&self) -> Result<String, io::Error> {
+        if self.path.is_dir() {
+            let summary = DumpSummary::read(&self.path)?;
+            let manifest = BackupManifest::read(&self.path)?.unwrap_or_default();
+            let mut checked = 0;
+            for en in summary.entries.iter() {
+                let path = match summary.data_files.get(&en.dump_id) {
+                    Some(path) => path,
+                    None => continue
+                };
+                if !path.is_file() {
+                    let chained = match (&en.namespace, &en.tag) {
+                        (Some(schema), Some(table)) => manifest.data_sources.contains_key(&table_key(schema, table)),
+                        _ => false
+                    };
+                    if chained {
+                        continue;
+                    }
+                    return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                        "Data file not found: {}", path.to_string_lossy())));
+                }
+                let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                if let Some(expected) = manifest.checksums.get(&name) {
+                    if &sha256_file_hex(path)? != expected {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                            "Data file checksum mismatch: {}", path.to_string_lossy())));
+                    }
+                    checked += 1;
+                }
+            }
+            return Ok(format!("TOC entries: {}, data files: {}, checksums verified: {}",
+                summary.entries.len(), summary.data_files.len(), checked));
+        }
+        pg_toc::check_zip_dump(&self.path)?;
+        let count = pg_toc::verify_zip(&self.path)?;
+        Ok(format!("Zip entries checked: {}", count))
+    }
+}
+
+// Metadata of the backups found in all scanned folders, keyed by path,
+// entry is reused while the size and modification time of the backup
+// stay the same, so multi-GB zip files are not re-opened on every scan.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct CatalogIndex {
+    entries: Vec<CatalogEntry>,
+}
+
+impl CatalogIndex {
+    fn load() -> Result<Self, io::Error> {
+        let path = Self::index_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(&path)?;
+        match serde_json::from_str(&json) {
+            Ok(index) => Ok(index),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Error reading catalog index: {}, message: {}", path.to_string_lossy(), e)))
+        }
+    }
+
+    fn save(&self) -> Result<(), io::Error> {
+        let path = Self::index_file_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = match serde_json::to_string(self) {
+            Ok(json) => json,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Error serializing catalog index, message: {}", e)))
+        };
+        fs::write(&path, json)
+    }
+
+    // backups that could not be read are not reused, they may have been
+    // still written during the previous scan
+    fn find(&self, path: &Path, size: u64, modified: i64) -> Option<&CatalogEntry> {
+        self.entries.iter().find(|en| {
+            en.path == path && en.size == size && en.modified == modified && en.is_valid()
+        })
+    }
+
+    fn index_file_path() -> Result<PathBuf, io::Error> {
+        Ok(AppSettings::app_data_dir()?.join(CATALOG_INDEX_FILE_NAME))
+    }
+}
+
+// Zip files and unzipped dump directories directly inside the folder are
+// listed, most recent first. Index errors are logged, the folder is then
+// scanned without the index.
+pub fn scan_backup_dir(dir: &Path) -> Result<Vec<CatalogEntry>, io::Error> {
+    let mut index = match CatalogIndex::load() {
+        Ok(index) => index,
+        Err(e) => {
+            log::warn!("{}", e);
+            CatalogIndex::default()
+        }
+    };
+    let mut res = Vec::new();
+    let mut parsed = 0;
+    for en_res in fs::read_dir(dir)? {
+        let path = en_res?.path();
+        if !is_catalog_backup(&path) {
+            continue;
+        }
+        let (size, modified) = path_size(&path).unwrap_or((0, 0));
+        match index.find(&path, size, modified) {
+            Some(en) => res.push(en.clone()),
+            None => {
+                res.push(CatalogEntry::read(&path));
+                parsed += 1;
+            }
+        }
+    }
+    res.sort_by_key(|en| std::cmp::Reverse(en.modified));
+    log::info!("Backup folder scanned: {}, backups: {}, read: {}", dir.to_string_lossy(), res.len(), parsed);
+
+    // entries of this folder are replaced, deleted backups are dropped
+    index.entries.retain(|en| en.path.parent() != Some(dir));
+    index.entries.extend(res.iter().cloned());
+    if let Err(e) = index.save() {
+        log::warn!("Error writing catalog index, message: {}", e);
+    }
+    Ok(res)
+}
+
+// Backups last modified more than the specified number of days ago, the
+// most recent readable backup of each DB is never selected.
+pub fn select_older_than(entries: &[CatalogEntry], days: u32, now: i64) -> Vec<CatalogEntry> {
+    let mut newest: HashMap<&str, i64> = HashMap::new();
+    for en in entries.iter().filter(|en| en.is_valid()) {
+        let modified = newest.entry(en.dbname.as_str()).or_insert(en.modified);
+        if en.modified > *modified {
+            *modified = en.modified;
+        }
+    }
+    let max_age = days as i64 * 24 * 60 * 60;
+    entries.iter().filter(|en| {
+        if now - en.modified <= max_age {
+            return false;
+        }
+        match newest.get(en.dbname.as_str()) {
+            Some(modified) if en.is_valid() => en.modified < *modified,
+            _ => true
+        }
+    }).cloned().collect()
+}
+
+pub fn delete_backup(path: &Path, recycle: bool) -> Result<(), io::Error> {
+    if recycle {
+        move_to_recycle_bin(path)
+    } else if path.is_dir() {
+        fs::remove_dir_all(long_path(path))
+    } else {
+        fs::remove_file(long_path(path))
+    }
+}
+
+// other files with zip format (without ".zip" extension) are not opened
+fn is_catalog_backup(path: &Path) -> bool {
+    if path.is_dir() {
+        return path.join("toc.dat").is_file();
+    }
+    match path.extension() {
+        Some(ext) => ext.eq_ignore_ascii_case("zip"),
+        None => false
+    }
+}
+
+// size of the zip file or of all files in the dump directory
+fn path_size(path: &Path) -> Result<(u64, i64), io::Error> {
+    let meta = fs::metadata(path)?;
+    let modified = meta.modified()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|dur| dur.as_secs() as i64)
+        .unwrap_or(0);
+    if !meta.is_dir() {
+        return Ok((meta.len(), modified));
+    }
+    let mut size = 0;
+    for en_res in fs::read_dir(path)? {
+        let en = en_res?;
+        let meta = en.metadata()?;
+        if meta.is_file() {
+            size += meta.len();
+        }
+    }
+    Ok((size, modified))
+}
@@ -0,0 +1,192 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+// Output of pg_dump directory format is zipped by default,
+// it can also be left as-is for deduplicating storage;
+// tar format is written by pg_dump as a single file;
+// plain SQL dumps are restored with psql instead of pg_restore
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BackupFormat {
+    #[default]
+    Zip,
+    Directory,
+    Tar,
+    PlainSql,
+    PlainSqlGzip,
+}
+
+impl BackupFormat {
+    pub fn all() -> Vec<Self> {
+        vec!(
+            Self::Zip,
+            Self::Directory,
+            Self::Tar,
+            Self::PlainSql,
+            Self::PlainSqlGzip,
+        )
+    }
+
+    pub fn from_index(idx: Option<usize>) -> Self {
+        match idx {
+            Some(idx) => Self::all().get(idx).copied().unwrap_or_default(),
+            None => Self::default()
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        Self::all().iter().position(|fmt| fmt == self).unwrap_or(0)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Zip => "ZIP archive",
+            Self::Directory => "Directory, not zipped",
+            Self::Tar => "TAR archive",
+            Self::PlainSql => "Plain SQL",
+            Self::PlainSqlGzip => "Plain SQL, gzip compressed",
+        }
+    }
+
+    pub fn cli_name(&self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Directory => "dir",
+            Self::Tar => "tar",
+            Self::PlainSql => "plain",
+            Self::PlainSqlGzip => "plain-gz",
+        }
+    }
+
+    pub fn from_cli_name(name: &str) -> Option<Self> {
+        Self::all().into_iter().find(|fmt| fmt.cli_name() == name.to_lowercase())
+    }
+
+    // includes the leading dot, empty for directories
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Zip => ".zip",
+            Self::Directory => "",
+            Self::Tar => ".tar",
+            Self::PlainSql => ".sql",
+            Self::PlainSqlGzip => ".sql.gz",
+        }
+    }
+
+    // pg_dump writes the backup file directly, without temp directory
+    pub fn is_single_file(&self) -> bool {
+        match self {
+            Self::Zip | Self::Directory => false,
+            Self::Tar | Self::PlainSql | Self::PlainSqlGzip => true,
+        }
+    }
+
+    pub fn is_plain_sql(&self) -> bool {
+        matches!(self, Self::PlainSql | Self::PlainSqlGzip)
+    }
+
+    pub fn pg_dump_args(&self) -> Vec<&'static str> {
+        match self {
+            Self::Zip | Self::Directory => vec!("-F", "d", "-Z", "6", "-j", "4"),
+            // tar format does not support compression
+            Self::Tar => vec!("-F", "t"),
+            Self::PlainSql => vec!("-F", "p"),
+            // compression of plain format output produces a gzip file
+            Self::PlainSqlGzip => vec!("-F", "p", "-Z", "6"),
+        }
+    }
+
+    // backups created by older versions may have any extension, these are treated as zip
+    pub fn detect(path: &Path) -> Self {
+        if path.is_dir() {
+            return Self::Directory;
+        }
+        let name = path.file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        for fmt in vec!(Self::Tar, Self::PlainSqlGzip, Self::PlainSql) {
+            if name.ends_with(fmt.extension()) {
+                return fmt;
+            }
+        }
+        Self::Zip
+    }
+
+    pub fn backup_name(&self, stem: &str) -> String {
+        format!("{}{}", stem, self.extension())
+    }
+
+    // Name under which the backup is written, zip files keep the
+    // extension chosen by user, default extension is added if missing
+    pub fn dest_name(&self, filename: &str) -> String {
+        match self {
+            Self::Zip => match Path::new(filename).extension() {
+                Some(_) => filename.to_string(),
+                None => self.backup_name(filename)
+            },
+            Self::Directory => Self::strip_extension(filename),
+            _ => if filename.to_lowercase().ends_with(self.extension()) {
+                filename.to_string()
+            } else {
+                self.backup_name(&Self::strip_extension(filename))
+            }
+        }
+    }
+
+    // Destination name with "_1", "_2", ... appended to the stem when
+    // a backup with the same name already exists in the directory
+    pub fn unique_dest_name(&self, dir: &Path, filename: &str) -> String {
+        let name = self.dest_name(filename);
+        if !dir.join(&name).exists() {
+            return name;
+        }
+        let stem = match self {
+            Self::Zip => match Path::new(&name).file_stem() {
+                Some(stem) => stem.to_string_lossy().to_string(),
+                None => name.clone()
+            },
+            _ => Self::strip_extension(&name)
+        };
+        let ext: String = name.chars().skip(stem.chars().count()).collect();
+        let mut idx = 1;
+        loop {
+            let candidate = format!("{}_{}{}", stem, idx, ext);
+            if !dir.join(&candidate).exists() {
+                return candidate;
+            }
+            idx += 1;
+        }
+    }
+
+    // removes the extension of any of the supported formats
+    pub fn strip_extension(filename: &str) -> String {
+        let lower = filename.to_lowercase();
+        let mut formats = Self::all();
+        // ".sql.gz" is checked before ".sql"
+        formats.sort_by_key(|fmt| std::cmp::Reverse(fmt.extension().len()));
+        for fmt in formats {
+            let ext = fmt.extension();
+            if !ext.is_empty() && lower.ends_with(ext) {
+                return filename.chars().take(filename.chars().count() - ext.chars().count()).collect();
+            }
+        }
+        filename.to_string()
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::*;
+
+// Backup settings saved for running without GUI, password
+// is never stored and is read from pgpass file instead.
+// Jobs with cron expression are run by built-in scheduler,
+// others - by Windows Task Scheduler.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupJob {
+    pub name: String,
+    pub hostname: String,
+    pub port: u16,
+    pub username: String,
+    pub connect_db: String,
+    pub enable_tls: bool,
+    pub accept_invalid_tls: bool,
+    pub tls_ca_file: String,
+    // zero values - defaults of PgConnConfig
+    pub connect_timeout_secs: u64,
+    pub read_timeout_secs: u64,
+    pub statement_timeout_secs: u64,
+    pub retries: u32,
+    // empty - direct connection, key file or ssh-agent is used for authentication
+    pub ssh_host: String,
+    pub ssh_user: String,
+    pub ssh_key_file: String,
+    pub bbf_db: String,
+    pub dbname: String,
+    pub dest_dir: String,
+    pub format: BackupFormat,
+    pub cron: String,
+    pub include_globals: bool,
+    // full, incremental or differential
+    pub mode: BackupMode,
+    // backup file is split into volumes of this size, zero - not split
+    pub volume_size_mb: u64,
+    // zip and volume writes are limited to this rate, zero - no limit
+    pub throttle_mb_per_sec: u64,
+    // PowerShell restore script is written next to the backup
+    pub restore_script: bool,
+}
+
+impl BackupJob {
+    pub fn new(name: &str, pcc: &PgConnConfig, bbf_db: &str, dbname: &str, dest_dir: &str, format: BackupFormat) -> Self {
+        Self {
+            name: name.to_string(),
+            hostname: pcc.hostname.clone(),
+            port: pcc.port,
+            username: pcc.username.clone(),
+            connect_db: pcc.connect_db.clone(),
+            enable_tls: pcc.enable_tls,
+            accept_invalid_tls: pcc.accept_invalid_tls,
+            tls_ca_file: pcc.tls_ca_file.clone(),
+            connect_timeout_secs: pcc.connect_timeout_secs,
+            read_timeout_secs: pcc.read_timeout_secs,
+            statement_timeout_secs: pcc.statement_timeout_secs,
+            retries: pcc.retries,
+            ssh_host: pcc.ssh_host.clone(),
+            ssh_user: pcc.ssh_user.clone(),
+            ssh_key_file: pcc.ssh_key_file.clone(),
+            bbf_db: bbf_db.to_string(),
+            dbname: dbname.to_string(),
+            dest_dir: dest_dir.to_string(),
+            format,
+            cron: String::new(),
+            include_globals: false,
+            mode: BackupMode::Full,
+            volume_size_mb: 0,
+            throttle_mb_per_sec: 0,
+            restore_script: false,
+        }
+    }
+
+    pub fn pg_conn_config(&self) -> PgConnConfig {
+        PgConnConfig {
+            hostname: self.hostname.clone(),
+            port: self.port,
+            username: self.username.clone(),
+            password: SecretString::default(),
+            use_pgpass_file: true,
+            connect_db: self.connect_db.clone(),
+            enable_tls: self.enable_tls,
+            accept_invalid_tls: self.accept_invalid_tls,
+            tls_ca_file: self.tls_ca_file.clone(),
+            connect_timeout_secs: self.connect_timeout_secs,
+            read_timeout_secs: self.read_timeout_secs,
+            statement_timeout_secs: self.statement_timeout_secs,
+            retries: self.retries,
+            ssh_host: self.ssh_host.clone(),
+            ssh_user: self.ssh_user.clone(),
+            ssh_key_file: self.ssh_key_file.clone(),
+        }
+    }
+
+    // timestamped names allow retention policy to keep multiple backups
+    pub fn dest_filename(&self) -> String {
+        let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        self.format.backup_name(&format!("{}_{}", self.dbname, ts))
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::backup_mode::BackupMode;
+
+// written into the dump directory next to "toc.dat", pg_restore ignores it
+pub const MANIFEST_FILE_NAME: &str = "wdb_manifest.json";
+
+// logins and roles dumped with pg_dumpall, written next to the manifest
+pub const GLOBALS_FILE_NAME: &str = "globals.sql";
+
+// Tables are matched by TOC dump ID, it is kept when the dump is restored
+// under a new name and schemas are renamed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TableRowCount {
+    pub dump_id: i64,
+    pub schema: String,
+    pub table: String,
+    pub rows: i64,
+    // data file name on disk, "NNNN.dat.gz" when compression is enabled
+    #[serde(default)]
+    pub file: String,
+}
+
+// Modification stamp of a table, compared with the stamp recorded by the
+// previous backup to find tables that were not changed since then.
+// Counters from "pg_stat_user_tables" only grow, TRUNCATE and table rewrites
+// change the file node, column list catches DDL changes.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableStamp {
+    pub schema: String,
+    pub table: String,
+    pub relfilenode: i64,
+    pub n_tup_ins: i64,
+    pub n_tup_upd: i64,
+    pub n_tup_del: i64,
+    pub columns: String,
+}
+
+impl TableStamp {
+    pub fn key(&self) -> String {
+        table_key(&self.schema, &self.table)
+    }
+}
+
+// key of the "data_sources" map
+pub fn table_key(schema: &str, table: &str) -> String {
+    format!("{}.{}", schema, table)
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub dbname: String,
+    // "host:port" of the backed up server
+    #[serde(default)]
+    pub server: String,
+    pub created_at: i64,
+    pub tables: Vec<TableRowCount>,
+    // Babelfish server settings at backup time
+    #[serde(default)]
+    pub settings: BTreeMap<String, String>,
+    #[serde(default)]
+    pub mode: BackupMode,
+    // data file name -> SHA-256, files taken from earlier backups are not listed
+    #[serde(default)]
+    pub checksums: BTreeMap<String, String>,
+    #[serde(default)]
+    pub table_stamps: Vec<TableStamp>,
+    // file name of the previous backup in the same directory, empty for full backups
+    #[serde(default)]
+    pub base_backup: String,
+    // Tables whose data is not included into this backup, mapped to the file name
+    // of the backup in the same directory that contains it. Sources are resolved
+    // when the backup is created, so restore never needs to follow the chain.
+    #[serde(default)]
+    pub data_sources: BTreeMap<String, String>,
+}
+
+impl BackupManifest {
+    pub fn new(dbname: &str, server: &str, tables: Vec<TableRowCount>, settings: BTreeMap<String, String>) -> Self {
+        Self {
+            dbname: dbname.to_string(),
+            server: server.to_string(),
+            created_at: chrono::Local::now().timestamp(),
+            tables,
+            settings,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_mode(mut self, mode: BackupMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_stamps(mut self, table_stamps: Vec<TableStamp>) -> Self {
+        self.table_stamps = table_stamps;
+        self
+    }
+
+    pub fn with_base(mut self, base_backup: &str, data_sources: BTreeMap<String, String>) -> Self {
+        self.base_backup = base_backup.to_string();
+        self.data_sources = data_sources;
+        self
+    }
+
+    // backup can only be restored together with the backups it refers to
+    pub fn is_chained(&self) -> bool {
+        !self.base_backup.is_empty()
+    }
+
+    pub fn table_checksum(&self, schema: &str, table: &str) -> Option<&String> {
+        let tbl = self.tables.iter().find(|tbl| tbl.schema == schema && tbl.table == table)?;
+        self.checksums.get(&tbl.file)
+    }
+
+    // Tables that have the same stamp as recorded in this manifest, mapped to the
+    // backup that holds their data, "file_name" is the name of this backup.
+    pub fn unchanged_tables(&self, file_name: &str, stamps: &[TableStamp]) -> BTreeMap<String, String> {
+        let mut res = BTreeMap::new();
+        for st in stamps.iter() {
+            if !self.table_stamps.contains(st) {
+                continue;
+            }
+            let key = st.key();
+            let source = match self.data_sources.get(&key) {
+                Some(source) => source.clone(),
+                None => file_name.to_string()
+            };
+            res.insert(key, source);
+        }
+        res
+    }
+
+    // settings that are missing on either side are reported with an empty value
+    pub fn settings_diff(&self, server: &BTreeMap<String, String>) -> Vec<(String, String, String)> {
+        let mut res = Vec::new();
+        for (name, value) in self.settings.iter() {
+            let server_value = server.get(name).cloned().unwrap_or_default();
+            if &server_value != value {
+                res.push((name.clone(), value.clone(), server_value));
+            }
+        }
+        for (name, value) in server.iter() {
+            if !self.settings.contains_key(name) {
+                res.push((name.clone(), String::new(), value.clone()));
+            }
+        }
+        res.sort();
+        res
+    }
+
+    pub fn write(&self, dump_dir: &Path) -> Result<(), io::Error> {
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(json) => json,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Error serializing backup manifest, message: {}", e)))
+        };
+        fs::write(dump_dir.join(MANIFEST_FILE_NAME), json)
+    }
+
+    // backups created by older versions do not have a manifest
+    pub fn read(dump_dir: &Path) -> Result<Option<Self>, io::Error> {
+        let path = dump_dir.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path)?;
+        Self::parse(&json, &path).map(Some)
+    }
+
+    // unzipped dump directory or zip file
+    pub fn read_path(path: &Path) -> Result<Option<Self>, io::Error> {
+        match pg_toc::read_dump_file(path, MANIFEST_FILE_NAME)? {
+            Some(json) => Self::parse(&json, path).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    // manifest may also be read directly from a zip file
+    pub fn parse(json: &str, path: &Path) -> Result<Self, io::Error> {
+        match serde_json::from_str(json) {
+            Ok(manifest) => Ok(manifest),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Error reading backup manifest: {}, message: {}", path.to_string_lossy(), e)))
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+use serde::Serialize;
+
+// Incremental backups contain data of the tables changed since the previous
+// backup of any kind, differential ones - data files that differ from the
+// last full backup; both are restored together with the backups they refer to.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BackupMode {
+    #[default]
+    Full,
+    Incremental,
+    Differential,
+}
+
+impl BackupMode {
+    pub fn all() -> Vec<Self> {
+        vec!(
+            Self::Full,
+            Self::Incremental,
+            Self::Differential,
+        )
+    }
+
+    pub fn from_index(idx: Option<usize>) -> Self {
+        match idx {
+            Some(idx) => Self::all().get(idx).copied().unwrap_or_default(),
+            None => Self::default()
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        Self::all().iter().position(|mode| mode == self).unwrap_or(0)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Full => "Full",
+            Self::Incremental => "Incremental",
+            Self::Differential => "Differential",
+        }
+    }
+}
@@ -0,0 +1,52 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::*;
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnProfile {
+    pub hostname: String,
+    pub port: u16,
+    pub username: String,
+    pub backup_bbf_db: String,
+    // details from the last successful connection
+    pub server_info: ServerInfo,
+}
+
+impl ConnProfile {
+    pub fn new(pcc: &PgConnConfig) -> Self {
+        Self {
+            hostname: pcc.hostname.clone(),
+            port: pcc.port,
+            username: pcc.username.clone(),
+            ..Default::default()
+        }
+    }
+
+    pub fn matches(&self, pcc: &PgConnConfig) -> bool {
+        self.matches_key(&pcc.hostname, pcc.port, &pcc.username)
+    }
+
+    pub fn matches_key(&self, hostname: &str, port: u16, username: &str) -> bool {
+        self.hostname.to_lowercase() == hostname.to_lowercase() &&
+            self.port == port &&
+            self.username == username
+    }
+}
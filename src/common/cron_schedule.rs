@@ -0,0 +1,112 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::Datelike;
+use chrono::NaiveDateTime;
+use chrono::Timelike;
+
+// Standard 5-field cron expression: "MIN HOUR DAY MONTH WEEKDAY",
+// each field supports "*", "N", "N-M", lists and "/STEP" suffix.
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days: Vec<bool>,
+    months: Vec<bool>,
+    weekdays: Vec<bool>,
+    days_any: bool,
+    weekdays_any: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if 5 != fields.len() {
+            return Err(format!(
+                "Invalid cron expression: '{}', expected 5 fields: MIN HOUR DAY MONTH WEEKDAY", expr));
+        }
+        let mut weekdays = parse_field(fields[4], 0, 7)?;
+        // both 0 and 7 are Sunday
+        if weekdays[7] {
+            weekdays[0] = true;
+        }
+        weekdays.truncate(7);
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            weekdays,
+            days_any: "*" == fields[2],
+            weekdays_any: "*" == fields[4],
+        })
+    }
+
+    pub fn matches(&self, dt: &NaiveDateTime) -> bool {
+        if !self.minutes[dt.minute() as usize] ||
+            !self.hours[dt.hour() as usize] ||
+            !self.months[dt.month() as usize] {
+            return false;
+        }
+        let day_match = self.days[dt.day() as usize];
+        let weekday_match = self.weekdays[dt.weekday().num_days_from_sunday() as usize];
+        // when both are restricted, cron matches either of them
+        match (self.days_any, self.weekdays_any) {
+            (true, true) => true,
+            (true, false) => weekday_match,
+            (false, true) => day_match,
+            (false, false) => day_match || weekday_match
+        }
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<bool>, String> {
+    let mut res = vec!(false; (max + 1) as usize);
+    for part in field.split(',') {
+        let (range, step) = match part.find('/') {
+            Some(idx) => (&part[..idx], parse_number(&part[idx + 1..])?),
+            None => (part, 1)
+        };
+        if 0 == step {
+            return Err(format!("Invalid cron step: '{}'", part));
+        }
+        let (start, end) = if "*" == range {
+            (min, max)
+        } else if let Some(idx) = range.find('-') {
+            (parse_number(&range[..idx])?, parse_number(&range[idx + 1..])?)
+        } else {
+            let num = parse_number(range)?;
+            // "N/STEP" means from N to the end of the range
+            if step > 1 { (num, max) } else { (num, num) }
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("Invalid cron field: '{}', allowed values: {}-{}", part, min, max));
+        }
+        let mut val = start;
+        while val <= end {
+            res[val as usize] = true;
+            val += step;
+        }
+    }
+    Ok(res)
+}
+
+fn parse_number(st: &str) -> Result<u32, String> {
+    match st.parse::<u32>() {
+        Ok(num) => Ok(num),
+        Err(_) => Err(format!("Invalid cron value: '{}'", st))
+    }
+}
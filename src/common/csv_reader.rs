@@ -0,0 +1,169 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::Path;
+
+use super::long_path;
+
+const DELIMITERS: [char; 3] = [',', ';', '\t'];
+
+// Quoted empty field is an empty string, unquoted one is NULL, same as in Postgres COPY.
+#[derive(Default, Clone)]
+pub struct CsvField {
+    pub value: String,
+    pub quoted: bool,
+}
+
+// Reads RFC 4180 records, quoted fields may span multiple lines, UTF-8 BOM
+// written by Excel is skipped.
+pub struct CsvReader<R: BufRead> {
+    reader: R,
+    delimiter: char,
+    first: bool,
+}
+
+impl<R: BufRead> CsvReader<R> {
+    pub fn new(reader: R, delimiter: char) -> Self {
+        Self {
+            reader,
+            delimiter,
+            first: true,
+        }
+    }
+
+    pub fn read_record(&mut self) -> Result<Option<Vec<CsvField>>, io::Error> {
+        let mut line = String::new();
+        if 0 == self.reader.read_line(&mut line)? {
+            return Ok(None);
+        }
+        if self.first {
+            self.first = false;
+            if line.starts_with('\u{feff}') {
+                line.remove(0);
+            }
+        }
+        let mut fields = Vec::new();
+        let mut field = CsvField::default();
+        let mut in_quotes = false;
+        loop {
+            let mut chars = line.chars().peekable();
+            while let Some(ch) = chars.next() {
+                if in_quotes {
+                    if '"' == ch {
+                        if Some(&'"') == chars.peek() {
+                            chars.next();
+                            field.value.push('"');
+                        } else {
+                            in_quotes = false;
+                        }
+                    } else {
+                        field.value.push(ch);
+                    }
+                } else if '"' == ch && field.value.is_empty() {
+                    in_quotes = true;
+                    field.quoted = true;
+                } else if self.delimiter == ch {
+                    fields.push(std::mem::take(&mut field));
+                } else if '\r' == ch || '\n' == ch {
+                    // line ending outside of quotes
+                } else {
+                    field.value.push(ch);
+                }
+            }
+            if !in_quotes {
+                break;
+            }
+            line.clear();
+            if 0 == self.reader.read_line(&mut line)? {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                    "Unexpected end of file inside a quoted field"));
+            }
+        }
+        fields.push(field);
+        Ok(Some(fields))
+    }
+}
+
+// Fields are written quoted, except for unquoted empty ones that stand for NULL.
+pub fn write_csv_record<W: Write>(writer: &mut W, fields: &[&CsvField], delimiter: char) -> Result<(), io::Error> {
+    let mut line = String::new();
+    for (idx, field) in fields.iter().enumerate() {
+        if idx > 0 {
+            line.push(delimiter);
+        }
+        if field.quoted || !field.value.is_empty() {
+            line.push('"');
+            line.push_str(&field.value.replace('"', "\"\""));
+            line.push('"');
+        }
+    }
+    line.push('\n');
+    writer.write_all(line.as_bytes())
+}
+
+// Delimiter that occurs most often in the header line, comma when there are none.
+pub fn detect_csv_delimiter(header_line: &str) -> char {
+    let mut res = ',';
+    let mut max = 0;
+    for delim in DELIMITERS.iter() {
+        let count = header_line.matches(*delim).count();
+        if count > max {
+            max = count;
+            res = *delim;
+        }
+    }
+    res
+}
+
+#[derive(Default, Clone)]
+pub struct CsvPreview {
+    pub delimiter: char,
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CsvPreview {
+    pub fn read(path: &Path, max_rows: usize) -> Result<Self, io::Error> {
+        let mut reader = BufReader::new(File::open(long_path(path))?);
+        let mut first_line = String::new();
+        reader.read_line(&mut first_line)?;
+        let delimiter = detect_csv_delimiter(&first_line);
+
+        let mut csv = CsvReader::new(BufReader::new(File::open(long_path(path))?), delimiter);
+        let header: Vec<String> = match csv.read_record()? {
+            Some(fields) => fields.into_iter().map(|fi| fi.value).collect(),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "CSV file is empty: {}", path.to_string_lossy())))
+        };
+        let mut rows = Vec::new();
+        while rows.len() < max_rows {
+            match csv.read_record()? {
+                Some(fields) => rows.push(fields.into_iter().map(|fi| fi.value).collect()),
+                None => break
+            }
+        }
+        Ok(Self {
+            delimiter,
+            header,
+            rows,
+        })
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Babelfish creates "{dbname}_dbo", "{dbname}_db_owner" and "{dbname}_guest"
+// roles, restore creates them with unquoted names, so only regular
+// identifiers are accepted.
+const MAX_DB_NAME_LEN: usize = 128;
+
+const RESERVED_DB_NAMES: [&str; 4] = ["master", "model", "msdb", "tempdb"];
+
+pub fn validate_db_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("DB name must be specified".to_string());
+    }
+    if name.chars().count() > MAX_DB_NAME_LEN {
+        return Err(format!("DB name must not be longer than {} characters", MAX_DB_NAME_LEN));
+    }
+    if let Some(ch) = name.chars().find(|ch| !(ch.is_ascii_alphanumeric() || '_' == *ch)) {
+        return Err(format!("DB name must contain only Latin letters, digits and '_', invalid character: '{}'", ch));
+    }
+    if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        return Err("DB name must not start with a digit".to_string());
+    }
+    let lower = name.to_lowercase();
+    if RESERVED_DB_NAMES.contains(&lower.as_str()) {
+        return Err(format!("DB name '{}' is reserved for a system database", name));
+    }
+    Ok(())
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+
+use winapi::shared::minwindef::LPARAM;
+use winapi::um::winuser::SendMessageW;
+use winapi::um::winuser::EM_REPLACESEL;
+use winapi::um::winuser::EM_SCROLLCARET;
+
+const NORMAL_COLOR: [u8; 3] = [0, 0, 0];
+const WARNING_COLOR: [u8; 3] = [176, 96, 0];
+const ERROR_COLOR: [u8; 3] = [192, 0, 0];
+
+// rich edit clamps positions to the end of the text
+const END_POS: u32 = i32::MAX as u32;
+
+fn line_color(line: &str) -> Option<[u8; 3]> {
+    if line.contains("ERROR") || line.contains("FATAL") || line.contains(": error:") {
+        Some(ERROR_COLOR)
+    } else if line.contains("WARNING") || line.contains(": warning:") {
+        Some(WARNING_COLOR)
+    } else {
+        None
+    }
+}
+
+fn set_color(details_box: &nwg::RichTextBox, color: [u8; 3]) {
+    details_box.set_char_format(&nwg::CharFormat {
+        text_color: Some(color),
+        ..Default::default()
+    });
+}
+
+// Appends progress lines to the end of the details box,
+// lines with errors and warnings are colorized
+pub fn append_highlighted(details_box: &nwg::RichTextBox, text: &str) {
+    let hwnd = match details_box.handle.hwnd() {
+        Some(hwnd) => hwnd,
+        None => return
+    };
+    details_box.set_selection(END_POS..END_POS);
+    let start = details_box.selection().start;
+    let wide: Vec<u16> = OsStr::new(text).encode_wide()
+        .chain(OsStr::new("\r\n").encode_wide())
+        .chain(Some(0))
+        .collect();
+    unsafe {
+        SendMessageW(hwnd, EM_REPLACESEL as u32, 0, wide.as_ptr() as LPARAM);
+    }
+
+    // new text inherits the color of the preceding line
+    details_box.set_selection(start..END_POS);
+    set_color(details_box, NORMAL_COLOR);
+    // rich edit stores line breaks as a single char
+    let mut pos = start;
+    for line in text.lines() {
+        let len = line.encode_utf16().count() as u32;
+        if let Some(color) = line_color(line) {
+            details_box.set_selection(pos..(pos + len));
+            set_color(details_box, color);
+        }
+        pos += len + 1;
+    }
+
+    details_box.set_selection(END_POS..END_POS);
+    unsafe {
+        SendMessageW(hwnd, EM_SCROLLCARET as u32, 0, 0);
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::ptr;
+
+use winapi::shared::minwindef::LPARAM;
+use winapi::shared::minwindef::WPARAM;
+use winapi::shared::windef::HWND;
+use winapi::shared::windef::POINTL;
+use winapi::um::winuser::InvalidateRect;
+use winapi::um::winuser::SendMessageW;
+use winapi::um::winuser::EM_CHARFROMPOS;
+use winapi::um::winuser::EM_GETFIRSTVISIBLELINE;
+use winapi::um::winuser::EM_LINEFROMCHAR;
+use winapi::um::winuser::EM_LINESCROLL;
+use winapi::um::winuser::EM_REPLACESEL;
+use winapi::um::winuser::EM_SCROLLCARET;
+use winapi::um::winuser::WM_SETREDRAW;
+
+use super::append_highlighted;
+use super::redact_secrets;
+use super::COMMAND_LINE_PREFIX;
+
+// lines displayed in the details box, older lines are removed from the control
+const MAX_SHOWN_LINES: usize = 2000;
+// trimming is done in chunks to not re-layout the control on every append
+const TRIM_CHUNK_LINES: usize = 500;
+// lines kept in memory for copying to clipboard
+const MAX_KEPT_LINES: usize = 200_000;
+
+// Bounded output log behind the details box, keeps the control small
+// and responsive with verbose output on big databases
+#[derive(Default)]
+pub struct DetailsLog {
+    kept: VecDeque<String>,
+    dropped: usize,
+    // lengths of the displayed lines in rich edit chars
+    shown: VecDeque<u32>,
+    // auto-scroll is paused to inspect earlier output
+    paused: bool,
+    // commands of the client tools run so far
+    commands: Vec<String>,
+}
+
+// Part of the text the user scrolled to, restored after appending
+struct ViewState {
+    selection: Range<u32>,
+    first_visible: u32,
+}
+
+impl DetailsLog {
+    pub fn append(&mut self, details_box: &nwg::RichTextBox, text: &str) {
+        let hwnd = match details_box.handle.hwnd() {
+            Some(hwnd) => hwnd,
+            None => return
+        };
+        let view = if self.paused {
+            Some(Self::save_view(hwnd, details_box))
+        } else {
+            None
+        };
+        let text = redact_secrets(text);
+        append_highlighted(details_box, &text);
+        for line in text.lines() {
+            if let Some(cmd) = line.strip_prefix(COMMAND_LINE_PREFIX) {
+                self.commands.push(cmd.to_string());
+            }
+            self.shown.push_back(line.encode_utf16().count() as u32 + 1);
+            self.kept.push_back(line.to_string());
+        }
+        while self.kept.len() > MAX_KEPT_LINES {
+            self.kept.pop_front();
+            self.dropped += 1;
+        }
+        let mut remove_len = 0;
+        if self.shown.len() > MAX_SHOWN_LINES + TRIM_CHUNK_LINES {
+            while self.shown.len() > MAX_SHOWN_LINES {
+                remove_len += self.shown.pop_front().unwrap_or(0);
+            }
+            Self::remove_leading(hwnd, details_box, remove_len);
+        }
+        if let Some(view) = view {
+            Self::restore_view(hwnd, details_box, view, remove_len);
+        }
+    }
+
+    pub fn set_paused(&mut self, details_box: &nwg::RichTextBox, paused: bool) {
+        self.paused = paused;
+        if !paused {
+            let end = i32::MAX as u32;
+            details_box.set_selection(end..end);
+            if let Some(hwnd) = details_box.handle.hwnd() {
+                unsafe {
+                    SendMessageW(hwnd, EM_SCROLLCARET as u32, 0, 0);
+                }
+            }
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut lines: Vec<String> = Vec::with_capacity(self.kept.len() + 1);
+        if self.dropped > 0 {
+            lines.push(format!("({} earlier lines omitted)", self.dropped));
+        }
+        lines.extend(self.kept.iter().cloned());
+        lines.join("\r\n")
+    }
+
+    pub fn has_commands(&self) -> bool {
+        !self.commands.is_empty()
+    }
+
+    pub fn commands_text(&self) -> String {
+        self.commands.join("\r\n")
+    }
+
+    fn remove_leading(hwnd: HWND, details_box: &nwg::RichTextBox, len: u32) {
+        let empty: [u16; 1] = [0];
+        let end = i32::MAX as u32;
+        details_box.set_selection(0..len);
+        unsafe {
+            SendMessageW(hwnd, EM_REPLACESEL as u32, 0, empty.as_ptr() as LPARAM);
+        }
+        details_box.set_selection(end..end);
+    }
+    fn save_view(hwnd: HWND, details_box: &nwg::RichTextBox) -> ViewState {
+        // top-left corner of the client area
+        let pt = POINTL { x: 1, y: 1 };
+        let first_visible = unsafe {
+            // no redraws until the view is restored
+            SendMessageW(hwnd, WM_SETREDRAW, 0, 0);
+            SendMessageW(hwnd, EM_CHARFROMPOS as u32, 0, &pt as *const POINTL as LPARAM)
+        };
+        ViewState {
+            selection: details_box.selection(),
+            first_visible: first_visible as u32,
+        }
+    }
+
+    fn restore_view(hwnd: HWND, details_box: &nwg::RichTextBox, view: ViewState, removed_len: u32) {
+        let shift = |pos: u32| pos.saturating_sub(removed_len);
+        details_box.set_selection(shift(view.selection.start)..shift(view.selection.end));
+        unsafe {
+            let target = SendMessageW(hwnd, EM_LINEFROMCHAR as u32, shift(view.first_visible) as WPARAM, 0);
+            let current = SendMessageW(hwnd, EM_GETFIRSTVISIBLELINE as u32, 0, 0);
+            SendMessageW(hwnd, EM_LINESCROLL as u32, 0, (target - current) as LPARAM);
+            SendMessageW(hwnd, WM_SETREDRAW, 1, 0);
+            InvalidateRect(hwnd, ptr::null(), 1);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ffi::OsStr;
+use std::io;
+use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+
+use winapi::um::fileapi::GetDiskFreeSpaceExW;
+use winapi::um::winnt::ULARGE_INTEGER;
+
+use super::run_history::format_size;
+
+// Space available to the current user, quotas are taken into account
+pub fn free_disk_space(dir: &Path) -> Result<u64, io::Error> {
+    let wide: Vec<u16> = OsStr::new(dir).encode_wide().chain(Some(0)).collect();
+    let mut available: ULARGE_INTEGER = unsafe { mem::zeroed() };
+    let success = unsafe {
+        GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, ptr::null_mut(), ptr::null_mut())
+    };
+    if success == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { *available.QuadPart() })
+}
+
+pub fn format_disk_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 * 1024 {
+        format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    } else {
+        format_size(bytes)
+    }
+}
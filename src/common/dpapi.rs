@@ -0,0 +1,81 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::ptr;
+use std::slice;
+
+use winapi::um::dpapi::CryptProtectData;
+use winapi::um::dpapi::CryptUnprotectData;
+use winapi::um::dpapi::CRYPTPROTECT_UI_FORBIDDEN;
+use winapi::um::winbase::LocalFree;
+use winapi::um::wincrypt::DATA_BLOB;
+
+// Secrets stored in settings file are encrypted for the current Windows user
+// and are kept as hex strings
+
+pub fn protect_string(plain: &str) -> Result<String, io::Error> {
+    let mut bytes = plain.as_bytes().to_vec();
+    let encrypted = crypt_blob(&mut bytes, true)?;
+    Ok(encrypted.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+pub fn unprotect_string(hex: &str) -> Result<String, io::Error> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid protected value"));
+    }
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    for i in (0..hex.len()).step_by(2) {
+        match u8::from_str_radix(&hex[i..i + 2], 16) {
+            Ok(b) => bytes.push(b),
+            Err(_) => return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid protected value"))
+        }
+    }
+    let decrypted = crypt_blob(&mut bytes, false)?;
+    match String::from_utf8(decrypted) {
+        Ok(st) => Ok(st),
+        Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid protected value"))
+    }
+}
+
+fn crypt_blob(data: &mut [u8], protect: bool) -> Result<Vec<u8>, io::Error> {
+    let mut input = DATA_BLOB {
+        cbData: data.len() as u32,
+        pbData: data.as_mut_ptr()
+    };
+    let mut output = DATA_BLOB {
+        cbData: 0,
+        pbData: ptr::null_mut()
+    };
+    let success = unsafe {
+        if protect {
+            CryptProtectData(&mut input, ptr::null(), ptr::null_mut(), ptr::null_mut(),
+                ptr::null_mut(), CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+        } else {
+            CryptUnprotectData(&mut input, ptr::null_mut(), ptr::null_mut(), ptr::null_mut(),
+                ptr::null_mut(), CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+        }
+    };
+    if success == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let res = unsafe {
+        let res = slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        LocalFree(output.pbData as _);
+        res
+    };
+    Ok(res)
+}
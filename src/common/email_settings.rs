@@ -0,0 +1,129 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::*;
+use super::dpapi;
+use super::run_history::format_size;
+use super::smtp_client::SmtpClient;
+use super::smtp_client::SmtpMessage;
+
+pub const DEFAULT_SMTP_PORT: u16 = 587;
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailSettings {
+    pub enabled: bool,
+    pub only_on_failure: bool,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub security: SmtpSecurity,
+    pub username: String,
+    // encrypted with DPAPI for the current user
+    pub password_protected: String,
+    pub from: String,
+    // comma or semicolon separated list
+    pub to: String,
+}
+
+impl EmailSettings {
+    pub fn password(&self) -> Result<String, io::Error> {
+        if self.password_protected.is_empty() {
+            return Ok(String::new());
+        }
+        dpapi::unprotect_string(&self.password_protected).map_err(|e| io::Error::new(e.kind(), format!(
+            "Error decrypting SMTP password, message: {}", e)))
+    }
+
+    pub fn set_password(&mut self, password: &str) -> Result<(), io::Error> {
+        self.password_protected = if password.is_empty() {
+            String::new()
+        } else {
+            dpapi::protect_string(password)?
+        };
+        Ok(())
+    }
+
+    pub fn recipients(&self) -> Vec<String> {
+        self.to.split(|c| c == ',' || c == ';')
+            .map(|addr| addr.trim().to_string())
+            .filter(|addr| !addr.is_empty())
+            .collect()
+    }
+
+    pub fn should_notify(&self, success: bool) -> bool {
+        self.enabled && (!self.only_on_failure || !success)
+    }
+
+    pub fn send(&self, subject: &str, body: &str) -> Result<(), io::Error> {
+        let to = self.recipients();
+        if self.smtp_host.is_empty() || self.from.is_empty() || to.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                "SMTP server, sender and recipient addresses must be specified"));
+        }
+        let port = if self.smtp_port > 0 {
+            self.smtp_port
+        } else {
+            DEFAULT_SMTP_PORT
+        };
+        let mut client = SmtpClient::connect(&self.smtp_host, port, self.security)?;
+        if !self.username.is_empty() {
+            client.login(&self.username, &self.password()?)?;
+        }
+        client.send(&SmtpMessage {
+            from: &self.from,
+            to,
+            subject,
+            body
+        })?;
+        client.quit()
+    }
+
+    pub fn send_backup_summary(&self, record: &RunRecord, archive_path: &Path, error: &str, log_tail: &str) -> Result<(), io::Error> {
+        let status = if record.success { "completed successfully" } else { "FAILED" };
+        let subject = format!("WiltonDB backup of DB '{}' {}", record.dbname, status);
+        let mut body = String::new();
+        body.push_str(&format!("DB name: {}\r\n", record.dbname));
+        body.push_str(&format!("Result: {}\r\n", status));
+        body.push_str(&format!("Duration: {}\r\n", format_duration(record.duration_secs)));
+        body.push_str(&format!("Archive: {}\r\n", archive_path.to_string_lossy()));
+        if record.size_bytes > 0 {
+            body.push_str(&format!("Size: {}\r\n", format_size(record.size_bytes)));
+        }
+        if !error.is_empty() {
+            body.push_str(&format!("\r\nError:\r\n{}\r\n", error));
+        }
+        if !log_tail.is_empty() {
+            body.push_str(&format!("\r\nLast log lines:\r\n{}\r\n", log_tail));
+        }
+        self.send(&subject, &body)
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{} sec", secs)
+    } else if secs < 3600 {
+        format!("{} min {} sec", secs / 60, secs % 60)
+    } else {
+        format!("{} h {} min", secs / 3600, (secs % 3600) / 60)
+    }
+}
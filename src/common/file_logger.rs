@@ -0,0 +1,149 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use log::LevelFilter;
+use log::Log;
+use log::Metadata;
+use log::Record;
+
+use super::*;
+
+const LOG_FILE_PREFIX: &str = "wdb_backup_";
+const LOG_FILE_EXT: &str = "log";
+const MAX_LOG_FILES: usize = 14;
+
+struct LogFile {
+    date: String,
+    file: File,
+}
+
+// Writes log records into daily files, only the most recent files are kept
+struct FileLogger {
+    dir: PathBuf,
+    current: Mutex<Option<LogFile>>,
+}
+
+impl FileLogger {
+    fn write_line(&self, line: &str) -> Result<(), io::Error> {
+        let today = chrono::Local::now().format("%Y%m%d").to_string();
+        let mut current = match self.current.lock() {
+            Ok(current) => current,
+            Err(_) => return Err(io::Error::new(io::ErrorKind::Other, "Log file lock poisoned"))
+        };
+        let reopen = match &*current {
+            Some(lf) => lf.date != today,
+            None => true
+        };
+        if reopen {
+            let path = self.dir.join(format!("{}{}.{}", LOG_FILE_PREFIX, today, LOG_FILE_EXT));
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            *current = Some(LogFile {
+                date: today,
+                file
+            });
+            remove_old_log_files();
+        }
+        if let Some(lf) = current.as_mut() {
+            lf.file.write_all(line.as_bytes())?;
+            lf.file.write_all(b"\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!("{} [{}] {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"), record.level(),
+            redact_secrets(&record.args().to_string()));
+        let _ = self.write_line(&line);
+    }
+
+    fn flush(&self) {
+        if let Ok(mut current) = self.current.lock() {
+            if let Some(lf) = current.as_mut() {
+                let _ = lf.file.flush();
+            }
+        }
+    }
+}
+
+pub fn logs_dir() -> Result<PathBuf, io::Error> {
+    Ok(AppSettings::app_data_dir()?.join("logs"))
+}
+
+// newest files first
+pub fn list_log_files() -> Result<Vec<PathBuf>, io::Error> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(logs_dir()?)? {
+        let path = entry?.path();
+        let is_log = match (path.file_name(), path.extension()) {
+            (Some(name), Some(ext)) => name.to_string_lossy().starts_with(LOG_FILE_PREFIX) && ext == LOG_FILE_EXT,
+            _ => false
+        };
+        if is_log && path.is_file() {
+            files.push(path);
+        }
+    }
+    // file names contain dates, so name order is chronological
+    files.sort();
+    files.reverse();
+    Ok(files)
+}
+
+pub fn init_file_logging() -> Result<(), io::Error> {
+    let dir = logs_dir()?;
+    fs::create_dir_all(&dir)?;
+    let logger = FileLogger {
+        dir,
+        current: Mutex::new(None)
+    };
+    if log::set_boxed_logger(Box::new(logger)).is_err() {
+        // already initialized
+        return Ok(());
+    }
+    log::set_max_level(LevelFilter::Info);
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        log::error!("Internal error: {}", info);
+        default_hook(info);
+    }));
+    Ok(())
+}
+
+fn remove_old_log_files() {
+    if let Ok(files) = list_log_files() {
+        for path in files.iter().skip(MAX_LOG_FILES) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
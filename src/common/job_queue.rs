@@ -0,0 +1,202 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+// Returns a message shown in the queue view on success or an error message
+pub type JobTask = Box<dyn FnOnce() -> Result<String, String> + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pending => "Pending",
+            Self::Running => "Running",
+            Self::Completed => "Completed",
+            Self::Failed => "Failed",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, Self::Pending | Self::Running)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedJob {
+    pub id: u64,
+    pub name: String,
+    pub status: JobStatus,
+    pub details: String,
+    pub duration: Duration,
+}
+
+impl QueuedJob {
+    pub fn duration_label(&self) -> String {
+        if !self.status.is_finished() || JobStatus::Cancelled == self.status {
+            return String::new();
+        }
+        let secs = self.duration.as_secs();
+        if secs < 60 {
+            format!("{} sec", secs)
+        } else {
+            format!("{} min {} sec", secs / 60, secs % 60)
+        }
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    jobs: Vec<QueuedJob>,
+    last_id: u64,
+}
+
+// Jobs are run one at a time in order of submission by a single worker
+// thread, listener is called from the worker after each status change.
+// Handle is cheap to clone and can be passed to dialogs.
+#[derive(Default, Clone)]
+pub struct JobQueue {
+    state: Arc<Mutex<QueueState>>,
+    sender: Option<mpsc::Sender<(u64, JobTask)>>,
+}
+
+impl JobQueue {
+    pub fn new<L: Fn() + Send + 'static>(listener: L) -> Self {
+        let state: Arc<Mutex<QueueState>> = Arc::new(Mutex::new(QueueState::default()));
+        let (sender, receiver) = mpsc::channel::<(u64, JobTask)>();
+        let worker_state = state.clone();
+        thread::spawn(move || {
+            // ends when all handles are dropped
+            for (id, task) in receiver {
+                if !Self::update(&worker_state, id, |job| {
+                    if JobStatus::Pending != job.status {
+                        return false;
+                    }
+                    job.status = JobStatus::Running;
+                    true
+                }) {
+                    continue;
+                }
+                listener();
+                let start = Instant::now();
+                let res = task();
+                Self::update(&worker_state, id, |job| {
+                    job.duration = start.elapsed();
+                    match res {
+                        Ok(msg) => {
+                            job.status = JobStatus::Completed;
+                            job.details = msg;
+                        },
+                        Err(e) => {
+                            job.status = JobStatus::Failed;
+                            job.details = e;
+                        }
+                    };
+                    true
+                });
+                listener();
+            }
+        });
+        Self {
+            state,
+            sender: Some(sender),
+        }
+    }
+
+    pub fn submit(&self, name: &str, task: JobTask) -> Result<u64, io::Error> {
+        let sender = match &self.sender {
+            Some(sender) => sender,
+            None => return Err(io::Error::new(io::ErrorKind::Other, "Job queue is not started"))
+        };
+        let id = match self.state.lock() {
+            Ok(mut state) => {
+                state.last_id += 1;
+                let id = state.last_id;
+                state.jobs.push(QueuedJob {
+                    id,
+                    name: name.to_string(),
+                    status: JobStatus::Pending,
+                    details: String::new(),
+                    duration: Duration::from_secs(0),
+                });
+                id
+            },
+            Err(_) => return Err(io::Error::new(io::ErrorKind::Other, "Job queue is not available"))
+        };
+        if sender.send((id, task)).is_err() {
+            return Err(io::Error::new(io::ErrorKind::Other, "Job queue worker is not running"));
+        }
+        Ok(id)
+    }
+
+    // only pending jobs can be cancelled, running job is left to complete
+    pub fn cancel(&self, id: u64) -> bool {
+        Self::update(&self.state, id, |job| {
+            if JobStatus::Pending != job.status {
+                return false;
+            }
+            job.status = JobStatus::Cancelled;
+            true
+        })
+    }
+
+    pub fn clear_finished(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.jobs.retain(|job| !job.status.is_finished());
+        }
+    }
+
+    pub fn jobs(&self) -> Vec<QueuedJob> {
+        match self.state.lock() {
+            Ok(state) => state.jobs.clone(),
+            Err(_) => Vec::new()
+        }
+    }
+
+    // names of pending and running jobs
+    pub fn active_jobs(&self) -> Vec<String> {
+        self.jobs().into_iter()
+            .filter(|job| !job.status.is_finished())
+            .map(|job| job.name)
+            .collect()
+    }
+
+    fn update<F: FnOnce(&mut QueuedJob) -> bool>(state: &Arc<Mutex<QueueState>>, id: u64, fun: F) -> bool {
+        let mut state = match state.lock() {
+            Ok(state) => state,
+            Err(_) => return false
+        };
+        match state.jobs.iter_mut().find(|job| job.id == id) {
+            Some(job) => fun(job),
+            None => false
+        }
+    }
+}
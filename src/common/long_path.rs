@@ -0,0 +1,63 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+use std::ffi::OsString;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::Prefix;
+
+// Extended-length form of a path: "\\?\C:\dir" or "\\?\UNC\server\share\dir",
+// such paths are not limited to MAX_PATH (260) characters. Windows does not
+// normalize them, so relative paths and "." and ".." components are resolved here.
+//
+// Only used for file operations done by this app, client tools get the
+// paths as entered by user.
+pub fn long_path(path: &Path) -> PathBuf {
+    let abs = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        match env::current_dir() {
+            Ok(dir) => dir.join(path),
+            Err(_) => return path.to_path_buf()
+        }
+    };
+    let mut res = PathBuf::new();
+    for comp in abs.components() {
+        match comp {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(letter) => res.push(format!("\\\\?\\{}:", letter as char)),
+                Prefix::UNC(server, share) => {
+                    let mut st = OsString::from("\\\\?\\UNC\\");
+                    st.push(server);
+                    st.push("\\");
+                    st.push(share);
+                    res.push(st);
+                },
+                // already verbatim or a device path
+                _ => return abs
+            },
+            Component::RootDir => res.push("\\"),
+            Component::CurDir => { },
+            Component::ParentDir => {
+                res.pop();
+            },
+            Component::Normal(name) => res.push(name),
+        }
+    }
+    res
+}
@@ -1,22 +1,146 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-pub mod labels;
-mod pg_access_error;
-mod pg_conn_config;
-
-pub use pg_access_error::PgAccessError;
-pub use pg_conn_config::PgConnConfig;
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod access_probe;
+mod app_settings;
+mod babelfish_check;
+mod backup_catalog;
+mod backup_format;
+mod backup_job;
+mod backup_manifest;
+mod backup_mode;
+mod conn_profile;
+mod cron_schedule;
+mod csv_reader;
+mod db_name;
+mod details_highlight;
+mod details_log;
+mod disk_space;
+mod dpapi;
+mod email_settings;
+mod file_logger;
+mod job_queue;
+pub mod labels;
+mod long_path;
+mod network_share;
+mod password_env;
+mod pg_access_error;
+mod pg_conn_config;
+mod pg_conn_uri;
+mod pg_service;
+mod pg_tool;
+mod progress_buffer;
+mod progress_sender;
+mod recycle_bin;
+mod redact;
+mod retention_policy;
+mod retry_policy;
+mod run_history;
+mod secret_string;
+mod server_info;
+mod sha256;
+mod single_instance;
+mod smtp_client;
+mod ssh_tunnel;
+mod streaming_zip;
+mod taskbar_progress;
+mod throttle;
+mod toast;
+mod volume_set;
+
+pub use access_probe::probe_dir_writable;
+pub use access_probe::probe_path_readable;
+pub use app_settings::AppSettings;
+pub use babelfish_check::babelfish_catalog_exists;
+pub use babelfish_check::babelfish_enabled;
+pub use babelfish_check::babelfish_settings;
+pub use backup_catalog::delete_backup;
+pub use backup_catalog::scan_backup_dir;
+pub use backup_catalog::select_older_than;
+pub use backup_catalog::CatalogEntry;
+pub use backup_format::BackupFormat;
+pub use backup_job::BackupJob;
+pub use backup_manifest::BackupManifest;
+pub use backup_manifest::GLOBALS_FILE_NAME;
+pub use backup_manifest::table_key;
+pub use backup_manifest::TableRowCount;
+pub use backup_manifest::TableStamp;
+pub use backup_mode::BackupMode;
+pub use conn_profile::ConnProfile;
+pub use cron_schedule::CronSchedule;
+pub use csv_reader::CsvField;
+pub use csv_reader::CsvPreview;
+pub use csv_reader::CsvReader;
+pub use csv_reader::write_csv_record;
+pub use db_name::validate_db_name;
+pub use details_highlight::append_highlighted;
+pub use details_log::DetailsLog;
+pub use disk_space::format_disk_size;
+pub use disk_space::free_disk_space;
+pub use email_settings::EmailSettings;
+pub use email_settings::DEFAULT_SMTP_PORT;
+pub use file_logger::init_file_logging;
+pub use file_logger::list_log_files;
+pub use file_logger::logs_dir;
+pub use job_queue::JobQueue;
+pub use job_queue::JobStatus;
+pub use job_queue::QueuedJob;
+pub use long_path::long_path;
+pub use network_share::connect_share;
+pub use network_share::share_accessible;
+pub use network_share::unc_share_root;
+pub use password_env::scrub_password_env;
+pub use pg_access_error::PgAccessError;
+pub use pg_conn_config::PgConnConfig;
+pub use pg_service::PgService;
+pub use pg_tool::pg_tool_cmd;
+pub use pg_tool::COMMAND_LINE_PREFIX;
+pub use progress_buffer::ProgressBuffer;
+pub use progress_sender::LogTail;
+pub use progress_sender::ProgressSender;
+pub use progress_sender::SilentProgress;
+pub use redact::redact_secrets;
+pub use retention_policy::RetentionMode;
+pub use retention_policy::RetentionPolicy;
+pub use retry_policy::RetryPolicy;
+pub use retry_policy::TransientError;
+pub use run_history::RunForecast;
+pub use run_history::RunKind;
+pub use run_history::RunRecord;
+pub use secret_string::SecretString;
+pub use server_info::ServerInfo;
+pub use sha256::Sha256;
+pub use sha256::sha256_file_hex;
+pub use sha256::sha256_hex;
+pub use single_instance::bring_to_foreground;
+pub use single_instance::forward_to_running_instance;
+pub use single_instance::listen_for_instances;
+pub use single_instance::SingleInstance;
+pub use smtp_client::SmtpSecurity;
+pub use ssh_tunnel::close_ssh_tunnels;
+pub use ssh_tunnel::ssh_tunnel_port;
+pub use streaming_zip::StreamingZip;
+pub use taskbar_progress::TaskbarProgress;
+pub use throttle::throttle_presets;
+pub use toast::show_toast;
+pub use toast::window_in_background;
+pub use volume_set::join_volumes;
+pub use volume_set::remove_volumes;
+pub use volume_set::split_into_volumes;
+pub use volume_set::volume_path;
+pub use volume_set::volume_set_base;
+pub use volume_set::volume_set_size;
+pub use volume_set::volume_size_presets;
@@ -0,0 +1,80 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ffi::OsStr;
+use std::io;
+use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+
+use winapi::shared::winerror::NO_ERROR;
+use winapi::um::winnetwk::WNetAddConnection2W;
+use winapi::um::winnetwk::NETRESOURCEW;
+use winapi::um::winnetwk::RESOURCETYPE_DISK;
+use zeroize::Zeroizing;
+
+// "\\server\share" part of a UNC path, None for local and verbatim paths
+pub fn unc_share_root(path: &str) -> Option<String> {
+    let path = path.trim().replace('/', "\\");
+    let rest = path.strip_prefix("\\\\")?;
+    if rest.starts_with("?\\") || rest.starts_with(".\\") {
+        return None;
+    }
+    let mut parts = rest.split('\\');
+    let server = parts.next().filter(|st| !st.is_empty())?;
+    let share = parts.next().filter(|st| !st.is_empty())?;
+    Some(format!("\\\\{}\\{}", server, share))
+}
+
+// share is not accessible when it requires credentials that were not
+// entered in this logon session
+pub fn share_accessible(share: &str) -> bool {
+    Path::new(&format!("{}\\", share)).is_dir()
+}
+
+// Connection is not mapped to a drive letter and is not restored on next
+// logon, user name and password may be empty to use the credentials of
+// the current user, empty password string would be used as a blank password.
+pub fn connect_share(share: &str, username: &str, password: &str) -> Result<(), io::Error> {
+    let mut remote = to_wide(share);
+    let user = to_wide(username);
+    let pwd = Zeroizing::new(to_wide(password));
+    let mut nr: NETRESOURCEW = unsafe { mem::zeroed() };
+    nr.dwType = RESOURCETYPE_DISK;
+    nr.lpRemoteName = remote.as_mut_ptr();
+    let user_ptr = if username.is_empty() {
+        ptr::null()
+    } else {
+        user.as_ptr()
+    };
+    let pwd_ptr = if password.is_empty() {
+        ptr::null()
+    } else {
+        pwd.as_ptr()
+    };
+    let res = unsafe {
+        WNetAddConnection2W(&mut nr, pwd_ptr, user_ptr, 0)
+    };
+    if NO_ERROR != res {
+        return Err(io::Error::from_raw_os_error(res as i32));
+    }
+    Ok(())
+}
+
+fn to_wide(st: &str) -> Vec<u16> {
+    OsStr::new(st).encode_wide().chain(Some(0)).collect()
+}
@@ -0,0 +1,115 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+
+use super::*;
+
+const PGPASSWORD: &str = "PGPASSWORD";
+
+// Password must only be passed to pg_dump/pg_restore using per-command
+// environment, any process-wide variable (inherited from the parent
+// process) is removed on startup so it cannot leak into crash dumps or
+// into other child processes.
+pub fn scrub_password_env() -> Option<SecretString> {
+    let res = env::var(PGPASSWORD).ok().map(SecretString::from);
+    env::remove_var(PGPASSWORD);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    // process environment is shared by the tests running in parallel
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    struct CollectProgress {
+        lines: RefCell<Vec<String>>,
+    }
+
+    impl ProgressSender for CollectProgress {
+        fn send_value<T: Into<String>>(&self, value: T) {
+            self.lines.borrow_mut().push(value.into());
+        }
+    }
+
+    fn test_config(password: &str) -> PgConnConfig {
+        PgConnConfig {
+            hostname: "localhost".to_string(),
+            port: 5432,
+            username: "wilton".to_string(),
+            password: SecretString::from(password),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scrub_returns_and_removes_password() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        env::set_var(PGPASSWORD, "inherited_secret");
+        let res = scrub_password_env();
+        assert_eq!(res.as_ref().map(|pwd| pwd.expose()), Some("inherited_secret"));
+        assert!(env::var_os(PGPASSWORD).is_none());
+        assert!(scrub_password_env().is_none());
+    }
+
+    #[test]
+    fn process_env_clean_after_job() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        scrub_password_env();
+        // password is passed the same way as to pg_dump and pg_restore
+        let out = duct::cmd!("cmd", "/C", "echo %PGPASSWORD%")
+            .env(PGPASSWORD, "child_secret")
+            .read()
+            .unwrap();
+        assert_eq!(out.trim(), "child_secret");
+        assert!(env::var_os(PGPASSWORD).is_none());
+    }
+
+    #[test]
+    fn client_tool_env_keeps_process_env_clean() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        scrub_password_env();
+        let tool_env = test_config("tool_env_secret").client_tool_env().unwrap();
+        let found = tool_env.iter().find(|(name, _)| PGPASSWORD == *name);
+        assert_eq!(found.map(|(_, value)| value.as_str()), Some("tool_env_secret"));
+        assert!(env::var_os(PGPASSWORD).is_none());
+    }
+
+    #[test]
+    fn pg_tool_cmd_sets_password_only_for_child() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        scrub_password_env();
+        let progress = CollectProgress {
+            lines: RefCell::new(Vec::new()),
+        };
+        let cmd = pg_tool_cmd(&progress, &test_config("child_secret"), "pg_dump", vec!("--version")).unwrap();
+        let found = cmd.env().iter().find(|(name, _)| PGPASSWORD == *name);
+        assert_eq!(found.map(|(_, value)| value.as_str()), Some("child_secret"));
+        let cmd_st = format!("{:?}", cmd);
+        assert!(cmd_st.contains("PGPASSWORD"));
+        assert!(!cmd_st.contains("child_secret"));
+        assert!(env::var_os(PGPASSWORD).is_none());
+        let lines = progress.lines.borrow();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with(COMMAND_LINE_PREFIX));
+        assert!(!lines[0].contains("child_secret"));
+    }
+}
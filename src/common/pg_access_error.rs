@@ -1,86 +1,124 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use std::fmt;
-use std::io;
-
-#[derive(Debug)]
-pub struct PgAccessError {
-    message: String
-}
-
-impl PgAccessError {
-    pub fn new<E: fmt::Display>(e: &E) -> Self {
-        Self {
-            message: format!("{}", e)
-        }
-    }
-
-    pub fn from_string(message: String) -> Self {
-        Self {
-            message
-        }
-    }
-
-    pub fn pgpass_not_found() -> Self {
-        Self {
-            message: "pgpass file not found on path: '%APPDATA%/postgresql/pgpass.conf'".to_string()
-        }
-    }
-
-    pub fn pgpass_error(path: &str, line_no: u32) -> Self {
-        Self {
-            message: format!(
-                "Error reading password from pgpass file on path: [{}], line number: [{}]", path, line_no)
-        }
-    }
-}
-
-impl fmt::Display for PgAccessError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
-
-impl From<postgres::Error> for PgAccessError {
-    fn from(value: postgres::Error) -> Self {
-        Self::new(&value)
-    }
-}
-
-impl From<native_tls::Error> for PgAccessError {
-    fn from(value: native_tls::Error) -> Self {
-        Self::new(&value)
-    }
-}
-
-impl From<io::Error> for PgAccessError {
-    fn from(value: io::Error) -> Self {
-        Self::new(&value)
-    }
-}
-
-impl From<&str> for PgAccessError {
-    fn from(value: &str) -> Self {
-        Self::new(&value)
-    }
-}
-
-impl From<std::string::FromUtf8Error> for PgAccessError {
-    fn from(value: std::string::FromUtf8Error) -> Self {
-        Self::new(&value)
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use super::*;
+
+#[derive(Debug)]
+pub struct PgAccessError {
+    message: String,
+    transient: bool,
+}
+
+impl PgAccessError {
+    pub fn new<E: fmt::Display>(e: &E) -> Self {
+        Self {
+            message: format!("{}", e),
+            transient: false,
+        }
+    }
+
+    pub fn from_string(message: String) -> Self {
+        Self {
+            message,
+            transient: false,
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.transient
+    }
+
+    pub fn pgpass_not_found() -> Self {
+        Self {
+            message: "pgpass file not found on path: '%APPDATA%/postgresql/pgpass.conf'".to_string(),
+            transient: false,
+        }
+    }
+
+    pub fn pgpass_error(path: &str, line_no: u32) -> Self {
+        Self {
+            message: format!(
+                "Error reading password from pgpass file on path: [{}], line number: [{}]", path, line_no),
+            transient: false,
+        }
+    }
+}
+
+impl fmt::Display for PgAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+// Connection failures and server shutdowns are considered transient,
+// errors reported by the server for the query itself are not.
+impl From<postgres::Error> for PgAccessError {
+    fn from(value: postgres::Error) -> Self {
+        let transient = if value.is_closed() {
+            true
+        } else if let Some(state) = value.code() {
+            let code = state.code();
+            // connection_exception, operator_intervention shutdowns, too_many_connections
+            code.starts_with("08") || code.starts_with("57P") || "53300" == code
+        } else {
+            match value.source().and_then(|src| src.downcast_ref::<io::Error>()) {
+                Some(e) => e.is_transient(),
+                None => false
+            }
+        };
+        let mut message = format!("{}", value);
+        // GSSAPI and SSPI auth requests are rejected by the client library
+        if message.contains("unsupported authentication method") {
+            message = format!("{}, integrated Windows (SSPI/GSSAPI) authentication is not supported, \
+                use password authentication (scram-sha-256 or md5 in pg_hba.conf) for this login", message);
+        }
+        Self {
+            message,
+            transient,
+        }
+    }
+}
+
+impl From<native_tls::Error> for PgAccessError {
+    fn from(value: native_tls::Error) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<io::Error> for PgAccessError {
+    fn from(value: io::Error) -> Self {
+        Self {
+            message: format!("{}", value),
+            transient: value.is_transient(),
+        }
+    }
+}
+
+impl From<&str> for PgAccessError {
+    fn from(value: &str) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for PgAccessError {
+    fn from(value: std::string::FromUtf8Error) -> Self {
+        Self::new(&value)
+    }
+}
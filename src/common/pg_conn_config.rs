@@ -1,209 +1,349 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::time::Duration;
-
-use native_tls::TlsConnector;
-use postgres::Client;
-use postgres::Config;
-use postgres::NoTls;
-use postgres_native_tls::MakeTlsConnector;
-
-use super::*;
-
-#[derive(Default, Debug, Clone)]
-pub struct PgConnConfig {
-    pub hostname: String,
-    pub port: u16,
-    pub username: String,
-    pub password: String,
-    pub use_pgpass_file: bool,
-    pub connect_db: String,
-    pub enable_tls: bool,
-    pub accept_invalid_tls: bool,
-}
-
-impl PgConnConfig {
-    pub fn open_connection_default(&self) -> Result<Client, PgAccessError> {
-       self.open_connection(&self.connect_db)
-    }
-
-    pub fn open_connection_to_db(&self, dbname: &str) -> Result<Client, PgAccessError> {
-        self.open_connection(dbname)
-    }
-
-    fn open_connection(&self, dbname: &str) -> Result<Client, PgAccessError> {
-        let pwd = self.resolve_password()?;
-        let conf = Config::new()
-            .host(&self.hostname)
-            .port(self.port)
-            .user(&self.username)
-            .password(&pwd)
-            .dbname(dbname)
-            .connect_timeout(Duration::from_secs(10))
-            .clone();
-
-        let res = if self.enable_tls {
-            let connector = TlsConnector::builder()
-                .danger_accept_invalid_certs(self.accept_invalid_tls)
-                .danger_accept_invalid_hostnames(self.accept_invalid_tls)
-                .build()?;
-            let tls = MakeTlsConnector::new(connector);
-            conf.connect(tls)?
-        } else {
-            conf.connect(NoTls)?
-        };
-
-        Ok(res)
-    }
-
-    fn resolve_password(&self) -> Result<String, PgAccessError> {
-        if self.use_pgpass_file {
-            let pgpass_path = PgConnConfig::resolve_pgpass_path()?;
-            let file = File::open(&pgpass_path)?;
-            let lines_it = BufReader::new(file).lines();
-            let mut line_no = 1;
-            for line_res in lines_it {
-                let line = line_res?;
-                let matched_opt = self.match_pgpass_line(&pgpass_path, &line, line_no)?;
-                if let Some(pwd) = matched_opt {
-                    return Ok(pwd)
-                }
-                line_no += 1;
-            }
-        }
-        Ok(self.password.clone())
-    }
-
-    fn resolve_pgpass_path() -> Result<String, PgAccessError> {
-        if let Ok(path_from_env) = std::env::var("PGPASSFILE") {
-            Ok(path_from_env)
-        } else if let Ok(appdir) = std::env::var("APPDATA") {
-            Ok(format!("{}\\postgresql\\pgpass.conf", appdir))
-        } else {
-            Err(PgAccessError::pgpass_not_found())
-        }
-    }
-
-    fn match_pgpass_line(&self, path: &str, line: &str, line_no: u32) -> Result<Option<String>, PgAccessError> {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with("#") {
-            return Ok(None);
-        }
-        let entry = PassfileEntry::new(path, trimmed.as_bytes(), line_no)?;
-        if entry.hostname.accepts(&self.hostname)
-            && entry.port.accepts(&self.port.to_string())
-            && entry.dbname.accepts(&self.connect_db)
-            && entry.user.accepts(&self.username)
-        {
-            return if entry.password.is_empty() {
-                // To be consistent with libpq, in this case we need to stop searching the password file, but not
-                // attempt to use the empty password string.
-                Ok(None)
-            } else {
-                let password_st = String::from_utf8(entry.password)?;
-                Ok(Some(password_st))
-            }
-        }
-        Ok(None)
-    }
-}
-
-// below impl is from https://github.com/sfackler/rust-postgres/pull/766
-
-enum PassfileField {
-    Wildcard,
-    Bytes(Vec<u8>),
-}
-
-impl PassfileField {
-    fn accepts(&self, value: &str) -> bool {
-        match self {
-            PassfileField::Wildcard => true,
-            PassfileField::Bytes(b) => b == value.as_bytes(),
-        }
-    }
-}
-
-struct PassfileEntry {
-    hostname: PassfileField,
-    port: PassfileField,
-    dbname: PassfileField,
-    user: PassfileField,
-    password: Vec<u8>,
-}
-
-impl PassfileEntry {
-    fn new(path: &str, s: &[u8], line_no: u32) -> Result<PassfileEntry, PgAccessError> {
-        let mut it = s.iter().copied();
-
-        let mut parse_one_field = || {
-            let mut value = Vec::new();
-            let mut has_any_escape = false;
-            while let Some(b) = it.next() {
-                if b == b':' {
-                    return Ok(match &value[..] {
-                        b"*" if !has_any_escape => PassfileField::Wildcard,
-                        _ => PassfileField::Bytes(value),
-                    });
-                } else if b == b'\\' {
-                    has_any_escape = true;
-                    value.push(it.next().ok_or(PgAccessError::pgpass_error(path, line_no))?);
-                } else if b == b'\0' {
-                    return Err(PgAccessError::pgpass_error(path, line_no));
-                } else {
-                    value.push(b)
-                }
-            }
-            Err(PgAccessError::pgpass_error(path, line_no))
-        };
-        let hostname = parse_one_field()?;
-        let port = parse_one_field()?;
-        let dbname = parse_one_field()?;
-        let user = parse_one_field()?;
-
-        let mut parse_final_field = || {
-            let mut value = Vec::new();
-            while let Some(b) = it.next() {
-                if b == b':' {
-                    // Text that looks like an additional field is ignored
-                    return Ok(value);
-                } else if b == b'\\' {
-                    // To be consistent with libpq, if the line ends with a backslash then the backslash is treated as
-                    // part of the last field's value.
-                    value.push(it.next().unwrap_or(b'\\'))
-                } else if b == b'\0' {
-                    return Err(PgAccessError::pgpass_error(path, line_no));
-                } else {
-                    value.push(b)
-                }
-            }
-            Ok(value)
-        };
-        let password = parse_final_field()?;
-
-        Ok(PassfileEntry {
-            hostname,
-            port,
-            dbname,
-            user,
-            password,
-        })
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::mem;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use native_tls::Certificate;
+use native_tls::TlsConnector;
+use postgres::Client;
+use postgres::Config;
+use postgres::NoTls;
+use postgres_native_tls::MakeTlsConnector;
+use zeroize::Zeroizing;
+
+use super::*;
+
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const KEEPALIVE_RETRIES: u32 = 3;
+const PEM_CERT_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+
+#[derive(Default, Debug, Clone)]
+pub struct PgConnConfig {
+    pub hostname: String,
+    pub port: u16,
+    pub username: String,
+    pub password: SecretString,
+    pub use_pgpass_file: bool,
+    pub connect_db: String,
+    pub enable_tls: bool,
+    pub accept_invalid_tls: bool,
+    // PEM file with CA certificates, server certificate and hostname are always
+    // verified when it is set, empty - Windows certificate store
+    pub tls_ca_file: String,
+    // zero - DEFAULT_CONNECT_TIMEOUT_SECS
+    pub connect_timeout_secs: u64,
+    // dead server detection with TCP keepalives, zero - OS defaults
+    pub read_timeout_secs: u64,
+    // applied to queries run by this app, not to pg_dump/pg_restore, zero - no limit
+    pub statement_timeout_secs: u64,
+    // repeats of the read-only queries failed with connection errors, zero - no retries
+    pub retries: u32,
+    // DB server is reached through a port forwarded with ssh, empty - direct connection
+    pub ssh_host: String,
+    pub ssh_user: String,
+    // empty - default keys of the user or ssh-agent
+    pub ssh_key_file: String,
+}
+
+impl PgConnConfig {
+    pub fn open_connection_default(&self) -> Result<Client, PgAccessError> {
+       self.open_connection(&self.connect_db)
+    }
+
+    pub fn open_connection_to_db(&self, dbname: &str) -> Result<Client, PgAccessError> {
+        self.open_connection(dbname)
+    }
+
+    fn open_connection(&self, dbname: &str) -> Result<Client, PgAccessError> {
+        let pwd = self.resolve_password(dbname)?;
+        let port = self.client_port()?;
+        let mut conf = Config::new()
+            .host(&self.hostname)
+            .port(port)
+            .user(&self.username)
+            .password(pwd.expose())
+            .dbname(dbname)
+            .connect_timeout(Duration::from_secs(self.connect_timeout()))
+            .clone();
+        if self.uses_ssh_tunnel() {
+            // hostname is still used for TLS checks and pgpass lookup
+            conf.hostaddr(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        }
+        if self.read_timeout_secs > 0 {
+            // keepalive probes start at half of the timeout, so the whole
+            // sequence of probes ends approximately at the timeout
+            let idle = (self.read_timeout_secs / 2).max(1);
+            let interval = (idle / KEEPALIVE_RETRIES as u64).max(1);
+            conf.keepalives(true)
+                .keepalives_idle(Duration::from_secs(idle))
+                .keepalives_interval(Duration::from_secs(interval))
+                .keepalives_retries(KEEPALIVE_RETRIES)
+                .tcp_user_timeout(Duration::from_secs(self.read_timeout_secs));
+        }
+        if self.statement_timeout_secs > 0 {
+            conf.options(&format!("-c statement_timeout={}s", self.statement_timeout_secs));
+        }
+
+        let res = if self.enable_tls {
+            let mut builder = TlsConnector::builder();
+            for cert in self.load_ca_certificates()? {
+                builder.add_root_certificate(cert);
+            }
+            let accept_invalid = self.accept_invalid_tls && self.tls_ca_file.is_empty();
+            let connector = builder
+                .danger_accept_invalid_certs(accept_invalid)
+                .danger_accept_invalid_hostnames(accept_invalid)
+                .build()?;
+            let tls = MakeTlsConnector::new(connector);
+            conf.connect(tls)?
+        } else {
+            conf.connect(NoTls)?
+        };
+
+        Ok(res)
+    }
+
+    pub fn uses_ssh_tunnel(&self) -> bool {
+        !self.ssh_host.is_empty()
+    }
+
+    // Port to connect to, SSH tunnel is opened on first use, client tools
+    // are pointed to its local end with "PGHOSTADDR" environment variable.
+    pub fn client_port(&self) -> Result<u16, io::Error> {
+        if self.uses_ssh_tunnel() {
+            ssh_tunnel_port(self, Duration::from_secs(self.connect_timeout()))
+        } else {
+            Ok(self.port)
+        }
+    }
+
+    // Environment for pg_dump, pg_restore and psql, tools read the same pgpass file
+    // as this app, when SSH tunnel is used the entry for the connect DB is read here,
+    // because pgpass entries would not match the forwarded port
+    // Values are wiped from memory when dropped, the password is among them.
+    pub fn client_tool_env(&self) -> Result<Vec<(&'static str, Zeroizing<String>)>, io::Error> {
+        let mut res = vec!();
+        if !self.use_pgpass_file {
+            res.push(("PGPASSWORD", Zeroizing::new(self.password.expose().to_string())));
+        } else if self.uses_ssh_tunnel() {
+            match self.resolve_password(&self.connect_db) {
+                Ok(pwd) => res.push(("PGPASSWORD", Zeroizing::new(pwd.expose().to_string()))),
+                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e.to_string()))
+            }
+        } else {
+            match PgConnConfig::resolve_pgpass_path() {
+                Ok(path) => res.push(("PGPASSFILE", Zeroizing::new(path))),
+                Err(e) => return Err(io::Error::new(io::ErrorKind::NotFound, e.to_string()))
+            }
+        }
+        if self.uses_ssh_tunnel() {
+            // port passed in args is the local end of the tunnel
+            res.push(("PGHOSTADDR", Zeroizing::new("127.0.0.1".to_string())));
+        }
+        if self.enable_tls && !self.tls_ca_file.is_empty() {
+            res.push(("PGSSLMODE", Zeroizing::new("verify-full".to_string())));
+            res.push(("PGSSLROOTCERT", Zeroizing::new(self.tls_ca_file.clone())));
+        }
+        Ok(res)
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(self.retries)
+    }
+
+    fn connect_timeout(&self) -> u64 {
+        if self.connect_timeout_secs > 0 {
+            self.connect_timeout_secs
+        } else {
+            DEFAULT_CONNECT_TIMEOUT_SECS
+        }
+    }
+
+    fn load_ca_certificates(&self) -> Result<Vec<Certificate>, PgAccessError> {
+        if self.tls_ca_file.is_empty() {
+            return Ok(vec!());
+        }
+        let pem = match fs::read_to_string(&self.tls_ca_file) {
+            Ok(pem) => pem,
+            Err(e) => return Err(PgAccessError::from_string(format!(
+                "Error reading TLS CA file: {}, message: {}", self.tls_ca_file, e)))
+        };
+        let mut res = vec!();
+        // bundle is split, only the first certificate is read from a PEM block
+        for part in pem.split(PEM_CERT_BEGIN).skip(1) {
+            let block = format!("{}{}", PEM_CERT_BEGIN, part);
+            match Certificate::from_pem(block.as_bytes()) {
+                Ok(cert) => res.push(cert),
+                Err(e) => return Err(PgAccessError::from_string(format!(
+                    "Error loading certificate from TLS CA file: {}, message: {}", self.tls_ca_file, e)))
+            }
+        }
+        if res.is_empty() {
+            return Err(PgAccessError::from_string(format!(
+                "No PEM certificates found in TLS CA file: {}", self.tls_ca_file)));
+        }
+        Ok(res)
+    }
+
+    // Like in libpq, the first entry matching host, port, DB and user is used,
+    // empty password is sent when there is no such entry
+    fn resolve_password(&self, dbname: &str) -> Result<SecretString, PgAccessError> {
+        if !self.use_pgpass_file {
+            return Ok(self.password.clone());
+        }
+        let pgpass_path = PgConnConfig::resolve_pgpass_path()?;
+        let file = match File::open(&pgpass_path) {
+            Ok(file) => file,
+            Err(e) => return Err(PgAccessError::from_string(format!(
+                "Error opening pgpass file: {}, message: {}", pgpass_path, e)))
+        };
+        let mut line_no = 1;
+        for line_res in BufReader::new(file).lines() {
+            let line = Zeroizing::new(line_res?);
+            if let Some(pwd) = self.match_pgpass_line(&pgpass_path, &line, line_no, dbname)? {
+                return Ok(pwd);
+            }
+            line_no += 1;
+        }
+        log::warn!("No matching entry found in pgpass file: {}, host: {}, port: {}, DB: {}, user: {}",
+            pgpass_path, self.hostname, self.port, dbname, self.username);
+        Ok(SecretString::default())
+    }
+
+    fn resolve_pgpass_path() -> Result<String, PgAccessError> {
+        if let Ok(path_from_env) = std::env::var("PGPASSFILE") {
+            Ok(path_from_env)
+        } else if let Ok(appdir) = std::env::var("APPDATA") {
+            Ok(format!("{}\\postgresql\\pgpass.conf", appdir))
+        } else {
+            Err(PgAccessError::pgpass_not_found())
+        }
+    }
+
+    fn match_pgpass_line(&self, path: &str, line: &str, line_no: u32, dbname: &str) -> Result<Option<SecretString>, PgAccessError> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("#") {
+            return Ok(None);
+        }
+        let mut entry = PassfileEntry::new(path, trimmed.as_bytes(), line_no)?;
+        if entry.hostname.accepts(&self.hostname)
+            && entry.port.accepts(&self.port.to_string())
+            && entry.dbname.accepts(dbname)
+            && entry.user.accepts(&self.username)
+        {
+            return if entry.password.is_empty() {
+                // To be consistent with libpq, in this case we need to stop searching the password file,
+                // no password is available for this connection.
+                Ok(Some(SecretString::default()))
+            } else {
+                let password_st = String::from_utf8(mem::take(&mut *entry.password))?;
+                Ok(Some(SecretString::from(password_st)))
+            }
+        }
+        Ok(None)
+    }
+}
+
+// below impl is from https://github.com/sfackler/rust-postgres/pull/766
+
+enum PassfileField {
+    Wildcard,
+    Bytes(Vec<u8>),
+}
+
+impl PassfileField {
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            PassfileField::Wildcard => true,
+            PassfileField::Bytes(b) => b == value.as_bytes(),
+        }
+    }
+}
+
+struct PassfileEntry {
+    hostname: PassfileField,
+    port: PassfileField,
+    dbname: PassfileField,
+    user: PassfileField,
+    password: Zeroizing<Vec<u8>>,
+}
+
+impl PassfileEntry {
+    fn new(path: &str, s: &[u8], line_no: u32) -> Result<PassfileEntry, PgAccessError> {
+        let mut it = s.iter().copied();
+
+        let mut parse_one_field = || {
+            let mut value = Vec::new();
+            let mut has_any_escape = false;
+            while let Some(b) = it.next() {
+                if b == b':' {
+                    return Ok(match &value[..] {
+                        b"*" if !has_any_escape => PassfileField::Wildcard,
+                        _ => PassfileField::Bytes(value),
+                    });
+                } else if b == b'\\' {
+                    has_any_escape = true;
+                    value.push(it.next().ok_or(PgAccessError::pgpass_error(path, line_no))?);
+                } else if b == b'\0' {
+                    return Err(PgAccessError::pgpass_error(path, line_no));
+                } else {
+                    value.push(b)
+                }
+            }
+            Err(PgAccessError::pgpass_error(path, line_no))
+        };
+        let hostname = parse_one_field()?;
+        let port = parse_one_field()?;
+        let dbname = parse_one_field()?;
+        let user = parse_one_field()?;
+
+        let mut parse_final_field = || {
+            let mut value = Vec::new();
+            while let Some(b) = it.next() {
+                if b == b':' {
+                    // Text that looks like an additional field is ignored
+                    return Ok(value);
+                } else if b == b'\\' {
+                    // To be consistent with libpq, if the line ends with a backslash then the backslash is treated as
+                    // part of the last field's value.
+                    value.push(it.next().unwrap_or(b'\\'))
+                } else if b == b'\0' {
+                    return Err(PgAccessError::pgpass_error(path, line_no));
+                } else {
+                    value.push(b)
+                }
+            }
+            Ok(value)
+        };
+        let password = Zeroizing::new(parse_final_field()?);
+
+        Ok(PassfileEntry {
+            hostname,
+            port,
+            dbname,
+            user,
+            password,
+        })
+    }
+}
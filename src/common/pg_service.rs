@@ -0,0 +1,102 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::*;
+
+// Connection service from "pg_service.conf", services from the user file
+// replace the ones with the same name from the system file, like in libpq.
+#[derive(Default, Debug, Clone)]
+pub struct PgService {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl PgService {
+    pub fn load_all() -> Result<Vec<PgService>, io::Error> {
+        let mut res: Vec<PgService> = vec!();
+        for path in Self::service_files() {
+            if !path.is_file() {
+                continue;
+            }
+            for svc in Self::read_file(&path)? {
+                res.retain(|existing| existing.name != svc.name);
+                res.push(svc);
+            }
+        }
+        res.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(res)
+    }
+
+    // parameters without a matching connection setting (hostaddr, options etc)
+    // are ignored
+    pub fn apply(&self, pcc: &mut PgConnConfig) {
+        for (key, value) in &self.params {
+            pcc.apply_param(key, value);
+        }
+    }
+
+    // system file is looked up in "etc" dir next to "bin" with pg tools,
+    // where bundled libpq expects it
+    fn service_files() -> Vec<PathBuf> {
+        let mut res = vec!();
+        if let Ok(dir) = env::var("PGSYSCONFDIR") {
+            res.push(PathBuf::from(dir).join("pg_service.conf"));
+        } else if let Ok(exe) = env::current_exe() {
+            if let Some(install_dir) = exe.parent().and_then(|bin| bin.parent()) {
+                res.push(install_dir.join("etc").join("pg_service.conf"));
+            }
+        }
+        if let Ok(file) = env::var("PGSERVICEFILE") {
+            res.push(PathBuf::from(file));
+        } else if let Ok(appdir) = env::var("APPDATA") {
+            res.push(PathBuf::from(appdir).join("postgresql").join(".pg_service.conf"));
+        }
+        res
+    }
+
+    fn read_file(path: &Path) -> Result<Vec<PgService>, io::Error> {
+        let text = fs::read_to_string(path)?;
+        let mut res: Vec<PgService> = vec!();
+        for (idx, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                res.push(PgService {
+                    name: trimmed[1..trimmed.len() - 1].trim().to_string(),
+                    params: vec!(),
+                });
+                continue;
+            }
+            let pos = match trimmed.find('=') {
+                Some(pos) if !res.is_empty() => pos,
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                    "Syntax error in service file: {}, line: {}", path.to_string_lossy(), idx + 1)))
+            };
+            if let Some(svc) = res.last_mut() {
+                svc.params.push((trimmed[..pos].trim().to_string(), trimmed[pos + 1..].trim().to_string()));
+            }
+        }
+        Ok(res)
+    }
+}
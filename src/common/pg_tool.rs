@@ -0,0 +1,133 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+use std::fmt;
+use std::io;
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+
+use zeroize::Zeroizing;
+
+use super::*;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+const PGPASSWORD: &str = "PGPASSWORD";
+
+// progress lines with this prefix are collected by the dialogs for copying
+pub const COMMAND_LINE_PREFIX: &str = "Running command: ";
+
+// pg_dump, pg_restore or psql process ready to be started, the expression
+// with the secrets in its environment is only created when it is spawned,
+// Debug output does not include the password.
+pub struct PgToolCmd {
+    tool_exe: PathBuf,
+    args: Vec<String>,
+    env: Vec<(&'static str, Zeroizing<String>)>,
+}
+
+impl PgToolCmd {
+    pub fn reader(&self) -> Result<duct::ReaderHandle, io::Error> {
+        self.expression().reader()
+    }
+
+    // variables set in the environment of the child process
+    pub fn env(&self) -> &[(&'static str, Zeroizing<String>)] {
+        &self.env
+    }
+
+    fn expression(&self) -> duct::Expression {
+        let mut cmd = duct::cmd(&self.tool_exe, &self.args)
+            .stdin_null()
+            .stderr_to_stdout()
+            .stdout_capture()
+            .before_spawn(|pcmd| {
+                let _ = pcmd.creation_flags(CREATE_NO_WINDOW);
+                Ok(())
+            });
+        if !self.env.iter().any(|(name, _)| PGPASSWORD == *name) {
+            // libpq prefers it to the pgpass file
+            cmd = cmd.env_remove(PGPASSWORD);
+        }
+        for (name, value) in self.env.iter() {
+            cmd = cmd.env(name, value.as_str());
+        }
+        cmd
+    }
+}
+
+impl fmt::Debug for PgToolCmd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let env: Vec<(&str, &str)> = self.env.iter()
+            .map(|(name, value)| if PGPASSWORD == *name {
+                (*name, "***")
+            } else {
+                (*name, value.as_str())
+            })
+            .collect();
+        f.debug_struct("PgToolCmd")
+            .field("tool_exe", &self.tool_exe)
+            .field("args", &self.args)
+            .field("env", &env)
+            .finish()
+    }
+}
+
+// Command for pg_dump, pg_restore or psql shipped next to the app executable.
+// Password and other connection settings are set only in the environment of
+// the child process, process-wide environment of the app is never modified.
+// Equivalent cmd.exe command line is reported to progress before running.
+pub fn pg_tool_cmd(progress: &impl ProgressSender, pcc: &PgConnConfig, tool: &str, args: Vec<&str>) -> Result<PgToolCmd, io::Error> {
+    let cur_exe = env::current_exe()?;
+    let bin_dir = match cur_exe.parent() {
+        Some(path) => path,
+        None => { // cannot happen
+            let exe_st = cur_exe.to_str().unwrap_or("");
+            return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Parent dir failure, exe path: {}", exe_st)))
+        }
+    };
+    let tool_exe = bin_dir.join(format!("{}.exe", tool));
+    let tool_env = pcc.client_tool_env()?;
+    progress.send_value(format!("{}{}", COMMAND_LINE_PREFIX,
+        command_line(&tool_exe.to_string_lossy(), &args, &tool_env)));
+    Ok(PgToolCmd {
+        tool_exe,
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        env: tool_env,
+    })
+}
+
+// Password is left out, the tool prompts for it when run from a terminal
+fn command_line(tool_exe: &str, args: &[&str], tool_env: &[(&'static str, Zeroizing<String>)]) -> String {
+    let mut parts: Vec<String> = tool_env.iter()
+        .filter(|(name, _)| PGPASSWORD != *name)
+        .map(|(name, value)| format!("set \"{}={}\"", name, value.as_str()))
+        .collect();
+    let mut cmd: Vec<String> = vec!(quote_arg(tool_exe));
+    cmd.extend(args.iter().map(|arg| quote_arg(arg)));
+    parts.push(cmd.join(" "));
+    parts.join(" && ")
+}
+
+fn quote_arg(arg: &str) -> String {
+    let plain = !arg.is_empty() && !arg.chars().any(|c| c.is_whitespace() || "\"&|<>^()".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    }
+}
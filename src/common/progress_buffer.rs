@@ -0,0 +1,84 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+use std::time::Instant;
+
+const MIN_INTERVAL_MS: f64 = 30.0;
+const MAX_INTERVAL_MS: f64 = 500.0;
+// message rate (per second) at which the flush interval reaches its maximum
+const MAX_INTERVAL_RATE: f64 = 2000.0;
+const RATE_SMOOTHING: f64 = 0.1;
+
+// Aggregates progress lines and decides when they need to be appended to the UI,
+// sparse output is flushed almost immediately, chatty output is batched
+pub struct ProgressBuffer {
+    pending: Vec<String>,
+    rate: f64,
+    last_received: Instant,
+    last_flushed: Instant,
+}
+
+impl ProgressBuffer {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            pending: Vec::new(),
+            rate: 0.0,
+            last_received: now,
+            last_flushed: now,
+        }
+    }
+
+    pub fn push(&mut self, msg: String) -> Option<String> {
+        let now = Instant::now();
+        let gap = now.duration_since(self.last_received).as_secs_f64().max(0.0001);
+        self.rate = (1.0 - RATE_SMOOTHING) * self.rate + RATE_SMOOTHING / gap;
+        self.last_received = now;
+        self.pending.push(msg);
+        self.flush_if_due()
+    }
+
+    pub fn flush_if_due(&mut self) -> Option<String> {
+        if self.last_flushed.elapsed() >= self.interval() {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    pub fn flush(&mut self) -> Option<String> {
+        self.last_flushed = Instant::now();
+        if self.pending.is_empty() {
+            return None;
+        }
+        let joined = self.pending.join("\r\n");
+        self.pending.clear();
+        Some(joined)
+    }
+
+    pub fn interval(&self) -> Duration {
+        let frac = (self.rate / MAX_INTERVAL_RATE).min(1.0);
+        let ms = MIN_INTERVAL_MS + (MAX_INTERVAL_MS - MIN_INTERVAL_MS) * frac;
+        Duration::from_millis(ms as u64)
+    }
+}
+
+impl Default for ProgressBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
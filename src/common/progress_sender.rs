@@ -0,0 +1,83 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use nwg_ui as ui;
+
+use super::redact_secrets;
+
+const LOG_TAIL_LINES: usize = 20;
+
+// Allows long-running operations to report progress both to
+// the GUI dialogs and to the console when running in CLI mode.
+pub trait ProgressSender {
+    fn send_value<T: Into<String>>(&self, value: T);
+}
+
+impl ProgressSender for ui::SyncNoticeValueSender<String> {
+    fn send_value<T: Into<String>>(&self, value: T) {
+        let st = redact_secrets(&value.into());
+        log::info!("{}", st);
+        ui::SyncNoticeValueSender::send_value(self, st)
+    }
+}
+
+// Writes progress messages only to the log file, used for backups run by built-in scheduler
+pub struct SilentProgress {}
+
+impl ProgressSender for SilentProgress {
+    fn send_value<T: Into<String>>(&self, value: T) {
+        log::info!("{}", redact_secrets(&value.into()));
+    }
+}
+
+// Forwards progress messages and keeps the last lines,
+// used to include the end of the log into notifications
+pub struct LogTail<'a, P: ProgressSender> {
+    inner: &'a P,
+    lines: Mutex<VecDeque<String>>,
+}
+
+impl<'a, P: ProgressSender> LogTail<'a, P> {
+    pub fn new(inner: &'a P) -> Self {
+        Self {
+            inner,
+            lines: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self.lines.lock() {
+            Ok(lines) => lines.iter().cloned().collect::<Vec<String>>().join("\r\n"),
+            Err(_) => String::new()
+        }
+    }
+}
+
+impl<'a, P: ProgressSender> ProgressSender for LogTail<'a, P> {
+    fn send_value<T: Into<String>>(&self, value: T) {
+        let st = redact_secrets(&value.into());
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.push_back(st.clone());
+            while lines.len() > LOG_TAIL_LINES {
+                lines.pop_front();
+            }
+        }
+        self.inner.send_value(st);
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ffi::OsStr;
+use std::io;
+use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+
+use winapi::um::shellapi::SHFileOperationW;
+use winapi::um::shellapi::SHFILEOPSTRUCTW;
+use winapi::um::shellapi::FO_DELETE;
+use winapi::um::shellapi::FOF_ALLOWUNDO;
+use winapi::um::shellapi::FOF_NOCONFIRMATION;
+use winapi::um::shellapi::FOF_NOERRORUI;
+use winapi::um::shellapi::FOF_SILENT;
+
+// Shell does not show any UI, file or directory is moved to the recycle bin
+// of its drive. Network shares do not have a recycle bin, files there are
+// deleted permanently.
+pub fn move_to_recycle_bin(path: &Path) -> Result<(), io::Error> {
+    if !path.is_absolute() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+            "Absolute path is required to move file to recycle bin: {}", path.to_string_lossy())));
+    }
+    // list of paths terminated with an empty string
+    let from: Vec<u16> = OsStr::new(path).encode_wide().chain(Some(0)).chain(Some(0)).collect();
+    let mut op: SHFILEOPSTRUCTW = unsafe { mem::zeroed() };
+    op.wFunc = FO_DELETE as u32;
+    op.pFrom = from.as_ptr();
+    op.fFlags = FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT;
+    let res = unsafe { SHFileOperationW(&mut op) };
+    if 0 != res {
+        return Err(io::Error::new(io::ErrorKind::Other, format!(
+            "Error moving to recycle bin: {}, code: {}", path.to_string_lossy(), res)));
+    }
+    if 0 != op.fAnyOperationsAborted {
+        return Err(io::Error::new(io::ErrorKind::Interrupted, format!(
+            "Moving to recycle bin was cancelled: {}", path.to_string_lossy())));
+    }
+    Ok(())
+}
@@ -0,0 +1,285 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+use pg_toc::DumpSummary;
+
+use super::volume_set::volume_set_base;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RetentionMode {
+    #[default]
+    KeepAll,
+    KeepLast,
+    MaxAgeDays,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub dest_dir: String,
+    pub mode: RetentionMode,
+    pub value: u32,
+}
+
+impl RetentionPolicy {
+    pub fn keep_all(dest_dir: &str) -> Self {
+        Self {
+            dest_dir: dest_dir.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn matches_dir(&self, dir: &str) -> bool {
+        normalize_dir(&self.dest_dir) == normalize_dir(dir)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.mode != RetentionMode::KeepAll && self.value > 0
+    }
+
+    // Lists backups of the specified DB in the policy directory that are
+    // not covered by the policy anymore, newest file is never selected.
+    pub fn select_expired(&self, dbname: &str, keep_file: &Path) -> Result<Vec<PathBuf>, io::Error> {
+        if !self.is_enabled() {
+            return Ok(Vec::new());
+        }
+        let keep_name = keep_file.file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        // unzipped backups are directories with a dump
+        let keep_is_dir = keep_file.is_dir();
+        let ext = match keep_file.extension() {
+            _ if keep_is_dir => String::new(),
+            Some(ext) => ext.to_string_lossy().to_string(),
+            None => "zip".to_string()
+        };
+        let mut archives = Vec::new();
+        for en_res in fs::read_dir(&self.dest_dir)? {
+            let en = en_res?;
+            let meta = en.metadata()?;
+            let is_backup = if keep_is_dir {
+                meta.is_dir() && en.path().join("toc.dat").is_file()
+            } else {
+                meta.is_file()
+            };
+            if !is_backup {
+                continue;
+            }
+            // split backups are listed by their first volume
+            let name = en.file_name().to_string_lossy().to_string();
+            let name = match volume_set_base(Path::new(&name)) {
+                Some(base) if !keep_is_dir => base.to_string_lossy().to_string(),
+                _ => name
+            };
+            if name.to_lowercase() == keep_name || !is_archive_of(dbname, &name, &ext) {
+                continue;
+            }
+            if !is_dump_of(dbname, &en.path()) {
+                continue;
+            }
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            archives.push((en.path(), modified));
+        }
+        archives.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+        let res = match self.mode {
+            RetentionMode::KeepAll => Vec::new(),
+            RetentionMode::KeepLast => {
+                // newly created backup counts as one of the kept
+                let keep = (self.value - 1) as usize;
+                archives.into_iter().skip(keep).map(|(path, _)| path).collect()
+            },
+            RetentionMode::MaxAgeDays => {
+                let max_age = Duration::from_secs(self.value as u64 * 24 * 60 * 60);
+                let now = SystemTime::now();
+                archives.into_iter().filter(|(_, modified)| {
+                    match now.duration_since(*modified) {
+                        Ok(age) => age > max_age,
+                        Err(_) => false
+                    }
+                }).map(|(path, _)| path).collect()
+            }
+        };
+        Ok(res)
+    }
+}
+
+fn normalize_dir(dir: &str) -> String {
+    dir.trim().trim_end_matches(&['\\', '/'][..]).to_lowercase()
+}
+
+// Only the names written by this tool are matched: "mydb.zip", with the
+// "_20240101_120000" timestamp of scheduled jobs and with the "_1", "_2"
+// suffix added when the file exists. "mydb2.zip" and "mydb_eu.zip" are not
+// archives of "mydb".
+fn is_archive_of(dbname: &str, filename: &str, ext: &str) -> bool {
+    let name = filename.to_lowercase();
+    let suffix = if ext.is_empty() {
+        String::new()
+    } else {
+        format!(".{}", ext.to_lowercase())
+    };
+    if !name.ends_with(&suffix) {
+        return false;
+    }
+    let stem = &name[..name.len() - suffix.len()];
+    let db = dbname.to_lowercase();
+    if stem == db {
+        return true;
+    }
+    if !stem.starts_with(&db) {
+        return false;
+    }
+    let parts: Vec<&str> = match stem[db.len()..].strip_prefix('_') {
+        Some(rest) => rest.split('_').collect(),
+        None => return false
+    };
+    let is_num = |part: &str| !part.is_empty() && part.chars().all(|ch| ch.is_ascii_digit());
+    let is_ts = |date: &str, time: &str| 8 == date.len() && 6 == time.len() && is_num(date) && is_num(time);
+    match parts.as_slice() {
+        [idx] => is_num(idx),
+        [date, time] => is_ts(date, time),
+        [date, time, idx] => is_ts(date, time) && is_num(idx),
+        _ => false
+    }
+}
+
+// Name "mydb_2.zip" may also belong to the "mydb_2" DB, the DB name is
+// read from the dump when possible. Tar and SQL backups are matched by
+// name only.
+fn is_dump_of(dbname: &str, path: &Path) -> bool {
+    match DumpSummary::read_path(path) {
+        Ok(summary) => match summary.original_dbname {
+            Some(orig) if !orig.is_empty() => orig.to_lowercase() == dbname.to_lowercase(),
+            _ => true
+        },
+        Err(_) => true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::windows::io::AsRawHandle;
+    use std::ptr;
+    use std::time::UNIX_EPOCH;
+
+    use winapi::shared::minwindef::FILETIME;
+    use winapi::um::fileapi::SetFileTime;
+    use winapi::um::winnt::HANDLE;
+
+    use super::*;
+
+    // seconds between 1601-01-01 (FILETIME epoch) and 1970-01-01
+    const FILETIME_UNIX_OFFSET_SECS: u64 = 11_644_473_600;
+
+    // File::set_modified is not available in Rust 1.70 used for release builds
+    fn set_modified(file: &fs::File, time: SystemTime) {
+        let secs = time.duration_since(UNIX_EPOCH).unwrap().as_secs() + FILETIME_UNIX_OFFSET_SECS;
+        let ticks = secs * 10_000_000;
+        let ft = FILETIME {
+            dwLowDateTime: ticks as u32,
+            dwHighDateTime: (ticks >> 32) as u32,
+        };
+        let success = unsafe {
+            SetFileTime(file.as_raw_handle() as HANDLE, ptr::null(), ptr::null(), &ft)
+        };
+        assert_ne!(0, success, "SetFileTime error: {}", io::Error::last_os_error());
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wdb_backup_retention_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn policy(dir: &Path, mode: RetentionMode, value: u32) -> RetentionPolicy {
+        RetentionPolicy {
+            dest_dir: dir.to_string_lossy().to_string(),
+            mode,
+            value,
+        }
+    }
+
+    fn names(paths: &[PathBuf]) -> Vec<String> {
+        let mut res: Vec<String> = paths.iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        res.sort();
+        res
+    }
+
+    #[test]
+    fn archive_names() {
+        assert!(is_archive_of("sales", "sales.zip", "zip"));
+        assert!(is_archive_of("sales", "Sales_1.zip", "zip"));
+        assert!(is_archive_of("sales", "sales_20240101_120000.zip", "zip"));
+        assert!(is_archive_of("sales", "sales_20240101_120000_2.zip", "zip"));
+        assert!(is_archive_of("sales", "sales", ""));
+        assert!(!is_archive_of("sales", "sales_eu.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales_eu_1.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales_eu_20240101_120000.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales2.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales-2.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales_.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales_2024_1200.zip", "zip"));
+        assert!(!is_archive_of("sales", "sales.tar", "zip"));
+    }
+
+    #[test]
+    fn keep_last_skips_other_db() {
+        let dir = test_dir("keep_last");
+        for name in &["sales.zip", "sales_1.zip", "sales_20240101_120000.zip",
+                "sales_eu.zip", "sales_eu_1.zip", "sales_eu_20240101_120000.zip"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        let expired = policy(&dir, RetentionMode::KeepLast, 1)
+            .select_expired("sales", &dir.join("sales_2.zip")).unwrap();
+        assert_eq!(names(&expired), vec!("sales.zip", "sales_1.zip", "sales_20240101_120000.zip"));
+        let expired = policy(&dir, RetentionMode::KeepLast, 1)
+            .select_expired("sales_eu", &dir.join("sales_eu_2.zip")).unwrap();
+        assert_eq!(names(&expired), vec!("sales_eu.zip", "sales_eu_1.zip", "sales_eu_20240101_120000.zip"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn max_age_skips_other_db() {
+        let dir = test_dir("max_age");
+        let old = SystemTime::now() - Duration::from_secs(10 * 24 * 60 * 60);
+        for name in &["sales.zip", "sales_eu.zip", "sales_1.zip"] {
+            let file = fs::File::create(dir.join(name)).unwrap();
+            if "sales_1.zip" != *name {
+                set_modified(&file, old);
+            }
+        }
+        let expired = policy(&dir, RetentionMode::MaxAgeDays, 5)
+            .select_expired("sales", &dir.join("sales_2.zip")).unwrap();
+        assert_eq!(names(&expired), vec!("sales.zip"));
+        let expired = policy(&dir, RetentionMode::KeepAll, 5)
+            .select_expired("sales", &dir.join("sales_2.zip")).unwrap();
+        assert!(expired.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
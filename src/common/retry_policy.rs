@@ -0,0 +1,92 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use super::*;
+
+const INITIAL_DELAY: Duration = Duration::from_secs(2);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+// Errors that may go away when the same operation is repeated,
+// like a dropped connection or a server that is restarting.
+pub trait TransientError {
+    fn is_transient(&self) -> bool;
+}
+
+impl TransientError for io::Error {
+    fn is_transient(&self) -> bool {
+        match self.kind() {
+            io::ErrorKind::ConnectionRefused |
+            io::ErrorKind::ConnectionReset |
+            io::ErrorKind::ConnectionAborted |
+            io::ErrorKind::NotConnected |
+            io::ErrorKind::BrokenPipe |
+            io::ErrorKind::TimedOut |
+            io::ErrorKind::Interrupted => true,
+            _ => false
+        }
+    }
+}
+
+impl TransientError for PgAccessError {
+    fn is_transient(&self) -> bool {
+        PgAccessError::is_transient(self)
+    }
+}
+
+// Repeats operations that failed with transient errors, the delay
+// between attempts is doubled after each one. Only the operations
+// that can be safely repeated (queries that do not change anything)
+// must be run with it.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    retries: u32,
+}
+
+impl RetryPolicy {
+    pub fn new(retries: u32) -> Self {
+        Self {
+            retries
+        }
+    }
+
+    pub fn run<T, E, F, L>(&self, label: &str, listener: L, mut op: F) -> Result<T, E>
+    where
+        E: TransientError + fmt::Display,
+        F: FnMut() -> Result<T, E>,
+        L: Fn(String)
+    {
+        let mut delay = INITIAL_DELAY;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match op() {
+                Ok(res) => return Ok(res),
+                Err(e) if attempt <= self.retries && e.is_transient() => {
+                    listener(format!("Warning: {} failed, attempt {} of {}, retrying in {} seconds: {}",
+                        label, attempt, self.retries + 1, delay.as_secs(), e));
+                    thread::sleep(delay);
+                    delay = (delay * 2).min(MAX_DELAY);
+                },
+                Err(e) => return Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+// number of latest successful runs used for forecasting
+const FORECAST_RUNS: usize = 5;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RunKind {
+    #[default]
+    Backup,
+    Restore,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunRecord {
+    pub kind: RunKind,
+    pub dbname: String,
+    pub finished_at: i64,
+    pub duration_secs: u64,
+    pub size_bytes: u64,
+    pub success: bool,
+}
+
+impl RunRecord {
+    pub fn new(kind: RunKind, dbname: &str, archive_path: &Path, duration: Duration, success: bool) -> Self {
+        let size_bytes = match fs::metadata(archive_path) {
+            Ok(meta) if meta.is_dir() => dump_dir_size(archive_path),
+            Ok(meta) => meta.len(),
+            Err(_) => 0
+        };
+        Self {
+            kind,
+            dbname: dbname.to_string(),
+            finished_at: chrono::Local::now().timestamp(),
+            duration_secs: duration.as_secs(),
+            size_bytes,
+            success,
+        }
+    }
+
+    fn matches(&self, kind: RunKind, dbname: &str) -> bool {
+        self.success && self.kind == kind && self.dbname.to_lowercase() == dbname.to_lowercase()
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct RunForecast {
+    pub duration: Duration,
+    pub bytes_per_sec: u64,
+    pub size_bytes: u64,
+    pub overrun_factor: f64,
+}
+
+impl RunForecast {
+    pub fn from_history(history: &[RunRecord], kind: RunKind, dbname: &str, overrun_factor: f64) -> Option<Self> {
+        let mut recent: Vec<&RunRecord> = history.iter()
+            .rev()
+            .filter(|rec| rec.matches(kind, dbname))
+            .take(FORECAST_RUNS)
+            .collect();
+        if recent.is_empty() {
+            return None;
+        }
+        let total_secs: u64 = recent.iter().map(|rec| rec.duration_secs).sum();
+        let total_bytes: u64 = recent.iter().map(|rec| rec.size_bytes).sum();
+        let bytes_per_sec = total_bytes.checked_div(total_secs).unwrap_or(0);
+        let size_bytes = total_bytes / recent.len() as u64;
+        // median is not skewed by a single unusually slow run
+        recent.sort_by_key(|rec| rec.duration_secs);
+        let median_secs = recent[recent.len() / 2].duration_secs;
+        Some(Self {
+            duration: Duration::from_secs(median_secs),
+            bytes_per_sec,
+            size_bytes,
+            overrun_factor
+        })
+    }
+
+    pub fn is_exceeded(&self, elapsed: Duration) -> bool {
+        // short runs are not flagged, their duration varies too much
+        let expected = self.duration.as_secs_f64().max(60.0);
+        elapsed.as_secs_f64() > expected * self.overrun_factor
+    }
+
+    pub fn describe(&self) -> String {
+        let duration = format_duration_approx(self.duration);
+        if self.bytes_per_sec > 0 {
+            format!("usually takes {}, {}/s", duration, format_size(self.bytes_per_sec))
+        } else {
+            format!("usually takes {}", duration)
+        }
+    }
+}
+
+fn format_duration_approx(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("~{} sec", secs.max(1))
+    } else if secs < 3600 {
+        format!("~{} min", (secs + 30) / 60)
+    } else {
+        let mins = (secs + 30) / 60;
+        format!("~{} h {} min", mins / 60, mins % 60)
+    }
+}
+
+// unzipped dumps are flat directories
+fn dump_dir_size(dir: &Path) -> u64 {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return 0
+    };
+    entries
+        .filter_map(|en| en.ok())
+        .filter_map(|en| en.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+pub(super) fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", (bytes + 1023) / 1024)
+    }
+}
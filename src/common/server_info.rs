@@ -0,0 +1,144 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use postgres::Client;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::*;
+
+// Server details shown by connection check, last known details are
+// saved with the connection profile to be used for compatibility checks.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerInfo {
+    // full version() string, includes WiltonDB build details
+    pub server: String,
+    pub pg_version: String,
+    pub pg_version_num: u32,
+    // empty when Babelfish is not enabled
+    pub bbf_db: String,
+    pub babelfish_version: String,
+    pub migration_mode: String,
+    pub login: String,
+    pub roles: Vec<String>,
+    pub is_sysadmin: bool,
+    pub checked_at: String,
+}
+
+impl ServerInfo {
+    pub fn query(pcc: &PgConnConfig) -> Result<Self, PgAccessError> {
+        let mut client = pcc.open_connection_default()?;
+        let rs = client.query("
+            select
+                version() as server,
+                current_setting('server_version') as pg_version,
+                current_setting('server_version_num')::int as pg_version_num,
+                current_setting('babelfishpg_tsql.database_name', true) as bbf_db,
+                current_setting('babelfishpg_tsql.migration_mode', true) as migration_mode,
+                current_user::text as login,
+                r.rolsuper or exists(
+                    select 1 from pg_roles s
+                    where s.rolname = 'sysadmin'
+                    and pg_has_role(r.oid, s.oid, 'member')
+                ) as is_sysadmin,
+                array(
+                    select g.rolname::text
+                    from pg_auth_members m
+                    join pg_roles g on g.oid = m.roleid
+                    where m.member = r.oid
+                    order by g.rolname
+                ) as roles
+            from pg_roles r
+            where r.rolname = current_user", &[])?;
+        let row = &rs[0];
+        let version_num: i32 = row.get("pg_version_num");
+        let bbf_db: Option<String> = row.get("bbf_db");
+        let migration_mode: Option<String> = row.get("migration_mode");
+        let mut res = Self {
+            server: row.get("server"),
+            pg_version: row.get("pg_version"),
+            pg_version_num: version_num as u32,
+            bbf_db: bbf_db.unwrap_or_default(),
+            babelfish_version: String::new(),
+            migration_mode: migration_mode.unwrap_or_default(),
+            login: row.get("login"),
+            roles: row.get("roles"),
+            is_sysadmin: row.get("is_sysadmin"),
+            checked_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+        // extension is only created in the Babelfish DB
+        if !res.bbf_db.is_empty() {
+            if res.bbf_db == pcc.connect_db {
+                res.babelfish_version = Self::babelfish_version(&mut client)?;
+            } else {
+                let mut client_bbf = pcc.open_connection_to_db(&res.bbf_db)?;
+                res.babelfish_version = Self::babelfish_version(&mut client_bbf)?;
+                client_bbf.close()?;
+            }
+        }
+        client.close()?;
+        Ok(res)
+    }
+
+    pub fn babelfish_enabled(&self) -> bool {
+        !self.bbf_db.is_empty()
+    }
+
+    // only one user DB can be created in this mode
+    pub fn is_single_db(&self) -> bool {
+        "single-db" == self.migration_mode
+    }
+
+    pub fn describe(&self) -> String {
+        let mut lines = vec!(
+            format!("PostgreSQL version: {}", self.pg_version),
+        );
+        if self.babelfish_enabled() {
+            lines.push(format!("Babelfish version: {}", if self.babelfish_version.is_empty() {
+                "extension not found"
+            } else {
+                &self.babelfish_version
+            }));
+            lines.push(format!("Babelfish DB: {}", self.bbf_db));
+            lines.push(format!("Migration mode: {}", self.migration_mode));
+        } else {
+            lines.push("Babelfish: not enabled".to_string());
+        }
+        lines.push(format!("Login: {}{}", self.login, if self.is_sysadmin {
+            " (sysadmin)"
+        } else {
+            ""
+        }));
+        lines.push(format!("Roles: {}", if self.roles.is_empty() {
+            "none".to_string()
+        } else {
+            self.roles.join(", ")
+        }));
+        lines.push(String::new());
+        lines.push(self.server.clone());
+        lines.join("\r\n")
+    }
+
+    fn babelfish_version(client: &mut Client) -> Result<String, PgAccessError> {
+        let rs = client.query(
+            "select extversion from pg_extension where extname = 'babelfishpg_tsql'", &[])?;
+        Ok(match rs.first() {
+            Some(row) => row.get("extversion"),
+            None => String::new()
+        })
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::ptr;
+
+use winapi::um::wincrypt::CALG_SHA_256;
+use winapi::um::wincrypt::CRYPT_VERIFYCONTEXT;
+use winapi::um::wincrypt::CryptAcquireContextW;
+use winapi::um::wincrypt::CryptCreateHash;
+use winapi::um::wincrypt::CryptDestroyHash;
+use winapi::um::wincrypt::CryptGetHashParam;
+use winapi::um::wincrypt::CryptHashData;
+use winapi::um::wincrypt::CryptReleaseContext;
+use winapi::um::wincrypt::HCRYPTHASH;
+use winapi::um::wincrypt::HCRYPTPROV;
+use winapi::um::wincrypt::HP_HASHVAL;
+use winapi::um::wincrypt::PROV_RSA_AES;
+
+// Hash of the data passed in chunks, used for dump files
+// that are too large to be read into memory.
+pub struct Sha256 {
+    prov: HCRYPTPROV,
+    hash: HCRYPTHASH,
+}
+
+impl Sha256 {
+    pub fn new() -> Result<Self, io::Error> {
+        let mut res = Self {
+            prov: 0,
+            hash: 0,
+        };
+        unsafe {
+            if 0 == CryptAcquireContextW(&mut res.prov, ptr::null(), ptr::null(), PROV_RSA_AES, CRYPT_VERIFYCONTEXT) {
+                return Err(io::Error::last_os_error());
+            }
+            if 0 == CryptCreateHash(res.prov, CALG_SHA_256, 0, 0, &mut res.hash) {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(res)
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> Result<(), io::Error> {
+        for chunk in data.chunks(u32::MAX as usize) {
+            if 0 == unsafe { CryptHashData(self.hash, chunk.as_ptr(), chunk.len() as u32, 0) } {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish_hex(self) -> Result<String, io::Error> {
+        let mut digest = [0u8; 32];
+        let mut len = digest.len() as u32;
+        if 0 == unsafe { CryptGetHashParam(self.hash, HP_HASHVAL, digest.as_mut_ptr(), &mut len, 0) } {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(digest.iter().map(|b| format!("{:02X}", b)).collect())
+    }
+}
+
+impl Drop for Sha256 {
+    fn drop(&mut self) {
+        unsafe {
+            if 0 != self.hash {
+                CryptDestroyHash(self.hash);
+            }
+            if 0 != self.prov {
+                CryptReleaseContext(self.prov, 0);
+            }
+        }
+    }
+}
+
+// Hash is returned as an uppercase hex string, the form used in
+// checksums of the package parts.
+pub fn sha256_hex(data: &[u8]) -> Result<String, io::Error> {
+    let mut hasher = Sha256::new()?;
+    hasher.update(data)?;
+    hasher.finish_hex()
+}
+
+pub fn sha256_file_hex(path: &Path) -> Result<String, io::Error> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new()?;
+    let mut buf = vec!(0u8; 64 * 1024);
+    loop {
+        let len = file.read(&mut buf)?;
+        if 0 == len {
+            break;
+        }
+        hasher.update(&buf[..len])?;
+    }
+    hasher.finish_hex()
+}
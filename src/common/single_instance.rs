@@ -0,0 +1,150 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::FromRawHandle;
+use std::os::windows::io::RawHandle;
+use std::ptr;
+use std::thread;
+use std::time::Duration;
+
+use nwg_ui as ui;
+use winapi::shared::winerror::ERROR_ALREADY_EXISTS;
+use winapi::shared::winerror::ERROR_PIPE_CONNECTED;
+use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::handleapi::CloseHandle;
+use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+use winapi::um::namedpipeapi::ConnectNamedPipe;
+use winapi::um::namedpipeapi::CreateNamedPipeW;
+use winapi::um::synchapi::CreateMutexW;
+use winapi::um::winbase::PIPE_ACCESS_INBOUND;
+use winapi::um::winbase::PIPE_REJECT_REMOTE_CLIENTS;
+use winapi::um::winbase::PIPE_TYPE_BYTE;
+use winapi::um::winbase::PIPE_UNLIMITED_INSTANCES;
+use winapi::um::winbase::PIPE_WAIT;
+use winapi::um::winnt::HANDLE;
+use winapi::um::winuser::AllowSetForegroundWindow;
+use winapi::um::winuser::SetForegroundWindow;
+use winapi::um::winuser::ASFW_ANY;
+
+const MUTEX_NAME: &str = "Local\\wdb_backup_single_instance";
+const PIPE_BUFFER_SIZE: u32 = 4096;
+const MAX_MESSAGE_LEN: u64 = 32 * 1024;
+const CONNECT_ATTEMPTS: u32 = 10;
+const CONNECT_RETRY_DELAY_MS: u64 = 100;
+
+fn to_wide(st: &str) -> Vec<u16> {
+    OsStr::new(st).encode_wide().chain(Some(0)).collect()
+}
+
+// pipes are visible across sessions, so the name includes the user
+fn pipe_name() -> String {
+    let user: String = env::var("USERNAME").unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    format!("\\\\.\\pipe\\wdb_backup_{}", user)
+}
+
+// Named mutex held while the GUI is running
+pub struct SingleInstance {
+    mutex: HANDLE,
+}
+
+impl SingleInstance {
+    // returns None when another instance is already running
+    pub fn acquire() -> Result<Option<Self>, io::Error> {
+        let name = to_wide(MUTEX_NAME);
+        let mutex = unsafe { CreateMutexW(ptr::null_mut(), 0, name.as_ptr()) };
+        if mutex.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+            unsafe { CloseHandle(mutex) };
+            return Ok(None);
+        }
+        Ok(Some(Self { mutex }))
+    }
+}
+
+impl Drop for SingleInstance {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.mutex) };
+    }
+}
+
+// Passes the file argument (empty to only activate the window) to the running instance
+pub fn forward_to_running_instance(arg: &str) -> Result<(), io::Error> {
+    // running instance needs permission to take the foreground from us
+    unsafe { AllowSetForegroundWindow(ASFW_ANY) };
+    let name = pipe_name();
+    let mut attempt = 1;
+    loop {
+        match OpenOptions::new().write(true).open(&name) {
+            Ok(mut pipe) => return pipe.write_all(arg.as_bytes()),
+            Err(e) => {
+                if attempt >= CONNECT_ATTEMPTS {
+                    return Err(io::Error::new(e.kind(), format!(
+                        "Error connecting to running instance, pipe: {}, message: {}", name, e)));
+                }
+                attempt += 1;
+                thread::sleep(Duration::from_millis(CONNECT_RETRY_DELAY_MS));
+            }
+        }
+    }
+}
+
+// Receives messages from instances started later, runs until the process exits
+pub fn listen_for_instances(sender: ui::SyncNoticeValueSender<String>) {
+    let name = to_wide(&pipe_name());
+    thread::spawn(move || loop {
+        let handle = unsafe {
+            CreateNamedPipeW(name.as_ptr(), PIPE_ACCESS_INBOUND,
+                PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES, 0, PIPE_BUFFER_SIZE, 0, ptr::null_mut())
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            log::error!("Error creating instance pipe, message: {}", io::Error::last_os_error());
+            return;
+        }
+        let connected = unsafe {
+            ConnectNamedPipe(handle, ptr::null_mut()) != 0 || GetLastError() == ERROR_PIPE_CONNECTED
+        };
+        // closes the pipe handle when dropped
+        let pipe = unsafe { File::from_raw_handle(handle as RawHandle) };
+        if !connected {
+            continue;
+        }
+        let mut msg = String::new();
+        match pipe.take(MAX_MESSAGE_LEN).read_to_string(&mut msg) {
+            Ok(_) => sender.send_value(msg),
+            Err(e) => log::error!("Error reading instance pipe, message: {}", e)
+        }
+    });
+}
+
+pub fn bring_to_foreground(window: &nwg::Window) {
+    if let Some(hwnd) = window.handle.hwnd() {
+        unsafe { SetForegroundWindow(hwnd) };
+    }
+}
@@ -0,0 +1,350 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::net::ToSocketAddrs;
+use std::time::Duration;
+
+use native_tls::TlsConnector;
+use native_tls::TlsStream;
+use serde::Deserialize;
+use serde::Serialize;
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SmtpSecurity {
+    None,
+    #[default]
+    StartTls,
+    Tls,
+}
+
+pub struct SmtpMessage<'a> {
+    pub from: &'a str,
+    pub to: Vec<String>,
+    pub subject: &'a str,
+    pub body: &'a str,
+}
+
+enum SmtpStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+    Closed,
+}
+
+impl Read for SmtpStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            SmtpStream::Plain(st) => st.read(buf),
+            SmtpStream::Tls(st) => st.read(buf),
+            SmtpStream::Closed => Err(io::Error::new(io::ErrorKind::NotConnected, "SMTP connection closed"))
+        }
+    }
+}
+
+impl Write for SmtpStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            SmtpStream::Plain(st) => st.write(buf),
+            SmtpStream::Tls(st) => st.write(buf),
+            SmtpStream::Closed => Err(io::Error::new(io::ErrorKind::NotConnected, "SMTP connection closed"))
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            SmtpStream::Plain(st) => st.flush(),
+            SmtpStream::Tls(st) => st.flush(),
+            SmtpStream::Closed => Ok(())
+        }
+    }
+}
+
+pub struct SmtpClient {
+    host: String,
+    stream: SmtpStream,
+}
+
+impl SmtpClient {
+    pub fn connect(host: &str, port: u16, security: SmtpSecurity) -> Result<Self, io::Error> {
+        let addr = match (host, port).to_socket_addrs()?.next() {
+            Some(addr) => addr,
+            None => return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+                "SMTP server address not resolved, host: {}", host)))
+        };
+        let tcp = TcpStream::connect_timeout(&addr, SMTP_TIMEOUT)?;
+        tcp.set_read_timeout(Some(SMTP_TIMEOUT))?;
+        tcp.set_write_timeout(Some(SMTP_TIMEOUT))?;
+        let stream = if security == SmtpSecurity::Tls {
+            SmtpStream::Tls(Box::new(tls_connect(host, tcp)?))
+        } else {
+            SmtpStream::Plain(tcp)
+        };
+        let mut client = Self {
+            host: host.to_string(),
+            stream
+        };
+        client.read_reply(220)?;
+        client.command("EHLO localhost", 250)?;
+        if security == SmtpSecurity::StartTls {
+            client.command("STARTTLS", 220)?;
+            let tcp = match std::mem::replace(&mut client.stream, SmtpStream::Closed) {
+                SmtpStream::Plain(tcp) => tcp,
+                _ => return Err(io::Error::new(io::ErrorKind::Other, "Invalid SMTP connection state"))
+            };
+            client.stream = SmtpStream::Tls(Box::new(tls_connect(&client.host, tcp)?));
+            client.command("EHLO localhost", 250)?;
+        }
+        Ok(client)
+    }
+
+    // credentials are sent base64-encoded, that is in clear text, so they
+    // are never sent over a connection without TLS
+    pub fn login(&mut self, username: &str, password: &str) -> Result<(), io::Error> {
+        if let SmtpStream::Plain(_) = self.stream {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!(
+                "SMTP authentication over an unencrypted connection is not allowed, user: {}, \
+                select STARTTLS or TLS security", username)));
+        }
+        let credentials = format!("\0{}\0{}", username, password);
+        let cmd = format!("AUTH PLAIN {}", base64_encode(credentials.as_bytes()));
+        self.command(&cmd, 235).map_err(|e| io::Error::new(e.kind(), format!(
+            "SMTP authentication failed, user: {}, message: {}", username, e)))
+    }
+
+    pub fn send(&mut self, msg: &SmtpMessage) -> Result<(), io::Error> {
+        check_address(msg.from)?;
+        for rcpt in &msg.to {
+            check_address(rcpt)?;
+        }
+        self.command(&format!("MAIL FROM:<{}>", msg.from), 250)?;
+        for rcpt in &msg.to {
+            self.command(&format!("RCPT TO:<{}>", rcpt), 250)?;
+        }
+        self.command("DATA", 354)?;
+        let mut data = String::new();
+        data.push_str(&format!("From: {}\r\n", msg.from));
+        data.push_str(&format!("To: {}\r\n", msg.to.join(", ")));
+        data.push_str(&format!("Subject: {}\r\n", encode_header(msg.subject)));
+        data.push_str(&format!("Date: {}\r\n", chrono::Local::now().to_rfc2822()));
+        data.push_str("MIME-Version: 1.0\r\n");
+        data.push_str("Content-Type: text/plain; charset=utf-8\r\n");
+        data.push_str("Content-Transfer-Encoding: 8bit\r\n");
+        data.push_str("\r\n");
+        for line in msg.body.replace("\r\n", "\n").split('\n') {
+            // dot-stuffing, RFC 5321 section 4.5.2
+            if line.starts_with('.') {
+                data.push('.');
+            }
+            data.push_str(line);
+            data.push_str("\r\n");
+        }
+        data.push('.');
+        self.command(&data, 250)
+    }
+
+    pub fn quit(mut self) -> Result<(), io::Error> {
+        self.command("QUIT", 221)
+    }
+
+    fn command(&mut self, cmd: &str, expected: u16) -> Result<(), io::Error> {
+        self.stream.write_all(cmd.as_bytes())?;
+        self.stream.write_all(b"\r\n")?;
+        self.stream.flush()?;
+        self.read_reply(expected)
+    }
+
+    fn read_reply(&mut self, expected: u16) -> Result<(), io::Error> {
+        let mut reply = String::new();
+        loop {
+            let line = self.read_line()?;
+            reply.push_str(&line);
+            reply.push_str("\r\n");
+            // multiline replies use '-' after the code on all lines except the last one
+            if line.len() < 4 || line.as_bytes()[3] != b'-' {
+                let code = line.get(0..3).unwrap_or("").parse::<u16>().unwrap_or(0);
+                if code == expected || (expected == 250 && code == 251) {
+                    return Ok(());
+                }
+                return Err(io::Error::new(io::ErrorKind::Other, format!(
+                    "Unexpected SMTP server reply, expected code: {}, reply: {}", expected, reply.trim_end())));
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String, io::Error> {
+        let mut bytes = Vec::new();
+        let mut buf = [0u8; 1];
+        loop {
+            let read = self.stream.read(&mut buf)?;
+            if read == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "SMTP connection closed by server"));
+            }
+            if buf[0] == b'\n' {
+                break;
+            }
+            if buf[0] != b'\r' {
+                bytes.push(buf[0]);
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
+
+fn tls_connect(host: &str, tcp: TcpStream) -> Result<TlsStream<TcpStream>, io::Error> {
+    let connector = match TlsConnector::new() {
+        Ok(connector) => connector,
+        Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e.to_string()))
+    };
+    match connector.connect(host, tcp) {
+        Ok(stream) => Ok(stream),
+        Err(e) => Err(io::Error::new(io::ErrorKind::Other, format!(
+            "SMTP TLS handshake failed, host: {}, message: {}", host, e)))
+    }
+}
+
+// line breaks in addresses would add SMTP commands or message headers
+fn check_address(address: &str) -> Result<(), io::Error> {
+    if address.contains(is_line_break) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+            "Invalid email address: {}", address.escape_default())));
+    }
+    Ok(())
+}
+
+// line breaks in a header value would start a new header or end the
+// DATA block early, they are replaced with spaces
+fn encode_header(value: &str) -> String {
+    let value = value.replace(is_line_break, " ");
+    if value.is_ascii() {
+        value
+    } else {
+        format!("=?utf-8?B?{}?=", base64_encode(value.as_bytes()))
+    }
+}
+
+fn is_line_break(ch: char) -> bool {
+    '\r' == ch || '\n' == ch
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut res = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+        let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+        res.push(BASE64_CHARS[(triple >> 18) as usize & 0x3f] as char);
+        res.push(BASE64_CHARS[(triple >> 12) as usize & 0x3f] as char);
+        if chunk.len() > 1 {
+            res.push(BASE64_CHARS[(triple >> 6) as usize & 0x3f] as char);
+        } else {
+            res.push('=');
+        }
+        if chunk.len() > 2 {
+            res.push(BASE64_CHARS[triple as usize & 0x3f] as char);
+        } else {
+            res.push('=');
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    // replies to every command and returns the lines received after DATA
+    fn fake_server(listener: TcpListener) -> thread::JoinHandle<Vec<String>> {
+        thread::spawn(move || {
+            let (tcp, _) = listener.accept().unwrap();
+            let mut writer = tcp.try_clone().unwrap();
+            let mut reader = BufReader::new(tcp);
+            writer.write_all(b"220 localhost\r\n").unwrap();
+            let mut data = Vec::new();
+            let mut in_data = false;
+            loop {
+                let mut line = String::new();
+                if 0 == reader.read_line(&mut line).unwrap() {
+                    break;
+                }
+                let line = line.trim_end_matches("\r\n").to_string();
+                if in_data {
+                    if "." == line {
+                        in_data = false;
+                        writer.write_all(b"250 queued\r\n").unwrap();
+                    } else {
+                        data.push(line);
+                    }
+                } else if "DATA" == line {
+                    in_data = true;
+                    writer.write_all(b"354 go ahead\r\n").unwrap();
+                } else if "QUIT" == line {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    writer.write_all(b"250 ok\r\n").unwrap();
+                }
+            }
+            data
+        })
+    }
+
+    #[test]
+    fn header_line_breaks() {
+        assert_eq!(encode_header("Backup of mydb\r\n.\r\nRSET"), "Backup of mydb  .  RSET");
+        assert_eq!(encode_header("Backup\nBcc: x@example.com"), "Backup Bcc: x@example.com");
+        assert!(check_address("backup@example.com").is_ok());
+        let err = check_address("backup@example.com\r\nBcc: x@example.com").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn send_without_injected_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = fake_server(listener);
+        let mut client = SmtpClient::connect("127.0.0.1", port, SmtpSecurity::None).unwrap();
+        let err = client.send(&SmtpMessage {
+            from: "backup@example.com\nBcc: x@example.com",
+            to: vec!("dba@example.com".to_string()),
+            subject: "Backup",
+            body: "",
+        }).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        client.send(&SmtpMessage {
+            from: "backup@example.com",
+            to: vec!("dba@example.com".to_string()),
+            subject: "Backup of mydb\r\n.\r\nRSET",
+            body: "done",
+        }).unwrap();
+        client.quit().unwrap();
+        let data = server.join().unwrap();
+        assert_eq!(data[2], "Subject: Backup of mydb  .  RSET");
+        assert!(data.iter().all(|line| "RSET" != line && "." != line));
+        assert_eq!(data.last().map(|line| line.as_str()), Some("done"));
+    }
+}
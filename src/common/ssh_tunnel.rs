@@ -0,0 +1,149 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::windows::process::CommandExt;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use super::*;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+const PROBE_INTERVAL_MILLIS: u64 = 200;
+
+// Tunnels are shared by all connections to the same DB server and are kept
+// open until the app exits, ssh process is started again when it dies.
+static TUNNELS: Mutex<Vec<SshTunnel>> = Mutex::new(Vec::new());
+
+struct SshTunnel {
+    key: String,
+    local_port: u16,
+    handle: duct::Handle,
+}
+
+// Local port forwarded to the DB server, OpenSSH client shipped with Windows
+// is used, it authenticates with the key file or with ssh-agent keys,
+// password prompts are disabled.
+pub fn ssh_tunnel_port(pcc: &PgConnConfig, timeout: Duration) -> Result<u16, io::Error> {
+    let key = format!("{}@{}|{}|{}:{}", pcc.ssh_user, pcc.ssh_host, pcc.ssh_key_file, pcc.hostname, pcc.port);
+    let mut tunnels = match TUNNELS.lock() {
+        Ok(tunnels) => tunnels,
+        Err(e) => e.into_inner()
+    };
+    if let Some(idx) = tunnels.iter().position(|tn| tn.key == key) {
+        if let Ok(None) = tunnels[idx].handle.try_wait() {
+            return Ok(tunnels[idx].local_port);
+        }
+        log::warn!("SSH tunnel process exited, reopening, host: {}", pcc.ssh_host);
+        tunnels.remove(idx);
+    }
+    let tunnel = open_tunnel(pcc, key, timeout)?;
+    let port = tunnel.local_port;
+    tunnels.push(tunnel);
+    Ok(port)
+}
+
+// ssh processes are not stopped by Windows when the app exits
+pub fn close_ssh_tunnels() {
+    let mut tunnels = match TUNNELS.lock() {
+        Ok(tunnels) => tunnels,
+        Err(e) => e.into_inner()
+    };
+    for tn in tunnels.drain(..) {
+        if let Err(e) = tn.handle.kill() {
+            log::warn!("Error stopping SSH tunnel process, message: {}", e);
+        }
+    }
+}
+
+fn open_tunnel(pcc: &PgConnConfig, key: String, timeout: Duration) -> Result<SshTunnel, io::Error> {
+    let local_port = free_local_port()?;
+    let mut args: Vec<String> = vec!(
+        "-N".to_string(),
+        "-L".to_string(), format!("127.0.0.1:{}:{}:{}", local_port, pcc.hostname, pcc.port),
+        "-o".to_string(), "BatchMode=yes".to_string(),
+        "-o".to_string(), "ExitOnForwardFailure=yes".to_string(),
+        "-o".to_string(), "StrictHostKeyChecking=accept-new".to_string(),
+        "-o".to_string(), "ServerAliveInterval=30".to_string(),
+        "-o".to_string(), format!("ConnectTimeout={}", timeout.as_secs()),
+    );
+    if !pcc.ssh_key_file.is_empty() {
+        args.push("-i".to_string());
+        args.push(pcc.ssh_key_file.clone());
+    }
+    if !pcc.ssh_user.is_empty() {
+        args.push("-l".to_string());
+        args.push(pcc.ssh_user.clone());
+    }
+    args.push(pcc.ssh_host.clone());
+    log::info!("Opening SSH tunnel: ssh {}", args.join(" "));
+    let handle = match duct::cmd("ssh", &args)
+        .stdin_null()
+        .stdout_null()
+        .stderr_capture()
+        .unchecked()
+        .before_spawn(|pcmd| {
+            let _ = pcmd.creation_flags(CREATE_NO_WINDOW);
+            Ok(())
+        })
+        .start() {
+        Ok(handle) => handle,
+        Err(e) => return Err(io::Error::new(e.kind(), format!(
+            "SSH client (ssh.exe) spawn failure: {}", e)))
+    };
+
+    // forward is ready when the local port accepts connections
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, local_port));
+    let probe_timeout = Duration::from_millis(PROBE_INTERVAL_MILLIS);
+    let deadline = Instant::now() + timeout;
+    loop {
+        match handle.try_wait() {
+            Ok(None) => { },
+            Ok(Some(output)) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "SSH tunnel failure, host: {}, message: {}",
+                pcc.ssh_host, String::from_utf8_lossy(&output.stderr).trim()))),
+            Err(e) => return Err(io::Error::new(e.kind(), format!(
+                "SSH tunnel failure, host: {}, message: {}", pcc.ssh_host, e)))
+        }
+        if TcpStream::connect_timeout(&addr, probe_timeout).is_ok() {
+            log::info!("SSH tunnel opened, host: {}, local port: {}", pcc.ssh_host, local_port);
+            return Ok(SshTunnel {
+                key,
+                local_port,
+                handle
+            });
+        }
+        if Instant::now() > deadline {
+            let _ = handle.kill();
+            return Err(io::Error::new(io::ErrorKind::TimedOut, format!(
+                "SSH tunnel was not opened in {} seconds, host: {}", timeout.as_secs(), pcc.ssh_host)));
+        }
+        thread::sleep(probe_timeout);
+    }
+}
+
+// port is released before ssh binds it, a collision is reported by ssh
+fn free_local_port() -> Result<u16, io::Error> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
@@ -0,0 +1,308 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::os::windows::fs::OpenOptionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use zip::CompressionMethod;
+use zip::ZipWriter;
+use zip::write::FileOptions;
+use pg_toc::Throttle;
+
+use super::long_path;
+use super::sha256::Sha256;
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(1);
+// Zip64 is required for the entries of 4 GB and larger, deflated size of
+// incompressible data can slightly exceed the original size
+const DEFLATE_OVERHEAD_DIVISOR: u64 = 1024;
+
+// Adds files to the zip while pg_dump is still writing the dump directory,
+// each file is removed from disk as soon as it is zipped, so the backup
+// needs only slightly more space than the resulting archive.
+//
+// pg_dump keeps a data file open until it is fully written, file that can
+// be opened without sharing is considered complete; "toc.dat" is written
+// when pg_dump finishes. All files are placed in the archive under the
+// dump directory name, SHA-256 of each file is taken while it is zipped.
+pub struct StreamingZip {
+    dump_dir: PathBuf,
+    zip_path: PathBuf,
+    root_name: String,
+    writer: ZipWriter<BufWriter<File>>,
+    added: HashSet<String>,
+    checksums: BTreeMap<String, String>,
+    last_scan: Option<Instant>,
+    compression: CompressionMethod,
+    throttle: Throttle,
+}
+
+impl StreamingZip {
+    pub fn create(dump_dir: &Path, zip_path: &Path) -> Result<Self, io::Error> {
+        let root_name = match dump_dir.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+                "Invalid dump directory: {}", dump_dir.to_string_lossy())))
+        };
+        // dump file names are long, dump directory may be deep
+        let zip_path = long_path(zip_path);
+        let file = File::create(&zip_path)?;
+        let mut writer = ZipWriter::new(BufWriter::new(file));
+        writer.add_directory(format!("{}/", root_name), Self::options(CompressionMethod::Stored, false))?;
+        Ok(Self {
+            dump_dir: long_path(dump_dir),
+            zip_path,
+            root_name,
+            writer,
+            added: HashSet::new(),
+            checksums: BTreeMap::new(),
+            last_scan: None,
+            compression: CompressionMethod::Stored,
+            throttle: Throttle::unlimited(),
+        })
+    }
+
+    // files that are not compressed on disk, like CSV exports, are deflated
+    pub fn with_compression(mut self) -> Self {
+        self.compression = CompressionMethod::Deflated;
+        self
+    }
+
+    // data read from the dump directory is limited to the throttle rate
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    // called on every line of pg_dump output, directory is scanned at most once per second
+    pub fn add_completed<F: FnMut(&str)>(&mut self, listener: F) -> Result<(), io::Error> {
+        if let Some(last) = self.last_scan {
+            if last.elapsed() < SCAN_INTERVAL {
+                return Ok(());
+            }
+        }
+        self.last_scan = Some(Instant::now());
+        self.add_files(false, listener)
+    }
+
+    // called after pg_dump exited, all data files are complete, "toc.dat"
+    // is left on disk to be read for the manifest and is added by "finish"
+    pub fn add_data_files<F: FnMut(&str)>(&mut self, listener: F) -> Result<(), io::Error> {
+        self.last_scan = Some(Instant::now());
+        self.add_files(false, listener)
+    }
+
+    // file name -> SHA-256 of the files zipped so far
+    pub fn checksums(&self) -> &BTreeMap<String, String> {
+        &self.checksums
+    }
+
+    // called after pg_dump exited successfully
+    pub fn finish<F: FnMut(&str)>(mut self, listener: F) -> Result<(), io::Error> {
+        self.add_files(true, listener)?;
+        self.writer.finish()?;
+        fs::remove_dir_all(&self.dump_dir)?;
+        Ok(())
+    }
+
+    // called when pg_dump failed, partial archive is removed
+    pub fn abort(self) {
+        let zip_path = self.zip_path.clone();
+        drop(self);
+        let _ = fs::remove_file(zip_path);
+    }
+
+    fn add_files<F: FnMut(&str)>(&mut self, all: bool, mut listener: F) -> Result<(), io::Error> {
+        // pg_dump creates the directory itself
+        if !self.dump_dir.exists() {
+            return Ok(());
+        }
+        let mut names = Vec::new();
+        for en_res in fs::read_dir(&self.dump_dir)? {
+            let en = en_res?;
+            if !en.file_type()?.is_file() {
+                continue;
+            }
+            let name = en.file_name().to_string_lossy().to_string();
+            if self.added.contains(&name) || (!all && "toc.dat" == name) {
+                continue;
+            }
+            names.push(name);
+        }
+        names.sort();
+        for name in names {
+            let path = self.dump_dir.join(&name);
+            let opened = fs::OpenOptions::new()
+                .read(true)
+                .share_mode(0)
+                .open(&path);
+            let mut file = match opened {
+                Ok(file) => file,
+                // still being written by pg_dump
+                Err(_) if !all => continue,
+                Err(e) => return Err(e)
+            };
+            let len = file.metadata()?.len();
+            let entry_name = format!("{}/{}", self.root_name, name);
+            self.writer.start_file(entry_name.as_str(), Self::options(self.compression, Self::is_large_file(self.compression, len)))?;
+            let mut hasher = Sha256::new()?;
+            let mut buf = vec!(0u8; 64 * 1024);
+            loop {
+                let read = file.read(&mut buf)?;
+                if 0 == read {
+                    break;
+                }
+                hasher.update(&buf[..read])?;
+                self.writer.write_all(&buf[..read])?;
+                self.throttle.consume(read);
+            }
+            self.checksums.insert(name.clone(), hasher.finish_hex()?);
+            drop(file);
+            fs::remove_file(&path)?;
+            self.added.insert(name);
+            listener(&entry_name);
+        }
+        Ok(())
+    }
+
+    fn is_large_file(compression: CompressionMethod, len: u64) -> bool {
+        let max_written = match compression {
+            CompressionMethod::Stored => len,
+            _ => len + len / DEFLATE_OVERHEAD_DIVISOR + 64 * 1024
+        };
+        max_written >= u32::MAX as u64
+    }
+
+    // data files are already compressed by pg_dump and are stored by default
+    fn options(compression: CompressionMethod, large_file: bool) -> FileOptions {
+        FileOptions::default()
+            .compression_method(compression)
+            .large_file(large_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use pg_toc::TocArchive;
+    use pg_toc::TocEntry;
+    use pg_toc::TocHeader;
+
+    use super::*;
+
+    const MAX_32: u64 = u32::MAX as u64;
+    const DATA_FILE_NAME: &str = "3000.dat";
+
+    #[test]
+    fn large_file_thresholds() {
+        let stored = CompressionMethod::Stored;
+        assert!(!StreamingZip::is_large_file(stored, 0));
+        assert!(!StreamingZip::is_large_file(stored, MAX_32 - 1));
+        assert!(StreamingZip::is_large_file(stored, MAX_32));
+        assert!(StreamingZip::is_large_file(stored, MAX_32 + 1));
+        assert!(StreamingZip::is_large_file(stored, 16 * (MAX_32 + 1)));
+
+        // deflate overhead is reserved for incompressible data
+        let deflated = CompressionMethod::Deflated;
+        assert!(!StreamingZip::is_large_file(deflated, 0));
+        assert!(!StreamingZip::is_large_file(deflated, 4_000_000_000));
+        assert!(StreamingZip::is_large_file(deflated, MAX_32 - MAX_32 / DEFLATE_OVERHEAD_DIVISOR));
+        assert!(StreamingZip::is_large_file(deflated, MAX_32 - 1));
+        assert!(StreamingZip::is_large_file(deflated, MAX_32));
+        assert!(StreamingZip::is_large_file(deflated, MAX_32 + 1));
+    }
+
+    // minimal directory format TOC with a single data entry
+    fn write_toc(dump_dir: &Path) {
+        let toc = TocArchive {
+            header: TocHeader {
+                vmaj: 1,
+                vmin: 14,
+                vrev: 0,
+                int_size: 4,
+                off_size: 8,
+                format: 5,
+                timestamp: vec!(0; 7),
+                dbname: Some("test".to_string()),
+                ..Default::default()
+            },
+            entries: vec!(TocEntry {
+                dump_id: 3000,
+                had_dumper: 1,
+                tag: Some("big".to_string()),
+                desc: Some("TABLE DATA".to_string()),
+                namespace: Some("public".to_string()),
+                copy_stmt: Some("COPY public.big (data) FROM stdin;\n".to_string()),
+                filename: Some(DATA_FILE_NAME.to_string()),
+                ..Default::default()
+            }),
+            trailer: Vec::new(),
+        };
+        toc.write_file(&dump_dir.join("toc.dat")).unwrap();
+    }
+
+    // Data file is created sparse where supported and is read back as zeros,
+    // the zip needs up to 4 GB of disk space, same for the extracted copy.
+    fn round_trip_over_4gb(name: &str, compression: CompressionMethod) {
+        let base_dir = env::temp_dir().join(format!("wdb_backup_zip64_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&base_dir);
+        let dump_dir = base_dir.join("dump");
+        fs::create_dir_all(&dump_dir).unwrap();
+        write_toc(&dump_dir);
+        let data_len = MAX_32 + 1 + 1024 * 1024;
+        File::create(dump_dir.join(DATA_FILE_NAME)).unwrap().set_len(data_len).unwrap();
+
+        let zip_path = base_dir.join("dump.zip");
+        let mut zip = StreamingZip::create(&dump_dir, &zip_path).unwrap();
+        if CompressionMethod::Deflated == compression {
+            zip = zip.with_compression();
+        }
+        zip.add_data_files(|_| {}).unwrap();
+        zip.finish(|_| {}).unwrap();
+        // root directory, data file and TOC written after it at an offset over 4 GB
+        assert_eq!(pg_toc::verify_zip(&zip_path).unwrap(), 3);
+
+        let dest_dir = base_dir.join("extracted");
+        let extracted = pg_toc::extract_zip_dump(&zip_path, &dest_dir, Throttle::unlimited(), |_| {}, |_| Ok(())).unwrap();
+        assert_eq!(fs::metadata(extracted.join(DATA_FILE_NAME)).unwrap().len(), data_len);
+        assert!(TocArchive::read_file(&extracted.join("toc.dat")).is_ok());
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    #[ignore] // writes and reads more than 8 GB
+    fn stored_entry_over_4gb() {
+        round_trip_over_4gb("stored", CompressionMethod::Stored);
+    }
+
+    #[test]
+    #[ignore] // compresses more than 4 GB
+    fn deflated_entry_over_4gb() {
+        round_trip_over_4gb("deflated", CompressionMethod::Deflated);
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ptr;
+
+use winapi::Interface;
+use winapi::ctypes::c_void;
+use winapi::shared::windef::HWND;
+use winapi::shared::winerror::SUCCEEDED;
+use winapi::shared::wtypesbase::CLSCTX_INPROC_SERVER;
+use winapi::um::combaseapi::CoCreateInstance;
+use winapi::um::combaseapi::CoInitializeEx;
+use winapi::um::objbase::COINIT_APARTMENTTHREADED;
+use winapi::um::shobjidl_core::CLSID_TaskbarList;
+use winapi::um::shobjidl_core::ITaskbarList3;
+use winapi::um::shobjidl_core::TBPF_ERROR;
+use winapi::um::shobjidl_core::TBPF_INDETERMINATE;
+use winapi::um::shobjidl_core::TBPF_NOPROGRESS;
+use winapi::um::shobjidl_core::TBPF_NORMAL;
+
+// Progress shown on the taskbar button of the specified window,
+// all calls are no-op when the taskbar interface is not available
+pub struct TaskbarProgress {
+    taskbar: *mut ITaskbarList3,
+    hwnd: HWND,
+}
+
+impl TaskbarProgress {
+    pub fn new(window: &nwg::Window) -> Option<Self> {
+        let hwnd = window.handle.hwnd()?;
+        let mut taskbar: *mut ITaskbarList3 = ptr::null_mut();
+        unsafe {
+            // result is ignored, COM may be already initialized on this thread
+            let _ = CoInitializeEx(ptr::null_mut(), COINIT_APARTMENTTHREADED);
+            let hr = CoCreateInstance(&CLSID_TaskbarList, ptr::null_mut(), CLSCTX_INPROC_SERVER,
+                &ITaskbarList3::uuidof(), &mut taskbar as *mut *mut ITaskbarList3 as *mut *mut c_void);
+            if !SUCCEEDED(hr) || taskbar.is_null() {
+                return None;
+            }
+            if !SUCCEEDED((*taskbar).HrInit()) {
+                (*taskbar).Release();
+                return None;
+            }
+        }
+        Some(Self {
+            taskbar,
+            hwnd
+        })
+    }
+
+    pub fn set_indeterminate(&self) {
+        unsafe {
+            (*self.taskbar).SetProgressState(self.hwnd, TBPF_INDETERMINATE);
+        }
+    }
+
+    pub fn set_complete(&self) {
+        unsafe {
+            (*self.taskbar).SetProgressState(self.hwnd, TBPF_NORMAL);
+            (*self.taskbar).SetProgressValue(self.hwnd, 1, 1);
+        }
+    }
+
+    pub fn set_error(&self) {
+        unsafe {
+            (*self.taskbar).SetProgressState(self.hwnd, TBPF_ERROR);
+            (*self.taskbar).SetProgressValue(self.hwnd, 1, 1);
+        }
+    }
+
+    pub fn clear(&self) {
+        unsafe {
+            (*self.taskbar).SetProgressState(self.hwnd, TBPF_NOPROGRESS);
+        }
+    }
+}
+
+impl Drop for TaskbarProgress {
+    fn drop(&mut self) {
+        unsafe {
+            (*self.taskbar).Release();
+        }
+    }
+}
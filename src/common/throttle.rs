@@ -0,0 +1,26 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// rates offered in the UI, zero means no limit
+pub fn throttle_presets() -> Vec<(u64, &'static str)> {
+    vec!(
+        (0, "No I/O limit"),
+        (10, "10 MB/s"),
+        (25, "25 MB/s"),
+        (50, "50 MB/s"),
+        (100, "100 MB/s"),
+    )
+}
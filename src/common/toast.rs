@@ -0,0 +1,39 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use winapi::um::winuser::GetForegroundWindow;
+use winapi::um::winuser::IsIconic;
+
+// longer balloon texts are cut by the shell
+const TOAST_TEXT_MAX_LEN: usize = 200;
+
+pub fn window_in_background(window: &nwg::Window) -> bool {
+    let hwnd = match window.handle.hwnd() {
+        Some(hwnd) => hwnd,
+        None => return false
+    };
+    unsafe {
+        IsIconic(hwnd) != 0 || GetForegroundWindow() != hwnd
+    }
+}
+
+// Tray balloons are displayed as toast notifications on Windows 10 and newer
+pub fn show_toast(tray: &nwg::TrayNotification, icon: &nwg::Icon, title: &str, text: &str) {
+    let text: String = text.chars().take(TOAST_TEXT_MAX_LEN).collect();
+    let flags = nwg::TrayNotificationFlags::USER_ICON | nwg::TrayNotificationFlags::LARGE_ICON;
+    tray.set_visibility(true);
+    tray.show(&text, Some(title), Some(flags), Some(icon));
+}
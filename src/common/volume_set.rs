@@ -0,0 +1,171 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+
+use pg_toc::Throttle;
+use pg_toc::ThrottledWriter;
+
+use super::long_path::long_path;
+
+// Backup file can be split into fixed-size volumes "mydb.zip.001",
+// "mydb.zip.002", ... (same naming as 7-Zip and HJSplit use), volumes
+// are concatenated back into "mydb.zip" before restore.
+
+// sizes offered in the UI, zero means the backup is not split
+pub fn volume_size_presets() -> Vec<(u64, &'static str)> {
+    vec!(
+        (0, "Single file"),
+        (650, "650 MB volumes (CD)"),
+        (1024, "1 GB volumes"),
+        (4095, "4 GB volumes (FAT32)"),
+        (4480, "4.7 GB volumes (DVD)"),
+    )
+}
+
+// "mydb.zip.001" for index 1
+pub fn volume_path(path: &Path, idx: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{:03}", idx));
+    PathBuf::from(name)
+}
+
+// "mydb.zip" for "mydb.zip.001", None for the files that are not first volumes
+pub fn volume_set_base(first_volume: &Path) -> Option<PathBuf> {
+    let name = first_volume.file_name()?.to_string_lossy().to_string();
+    match name.strip_suffix(".001") {
+        Some(stem) if !stem.is_empty() => Some(first_volume.with_file_name(stem)),
+        _ => None
+    }
+}
+
+// Consecutive volumes starting from the first one, a gap in numbering is
+// reported as an error instead of silently producing a truncated file.
+pub fn list_volumes(base: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut res = Vec::new();
+    loop {
+        let vol = volume_path(base, res.len() + 1);
+        if !long_path(&vol).is_file() {
+            break;
+        }
+        res.push(vol);
+    }
+    let next = volume_path(base, res.len() + 2);
+    if long_path(&next).is_file() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+            "Volume is missing: {}", volume_path(base, res.len() + 1).to_string_lossy())));
+    }
+    Ok(res)
+}
+
+pub fn volume_set_size(base: &Path) -> Result<u64, io::Error> {
+    let mut res = 0;
+    for vol in list_volumes(base)? {
+        res += fs::metadata(long_path(&vol))?.len();
+    }
+    Ok(res)
+}
+
+// All numbered volumes of the base file, including the ones after a gap
+pub fn remove_volumes(base: &Path) -> Result<usize, io::Error> {
+    let (dir, prefix) = match (base.parent(), base.file_name()) {
+        (Some(dir), Some(name)) => (dir, format!("{}.", name.to_string_lossy().to_lowercase())),
+        _ => return Ok(0)
+    };
+    let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+    let mut count = 0;
+    for en_res in fs::read_dir(long_path(dir))? {
+        let en = en_res?;
+        let name = en.file_name().to_string_lossy().to_lowercase();
+        let is_volume = match name.strip_prefix(&prefix) {
+            Some(num) => num.len() >= 3 && num.chars().all(|ch| ch.is_ascii_digit()),
+            None => false
+        };
+        if is_volume && en.file_type()?.is_file() {
+            fs::remove_file(en.path())?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+// Volumes are cut from the end of the file and the file is truncated after
+// each of them, so only one volume of extra disk space is needed. File that
+// fits into a single volume is left as is. Volumes left from an earlier
+// backup with the same name are removed first.
+pub fn split_into_volumes<F: FnMut(&str)>(path: &Path, volume_size: u64, mut throttle: Throttle, mut listener: F) -> Result<Vec<PathBuf>, io::Error> {
+    if 0 == volume_size {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid volume size: 0"));
+    }
+    remove_volumes(path)?;
+    let size = fs::metadata(long_path(path))?.len();
+    if size <= volume_size {
+        return Ok(vec!(path.to_path_buf()));
+    }
+    let count = ((size + volume_size - 1) / volume_size) as usize;
+    let mut file = OpenOptions::new().read(true).write(true).open(long_path(path))?;
+    for idx in (1..count).rev() {
+        let offset = idx as u64 * volume_size;
+        let vol = volume_path(path, idx + 1);
+        file.seek(SeekFrom::Start(offset))?;
+        let dest = File::create(long_path(&vol))?;
+        io::copy(&mut (&mut file).take(volume_size), &mut ThrottledWriter::new(&dest, &mut throttle))?;
+        dest.sync_all()?;
+        file.set_len(offset)?;
+        listener(&format!("Volume written: {}", vol.to_string_lossy()));
+    }
+    drop(file);
+    let first = volume_path(path, 1);
+    fs::rename(long_path(path), long_path(&first))?;
+    listener(&format!("Volume written: {}", first.to_string_lossy()));
+    Ok((1..=count).map(|idx| volume_path(path, idx)).collect())
+}
+
+// Volumes are concatenated into the base file, existing file is not
+// overwritten, partially written file is removed on error.
+pub fn join_volumes<F: FnMut(&str)>(base: &Path, throttle: Throttle, listener: F) -> Result<u64, io::Error> {
+    let volumes = list_volumes(base)?;
+    if volumes.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+            "Volume not found: {}", volume_path(base, 1).to_string_lossy())));
+    }
+    let mut dest = OpenOptions::new().write(true).create_new(true).open(long_path(base))?;
+    let res = copy_volumes(&volumes, &mut dest, throttle, listener);
+    drop(dest);
+    if res.is_err() {
+        let _ = fs::remove_file(long_path(base));
+    }
+    res
+}
+
+fn copy_volumes<F: FnMut(&str)>(volumes: &[PathBuf], dest: &mut File, mut throttle: Throttle, mut listener: F) -> Result<u64, io::Error> {
+    let mut total = 0;
+    for vol in volumes {
+        let mut src = File::open(long_path(vol))?;
+        total += io::copy(&mut src, &mut ThrottledWriter::new(&*dest, &mut throttle))?;
+        listener(&format!("Volume joined: {}", vol.to_string_lossy()));
+    }
+    dest.sync_all()?;
+    Ok(total)
+}
@@ -1,37 +1,47 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-#[derive(Default, Clone)]
-pub struct ConnectCheckDialogResult {
-    pub success: bool,
-    pub message: String,
-}
-
-impl ConnectCheckDialogResult {
-    pub(super) fn success(message: String) -> Self {
-        Self {
-            success: true,
-            message
-        }
-    }
-
-    pub(super) fn failure(message: String) -> Self {
-        Self {
-            success: false,
-            message
-        }
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default, Clone)]
+pub struct ConnectCheckDialogResult {
+    pub success: bool,
+    pub message: String,
+    pub server_info: Option<ServerInfo>,
+}
+
+impl ConnectCheckDialogResult {
+    pub(super) fn success(server_info: ServerInfo) -> Self {
+        let message = if server_info.babelfish_enabled() {
+            server_info.describe()
+        } else {
+            format!("{}\r\n\r\nWarning: Babelfish extension is not enabled on this DB server.", server_info.describe())
+        };
+        Self {
+            success: true,
+            message,
+            server_info: Some(server_info),
+        }
+    }
+
+    pub(super) fn failure(message: String) -> Self {
+        Self {
+            success: false,
+            message,
+            server_info: None,
+        }
+    }
+}
@@ -1,188 +1,386 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-#[derive(Default)]
-pub(super) struct ConnectDialogControls {
-    layout: ConnectDialogLayout,
-
-    pub(super) font_normal: nwg::Font,
-
-    pub(super) icon: nwg::Icon,
-    pub(super) window: nwg::Window,
-
-    pub(super) hostname_label: nwg::Label,
-    pub(super) hostname_input: nwg::TextInput,
-    pub(super) port_label: nwg::Label,
-    pub(super) port_input: nwg::TextInput,
-    pub(super) username_label: nwg::Label,
-    pub(super) username_input: nwg::TextInput,
-    pub(super) password_label: nwg::Label,
-    pub(super) password_input: nwg::TextInput,
-    pub(super) use_pgpass_checkbox: nwg::CheckBox,
-    pub(super) connect_db_label: nwg::Label,
-    pub(super) connect_db_input: nwg::TextInput,
-    pub(super) enable_tls_checkbox: nwg::CheckBox,
-    pub(super) accept_invalid_tls_checkbox: nwg::CheckBox,
-
-    pub(super) test_button: nwg::Button,
-    pub(super) load_button: nwg::Button,
-    pub(super) cancel_button: nwg::Button,
-
-    pub(super) check_notice: ui::SyncNotice,
-    pub(super) load_notice: ui::SyncNotice,
-}
-
-impl ui::Controls for ConnectDialogControls {
-
-    fn build(&mut self) -> Result<(), nwg::NwgError> {
-        nwg::Font::builder()
-            .size(ui::font_size_builder()
-                .normal()
-                .build())
-            .build(&mut self.font_normal)?;
-
-        nwg::Icon::builder()
-            .source_embed(Some(&nwg::EmbedResource::load(None)
-                .expect("Error loading embedded resource")))
-            .source_embed_id(2)
-            .build(&mut self.icon)?;
-
-        nwg::Window::builder()
-            .size((480, 310))
-            .icon(Some(&self.icon))
-            .center(true)
-            .title("DB Connection")
-            .build(&mut self.window)?;
-
-        nwg::Label::builder()
-            .text("Hostname:")
-            .font(Some(&self.font_normal))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.window)
-            .build(&mut self.hostname_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.hostname_input)?;
-        nwg::Label::builder()
-            .text("Port:")
-            .font(Some(&self.font_normal))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.window)
-            .build(&mut self.port_label)?;
-        nwg::TextInput::builder()
-            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.port_input)?;
-        nwg::Label::builder()
-            .text("Username:")
-            .font(Some(&self.font_normal))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.window)
-            .build(&mut self.username_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.username_input)?;
-        nwg::Label::builder()
-            .text("Password:")
-            .font(Some(&self.font_normal))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.window)
-            .build(&mut self.password_label)?;
-        nwg::CheckBox::builder()
-            .check_state(nwg::CheckBoxState::Unchecked)
-            .text("Read password from pgpass.conf file")
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.use_pgpass_checkbox)?;
-        nwg::TextInput::builder()
-            .password(Some('*'))
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.password_input)?;
-        nwg::Label::builder()
-            .text("Connect DB:")
-            .font(Some(&self.font_normal))
-            .h_align(nwg::HTextAlign::Left)
-            .parent(&self.window)
-            .build(&mut self.connect_db_label)?;
-        nwg::TextInput::builder()
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.connect_db_input)?;
-        nwg::CheckBox::builder()
-            .check_state(nwg::CheckBoxState::Checked)
-            .text("Enable TLS")
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.enable_tls_checkbox)?;
-        nwg::CheckBox::builder()
-            .check_state(nwg::CheckBoxState::Checked)
-            .text("Accept invalid TLS certificates/hosts")
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.accept_invalid_tls_checkbox)?;
-
-        nwg::Button::builder()
-            .text("Test connection")
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.test_button)?;
-
-        nwg::Button::builder()
-            .text("Load DB names")
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.load_button)?;
-
-        nwg::Button::builder()
-            .text("Cancel")
-            .font(Some(&self.font_normal))
-            .parent(&self.window)
-            .build(&mut self.cancel_button)?;
-
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.check_notice)?;
-        ui::notice_builder()
-            .parent(&self.window)
-            .build(&mut self.load_notice)?;
-
-        self.layout.build(&self)?;
-
-        Ok(())
-    }
-
-    fn update_tab_order(&self) {
-        ui::tab_order_builder()
-            .control(&self.hostname_input)
-            .control(&self.port_input)
-            .control(&self.username_input)
-            .control(&self.password_input)
-            .control(&self.use_pgpass_checkbox)
-            .control(&self.connect_db_input)
-            .control(&self.enable_tls_checkbox)
-            .control(&self.accept_invalid_tls_checkbox)
-            .control(&self.test_button)
-            .control(&self.load_button)
-            .control(&self.cancel_button)
-            .build();
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ConnectDialogControls {
+    layout: ConnectDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) service_label: nwg::Label,
+    pub(super) service_combo: nwg::ComboBox<String>,
+    pub(super) uri_label: nwg::Label,
+    pub(super) uri_input: nwg::TextInput,
+    pub(super) uri_apply_button: nwg::Button,
+    pub(super) uri_copy_button: nwg::Button,
+    pub(super) hostname_label: nwg::Label,
+    pub(super) hostname_input: nwg::TextInput,
+    pub(super) port_label: nwg::Label,
+    pub(super) port_input: nwg::TextInput,
+    pub(super) username_label: nwg::Label,
+    pub(super) username_input: nwg::TextInput,
+    pub(super) password_label: nwg::Label,
+    pub(super) password_input: nwg::TextInput,
+    pub(super) use_pgpass_checkbox: nwg::CheckBox,
+    pub(super) connect_db_label: nwg::Label,
+    pub(super) connect_db_input: nwg::TextInput,
+    pub(super) enable_tls_checkbox: nwg::CheckBox,
+    pub(super) accept_invalid_tls_checkbox: nwg::CheckBox,
+    pub(super) tls_ca_file_label: nwg::Label,
+    pub(super) tls_ca_file_input: nwg::TextInput,
+    pub(super) tls_ca_file_button: nwg::Button,
+    pub(super) tls_ca_file_chooser: nwg::FileDialog,
+    pub(super) connect_timeout_label: nwg::Label,
+    pub(super) connect_timeout_input: nwg::TextInput,
+    pub(super) connect_timeout_hint_label: nwg::Label,
+    pub(super) read_timeout_label: nwg::Label,
+    pub(super) read_timeout_input: nwg::TextInput,
+    pub(super) read_timeout_hint_label: nwg::Label,
+    pub(super) statement_timeout_label: nwg::Label,
+    pub(super) statement_timeout_input: nwg::TextInput,
+    pub(super) statement_timeout_hint_label: nwg::Label,
+    pub(super) retries_label: nwg::Label,
+    pub(super) retries_input: nwg::TextInput,
+    pub(super) retries_hint_label: nwg::Label,
+    pub(super) ssh_host_label: nwg::Label,
+    pub(super) ssh_host_input: nwg::TextInput,
+    pub(super) ssh_user_label: nwg::Label,
+    pub(super) ssh_user_input: nwg::TextInput,
+    pub(super) ssh_key_file_label: nwg::Label,
+    pub(super) ssh_key_file_input: nwg::TextInput,
+
+    pub(super) test_button: nwg::Button,
+    pub(super) load_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+
+    pub(super) check_notice: ui::SyncNotice,
+    pub(super) load_notice: ui::SyncNotice,
+}
+
+impl ui::Controls for ConnectDialogControls {
+
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((480, 610))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("DB Connection")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Service:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.service_label)?;
+        nwg::ComboBox::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.service_combo)?;
+        nwg::Label::builder()
+            .text("URI:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.uri_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("postgresql://user@host:port/db?sslmode=require"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.uri_input)?;
+        nwg::Button::builder()
+            .text("Apply")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.uri_apply_button)?;
+        nwg::Button::builder()
+            .text("Copy")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.uri_copy_button)?;
+        nwg::Label::builder()
+            .text("Hostname:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.hostname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.hostname_input)?;
+        nwg::Label::builder()
+            .text("Port:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.port_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.port_input)?;
+        nwg::Label::builder()
+            .text("Username:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.username_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.username_input)?;
+        nwg::Label::builder()
+            .text("Password:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.password_label)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Read password from pgpass.conf file")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.use_pgpass_checkbox)?;
+        nwg::TextInput::builder()
+            .password(Some('*'))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.password_input)?;
+        nwg::Label::builder()
+            .text("Connect DB:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.connect_db_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.connect_db_input)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Checked)
+            .text("Enable TLS")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.enable_tls_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Checked)
+            .text("Accept invalid TLS certificates/hosts")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.accept_invalid_tls_checkbox)?;
+        nwg::Label::builder()
+            .text("TLS CA file:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.tls_ca_file_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Windows certificate store when empty"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.tls_ca_file_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.tls_ca_file_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose CA certificates file")
+            .action(nwg::FileDialogAction::Open)
+            .filters("Certificates(*.pem;*.crt)|Any(*.*)")
+            .build(&mut self.tls_ca_file_chooser)?;
+        nwg::Label::builder()
+            .text("Connect timeout:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.connect_timeout_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .text("0")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.connect_timeout_input)?;
+        nwg::Label::builder()
+            .text("seconds, 0 - default (10)")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.connect_timeout_hint_label)?;
+        nwg::Label::builder()
+            .text("Read timeout:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.read_timeout_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .text("0")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.read_timeout_input)?;
+        nwg::Label::builder()
+            .text("seconds, 0 - OS default")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.read_timeout_hint_label)?;
+        nwg::Label::builder()
+            .text("Query timeout:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.statement_timeout_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .text("0")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.statement_timeout_input)?;
+        nwg::Label::builder()
+            .text("seconds, 0 - no limit")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.statement_timeout_hint_label)?;
+        nwg::Label::builder()
+            .text("Retries:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.retries_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .text("0")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.retries_input)?;
+        nwg::Label::builder()
+            .text("on connection failures, 0 - no retries")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.retries_hint_label)?;
+        nwg::Label::builder()
+            .text("SSH host:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.ssh_host_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Direct connection when empty"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.ssh_host_input)?;
+        nwg::Label::builder()
+            .text("SSH user:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.ssh_user_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Current user when empty"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.ssh_user_input)?;
+        nwg::Label::builder()
+            .text("SSH key file:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.ssh_key_file_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Default keys or ssh-agent when empty"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.ssh_key_file_input)?;
+
+        nwg::Button::builder()
+            .text("Test connection")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.test_button)?;
+
+        nwg::Button::builder()
+            .text("Load DB names")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.load_button)?;
+
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.check_notice)?;
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.load_notice)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.service_combo)
+            .control(&self.uri_input)
+            .control(&self.uri_apply_button)
+            .control(&self.uri_copy_button)
+            .control(&self.hostname_input)
+            .control(&self.port_input)
+            .control(&self.username_input)
+            .control(&self.password_input)
+            .control(&self.use_pgpass_checkbox)
+            .control(&self.connect_db_input)
+            .control(&self.enable_tls_checkbox)
+            .control(&self.accept_invalid_tls_checkbox)
+            .control(&self.tls_ca_file_input)
+            .control(&self.tls_ca_file_button)
+            .control(&self.connect_timeout_input)
+            .control(&self.read_timeout_input)
+            .control(&self.statement_timeout_input)
+            .control(&self.retries_input)
+            .control(&self.ssh_host_input)
+            .control(&self.ssh_user_input)
+            .control(&self.ssh_key_file_input)
+            .control(&self.test_button)
+            .control(&self.load_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
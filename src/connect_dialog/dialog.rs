@@ -1,186 +1,264 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-use nwg::EventData;
-
-#[derive(Default)]
-pub struct ConnectDialog {
-    pub(super) c: ConnectDialogControls,
-
-    args: ConnectDialogArgs,
-    result: ConnectDialogResult,
-    check_join_handle: ui::PopupJoinHandle<ConnectCheckDialogResult>,
-    load_join_handle: ui::PopupJoinHandle<LoadDbnamesDialogResult>,
-}
-
-impl ConnectDialog {
-    pub(super) fn open_check_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(false);
-        let config = self.config_from_input();
-        let args = ConnectCheckDialogArgs::new(&self.c.check_notice, config);
-        self.check_join_handle = ConnectCheckDialog::popup(args);
-    }
-
-    pub(super) fn await_check_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.check_notice.receive();
-        let _ = self.check_join_handle.join();
-        ui::shake_window(&self.c.window);
-        self.c.update_tab_order();
-    }
-
-    pub(super) fn open_load_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(false);
-        let config = self.config_from_input();
-        let args = LoadDbnamesDialogArgs::new(&self.c.load_notice, config);
-        self.load_join_handle = LoadDbnamesDialog::popup(args);
-    }
-
-    pub(super) fn await_load_dialog(&mut self, _: nwg::EventData) {
-        self.c.window.set_enabled(true);
-        self.c.load_notice.receive();
-        let res = self.load_join_handle.join();
-        if !res.success {
-            ui::shake_window(&self.c.window);
-            self.c.update_tab_order();
-        } else {
-            let config = self.config_from_input();
-            self.result = ConnectDialogResult::new(config, res.dbnames, res.bbf_db);
-            self.close(nwg::EventData::NoData);
-        }
-    }
-
-    pub(super) fn on_use_pgpass_checkbox_changed(&mut self, _: nwg::EventData) {
-        if self.c.use_pgpass_checkbox.check_state() == nwg::CheckBoxState::Checked {
-            self.c.password_input.set_readonly(true);
-        } else {
-            self.c.password_input.set_readonly(false);
-        }
-    }
-
-    pub(super) fn on_port_input_changed(&mut self, _: nwg::EventData) {
-        self.correct_port_value();
-    }
-
-    pub(super) fn on_enable_tls_checkbox_changed(&mut self, _: nwg::EventData) {
-        self.sync_tls_checkboxes_state();
-    }
-
-    fn correct_port_value(&self) {
-        let text = self.c.port_input.text();
-        if text.len() == 0 {
-            self.c.port_input.set_text("1");
-            return;
-        }
-        let num = match text.parse::<u128>() {
-            Err(_) => {
-                self.c.port_input.set_text("5432");
-                return;
-            },
-            Ok(n) => n
-        };
-        if num > 65535 {
-            self.c.port_input.set_text("65535");
-        }
-    }
-
-    fn config_from_input(&self) -> PgConnConfig {
-        let port = match self.c.port_input.text().parse::<u16>() {
-            Ok(n) => n,
-            Err(_) => 5432,
-        };
-        PgConnConfig {
-            hostname: self.c.hostname_input.text(),
-            port,
-            username: self.c.username_input.text(),
-            password: self.c.password_input.text(),
-            use_pgpass_file: self.c.use_pgpass_checkbox.check_state() == nwg::CheckBoxState::Checked,
-            connect_db: self.c.connect_db_input.text(),
-            enable_tls: self.c.enable_tls_checkbox.check_state() == nwg::CheckBoxState::Checked,
-            accept_invalid_tls: self.c.enable_tls_checkbox.enabled() &&
-                self.c.accept_invalid_tls_checkbox.check_state() == nwg::CheckBoxState::Checked
-        }
-    }
-
-    fn config_to_input(&self, config: &PgConnConfig) {
-        self.c.hostname_input.set_text(&config.hostname);
-        self.c.port_input.set_text(&config.port.to_string());
-        self.c.username_input.set_text(&config.username);
-        self.c.password_input.set_text(&config.password);
-        let pgpass_state = if config.use_pgpass_file {
-            self.c.password_input.set_readonly(true);
-            nwg::CheckBoxState::Checked
-        } else {
-            self.c.password_input.set_readonly(false);
-            nwg::CheckBoxState::Unchecked
-        };
-        self.c.use_pgpass_checkbox.set_check_state(pgpass_state);
-        self.c.connect_db_input.set_text(&config.connect_db);
-        let tls_state = if config.enable_tls {
-            nwg::CheckBoxState::Checked
-        } else {
-            nwg::CheckBoxState::Unchecked
-        };
-        self.c.enable_tls_checkbox.set_check_state(tls_state);
-        let accept_state = if config.accept_invalid_tls {
-            nwg::CheckBoxState::Checked
-        } else {
-            nwg::CheckBoxState::Unchecked
-        };
-        self.c.accept_invalid_tls_checkbox.set_check_state(accept_state);
-    }
-
-    fn sync_tls_checkboxes_state(&self) {
-        let enabled = self.c.enable_tls_checkbox.check_state() == nwg::CheckBoxState::Checked;
-        self.c.accept_invalid_tls_checkbox.set_enabled(enabled);
-    }
-}
-
-impl ui::PopupDialog<ConnectDialogArgs, ConnectDialogResult> for ConnectDialog {
-    fn popup(args: ConnectDialogArgs) -> ui::PopupJoinHandle<ConnectDialogResult> {
-        let join_handle = thread::spawn(move || {
-            let data = Self {
-                args,
-                ..Default::default()
-            };
-            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
-            nwg::dispatch_thread_events();
-            dialog.result()
-        });
-        ui::PopupJoinHandle::from(join_handle)
-    }
-
-    fn init(&mut self) {
-        self.config_to_input(&self.args.pg_conn_config);
-        self.result = ConnectDialogResult::cancelled();
-        ui::shake_window(&self.c.window);
-    }
-
-    fn result(&mut self) -> ConnectDialogResult {
-        self.result.clone()
-    }
-
-    fn close(&mut self, _: nwg::EventData) {
-        self.args.notify_parent();
-        self.c.window.set_visible(false);
-        nwg::stop_thread_dispatch();
-    }
-
-    fn on_resize(&mut self, _: EventData) {
-        self.c.update_tab_order();
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct ConnectDialog {
+    pub(super) c: ConnectDialogControls,
+
+    args: ConnectDialogArgs,
+    result: ConnectDialogResult,
+    services: Vec<PgService>,
+    check_join_handle: ui::PopupJoinHandle<ConnectCheckDialogResult>,
+    load_join_handle: ui::PopupJoinHandle<LoadDbnamesDialogResult>,
+}
+
+impl ConnectDialog {
+    pub(super) fn open_check_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let config = self.config_from_input();
+        let args = ConnectCheckDialogArgs::new(&self.c.check_notice, config);
+        self.check_join_handle = ConnectCheckDialog::popup(args);
+    }
+
+    pub(super) fn await_check_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.check_notice.receive();
+        let _ = self.check_join_handle.join();
+        ui::shake_window(&self.c.window);
+        self.c.update_tab_order();
+    }
+
+    pub(super) fn open_load_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(false);
+        let config = self.config_from_input();
+        let args = LoadDbnamesDialogArgs::new(&self.c.load_notice, config);
+        self.load_join_handle = LoadDbnamesDialog::popup(args);
+    }
+
+    pub(super) fn await_load_dialog(&mut self, _: nwg::EventData) {
+        self.c.window.set_enabled(true);
+        self.c.load_notice.receive();
+        let res = self.load_join_handle.join();
+        if !res.success {
+            ui::shake_window(&self.c.window);
+            self.c.update_tab_order();
+        } else {
+            let config = self.config_from_input();
+            self.result = ConnectDialogResult::new(config, res.dbnames, res.bbf_db, res.server_info);
+            self.close(nwg::EventData::NoData);
+        }
+    }
+
+    pub(super) fn on_use_pgpass_checkbox_changed(&mut self, _: nwg::EventData) {
+        if self.c.use_pgpass_checkbox.check_state() == nwg::CheckBoxState::Checked {
+            self.c.password_input.set_readonly(true);
+        } else {
+            self.c.password_input.set_readonly(false);
+        }
+    }
+
+    // parameters of the service are copied into the inputs, so they can be
+    // checked and edited before connecting
+    pub(super) fn on_service_selected(&mut self, _: nwg::EventData) {
+        let name = match self.c.service_combo.selection_string() {
+            Some(name) => name,
+            None => return
+        };
+        if let Some(svc) = self.services.iter().find(|svc| svc.name == name) {
+            let mut config = self.config_from_input();
+            svc.apply(&mut config);
+            self.config_to_input(&config);
+        }
+    }
+
+    pub(super) fn apply_uri(&mut self, _: nwg::EventData) {
+        let mut config = self.config_from_input();
+        match config.apply_uri(&self.c.uri_input.text()) {
+            Ok(_) => {
+                self.config_to_input(&config);
+                self.c.uri_input.set_text("");
+            },
+            Err(e) => {
+                nwg::modal_error_message(&self.c.window, "Connection URI", &e.to_string());
+            }
+        }
+    }
+
+    // password is not included, so the URI can be shared
+    pub(super) fn copy_uri(&mut self, _: nwg::EventData) {
+        let uri = self.config_from_input().to_uri();
+        let _ = set_clipboard(formats::Unicode, &uri);
+        self.c.uri_input.set_text(&uri);
+    }
+
+    pub(super) fn on_port_input_changed(&mut self, _: nwg::EventData) {
+        self.correct_port_value();
+    }
+
+    pub(super) fn on_enable_tls_checkbox_changed(&mut self, _: nwg::EventData) {
+        self.sync_tls_checkboxes_state();
+    }
+
+    pub(super) fn choose_tls_ca_file(&mut self, _: nwg::EventData) {
+        if self.c.tls_ca_file_chooser.run(Some(&self.c.window)) {
+            if let Ok(file) = self.c.tls_ca_file_chooser.get_selected_item() {
+                self.c.tls_ca_file_input.set_text(&file.to_string_lossy());
+            }
+        }
+    }
+
+    fn correct_port_value(&self) {
+        let text = self.c.port_input.text();
+        if text.len() == 0 {
+            self.c.port_input.set_text("1");
+            return;
+        }
+        let num = match text.parse::<u128>() {
+            Err(_) => {
+                self.c.port_input.set_text("5432");
+                return;
+            },
+            Ok(n) => n
+        };
+        if num > 65535 {
+            self.c.port_input.set_text("65535");
+        }
+    }
+
+    fn config_from_input(&self) -> PgConnConfig {
+        let port = match self.c.port_input.text().parse::<u16>() {
+            Ok(n) => n,
+            Err(_) => 5432,
+        };
+        PgConnConfig {
+            hostname: self.c.hostname_input.text(),
+            port,
+            username: self.c.username_input.text(),
+            password: SecretString::from(self.c.password_input.text()),
+            use_pgpass_file: self.c.use_pgpass_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            connect_db: self.c.connect_db_input.text(),
+            enable_tls: self.c.enable_tls_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            accept_invalid_tls: self.c.enable_tls_checkbox.enabled() &&
+                self.c.accept_invalid_tls_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            tls_ca_file: self.c.tls_ca_file_input.text().trim().to_string(),
+            connect_timeout_secs: Self::number_from_input(&self.c.connect_timeout_input),
+            read_timeout_secs: Self::number_from_input(&self.c.read_timeout_input),
+            statement_timeout_secs: Self::number_from_input(&self.c.statement_timeout_input),
+            retries: Self::number_from_input(&self.c.retries_input) as u32,
+            ssh_host: self.c.ssh_host_input.text().trim().to_string(),
+            ssh_user: self.c.ssh_user_input.text().trim().to_string(),
+            ssh_key_file: self.c.ssh_key_file_input.text().trim().to_string(),
+        }
+    }
+
+    fn config_to_input(&self, config: &PgConnConfig) {
+        self.c.hostname_input.set_text(&config.hostname);
+        self.c.port_input.set_text(&config.port.to_string());
+        self.c.username_input.set_text(&config.username);
+        self.c.password_input.set_text(config.password.expose());
+        let pgpass_state = if config.use_pgpass_file {
+            self.c.password_input.set_readonly(true);
+            nwg::CheckBoxState::Checked
+        } else {
+            self.c.password_input.set_readonly(false);
+            nwg::CheckBoxState::Unchecked
+        };
+        self.c.use_pgpass_checkbox.set_check_state(pgpass_state);
+        self.c.connect_db_input.set_text(&config.connect_db);
+        let tls_state = if config.enable_tls {
+            nwg::CheckBoxState::Checked
+        } else {
+            nwg::CheckBoxState::Unchecked
+        };
+        self.c.enable_tls_checkbox.set_check_state(tls_state);
+        let accept_state = if config.accept_invalid_tls {
+            nwg::CheckBoxState::Checked
+        } else {
+            nwg::CheckBoxState::Unchecked
+        };
+        self.c.accept_invalid_tls_checkbox.set_check_state(accept_state);
+        self.c.tls_ca_file_input.set_text(&config.tls_ca_file);
+        self.sync_tls_checkboxes_state();
+        self.c.connect_timeout_input.set_text(&config.connect_timeout_secs.to_string());
+        self.c.read_timeout_input.set_text(&config.read_timeout_secs.to_string());
+        self.c.statement_timeout_input.set_text(&config.statement_timeout_secs.to_string());
+        self.c.retries_input.set_text(&config.retries.to_string());
+        self.c.ssh_host_input.set_text(&config.ssh_host);
+        self.c.ssh_user_input.set_text(&config.ssh_user);
+        self.c.ssh_key_file_input.set_text(&config.ssh_key_file);
+    }
+
+    fn number_from_input(input: &nwg::TextInput) -> u64 {
+        match input.text().parse::<u64>() {
+            Ok(n) => n,
+            Err(_) => 0,
+        }
+    }
+
+    fn sync_tls_checkboxes_state(&self) {
+        let enabled = self.c.enable_tls_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        self.c.accept_invalid_tls_checkbox.set_enabled(enabled);
+        self.c.tls_ca_file_input.set_enabled(enabled);
+        self.c.tls_ca_file_button.set_enabled(enabled);
+    }
+}
+
+impl ui::PopupDialog<ConnectDialogArgs, ConnectDialogResult> for ConnectDialog {
+    fn popup(args: ConnectDialogArgs) -> ui::PopupJoinHandle<ConnectDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.config_to_input(&self.args.pg_conn_config);
+        self.services = match PgService::load_all() {
+            Ok(services) => services,
+            Err(e) => {
+                log::warn!("Error loading connection services, message: {}", e);
+                vec!()
+            }
+        };
+        self.c.service_combo.set_collection(self.services.iter().map(|svc| svc.name.clone()).collect());
+        self.c.service_combo.set_enabled(!self.services.is_empty());
+        self.result = ConnectDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> ConnectDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -1,216 +1,452 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-#[derive(Default)]
-pub(super) struct ConnectDialogLayout {
-    root_layout: nwg::FlexboxLayout,
-    hostname_layout: nwg::FlexboxLayout,
-    port_layout: nwg::FlexboxLayout,
-    username_layout: nwg::FlexboxLayout,
-    password_layout: nwg::FlexboxLayout,
-    use_pgpass_layout: nwg::FlexboxLayout,
-    connect_db_layout: nwg::FlexboxLayout,
-    enable_tls_layout: nwg::FlexboxLayout,
-    accept_invalid_tls_layout: nwg::FlexboxLayout,
-    spacer_layout: nwg::FlexboxLayout,
-    buttons_layout: nwg::FlexboxLayout,
-}
-
-impl ui::Layout<ConnectDialogControls> for ConnectDialogLayout {
-    fn build(&self, c: &ConnectDialogControls) -> Result<(), nwg::NwgError> {
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.hostname_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.hostname_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.hostname_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.port_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.port_input)
-            .child_size(ui::size_builder()
-                .width_number_input_normal()
-                .height_input_form_row()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.port_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.username_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.username_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.username_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.password_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.password_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.password_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.use_pgpass_checkbox)
-            .child_size(ui::size_builder()
-                .width_auto()
-                .height_input_form_row()
-                .build())
-            .child_flex_grow(1.0)
-            .child_margin(ui::margin_builder()
-                .start_no_label_normal()
-                .build())
-            .build_partial(&self.use_pgpass_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.connect_db_label)
-            .child_size(ui::size_builder()
-                .width_label_normal()
-                .height_input_form_row()
-                .build())
-            .child(&c.connect_db_input)
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child_flex_grow(1.0)
-            .build_partial(&self.connect_db_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.enable_tls_checkbox)
-            .child_size(ui::size_builder()
-                .width_auto()
-                .height_input_form_row()
-                .build())
-            .child_flex_grow(1.0)
-            .child_margin(ui::margin_builder()
-                .start_no_label_normal()
-                .build())
-            .build_partial(&self.enable_tls_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .child(&c.accept_invalid_tls_checkbox)
-            .child_size(ui::size_builder()
-                .width_auto()
-                .height_input_form_row()
-                .build())
-            .child_flex_grow(1.0)
-            .child_margin(ui::margin_builder()
-                .start_no_label_normal()
-                .build())
-            .build_partial(&self.accept_invalid_tls_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .auto_spacing(None)
-            .build_partial(&self.spacer_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Row)
-            .justify_content(ui::JustifyContent::FlexEnd)
-            .auto_spacing(None)
-            .child(&c.test_button)
-            .child_size(ui::size_builder()
-                .width_button_xwide()
-                .height_button()
-                .build())
-            .child(&c.load_button)
-            .child_size(ui::size_builder()
-                .width_button_xwide()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .child(&c.cancel_button)
-            .child_size(ui::size_builder()
-                .width_button_normal()
-                .height_button()
-                .build())
-            .child_margin(ui::margin_builder()
-                .start_pt(5)
-                .build())
-            .build_partial(&self.buttons_layout)?;
-
-        nwg::FlexboxLayout::builder()
-            .parent(&c.window)
-            .flex_direction(ui::FlexDirection::Column)
-            .child_layout(&self.hostname_layout)
-            .child_layout(&self.port_layout)
-            .child_layout(&self.username_layout)
-            .child_layout(&self.password_layout)
-            .child_layout(&self.use_pgpass_layout)
-            .child_layout(&self.connect_db_layout)
-            .child_layout(&self.enable_tls_layout)
-            .child_layout(&self.accept_invalid_tls_layout)
-            .child_layout(&self.spacer_layout)
-            .child_flex_grow(1.0)
-            .child_layout(&self.buttons_layout)
-            .build(&self.root_layout)?;
-
-        Ok(())
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ConnectDialogLayout {
+    root_layout: nwg::FlexboxLayout,
+    service_layout: nwg::FlexboxLayout,
+    uri_layout: nwg::FlexboxLayout,
+    hostname_layout: nwg::FlexboxLayout,
+    port_layout: nwg::FlexboxLayout,
+    username_layout: nwg::FlexboxLayout,
+    password_layout: nwg::FlexboxLayout,
+    use_pgpass_layout: nwg::FlexboxLayout,
+    connect_db_layout: nwg::FlexboxLayout,
+    enable_tls_layout: nwg::FlexboxLayout,
+    accept_invalid_tls_layout: nwg::FlexboxLayout,
+    tls_ca_file_layout: nwg::FlexboxLayout,
+    connect_timeout_layout: nwg::FlexboxLayout,
+    read_timeout_layout: nwg::FlexboxLayout,
+    statement_timeout_layout: nwg::FlexboxLayout,
+    retries_layout: nwg::FlexboxLayout,
+    ssh_host_layout: nwg::FlexboxLayout,
+    ssh_user_layout: nwg::FlexboxLayout,
+    ssh_key_file_layout: nwg::FlexboxLayout,
+    spacer_layout: nwg::FlexboxLayout,
+    buttons_layout: nwg::FlexboxLayout,
+}
+
+impl ui::Layout<ConnectDialogControls> for ConnectDialogLayout {
+    fn build(&self, c: &ConnectDialogControls) -> Result<(), nwg::NwgError> {
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.service_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.service_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.service_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.uri_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.uri_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.uri_apply_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.uri_copy_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.uri_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.hostname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.hostname_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.hostname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.port_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.port_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.port_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.username_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.username_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.username_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.password_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.password_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.password_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.use_pgpass_checkbox)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_input_form_row()
+                .build())
+            .child_flex_grow(1.0)
+            .child_margin(ui::margin_builder()
+                .start_no_label_normal()
+                .build())
+            .build_partial(&self.use_pgpass_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.connect_db_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.connect_db_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.connect_db_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.enable_tls_checkbox)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_input_form_row()
+                .build())
+            .child_flex_grow(1.0)
+            .child_margin(ui::margin_builder()
+                .start_no_label_normal()
+                .build())
+            .build_partial(&self.enable_tls_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.accept_invalid_tls_checkbox)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_input_form_row()
+                .build())
+            .child_flex_grow(1.0)
+            .child_margin(ui::margin_builder()
+                .start_no_label_normal()
+                .build())
+            .build_partial(&self.accept_invalid_tls_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.tls_ca_file_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.tls_ca_file_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.tls_ca_file_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.tls_ca_file_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.connect_timeout_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.connect_timeout_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.connect_timeout_hint_label)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.connect_timeout_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.read_timeout_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.read_timeout_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.read_timeout_hint_label)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.read_timeout_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.statement_timeout_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.statement_timeout_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.statement_timeout_hint_label)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.statement_timeout_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.retries_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.retries_input)
+            .child_size(ui::size_builder()
+                .width_number_input_normal()
+                .height_input_form_row()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.retries_hint_label)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.retries_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.ssh_host_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.ssh_host_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.ssh_host_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.ssh_user_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.ssh_user_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.ssh_user_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.ssh_key_file_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.ssh_key_file_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.ssh_key_file_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .build_partial(&self.spacer_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.test_button)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_button()
+                .build())
+            .child(&c.load_button)
+            .child_size(ui::size_builder()
+                .width_button_xwide()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.cancel_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.service_layout)
+            .child_layout(&self.uri_layout)
+            .child_layout(&self.hostname_layout)
+            .child_layout(&self.port_layout)
+            .child_layout(&self.username_layout)
+            .child_layout(&self.password_layout)
+            .child_layout(&self.use_pgpass_layout)
+            .child_layout(&self.connect_db_layout)
+            .child_layout(&self.enable_tls_layout)
+            .child_layout(&self.accept_invalid_tls_layout)
+            .child_layout(&self.tls_ca_file_layout)
+            .child_layout(&self.connect_timeout_layout)
+            .child_layout(&self.read_timeout_layout)
+            .child_layout(&self.statement_timeout_layout)
+            .child_layout(&self.retries_layout)
+            .child_layout(&self.ssh_host_layout)
+            .child_layout(&self.ssh_user_layout)
+            .child_layout(&self.ssh_key_file_layout)
+            .child_layout(&self.spacer_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.buttons_layout)
+            .build(&self.root_layout)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default, Clone)]
+pub(super) struct PgCopyArgs {
+    pub(super) dbname: String,
+    pub(super) dest_dbname: String,
+    pub(super) src_bbf_db: String,
+    pub(super) dest_bbf_db: String,
+    // dump is written into a subdirectory and removed after restore
+    pub(super) work_dir: String,
+}
+
+#[derive(Default)]
+pub struct CopyDialogArgs {
+    pub(super) notice_sender:  ui::SyncNoticeSender,
+    pub(super) src_pcc: PgConnConfig,
+    pub(super) dest_pcc: PgConnConfig,
+    pub(super) pg_copy_args: PgCopyArgs,
+    // DB is copied under a new name on the same server
+    pub(super) clone: bool,
+    // DB is migrated from SQL Server instead of the copy
+    pub(super) migration: Option<MigrationArgs>,
+}
+
+impl CopyDialogArgs {
+    pub fn new(notice: &ui::SyncNotice, src_pcc: &PgConnConfig, dest_pcc: &PgConnConfig,
+               dbname: &str, src_bbf_db: &str, dest_bbf_db: &str, work_dir: &str) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            src_pcc: src_pcc.clone(),
+            dest_pcc: dest_pcc.clone(),
+            pg_copy_args: PgCopyArgs {
+                dbname: dbname.to_string(),
+                dest_dbname: dbname.to_string(),
+                src_bbf_db: src_bbf_db.to_string(),
+                dest_bbf_db: dest_bbf_db.to_string(),
+                work_dir: work_dir.to_string(),
+            },
+            clone: false,
+            migration: None,
+        }
+    }
+
+    pub fn clone_db(notice: &ui::SyncNotice, pcc: &PgConnConfig, dbname: &str, dest_dbname: &str,
+                    bbf_db: &str, work_dir: &str) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            src_pcc: pcc.clone(),
+            dest_pcc: pcc.clone(),
+            pg_copy_args: PgCopyArgs {
+                dbname: dbname.to_string(),
+                dest_dbname: dest_dbname.to_string(),
+                src_bbf_db: bbf_db.to_string(),
+                dest_bbf_db: bbf_db.to_string(),
+                work_dir: work_dir.to_string(),
+            },
+            clone: true,
+            migration: None,
+        }
+    }
+
+    pub fn migrate(notice: &ui::SyncNotice, pcc: &PgConnConfig, migration: MigrationArgs) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            src_pcc: PgConnConfig::default(),
+            dest_pcc: pcc.clone(),
+            pg_copy_args: PgCopyArgs::default(),
+            clone: false,
+            migration: Some(migration),
+        }
+    }
+
+    pub fn send_notice(&self) {
+        self.notice_sender.send()
+    }
+}
+
+impl ui::PopupArgs for CopyDialogArgs {
+    fn notify_parent(&self) {
+        self.notice_sender.send()
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use super::*;
+use crate::copy_dialog::args::PgCopyArgs;
+
+#[derive(Default)]
+pub struct CopyDialog {
+    pub(super) c: CopyDialogControls,
+
+    args: CopyDialogArgs,
+    command_join_handle: ui::PopupJoinHandle<CopyResult>,
+    dialog_result: CopyDialogResult,
+
+    progress_buffer: ProgressBuffer,
+    details_log: DetailsLog,
+    taskbar_progress: Option<TaskbarProgress>,
+    started_at: Option<Instant>,
+}
+
+impl CopyDialog {
+
+    pub(super) fn on_progress(&mut self, _: nwg::EventData) {
+        let msg = self.c.progress_notice.receive();
+        if let Some(text) = self.progress_buffer.push(msg) {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_progress_tick(&mut self, _: nwg::EventData) {
+        if let Some(text) = self.progress_buffer.flush_if_due() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_complete(&mut self, _: nwg::EventData) {
+        self.c.complete_notice.receive();
+        let res = self.command_join_handle.join();
+        let success = res.error.is_empty();
+        let duration = match self.started_at {
+            Some(started) => started.elapsed(),
+            None => Duration::from_secs(0)
+        };
+        self.stop_progress_bar(success.clone());
+        let op = if self.args.migration.is_some() { "Migration" } else { "Copy" };
+        if !success {
+            self.dialog_result = CopyDialogResult::failure(duration);
+            self.c.label.set_text(&format!("{} failed", op));
+            log::error!("{} failed: {}", op, res.error);
+            if let Some(text) = self.progress_buffer.push(res.error.clone()) {
+                self.details_log.append(&self.c.details_box, &text);
+            }
+        } else {
+            self.dialog_result = CopyDialogResult::success(duration);
+            self.c.label.set_text(&format!("{} complete", op));
+        }
+        self.c.copy_clipboard_button.set_enabled(true);
+        self.c.close_button.set_enabled(true);
+        if window_in_background(&self.c.window) {
+            if success {
+                let text = if self.args.migration.is_some() {
+                    "Database migrated successfully"
+                } else {
+                    "Database copied successfully"
+                };
+                show_toast(&self.c.tray, &self.c.icon, &format!("{} complete", op), text);
+            } else {
+                show_toast(&self.c.tray, &self.c.icon, &format!("{} failed", op), &res.error);
+            }
+        }
+        self.c.progress_timer.stop();
+        if let Some(text) = self.progress_buffer.flush() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+        self.c.copy_command_button.set_enabled(self.details_log.has_commands());
+    }
+
+    pub(super) fn on_autoscroll_changed(&mut self, _: nwg::EventData) {
+        let paused = self.c.autoscroll_checkbox.check_state() != nwg::CheckBoxState::Checked;
+        self.details_log.set_paused(&self.c.details_box, paused);
+    }
+
+    // commands can be re-run from a terminal to reproduce a failure
+    pub(super) fn copy_command_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.commands_text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    fn stop_progress_bar(&self, success: bool) {
+        self.c.progress_bar.set_marquee(false, 0);
+        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_pos(1);
+        if !success {
+            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
+        }
+        if let Some(taskbar) = &self.taskbar_progress {
+            if success {
+                taskbar.set_complete();
+            } else {
+                taskbar.set_error();
+            }
+        }
+    }
+
+    // Source DB is dumped with the regular backup into a directory-format dump in
+    // a temp dir, this dump is restored into the target server, TOC is rewritten
+    // by the restore when the DB is cloned under a new name.
+    fn run_copy(progress: &ui::SyncNoticeValueSender<String>, src_pcc: &PgConnConfig, dest_pcc: &PgConnConfig, ca: &PgCopyArgs) -> CopyResult {
+        progress.send_value(format!("Copying DB: {}, from: {}:{}, to: {}:{}, as: {} ...", &ca.dbname,
+            &src_pcc.hostname, src_pcc.port, &dest_pcc.hostname, dest_pcc.port, &ca.dest_dbname));
+
+        let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let copy_dir = Path::new(&ca.work_dir).join(format!("wdb_copy_{}_{}", &ca.dbname, ts));
+        progress.send_value(format!("Creating temp directory: {} ...", copy_dir.to_string_lossy()));
+        if let Err(e) = fs::create_dir_all(&copy_dir) {
+            return CopyResult::failure(format!(
+                "Error creating temp directory: {}, message: {}", copy_dir.to_string_lossy(), e))
+        }
+
+        let res = Self::dump_and_restore(progress, src_pcc, dest_pcc, ca, &copy_dir);
+
+        progress.send_value("Cleaning up temp directory ...");
+        if let Err(e) = fs::remove_dir_all(&copy_dir) {
+            progress.send_value(format!(
+                "Warning: error removing temp directory: {}, message: {}", copy_dir.to_string_lossy(), e));
+        }
+        if res.error.is_empty() {
+            progress.send_value("Copy complete");
+        }
+        res
+    }
+
+    fn dump_and_restore(progress: &ui::SyncNoticeValueSender<String>, src_pcc: &PgConnConfig, dest_pcc: &PgConnConfig,
+                        ca: &PgCopyArgs, copy_dir: &Path) -> CopyResult {
+        let parent_dir = copy_dir.to_string_lossy().to_string();
+        let pargs = PgDumpArgs::new(&ca.dbname, &ca.src_bbf_db, &parent_dir, &ca.dbname,
+            BackupFormat::Directory, &RetentionPolicy::default());
+        let bres = BackupDialog::run_backup(progress, src_pcc, &pargs);
+        if !bres.error.is_empty() {
+            return CopyResult::failure(bres.error);
+        }
+
+        let dump_dir = pargs.dest_path().to_string_lossy().to_string();
+        let ra = PgRestoreArgs::new(&dump_dir, &ca.dest_dbname, &ca.dest_bbf_db, &RestoreOptions::default());
+        let rres = RestoreDialog::run_restore(progress, dest_pcc, &ra);
+        if !rres.error.is_empty() {
+            return CopyResult::failure(rres.error);
+        }
+        CopyResult::success()
+    }
+
+    fn run_migration(progress: &ui::SyncNoticeValueSender<String>, dest_pcc: &PgConnConfig, ma: &MigrationArgs) -> CopyResult {
+        match mssql::run_migration(progress, dest_pcc, ma) {
+            Ok(_) => CopyResult::success(),
+            Err(e) => CopyResult::failure(e)
+        }
+    }
+}
+
+impl ui::PopupDialog<CopyDialogArgs, CopyDialogResult> for CopyDialog {
+    fn popup(args: CopyDialogArgs) -> ui::PopupJoinHandle<CopyDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        if self.args.clone {
+            self.c.window.set_text("Clone database");
+            self.c.label.set_text("Cloning database ...");
+        } else if self.args.migration.is_some() {
+            self.c.window.set_text("Migrate from SQL Server");
+            self.c.label.set_text("Migrating database ...");
+        }
+        self.taskbar_progress = TaskbarProgress::new(&self.c.window);
+        self.started_at = Some(Instant::now());
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.set_indeterminate();
+        }
+        let complete_sender = self.c.complete_notice.sender();
+        let progress_sender = self.c.progress_notice.sender();
+        let src_pcc: PgConnConfig = self.args.src_pcc.clone();
+        let dest_pcc: PgConnConfig = self.args.dest_pcc.clone();
+        let ca: PgCopyArgs = self.args.pg_copy_args.clone();
+        let ma: Option<MigrationArgs> = self.args.migration.clone();
+        let join_handle = thread::spawn(move || {
+            let start = Instant::now();
+            let res = match &ma {
+                Some(ma) => CopyDialog::run_migration(&progress_sender, &dest_pcc, ma),
+                None => CopyDialog::run_copy(&progress_sender, &src_pcc, &dest_pcc, &ca)
+            };
+            let remaining = 1000 - start.elapsed().as_millis() as i64;
+            if remaining > 0 {
+                thread::sleep(Duration::from_millis(remaining as u64));
+            }
+            complete_sender.send();
+            res
+        });
+        self.command_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn result(&mut self) -> CopyDialogResult {
+        self.dialog_result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.c.tray.set_visibility(false);
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.clear();
+        }
+        self.args.send_notice();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: nwg::EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod args;
+mod controls;
+mod dialog;
+mod events;
+mod layout;
+mod nui;
+mod result;
+
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use clipboard_win::formats;
+use clipboard_win::set_clipboard;
+use nwg::NativeUi;
+
+use crate::*;
+use backup_dialog::BackupDialog;
+use backup_dialog::PgDumpArgs;
+use common::BackupFormat;
+use common::DetailsLog;
+use common::PgConnConfig;
+use common::ProgressBuffer;
+use common::ProgressSender;
+use common::RetentionPolicy;
+use common::show_toast;
+use common::TaskbarProgress;
+use common::window_in_background;
+use mssql::MigrationArgs;
+use nwg_ui as ui;
+use restore_dialog::PgRestoreArgs;
+use restore_dialog::RestoreDialog;
+use restore_dialog::RestoreOptions;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupDialog;
+
+pub use args::CopyDialogArgs;
+pub(self) use controls::CopyDialogControls;
+pub use dialog::CopyDialog;
+use events::CopyDialogEvents;
+use layout::CopyDialogLayout;
+pub use result::CopyDialogResult;
+use result::CopyResult;
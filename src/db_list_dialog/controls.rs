@@ -0,0 +1,109 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct DbListDialogControls {
+    layout: DbListDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) db_list_view: nwg::ListView,
+    pub(super) label: nwg::Label,
+
+    pub(super) select_button: nwg::Button,
+    pub(super) close_button: nwg::Button,
+
+    pub(super) load_notice: ui::SyncNotice,
+}
+
+impl ui::Controls for DbListDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((560, 360))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Databases")
+            .build(&mut self.window)?;
+
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.window)
+            .build(&mut self.db_list_view)?;
+        self.db_list_view.insert_column("Database");
+        self.db_list_view.insert_column("Size");
+        self.db_list_view.insert_column("Owner");
+        self.db_list_view.insert_column("Created");
+        self.db_list_view.set_column_width(0, 180);
+        self.db_list_view.set_column_width(1, 90);
+        self.db_list_view.set_column_width(2, 120);
+        self.db_list_view.set_column_width(3, 130);
+        self.db_list_view.set_headers_enabled(true);
+
+        nwg::Label::builder()
+            .text("Loading database sizes ...")
+            .flags(nwg::LabelFlags::VISIBLE | nwg::LabelFlags::ELIPSIS)
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.label)?;
+
+        nwg::Button::builder()
+            .text("Select")
+            .font(Some(&self.font_normal))
+            .enabled(false)
+            .parent(&self.window)
+            .build(&mut self.select_button)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.close_button)?;
+
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.load_notice)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.db_list_view)
+            .control(&self.select_button)
+            .control(&self.close_button)
+            .build();
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct DbListDialog {
+    pub(super) c: DbListDialogControls,
+
+    args: DbListDialogArgs,
+    load_join_handle: ui::PopupJoinHandle<DbListLoadResult>,
+    databases: Vec<DbInfo>,
+    result: DbListDialogResult,
+}
+
+impl DbListDialog {
+    pub(super) fn on_load_complete(&mut self, _: nwg::EventData) {
+        self.c.load_notice.receive();
+        let res = self.load_join_handle.join();
+        if !res.error.is_empty() {
+            self.c.label.set_text(&format!("Load failed: {}", res.error));
+            return;
+        }
+        // system databases cannot be backed up
+        self.databases = res.databases.into_iter().filter(|db| {
+            !vec!("master", "msdb", "tempdb").contains(&db.name.as_str())
+        }).collect();
+        let mut total = 0;
+        for (idx, db) in self.databases.iter().enumerate() {
+            total += db.size;
+            self.c.db_list_view.insert_items_row(None, &[
+                &db.name, &format_disk_size(db.size), &db.owner, &db.created]);
+            if db.name == self.args.selected_dbname {
+                self.c.db_list_view.select_item(idx, true);
+            }
+        }
+        self.c.label.set_text(&format!("Databases: {}, total size: {}",
+            self.databases.len(), format_disk_size(total)));
+        self.c.select_button.set_enabled(!self.databases.is_empty());
+    }
+
+    pub(super) fn select_db(&mut self, _: nwg::EventData) {
+        let idx = match self.c.db_list_view.selected_item() {
+            Some(idx) if idx < self.databases.len() => idx,
+            _ => return
+        };
+        self.result = DbListDialogResult::new(&self.databases[idx].name);
+        self.close(nwg::EventData::NoData);
+    }
+
+    // All Babelfish DBs share a single Postgres DB, so the size is summed over
+    // tables (with their indexes) in the schemas of each Babelfish DB.
+    fn load_databases(pcc: &PgConnConfig, bbf_db: &str) -> Result<Vec<DbInfo>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(bbf_db)?;
+        let rs = client.query("
+            select
+                d.name,
+                coalesce(d.owner::text, '') as owner,
+                coalesce(to_char(d.crdate, 'YYYY-MM-DD HH24:MI'), '') as created,
+                coalesce((
+                    select sum(pg_total_relation_size(c.oid))
+                    from pg_class c
+                    join pg_namespace n on n.oid = c.relnamespace
+                    join sys.babelfish_namespace_ext ns on ns.nspname = n.nspname
+                    where ns.dbid = d.dbid
+                    and c.relkind in ('r', 'm')), 0)::bigint as size
+            from sys.babelfish_sysdatabases d
+            order by d.name", &[])?;
+        let mut res = Vec::new();
+        for row in rs.iter() {
+            let size: i64 = row.get("size");
+            res.push(DbInfo {
+                name: row.get("name"),
+                owner: row.get("owner"),
+                created: row.get("created"),
+                size: size as u64,
+            });
+        }
+        client.close()?;
+        Ok(res)
+    }
+}
+
+impl ui::PopupDialog<DbListDialogArgs, DbListDialogResult> for DbListDialog {
+    fn popup(args: DbListDialogArgs) -> ui::PopupJoinHandle<DbListDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.result = DbListDialogResult::cancelled();
+        let sender = self.c.load_notice.sender();
+        let pcc = self.args.pg_conn_config.clone();
+        let bbf_db = self.args.bbf_db.clone();
+        let join_handle = thread::spawn(move || {
+            let res = match DbListDialog::load_databases(&pcc, &bbf_db) {
+                Ok(databases) => DbListLoadResult::success(databases),
+                Err(e) => DbListLoadResult::failure(format!("{}", e))
+            };
+            sender.send();
+            res
+        });
+        self.load_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn result(&mut self) -> DbListDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[derive(Default, Clone)]
+pub(super) struct DbInfo {
+    pub(super) name: String,
+    pub(super) owner: String,
+    pub(super) created: String,
+    pub(super) size: u64,
+}
+
+#[derive(Default)]
+pub(super) struct DbListLoadResult {
+    pub(super) databases: Vec<DbInfo>,
+    pub(super) error: String,
+}
+
+impl DbListLoadResult {
+    pub(super) fn success(databases: Vec<DbInfo>) -> Self {
+        Self {
+            databases,
+            error: String::new(),
+        }
+    }
+
+    pub(super) fn failure(error: String) -> Self {
+        Self {
+            error,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct DbListDialogResult {
+    pub cancelled: bool,
+    pub dbname: String,
+}
+
+impl DbListDialogResult {
+    pub fn new(dbname: &str) -> Self {
+        Self {
+            cancelled: false,
+            dbname: dbname.to_string(),
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self {
+            cancelled: true,
+            ..Default::default()
+        }
+    }
+}
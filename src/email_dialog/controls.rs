@@ -0,0 +1,193 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct EmailDialogControls {
+    layout: EmailDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) enabled_checkbox: nwg::CheckBox,
+    pub(super) only_failure_checkbox: nwg::CheckBox,
+    pub(super) host_label: nwg::Label,
+    pub(super) host_input: nwg::TextInput,
+    pub(super) port_label: nwg::Label,
+    pub(super) port_input: nwg::TextInput,
+    pub(super) security_label: nwg::Label,
+    pub(super) security_combo: nwg::ComboBox<String>,
+    pub(super) username_label: nwg::Label,
+    pub(super) username_input: nwg::TextInput,
+    pub(super) password_label: nwg::Label,
+    pub(super) password_input: nwg::TextInput,
+    pub(super) from_label: nwg::Label,
+    pub(super) from_input: nwg::TextInput,
+    pub(super) to_label: nwg::Label,
+    pub(super) to_input: nwg::TextInput,
+
+    pub(super) save_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for EmailDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((420, 380))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Email notifications")
+            .build(&mut self.window)?;
+
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Send email notification after each backup")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.enabled_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Only when backup fails")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.only_failure_checkbox)?;
+        nwg::Label::builder()
+            .text("SMTP server:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.host_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.host_input)?;
+        nwg::Label::builder()
+            .text("Port:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.port_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.port_input)?;
+        nwg::Label::builder()
+            .text("Security:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.security_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                "None".to_string(),
+                "STARTTLS".to_string(),
+                "TLS".to_string(),
+            ))
+            .selected_index(Some(1))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.security_combo)?;
+        nwg::Label::builder()
+            .text("Username:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.username_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.username_input)?;
+        nwg::Label::builder()
+            .text("Password:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.password_label)?;
+        nwg::TextInput::builder()
+            .password(Some('*'))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.password_input)?;
+        nwg::Label::builder()
+            .text("From:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.from_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.from_input)?;
+        nwg::Label::builder()
+            .text("To:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.to_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Comma separated addresses"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.to_input)?;
+
+        nwg::Button::builder()
+            .text("Save")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.save_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.enabled_checkbox)
+            .control(&self.only_failure_checkbox)
+            .control(&self.host_input)
+            .control(&self.port_input)
+            .control(&self.security_combo)
+            .control(&self.username_input)
+            .control(&self.password_input)
+            .control(&self.from_input)
+            .control(&self.to_input)
+            .control(&self.save_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
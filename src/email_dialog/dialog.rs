@@ -0,0 +1,146 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct EmailDialog {
+    pub(super) c: EmailDialogControls,
+
+    args: EmailDialogArgs,
+    result: EmailDialogResult,
+}
+
+impl EmailDialog {
+    pub(super) fn on_enabled_checkbox_changed(&mut self, _: nwg::EventData) {
+        let enabled = self.c.enabled_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        self.c.only_failure_checkbox.set_enabled(enabled);
+    }
+
+    pub(super) fn save(&mut self, _: nwg::EventData) {
+        let email = match self.email_from_input() {
+            Ok(email) => email,
+            Err(msg) => {
+                nwg::modal_error_message(&self.c.window, "Email notifications", &msg);
+                return;
+            }
+        };
+        self.result = EmailDialogResult::new(email);
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn email_from_input(&self) -> Result<EmailSettings, String> {
+        let mut email = self.args.email.clone();
+        email.enabled = self.c.enabled_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        email.only_on_failure = self.c.only_failure_checkbox.check_state() == nwg::CheckBoxState::Checked;
+        email.smtp_host = self.c.host_input.text().trim().to_string();
+        email.smtp_port = match self.c.port_input.text().trim().parse::<u16>() {
+            Ok(port) if port > 0 => port,
+            _ => return Err(format!("Invalid SMTP port: {}", self.c.port_input.text()))
+        };
+        email.security = match self.c.security_combo.selection() {
+            Some(0) => SmtpSecurity::None,
+            Some(2) => SmtpSecurity::Tls,
+            _ => SmtpSecurity::StartTls
+        };
+        email.username = self.c.username_input.text().trim().to_string();
+        email.from = self.c.from_input.text().trim().to_string();
+        email.to = self.c.to_input.text().trim().to_string();
+        if email.enabled && (email.smtp_host.is_empty() || email.from.is_empty() || email.recipients().is_empty()) {
+            return Err("SMTP server, 'From' and 'To' addresses must be specified".to_string());
+        }
+        if email.security == SmtpSecurity::None && !email.username.is_empty() {
+            return Err("SMTP user name and password cannot be sent without encryption, \
+                select STARTTLS or TLS security or clear the user name".to_string());
+        }
+        // empty input keeps previously saved password
+        let password = self.c.password_input.text();
+        if email.username.is_empty() {
+            email.password_protected = String::new();
+        } else if !password.is_empty() {
+            if let Err(e) = email.set_password(&password) {
+                return Err(format!("Error encrypting SMTP password: {}", e));
+            }
+        }
+        Ok(email)
+    }
+
+    fn email_to_input(&self, email: &EmailSettings) {
+        let checked = |flag: bool| if flag {
+            nwg::CheckBoxState::Checked
+        } else {
+            nwg::CheckBoxState::Unchecked
+        };
+        self.c.enabled_checkbox.set_check_state(checked(email.enabled));
+        self.c.only_failure_checkbox.set_check_state(checked(email.only_on_failure));
+        self.c.host_input.set_text(&email.smtp_host);
+        let port = if email.smtp_port > 0 {
+            email.smtp_port
+        } else {
+            DEFAULT_SMTP_PORT
+        };
+        self.c.port_input.set_text(&port.to_string());
+        let security_idx = match email.security {
+            SmtpSecurity::None => 0,
+            SmtpSecurity::StartTls => 1,
+            SmtpSecurity::Tls => 2,
+        };
+        self.c.security_combo.set_selection(Some(security_idx));
+        self.c.username_input.set_text(&email.username);
+        if !email.password_protected.is_empty() {
+            self.c.password_input.set_placeholder_text(Some("(unchanged)"));
+        }
+        self.c.from_input.set_text(&email.from);
+        self.c.to_input.set_text(&email.to);
+    }
+}
+
+impl ui::PopupDialog<EmailDialogArgs, EmailDialogResult> for EmailDialog {
+    fn popup(args: EmailDialogArgs) -> ui::PopupJoinHandle<EmailDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.email_to_input(&self.args.email);
+        self.on_enabled_checkbox_changed(nwg::EventData::NoData);
+        self.result = EmailDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> EmailDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
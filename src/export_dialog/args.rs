@@ -0,0 +1,143 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+// Table of a Babelfish DB, "schema" is the T-SQL schema name shown to user,
+// "nspname" is the Postgres schema the table is stored in.
+#[derive(Default, Clone)]
+pub struct ExportTable {
+    pub schema: String,
+    pub nspname: String,
+    pub table: String,
+    pub rows: i64,
+}
+
+impl ExportTable {
+    pub(super) fn display_name(&self) -> String {
+        format!("{}.{}", &self.schema, &self.table)
+    }
+
+    // characters not allowed in Windows file names are replaced
+    pub(super) fn csv_file_name(&self) -> String {
+        let name: String = self.display_name().chars().map(|ch| {
+            if ch.is_control() || "\\/:*?\"<>|".contains(ch) { '_' } else { ch }
+        }).collect();
+        format!("{}.csv", name)
+    }
+
+    pub(super) fn copy_to_query(&self, header: bool) -> String {
+        // partitioned tables can only be copied with a query
+        format!("COPY (SELECT * FROM {}.{}) TO STDOUT WITH (FORMAT csv{}, ENCODING 'UTF8')",
+            quote_ident(&self.nspname), quote_ident(&self.table), if header { ", HEADER" } else { "" })
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[derive(Default, Clone)]
+pub(super) struct PgExportArgs {
+    pub(super) dbname: String,
+    pub(super) bbf_db: String,
+    pub(super) tables: Vec<ExportTable>,
+    // CSV files are written into a subdirectory and zipped
+    pub(super) dest_dir: String,
+    // single .bacpac package is written instead of CSV files
+    pub(super) bacpac: bool,
+}
+
+#[derive(Default, Clone)]
+pub(super) struct PgImportArgs {
+    pub(super) bbf_db: String,
+    pub(super) table: ExportTable,
+    pub(super) csv_file: String,
+    pub(super) delimiter: char,
+    // table column for each CSV column, None for skipped columns
+    pub(super) columns: Vec<Option<String>>,
+}
+
+impl PgImportArgs {
+    // header line is skipped by COPY only when the file is streamed as-is
+    pub(super) fn copy_from_query(&self, header: bool) -> String {
+        let columns: Vec<String> = self.columns.iter()
+            .filter_map(|col| col.as_ref())
+            .map(|col| quote_ident(col))
+            .collect();
+        format!("COPY {}.{} ({}) FROM STDIN WITH (FORMAT csv{}, DELIMITER '{}', ENCODING 'UTF8')",
+            quote_ident(&self.table.nspname), quote_ident(&self.table.table), columns.join(", "),
+            if header { ", HEADER" } else { "" }, self.delimiter)
+    }
+}
+
+#[derive(Default)]
+pub struct ExportDialogArgs {
+    pub(super) notice_sender:  ui::SyncNoticeSender,
+    pub(super) pg_conn_config: PgConnConfig,
+    pub(super) pg_export_args: PgExportArgs,
+    // CSV file is imported into a table instead of the export
+    pub(super) pg_import_args: Option<PgImportArgs>,
+}
+
+impl ExportDialogArgs {
+    pub fn new(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig, dbname: &str, bbf_db: &str,
+               tables: Vec<ExportTable>, dest_dir: &str) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            pg_conn_config: pg_conn_config.clone(),
+            pg_export_args: PgExportArgs {
+                dbname: dbname.to_string(),
+                bbf_db: bbf_db.to_string(),
+                tables,
+                dest_dir: dest_dir.to_string(),
+                bacpac: false,
+            },
+            pg_import_args: None,
+        }
+    }
+
+    pub fn bacpac(mut self) -> Self {
+        self.pg_export_args.bacpac = true;
+        self
+    }
+
+    pub fn import(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig, bbf_db: &str, table: &ExportTable,
+                  csv_file: &str, delimiter: char, columns: Vec<Option<String>>) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            pg_conn_config: pg_conn_config.clone(),
+            pg_export_args: PgExportArgs::default(),
+            pg_import_args: Some(PgImportArgs {
+                bbf_db: bbf_db.to_string(),
+                table: table.clone(),
+                csv_file: csv_file.to_string(),
+                delimiter,
+                columns,
+            }),
+        }
+    }
+
+    pub fn send_notice(&self) {
+        self.notice_sender.send()
+    }
+}
+
+impl ui::PopupArgs for ExportDialogArgs {
+    fn notify_parent(&self) {
+        self.notice_sender.send()
+    }
+}
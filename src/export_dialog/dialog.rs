@@ -0,0 +1,451 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use super::*;
+use crate::export_dialog::args::PgExportArgs;
+use crate::export_dialog::args::PgImportArgs;
+
+#[derive(Default)]
+pub struct ExportDialog {
+    pub(super) c: ExportDialogControls,
+
+    args: ExportDialogArgs,
+    command_join_handle: ui::PopupJoinHandle<ExportResult>,
+    dialog_result: ExportDialogResult,
+
+    progress_buffer: ProgressBuffer,
+    details_log: DetailsLog,
+    taskbar_progress: Option<TaskbarProgress>,
+    started_at: Option<Instant>,
+}
+
+impl ExportDialog {
+
+    pub(super) fn on_progress(&mut self, _: nwg::EventData) {
+        let msg = self.c.progress_notice.receive();
+        if let Some(text) = self.progress_buffer.push(msg) {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_progress_tick(&mut self, _: nwg::EventData) {
+        if let Some(text) = self.progress_buffer.flush_if_due() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_complete(&mut self, _: nwg::EventData) {
+        self.c.complete_notice.receive();
+        let res = self.command_join_handle.join();
+        let success = res.error.is_empty();
+        let duration = match self.started_at {
+            Some(started) => started.elapsed(),
+            None => Duration::from_secs(0)
+        };
+        self.stop_progress_bar(success.clone());
+        let op = if self.args.pg_import_args.is_some() { "Import" } else { "Export" };
+        if !success {
+            self.dialog_result = ExportDialogResult::failure(duration);
+            self.c.label.set_text(&format!("{} failed", op));
+            log::error!("{} failed: {}", op, res.error);
+            if let Some(text) = self.progress_buffer.push(res.error.clone()) {
+                self.details_log.append(&self.c.details_box, &text);
+            }
+        } else {
+            self.dialog_result = ExportDialogResult::success(duration);
+            self.c.label.set_text(&format!("{} complete", op));
+        }
+        self.c.copy_clipboard_button.set_enabled(true);
+        self.c.close_button.set_enabled(true);
+        if window_in_background(&self.c.window) {
+            if success {
+                let text = if self.args.pg_export_args.bacpac {
+                    "BACPAC package written successfully"
+                } else {
+                    "CSV data transferred successfully"
+                };
+                show_toast(&self.c.tray, &self.c.icon, &format!("{} complete", op), text);
+            } else {
+                show_toast(&self.c.tray, &self.c.icon, &format!("{} failed", op), &res.error);
+            }
+        }
+        self.c.progress_timer.stop();
+        if let Some(text) = self.progress_buffer.flush() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_autoscroll_changed(&mut self, _: nwg::EventData) {
+        let paused = self.c.autoscroll_checkbox.check_state() != nwg::CheckBoxState::Checked;
+        self.details_log.set_paused(&self.c.details_box, paused);
+    }
+
+    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    fn stop_progress_bar(&self, success: bool) {
+        self.c.progress_bar.set_marquee(false, 0);
+        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_pos(1);
+        if !success {
+            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
+        }
+        if let Some(taskbar) = &self.taskbar_progress {
+            if success {
+                taskbar.set_complete();
+            } else {
+                taskbar.set_error();
+            }
+        }
+    }
+
+    // Tables of the logical DB, partitions are exported as a part of their
+    // parent table, row counts are planner estimates.
+    pub fn load_tables(pcc: &PgConnConfig, bbf_db: &str, dbname: &str) -> Result<Vec<ExportTable>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(bbf_db)?;
+        let rs = client.query("
+            select
+                ns.orig_name::text as schema,
+                n.nspname::text as nspname,
+                c.relname::text as table_name,
+                greatest(c.reltuples, 0)::bigint as rows
+            from pg_class c
+            join pg_namespace n on n.oid = c.relnamespace
+            join sys.babelfish_namespace_ext ns on ns.nspname = n.nspname
+            join sys.babelfish_sysdatabases db on db.dbid = ns.dbid
+            where db.name = $1
+            and c.relkind in ('r', 'p')
+            and not c.relispartition
+            order by 1, 3", &[&dbname])?;
+        let mut res = Vec::new();
+        for row in rs.iter() {
+            res.push(ExportTable {
+                schema: row.get("schema"),
+                nspname: row.get("nspname"),
+                table: row.get("table_name"),
+                rows: row.get("rows"),
+            });
+        }
+        client.close()?;
+        Ok(res)
+    }
+
+    // Each table is copied into a CSV file in a subdirectory of the destination
+    // dir, files are added to the zip as soon as they are written, so only
+    // the zip remains on disk after the export.
+    fn run_export(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ea: &PgExportArgs) -> ExportResult {
+        progress.send_value(format!("Exporting tables of DB: {}, count: {} ...", &ea.dbname, ea.tables.len()));
+
+        let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let name = format!("{}_csv_{}", &ea.dbname, ts);
+        let csv_dir = Path::new(&ea.dest_dir).join(&name);
+        let zip_path = Path::new(&ea.dest_dir).join(format!("{}.zip", &name));
+        progress.send_value(format!("Export file: {}", zip_path.to_string_lossy()));
+        if let Err(e) = fs::create_dir_all(long_path(&csv_dir)) {
+            return ExportResult::failure(format!(
+                "Error creating directory: {}, message: {}", csv_dir.to_string_lossy(), e))
+        }
+        let mut zip = match StreamingZip::create(&csv_dir, &zip_path) {
+            Ok(zip) => zip.with_compression(),
+            Err(e) => {
+                let _ = fs::remove_dir_all(long_path(&csv_dir));
+                return ExportResult::failure(format!(
+                    "Error creating zip file, path: {}, error: {}", zip_path.to_string_lossy(), e))
+            }
+        };
+
+        if let Err(e) = Self::export_tables(progress, pcc, ea, &csv_dir, &mut zip) {
+            zip.abort();
+            let _ = fs::remove_dir_all(long_path(&csv_dir));
+            return ExportResult::failure(e);
+        }
+        progress.send_value("Finishing zip file ...");
+        if let Err(e) = zip.finish(|name| progress.send_value(format!("Zipped: {}", name))) {
+            return ExportResult::failure(format!(
+                "Error writing zip file, path: {}, error: {}", zip_path.to_string_lossy(), e));
+        }
+        progress.send_value("Export complete");
+        ExportResult::success()
+    }
+
+    fn export_tables(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ea: &PgExportArgs,
+                     csv_dir: &Path, zip: &mut StreamingZip) -> Result<(), String> {
+        let mut client = match pcc.open_connection_to_db(&ea.bbf_db) {
+            Ok(client) => client,
+            Err(e) => return Err(e.to_string())
+        };
+        for tb in ea.tables.iter() {
+            progress.send_value(format!("Exporting table: {} ...", tb.display_name()));
+            let path = csv_dir.join(tb.csv_file_name());
+            let size = match Self::copy_table(&mut client, tb, &path) {
+                Ok(size) => size,
+                Err(e) => return Err(format!(
+                    "Error exporting table: {}, message: {}", tb.display_name(), e))
+            };
+            progress.send_value(format!("Table exported: {}, size: {}", tb.display_name(), format_disk_size(size)));
+            if let Err(e) = zip.add_completed(|name| progress.send_value(format!("Zipped: {}", name))) {
+                return Err(format!("Error writing zip file, message: {}", e));
+            }
+        }
+        let _ = client.close();
+        Ok(())
+    }
+
+    fn copy_table(client: &mut postgres::Client, tb: &ExportTable, path: &Path) -> Result<u64, PgAccessError> {
+        let mut reader = client.copy_out(tb.copy_to_query(true).as_str())?;
+        let mut writer = BufWriter::new(File::create(long_path(path))?);
+        let size = io::copy(&mut reader, &mut writer)?;
+        writer.flush()?;
+        Ok(size)
+    }
+
+    // Experimental: package contains only tables, columns and primary keys,
+    // tables with column types that cannot be expressed in T-SQL are skipped.
+    fn run_bacpac_export(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ea: &PgExportArgs) -> ExportResult {
+        progress.send_value(format!("Exporting BACPAC of DB: {}, tables count: {} ...", &ea.dbname, ea.tables.len()));
+        let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let path = Path::new(&ea.dest_dir).join(format!("{}_{}.bacpac", &ea.dbname, ts));
+        progress.send_value(format!("Export file: {}", path.to_string_lossy()));
+        if let Err(e) = Self::export_bacpac(progress, pcc, ea, &path) {
+            let _ = fs::remove_file(long_path(&path));
+            return ExportResult::failure(e);
+        }
+        progress.send_value("Export complete");
+        ExportResult::success()
+    }
+
+    fn export_bacpac(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ea: &PgExportArgs,
+                     path: &Path) -> Result<(), String> {
+        let mut client = match pcc.open_connection_to_db(&ea.bbf_db) {
+            Ok(client) => client,
+            Err(e) => return Err(e.to_string())
+        };
+        // date and time values are parsed from the text output
+        if let Err(e) = client.batch_execute("set datestyle = 'ISO, YMD'") {
+            return Err(e.to_string());
+        }
+        let mut tables = Vec::new();
+        for tb in ea.tables.iter() {
+            match Self::load_bacpac_table(progress, &mut client, tb) {
+                Ok(Some(bt)) => tables.push((tb, bt)),
+                Ok(None) => {},
+                Err(e) => return Err(format!(
+                    "Error loading table definition: {}, message: {}", tb.display_name(), e))
+            }
+        }
+        let bacpac_tables: Vec<BacpacTable> = tables.iter().map(|(_, bt)| bt.clone()).collect();
+        let mut writer = match BacpacWriter::create(&long_path(path), &ea.dbname, &bacpac_tables) {
+            Ok(writer) => writer,
+            Err(e) => return Err(format!(
+                "Error creating BACPAC file, path: {}, error: {}", path.to_string_lossy(), e))
+        };
+        for (tb, bt) in tables.iter() {
+            progress.send_value(format!("Exporting table: {} ...", tb.display_name()));
+            let res = match client.copy_out(tb.copy_to_query(false).as_str()) {
+                Ok(reader) => writer.write_table_data(bt, BufReader::new(reader)),
+                Err(e) => Err(io::Error::new(io::ErrorKind::Other, e))
+            };
+            match res {
+                Ok(rows) => progress.send_value(format!("Table exported: {}, rows: {}", tb.display_name(), rows)),
+                Err(e) => return Err(format!(
+                    "Error exporting table: {}, message: {}", tb.display_name(), e))
+            }
+        }
+        if let Err(e) = writer.finish() {
+            return Err(format!("Error writing BACPAC file, path: {}, error: {}", path.to_string_lossy(), e));
+        }
+        let _ = client.close();
+        Ok(())
+    }
+
+    // Column types are read in Postgres notation and mapped to T-SQL types,
+    // None is returned when some column cannot be mapped.
+    fn load_bacpac_table(progress: &ui::SyncNoticeValueSender<String>, client: &mut postgres::Client,
+                         tb: &ExportTable) -> Result<Option<BacpacTable>, PgAccessError> {
+        let rs = client.query("
+            select
+                a.attname::text as name,
+                format_type(a.atttypid, a.atttypmod) as type_name,
+                a.attnotnull as not_null
+            from pg_attribute a
+            join pg_class c on c.oid = a.attrelid
+            join pg_namespace n on n.oid = c.relnamespace
+            where n.nspname = $1
+            and c.relname = $2
+            and a.attnum > 0
+            and not a.attisdropped
+            order by a.attnum", &[&tb.nspname, &tb.table])?;
+        let mut columns = Vec::new();
+        for row in rs.iter() {
+            let name: String = row.get("name");
+            let type_name: String = row.get("type_name");
+            let not_null: bool = row.get("not_null");
+            let tsql_type = match TsqlType::from_pg(&type_name) {
+                Some(ty) => ty,
+                None => {
+                    progress.send_value(format!("WARNING: table skipped: {}, column: {}, type is not supported: {}",
+                        tb.display_name(), &name, &type_name));
+                    return Ok(None);
+                }
+            };
+            columns.push(BacpacColumn {
+                name,
+                tsql_type,
+                nullable: !not_null,
+            });
+        }
+        let rs = client.query("
+            select
+                con.conname::text as name,
+                array_agg(a.attname::text order by k.ord) as columns
+            from pg_constraint con
+            join pg_class c on c.oid = con.conrelid
+            join pg_namespace n on n.oid = c.relnamespace
+            cross join unnest(con.conkey) with ordinality as k(attnum, ord)
+            join pg_attribute a on a.attrelid = con.conrelid and a.attnum = k.attnum
+            where n.nspname = $1
+            and c.relname = $2
+            and con.contype = 'p'
+            group by con.conname", &[&tb.nspname, &tb.table])?;
+        let primary_key = rs.iter().next().map(|row| (row.get("name"), row.get("columns")));
+        Ok(Some(BacpacTable {
+            schema: tb.schema.clone(),
+            name: tb.table.clone(),
+            columns,
+            primary_key,
+        }))
+    }
+
+    fn run_import(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ia: &PgImportArgs) -> ExportResult {
+        progress.send_value(format!("Importing file: {}, into table: {} ...", &ia.csv_file, ia.table.display_name()));
+        match Self::copy_from_file(pcc, ia) {
+            Ok(rows) => {
+                progress.send_value(format!("Import complete, rows imported: {}", rows));
+                ExportResult::success()
+            },
+            Err(e) => ExportResult::failure(format!(
+                "Error importing file: {}, message: {}", &ia.csv_file, e))
+        }
+    }
+
+    // CSV columns are matched to table columns by position in the COPY column list,
+    // file is streamed as-is when every column is imported, otherwise records are
+    // rewritten without the skipped columns. Import runs in a single transaction,
+    // failed import leaves the table unchanged.
+    fn copy_from_file(pcc: &PgConnConfig, ia: &PgImportArgs) -> Result<u64, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(&ia.bbf_db)?;
+        let mut tx = client.transaction()?;
+        let all_columns = ia.columns.iter().all(|col| col.is_some());
+        let mut writer = tx.copy_in(ia.copy_from_query(all_columns).as_str())?;
+        let mut reader = BufReader::new(File::open(long_path(Path::new(&ia.csv_file)))?);
+        if all_columns {
+            io::copy(&mut reader, &mut writer)?;
+        } else {
+            let mut csv = CsvReader::new(reader, ia.delimiter);
+            // header
+            csv.read_record()?;
+            while let Some(fields) = csv.read_record()? {
+                let kept: Vec<&CsvField> = fields.iter().zip(ia.columns.iter())
+                    .filter(|(_, col)| col.is_some())
+                    .map(|(field, _)| field)
+                    .collect();
+                write_csv_record(&mut writer, &kept, ia.delimiter)?;
+            }
+        }
+        let rows = writer.finish()?;
+        tx.commit()?;
+        client.close()?;
+        Ok(rows)
+    }
+}
+
+impl ui::PopupDialog<ExportDialogArgs, ExportDialogResult> for ExportDialog {
+    fn popup(args: ExportDialogArgs) -> ui::PopupJoinHandle<ExportDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        if self.args.pg_import_args.is_some() {
+            self.c.window.set_text("Import CSV");
+            self.c.label.set_text("Importing CSV file ...");
+        } else if self.args.pg_export_args.bacpac {
+            self.c.window.set_text("Export BACPAC");
+            self.c.label.set_text("Exporting BACPAC package ...");
+        }
+        self.taskbar_progress = TaskbarProgress::new(&self.c.window);
+        self.started_at = Some(Instant::now());
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.set_indeterminate();
+        }
+        let complete_sender = self.c.complete_notice.sender();
+        let progress_sender = self.c.progress_notice.sender();
+        let pcc: PgConnConfig = self.args.pg_conn_config.clone();
+        let ea: PgExportArgs = self.args.pg_export_args.clone();
+        let ia: Option<PgImportArgs> = self.args.pg_import_args.clone();
+        let join_handle = thread::spawn(move || {
+            let start = Instant::now();
+            let res = match &ia {
+                Some(ia) => ExportDialog::run_import(&progress_sender, &pcc, ia),
+                None if ea.bacpac => ExportDialog::run_bacpac_export(&progress_sender, &pcc, &ea),
+                None => ExportDialog::run_export(&progress_sender, &pcc, &ea)
+            };
+            let remaining = 1000 - start.elapsed().as_millis() as i64;
+            if remaining > 0 {
+                thread::sleep(Duration::from_millis(remaining as u64));
+            }
+            complete_sender.send();
+            res
+        });
+        self.command_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn result(&mut self) -> ExportDialogResult {
+        self.dialog_result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.c.tray.set_visibility(false);
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.clear();
+        }
+        self.args.send_notice();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: nwg::EventData) {
+        self.c.update_tab_order();
+    }
+}
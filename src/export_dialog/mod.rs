@@ -0,0 +1,65 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod args;
+mod controls;
+mod dialog;
+mod events;
+mod layout;
+mod nui;
+mod result;
+
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use clipboard_win::formats;
+use clipboard_win::set_clipboard;
+use nwg::NativeUi;
+
+use crate::*;
+use bacpac::BacpacColumn;
+use bacpac::BacpacTable;
+use bacpac::BacpacWriter;
+use bacpac::TsqlType;
+use common::CsvField;
+use common::CsvReader;
+use common::DetailsLog;
+use common::format_disk_size;
+use common::long_path;
+use common::PgAccessError;
+use common::PgConnConfig;
+use common::ProgressBuffer;
+use common::ProgressSender;
+use common::show_toast;
+use common::StreamingZip;
+use common::TaskbarProgress;
+use common::window_in_background;
+use common::write_csv_record;
+use nwg_ui as ui;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupDialog;
+
+pub use args::ExportDialogArgs;
+pub use args::ExportTable;
+pub(self) use controls::ExportDialogControls;
+pub use dialog::ExportDialog;
+use events::ExportDialogEvents;
+use layout::ExportDialogLayout;
+pub use result::ExportDialogResult;
+use result::ExportResult;
@@ -0,0 +1,100 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct GlobalsDialogControls {
+    layout: GlobalsDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) label: nwg::Label,
+    pub(super) statements_box: nwg::TextBox,
+
+    pub(super) restore_button: nwg::Button,
+    pub(super) skip_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for GlobalsDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((560, 400))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Restore logins")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Backup contains logins and roles, following statements are run before restore:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.label)?;
+        nwg::TextBox::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .flags(nwg::TextBoxFlags::VSCROLL | nwg::TextBoxFlags::HSCROLL | nwg::TextBoxFlags::VISIBLE)
+            .readonly(true)
+            .parent(&self.window)
+            .build(&mut self.statements_box)?;
+
+        nwg::Button::builder()
+            .text("Restore logins")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.restore_button)?;
+        nwg::Button::builder()
+            .text("Skip logins")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.skip_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.statements_box)
+            .control(&self.restore_button)
+            .control(&self.skip_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
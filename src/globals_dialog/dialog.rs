@@ -0,0 +1,75 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct GlobalsDialog {
+    pub(super) c: GlobalsDialogControls,
+
+    args: GlobalsDialogArgs,
+    result: GlobalsDialogResult,
+}
+
+impl GlobalsDialog {
+    pub(super) fn restore_globals(&mut self, _: nwg::EventData) {
+        self.result = GlobalsDialogResult::new(true);
+        self.close(nwg::EventData::NoData);
+    }
+
+    pub(super) fn skip_globals(&mut self, _: nwg::EventData) {
+        self.result = GlobalsDialogResult::new(false);
+        self.close(nwg::EventData::NoData);
+    }
+}
+
+impl ui::PopupDialog<GlobalsDialogArgs, GlobalsDialogResult> for GlobalsDialog {
+    fn popup(args: GlobalsDialogArgs) -> ui::PopupJoinHandle<GlobalsDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        // pg_dumpall writes LF line endings, edit control needs CRLF
+        let text = self.args.statements.replace("\r\n", "\n").replace('\n', "\r\n");
+        self.c.statements_box.set_text(&text);
+        self.result = GlobalsDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> GlobalsDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct GlobalsDialogEvents {
+    pub(super) events: Vec<ui::Event<GlobalsDialog>>
+}
+
+impl ui::Events<GlobalsDialogControls> for GlobalsDialogEvents {
+    fn build(&mut self, c: &GlobalsDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(GlobalsDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(GlobalsDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.restore_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(GlobalsDialog::restore_globals)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.skip_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(GlobalsDialog::skip_globals)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.cancel_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(GlobalsDialog::close)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
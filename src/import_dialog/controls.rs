@@ -0,0 +1,177 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ImportDialogControls {
+    layout: ImportDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) file_label: nwg::Label,
+    pub(super) file_input: nwg::TextInput,
+    pub(super) table_label: nwg::Label,
+    pub(super) table_input: nwg::TextInput,
+    pub(super) preview_label: nwg::Label,
+    pub(super) preview_view: nwg::ListView,
+    pub(super) mapping_label: nwg::Label,
+    pub(super) mapping_view: nwg::ListView,
+    pub(super) target_label: nwg::Label,
+    pub(super) target_combo: nwg::ComboBox<String>,
+    pub(super) target_button: nwg::Button,
+
+    pub(super) status_label: nwg::Label,
+    pub(super) import_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+
+    pub(super) load_notice: ui::SyncNotice,
+}
+
+impl ui::Controls for ImportDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((560, 520))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Import CSV")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("CSV file:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.file_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .readonly(true)
+            .parent(&self.window)
+            .build(&mut self.file_input)?;
+        nwg::Label::builder()
+            .text("Table:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.table_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .readonly(true)
+            .parent(&self.window)
+            .build(&mut self.table_input)?;
+
+        nwg::Label::builder()
+            .text("First rows of the file:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.preview_label)?;
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.window)
+            .build(&mut self.preview_view)?;
+        self.preview_view.set_headers_enabled(true);
+
+        nwg::Label::builder()
+            .text("Column mapping:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.mapping_label)?;
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.window)
+            .build(&mut self.mapping_view)?;
+        self.mapping_view.insert_column("CSV column");
+        self.mapping_view.insert_column("Table column");
+        self.mapping_view.set_column_width(0, 220);
+        self.mapping_view.set_column_width(1, 220);
+        self.mapping_view.set_headers_enabled(true);
+        nwg::Label::builder()
+            .text("Table column:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.target_label)?;
+        nwg::ComboBox::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.target_combo)?;
+        nwg::Button::builder()
+            .text("Set")
+            .font(Some(&self.font_normal))
+            .enabled(false)
+            .parent(&self.window)
+            .build(&mut self.target_button)?;
+
+        nwg::Label::builder()
+            .text("Loading table columns ...")
+            .flags(nwg::LabelFlags::VISIBLE | nwg::LabelFlags::ELIPSIS)
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.status_label)?;
+        nwg::Button::builder()
+            .text("Import")
+            .font(Some(&self.font_normal))
+            .enabled(false)
+            .parent(&self.window)
+            .build(&mut self.import_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.load_notice)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.file_input)
+            .control(&self.table_input)
+            .control(&self.preview_view)
+            .control(&self.mapping_view)
+            .control(&self.target_combo)
+            .control(&self.target_button)
+            .control(&self.import_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+const PREVIEW_ROWS: usize = 10;
+const SKIP_COLUMN: &str = "(skip)";
+
+#[derive(Default)]
+pub struct ImportDialog {
+    pub(super) c: ImportDialogControls,
+
+    args: ImportDialogArgs,
+    load_join_handle: ui::PopupJoinHandle<ImportLoadResult>,
+    preview: CsvPreview,
+    table_columns: Vec<String>,
+    // table column for each CSV column, None for skipped columns
+    mapping: Vec<Option<String>>,
+    result: ImportDialogResult,
+}
+
+impl ImportDialog {
+    // CSV columns are mapped to the table columns with the same name, other
+    // columns are skipped until the user chooses a table column for them.
+    pub(super) fn on_load_complete(&mut self, _: nwg::EventData) {
+        self.c.load_notice.receive();
+        let res = self.load_join_handle.join();
+        if !res.error.is_empty() {
+            self.c.status_label.set_text(&format!("Load failed: {}", res.error));
+            return;
+        }
+        self.preview = res.preview;
+        self.table_columns = res.columns;
+
+        for (idx, name) in self.preview.header.iter().enumerate() {
+            self.c.preview_view.insert_column(name.as_str());
+            self.c.preview_view.set_column_width(idx, 100);
+        }
+        for row in self.preview.rows.iter() {
+            let mut cells = row.clone();
+            cells.resize(self.preview.header.len(), String::new());
+            self.c.preview_view.insert_items_row(None, &cells);
+        }
+
+        self.mapping = self.preview.header.iter().map(|name| {
+            self.table_columns.iter().find(|col| col.to_lowercase() == name.trim().to_lowercase()).cloned()
+        }).collect();
+        self.fill_mapping();
+        let mut targets = vec!(SKIP_COLUMN.to_string());
+        targets.extend(self.table_columns.iter().cloned());
+        self.c.target_combo.set_collection(targets);
+        self.c.target_combo.set_selection(Some(0));
+        self.c.target_button.set_enabled(true);
+        self.c.import_button.set_enabled(true);
+        self.update_status();
+    }
+
+    pub(super) fn set_target_column(&mut self, _: nwg::EventData) {
+        let target = match self.c.target_combo.selection_string() {
+            Some(name) if SKIP_COLUMN != name => Some(name),
+            Some(_) => None,
+            None => return
+        };
+        let selected = self.c.mapping_view.selected_items();
+        for idx in selected.iter() {
+            if let Some(col) = self.mapping.get_mut(*idx) {
+                *col = target.clone();
+            }
+        }
+        self.fill_mapping();
+        for idx in selected {
+            self.c.mapping_view.select_item(idx, true);
+        }
+        self.update_status();
+    }
+
+    pub(super) fn import(&mut self, _: nwg::EventData) {
+        let mapped: Vec<&String> = self.mapping.iter().filter_map(|col| col.as_ref()).collect();
+        if mapped.is_empty() {
+            nwg::modal_error_message(&self.c.window, "Import CSV",
+                "At least one CSV column must be mapped to a table column");
+            return;
+        }
+        for (idx, col) in mapped.iter().enumerate() {
+            if mapped[..idx].contains(col) {
+                nwg::modal_error_message(&self.c.window, "Import CSV", &format!(
+                    "Table column '{}' is mapped to more than one CSV column", col));
+                return;
+            }
+        }
+        self.result = ImportDialogResult::new(self.preview.delimiter, self.mapping.clone());
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn fill_mapping(&self) {
+        self.c.mapping_view.clear();
+        for (name, col) in self.preview.header.iter().zip(self.mapping.iter()) {
+            let target = match col {
+                Some(col) => col.as_str(),
+                None => SKIP_COLUMN
+            };
+            self.c.mapping_view.insert_items_row(None, &[name.as_str(), target]);
+        }
+    }
+
+    fn update_status(&self) {
+        let mapped = self.mapping.iter().filter(|col| col.is_some()).count();
+        let delimiter = match self.preview.delimiter {
+            '\t' => "tab".to_string(),
+            ch => format!("'{}'", ch)
+        };
+        self.c.status_label.set_text(&format!("Columns mapped: {} of {}, delimiter: {}",
+            mapped, self.mapping.len(), delimiter));
+    }
+
+    // Generated columns cannot be set with COPY, so they are not offered for mapping.
+    fn load_columns(pcc: &PgConnConfig, bbf_db: &str, table: &ExportTable) -> Result<Vec<String>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(bbf_db)?;
+        let rs = client.query("
+            select a.attname::text as name
+            from pg_attribute a
+            join pg_class c on c.oid = a.attrelid
+            join pg_namespace n on n.oid = c.relnamespace
+            where n.nspname = $1
+            and c.relname = $2
+            and a.attnum > 0
+            and not a.attisdropped
+            and a.attgenerated = ''
+            order by a.attnum", &[&table.nspname, &table.table])?;
+        let res = rs.iter().map(|row| row.get("name")).collect();
+        client.close()?;
+        Ok(res)
+    }
+}
+
+impl ui::PopupDialog<ImportDialogArgs, ImportDialogResult> for ImportDialog {
+    fn popup(args: ImportDialogArgs) -> ui::PopupJoinHandle<ImportDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.result = ImportDialogResult::cancelled();
+        self.c.file_input.set_text(&self.args.csv_file);
+        self.c.table_input.set_text(&format!("{}.{}", &self.args.table.schema, &self.args.table.table));
+        let sender = self.c.load_notice.sender();
+        let pcc = self.args.pg_conn_config.clone();
+        let bbf_db = self.args.bbf_db.clone();
+        let table = self.args.table.clone();
+        let csv_file = self.args.csv_file.clone();
+        let join_handle = thread::spawn(move || {
+            let preview = CsvPreview::read(Path::new(&csv_file), PREVIEW_ROWS);
+            let columns = ImportDialog::load_columns(&pcc, &bbf_db, &table);
+            let res = match (preview, columns) {
+                (Ok(preview), Ok(columns)) => ImportLoadResult::success(preview, columns),
+                (Err(e), _) => ImportLoadResult::failure(format!(
+                    "Error reading CSV file: {}, message: {}", csv_file, e)),
+                (_, Err(e)) => ImportLoadResult::failure(format!("{}", e))
+            };
+            sender.send();
+            res
+        });
+        self.load_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn result(&mut self) -> ImportDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ImportLoadResult {
+    pub(super) preview: CsvPreview,
+    pub(super) columns: Vec<String>,
+    pub(super) error: String,
+}
+
+impl ImportLoadResult {
+    pub(super) fn success(preview: CsvPreview, columns: Vec<String>) -> Self {
+        Self {
+            preview,
+            columns,
+            error: String::new(),
+        }
+    }
+
+    pub(super) fn failure(error: String) -> Self {
+        Self {
+            error,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ImportDialogResult {
+    pub cancelled: bool,
+    pub delimiter: char,
+    // table column for each CSV column, None for skipped columns
+    pub columns: Vec<Option<String>>,
+}
+
+impl ImportDialogResult {
+    pub fn new(delimiter: char, columns: Vec<Option<String>>) -> Self {
+        Self {
+            cancelled: false,
+            delimiter,
+            columns,
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self {
+            cancelled: true,
+            ..Default::default()
+        }
+    }
+}
@@ -1,125 +1,159 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-use nwg::EventData;
-
-#[derive(Default)]
-pub struct LoadDbnamesDialog {
-    pub(super) c: LoadDbnamesDialogControls,
-
-    args: LoadDbnamesDialogArgs,
-    load_join_handle: ui::PopupJoinHandle<LoadDbnamesResult>,
-    dialog_result: LoadDbnamesDialogResult
-}
-
-impl LoadDbnamesDialog {
-    pub(super) fn on_load_complete(&mut self, _: nwg::EventData) {
-        self.c.load_notice.receive();
-        let res = self.load_join_handle.join();
-        let success = res.error.is_empty();
-        self.stop_progress_bar(success.clone());
-        if !success {
-            self.dialog_result = LoadDbnamesDialogResult::failure();
-            self.c.label.set_text("Load failed");
-            self.c.details_box.set_text(&res.error);
-            self.c.copy_clipboard_button.set_enabled(true);
-            self.c.close_button.set_enabled(true);
-        } else {
-            self.dialog_result = LoadDbnamesDialogResult::success(res.dbnames, res.bbf_db);
-            self.close(nwg::EventData::NoData)
-        }
-    }
-
-    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
-        let text = self.c.details_box.text();
-        let _ = set_clipboard(formats::Unicode, &text);
-    }
-
-    fn stop_progress_bar(&self, success: bool) {
-        self.c.progress_bar.set_marquee(false, 0);
-        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
-        self.c.progress_bar.set_pos(1);
-        if !success {
-            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
-        }
-    }
-
-    fn load_dbnames_from_postgres(pg_conn_config: &PgConnConfig) -> Result<(Vec<String>, String), PgAccessError> {
-        let mut client_default = pg_conn_config.open_connection_default()?;
-        let rs_bbf_db = client_default.query("show babelfishpg_tsql.database_name", &[])?;
-        let bbf_db: String = rs_bbf_db[0].get("babelfishpg_tsql.database_name");
-        client_default.close()?;
-
-        let mut client_bbf = pg_conn_config.open_connection_to_db(&bbf_db)?;
-        let rs_dbnames = client_bbf.query("select name from sys.babelfish_sysdatabases", &[])?;
-        let dbnames = rs_dbnames.iter().map(|row| {
-            row.get("name")
-        }).collect();
-        client_bbf.close()?;
-
-        Ok((dbnames, bbf_db))
-    }
-}
-
-impl ui::PopupDialog<LoadDbnamesDialogArgs, LoadDbnamesDialogResult> for LoadDbnamesDialog {
-    fn popup(args: LoadDbnamesDialogArgs) -> ui::PopupJoinHandle<LoadDbnamesDialogResult> {
-        let join_handle = thread::spawn(move || {
-            let data = Self {
-                args,
-                ..Default::default()
-            };
-            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
-            nwg::dispatch_thread_events();
-            dialog.result()
-        });
-        ui::PopupJoinHandle::from(join_handle)
-    }
-
-    fn init(&mut self) {
-        let sender = self.c.load_notice.sender();
-        let pgconf = self.args.pg_conn_config.clone();
-        let join_handle = thread::spawn(move || {
-            let start = Instant::now();
-            let res = match LoadDbnamesDialog::load_dbnames_from_postgres(&pgconf) {
-                Ok((dbnames, bbf_db)) => LoadDbnamesResult::success(dbnames, bbf_db),
-                Err(e) => LoadDbnamesResult::failure(format!("{}", e))
-            };
-            let remaining = 1000 - start.elapsed().as_millis() as i64;
-            if remaining > 0 {
-                thread::sleep(Duration::from_millis(remaining as u64));
-            }
-            sender.send();
-            res
-        });
-        self.load_join_handle = ui::PopupJoinHandle::from(join_handle);
-    }
-
-    fn result(&mut self) -> LoadDbnamesDialogResult {
-        self.dialog_result.clone()
-    }
-
-    fn close(&mut self, _: nwg::EventData) {
-        self.args.send_notice();
-        self.c.window.set_visible(false);
-        nwg::stop_thread_dispatch();
-    }
-
-    fn on_resize(&mut self, _: EventData) {
-        self.c.update_tab_order();
-    }
-}
-
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct LoadDbnamesDialog {
+    pub(super) c: LoadDbnamesDialogControls,
+
+    args: LoadDbnamesDialogArgs,
+    load_join_handle: ui::PopupJoinHandle<LoadDbnamesResult>,
+    dialog_result: LoadDbnamesDialogResult
+}
+
+impl LoadDbnamesDialog {
+    pub(super) fn on_load_complete(&mut self, _: nwg::EventData) {
+        self.c.load_notice.receive();
+        let res = self.load_join_handle.join();
+        let success = res.error.is_empty();
+        self.stop_progress_bar(success.clone());
+        if !success {
+            self.dialog_result = LoadDbnamesDialogResult::failure();
+            self.c.label.set_text("Load failed");
+            self.c.details_box.set_text(&redact_secrets(&res.error));
+            self.c.copy_clipboard_button.set_enabled(true);
+            self.c.close_button.set_enabled(true);
+        } else {
+            self.dialog_result = LoadDbnamesDialogResult::success(res.dbnames, res.bbf_db, res.server_info);
+            self.close(nwg::EventData::NoData)
+        }
+    }
+
+    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.c.details_box.text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    fn stop_progress_bar(&self, success: bool) {
+        self.c.progress_bar.set_marquee(false, 0);
+        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_pos(1);
+        if !success {
+            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
+        }
+    }
+
+    // Empty Babelfish DB name is returned for plain Postgres servers,
+    // physical DBs are listed for them instead of Babelfish ones.
+    fn load_dbnames_from_postgres(pg_conn_config: &PgConnConfig) -> Result<(Vec<String>, String), PgAccessError> {
+        let mut client_default = pg_conn_config.open_connection_default()?;
+        if !babelfish_enabled(&mut client_default)? {
+            let dbnames = Self::load_plain_dbnames(&mut client_default)?;
+            client_default.close()?;
+            return Ok((dbnames, String::new()));
+        }
+        let rs_bbf_db = client_default.query("show babelfishpg_tsql.database_name", &[])?;
+        let bbf_db: String = rs_bbf_db[0].get("babelfishpg_tsql.database_name");
+
+        let mut client_bbf = pg_conn_config.open_connection_to_db(&bbf_db)?;
+        if !babelfish_catalog_exists(&mut client_bbf)? {
+            log::warn!("Babelfish catalog not found in DB: {}, listing Postgres DBs", bbf_db);
+            client_bbf.close()?;
+            let dbnames = Self::load_plain_dbnames(&mut client_default)?;
+            client_default.close()?;
+            return Ok((dbnames, String::new()));
+        }
+        client_default.close()?;
+        let rs_dbnames = client_bbf.query("select name from sys.babelfish_sysdatabases", &[])?;
+        let dbnames = rs_dbnames.iter().map(|row| {
+            row.get("name")
+        }).collect();
+        client_bbf.close()?;
+
+        Ok((dbnames, bbf_db))
+    }
+
+    fn load_plain_dbnames(client: &mut postgres::Client) -> Result<Vec<String>, PgAccessError> {
+        let rs = client.query("
+            select datname::text as name
+            from pg_catalog.pg_database
+            where not datistemplate
+            and datallowconn
+            order by datname", &[])?;
+        Ok(rs.iter().map(|row| row.get("name")).collect())
+    }
+}
+
+impl ui::PopupDialog<LoadDbnamesDialogArgs, LoadDbnamesDialogResult> for LoadDbnamesDialog {
+    fn popup(args: LoadDbnamesDialogArgs) -> ui::PopupJoinHandle<LoadDbnamesDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        let sender = self.c.load_notice.sender();
+        let pgconf = self.args.pg_conn_config.clone();
+        let join_handle = thread::spawn(move || {
+            let start = Instant::now();
+            let res = match LoadDbnamesDialog::load_dbnames_from_postgres(&pgconf) {
+                Ok((dbnames, bbf_db)) => {
+                    // server details are optional, names are still loaded when they cannot be read
+                    let server_info = match ServerInfo::query(&pgconf) {
+                        Ok(info) => Some(info),
+                        Err(e) => {
+                            log::warn!("Error reading server details: {}", e);
+                            None
+                        }
+                    };
+                    LoadDbnamesResult::success(dbnames, bbf_db, server_info)
+                },
+                Err(e) => LoadDbnamesResult::failure(format!("{}", e))
+            };
+            let remaining = 1000 - start.elapsed().as_millis() as i64;
+            if remaining > 0 {
+                thread::sleep(Duration::from_millis(remaining as u64));
+            }
+            sender.send();
+            res
+        });
+        self.load_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn result(&mut self) -> LoadDbnamesDialogResult {
+        self.dialog_result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.send_notice();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
+
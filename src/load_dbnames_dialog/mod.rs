@@ -1,48 +1,52 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-mod args;
-mod controls;
-mod dialog;
-mod events;
-mod layout;
-mod nui;
-mod result;
-
-use std::thread;
-use std::time::Duration;
-use std::time::Instant;
-
-use clipboard_win::formats;
-use clipboard_win::set_clipboard;
-use nwg::NativeUi;
-
-use crate::*;
-use nwg_ui as ui;
-use ui::Controls;
-use ui::Events;
-use ui::Layout;
-use ui::PopupDialog;
-use common::PgConnConfig;
-use common::PgAccessError;
-
-pub use args::LoadDbnamesDialogArgs;
-pub(self) use controls::LoadDbnamesDialogControls;
-pub use dialog::LoadDbnamesDialog;
-use events::LoadDbnamesDialogEvents;
-use layout::LoadDbnamesDialogLayout;
-pub use result::LoadDbnamesDialogResult;
-use result::LoadDbnamesResult;
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod args;
+mod controls;
+mod dialog;
+mod events;
+mod layout;
+mod nui;
+mod result;
+
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use clipboard_win::formats;
+use clipboard_win::set_clipboard;
+use nwg::NativeUi;
+
+use crate::*;
+use nwg_ui as ui;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupDialog;
+use common::babelfish_catalog_exists;
+use common::babelfish_enabled;
+use common::PgConnConfig;
+use common::PgAccessError;
+use common::redact_secrets;
+use common::ServerInfo;
+
+pub use args::LoadDbnamesDialogArgs;
+pub(self) use controls::LoadDbnamesDialogControls;
+pub use dialog::LoadDbnamesDialog;
+use events::LoadDbnamesDialogEvents;
+use layout::LoadDbnamesDialogLayout;
+pub use result::LoadDbnamesDialogResult;
+use result::LoadDbnamesResult;
@@ -1,63 +1,69 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-#[derive(Default)]
-pub(super) struct LoadDbnamesResult {
-    pub(super) dbnames: Vec<String>,
-    pub(super) bbf_db: String,
-    pub(super) error: String,
-}
-
-impl LoadDbnamesResult {
-    pub(super) fn success(dbnames: Vec<String>, bbf_db: String) -> Self {
-        Self {
-            dbnames,
-            bbf_db,
-            error: String::new()
-        }
-    }
-
-    pub(super) fn failure(error: String) -> Self {
-        Self {
-            error,
-            ..Default::default()
-        }
-    }
-}
-
-#[derive(Default, Clone)]
-pub struct LoadDbnamesDialogResult {
-    pub success: bool,
-    pub dbnames: Vec<String>,
-    pub bbf_db: String,
-}
-
-impl LoadDbnamesDialogResult {
-    pub fn success(dbnames: Vec<String>, bbf_db: String) -> Self {
-        Self {
-            success: true,
-            dbnames,
-            bbf_db
-        }
-    }
-
-    pub fn failure() -> Self {
-        Self {
-            success: false,
-            ..Default::default()
-        }
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct LoadDbnamesResult {
+    pub(super) dbnames: Vec<String>,
+    pub(super) bbf_db: String,
+    pub(super) server_info: Option<ServerInfo>,
+    pub(super) error: String,
+}
+
+impl LoadDbnamesResult {
+    pub(super) fn success(dbnames: Vec<String>, bbf_db: String, server_info: Option<ServerInfo>) -> Self {
+        Self {
+            dbnames,
+            bbf_db,
+            server_info,
+            error: String::new()
+        }
+    }
+
+    pub(super) fn failure(error: String) -> Self {
+        Self {
+            error,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct LoadDbnamesDialogResult {
+    pub success: bool,
+    pub dbnames: Vec<String>,
+    pub bbf_db: String,
+    pub server_info: Option<ServerInfo>,
+}
+
+impl LoadDbnamesDialogResult {
+    pub fn success(dbnames: Vec<String>, bbf_db: String, server_info: Option<ServerInfo>) -> Self {
+        Self {
+            success: true,
+            dbnames,
+            bbf_db,
+            server_info
+        }
+    }
+
+    pub fn failure() -> Self {
+        Self {
+            success: false,
+            ..Default::default()
+        }
+    }
+}
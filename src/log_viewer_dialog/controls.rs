@@ -0,0 +1,120 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct LogViewerDialogControls {
+    layout: LogViewerDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) file_label: nwg::Label,
+    pub(super) file_combo: nwg::ComboBox<String>,
+    pub(super) search_label: nwg::Label,
+    pub(super) search_input: nwg::TextInput,
+    pub(super) find_button: nwg::Button,
+    pub(super) content_box: nwg::TextBox,
+
+    pub(super) open_folder_button: nwg::Button,
+    pub(super) close_button: nwg::Button,
+}
+
+impl ui::Controls for LogViewerDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((640, 480))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Logs")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Log file:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.file_label)?;
+        nwg::ComboBox::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.file_combo)?;
+        nwg::Label::builder()
+            .text("Search:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.search_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.search_input)?;
+        nwg::Button::builder()
+            .text("Find next")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.find_button)?;
+
+        nwg::TextBox::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .limit(MAX_VIEW_CHARS)
+            .readonly(true)
+            .parent(&self.window)
+            .build(&mut self.content_box)?;
+
+        nwg::Button::builder()
+            .text("Open folder")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.open_folder_button)?;
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.close_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.file_combo)
+            .control(&self.search_input)
+            .control(&self.find_button)
+            .control(&self.content_box)
+            .control(&self.open_folder_button)
+            .control(&self.close_button)
+            .build();
+    }
+}
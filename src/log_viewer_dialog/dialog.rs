@@ -0,0 +1,178 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct LogViewerDialog {
+    pub(super) c: LogViewerDialogControls,
+
+    args: LogViewerDialogArgs,
+    files: Vec<PathBuf>,
+}
+
+impl LogViewerDialog {
+    pub(super) fn on_file_changed(&mut self, _: nwg::EventData) {
+        self.load_selected_file();
+    }
+
+    pub(super) fn find_next(&mut self, _: nwg::EventData) {
+        let needle: Vec<u16> = self.c.search_input.text().encode_utf16().map(lower_ascii).collect();
+        if needle.is_empty() {
+            return;
+        }
+        let haystack: Vec<u16> = self.c.content_box.text().encode_utf16().map(lower_ascii).collect();
+        let start = self.c.content_box.selection().end as usize;
+        let found = match find_from(&haystack, &needle, start) {
+            Some(idx) => Some(idx),
+            // wrap around to the beginning
+            None => find_from(&haystack, &needle, 0)
+        };
+        match found {
+            Some(idx) => {
+                let end = idx + needle.len();
+                self.c.content_box.set_focus();
+                self.c.content_box.set_selection((idx as u32)..(end as u32));
+                if let Some(hwnd) = self.c.content_box.handle.hwnd() {
+                    unsafe {
+                        SendMessageW(hwnd, EM_SCROLLCARET as u32, 0, 0);
+                    }
+                }
+            },
+            None => {
+                nwg::modal_info_message(&self.c.window, "Search",
+                    &format!("Text not found: {}", self.c.search_input.text()));
+            }
+        }
+    }
+
+    pub(super) fn open_folder(&mut self, _: nwg::EventData) {
+        let dir = match logs_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                nwg::modal_error_message(&self.c.window, "Error", &e.to_string());
+                return;
+            }
+        };
+        let _ = fs::create_dir_all(&dir);
+        if let Err(e) = Command::new("explorer.exe").arg(&dir).spawn() {
+            nwg::modal_error_message(&self.c.window, "Error",
+                &format!("Error opening folder: {}, message: {}", dir.to_string_lossy(), e));
+        }
+    }
+
+    fn reload_file_list(&mut self) {
+        self.files = match list_log_files() {
+            Ok(files) => files,
+            Err(e) => {
+                self.c.content_box.set_text(&format!("No log files found, message: {}", e));
+                vec!()
+            }
+        };
+        let names: Vec<String> = self.files.iter()
+            .map(|path| match path.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => path.to_string_lossy().to_string()
+            })
+            .collect();
+        let empty = names.is_empty();
+        self.c.file_combo.set_collection(names);
+        if !empty {
+            self.c.file_combo.set_selection(Some(0));
+            self.load_selected_file();
+        }
+    }
+
+    fn load_selected_file(&mut self) {
+        let path = match self.c.file_combo.selection() {
+            Some(idx) if idx < self.files.len() => self.files[idx].clone(),
+            _ => return
+        };
+        let text = match fs::read(&path) {
+            Ok(bytes) => {
+                let content = String::from_utf8_lossy(&bytes);
+                let mut lines: Vec<&str> = content.lines().collect();
+                let mut len = 0;
+                let mut first = lines.len();
+                while first > 0 && len + lines[first - 1].len() + 2 < MAX_VIEW_CHARS {
+                    len += lines[first - 1].len() + 2;
+                    first -= 1;
+                }
+                let truncated = first > 0;
+                lines.drain(0..first);
+                let mut text = lines.join("\r\n");
+                if truncated {
+                    text = format!("(log file is too large, showing last {} lines only)\r\n{}", lines.len(), text);
+                }
+                text
+            },
+            Err(e) => format!("Error reading log file: {}, message: {}", path.to_string_lossy(), e)
+        };
+        self.c.content_box.set_text(&text);
+    }
+}
+
+impl ui::PopupDialog<LogViewerDialogArgs, ()> for LogViewerDialog {
+    fn popup(args: LogViewerDialogArgs) -> ui::PopupJoinHandle<()> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.reload_file_list();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> () {
+        ()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
+
+fn lower_ascii(ch: u16) -> u16 {
+    if ch >= 'A' as u16 && ch <= 'Z' as u16 {
+        ch + ('a' as u16 - 'A' as u16)
+    } else {
+        ch
+    }
+}
+
+fn find_from(haystack: &[u16], needle: &[u16], start: usize) -> Option<usize> {
+    if start >= haystack.len() || needle.len() > haystack.len() - start {
+        return None;
+    }
+    haystack[start..].windows(needle.len())
+        .position(|win| win == needle)
+        .map(|idx| idx + start)
+}
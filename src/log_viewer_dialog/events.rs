@@ -0,0 +1,61 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct LogViewerDialogEvents {
+    pub(super) events: Vec<ui::Event<LogViewerDialog>>
+}
+
+impl ui::Events<LogViewerDialogControls> for LogViewerDialogEvents {
+    fn build(&mut self, c: &LogViewerDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(LogViewerDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(LogViewerDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.file_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(LogViewerDialog::on_file_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.find_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(LogViewerDialog::find_next)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.open_folder_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(LogViewerDialog::open_folder)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(LogViewerDialog::close)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod args;
+mod controls;
+mod dialog;
+mod events;
+mod layout;
+mod nui;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+
+use winapi::um::winuser::SendMessageW;
+use winapi::um::winuser::EM_SCROLLCARET;
+
+use nwg::NativeUi;
+
+use crate::*;
+use common::list_log_files;
+use common::logs_dir;
+use nwg_ui as ui;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupArgs;
+use ui::PopupDialog;
+
+pub use args::LogViewerDialogArgs;
+pub(self) use controls::LogViewerDialogControls;
+pub use dialog::LogViewerDialog;
+use events::LogViewerDialogEvents;
+use layout::LogViewerDialogLayout;
+
+// larger log files are shown partially, only their ending
+const MAX_VIEW_CHARS: usize = 2 * 1024 * 1024;
@@ -1,38 +1,85 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-#![windows_subsystem = "windows"]
-
-mod common;
-mod about_dialog;
-mod app_window;
-mod backup_dialog;
-mod connect_dialog;
-mod connect_check_dialog;
-mod load_dbnames_dialog;
-mod restore_dialog;
-
-use nwg::NativeUi;
-
-fn main() {
-    nwg::init().expect("Failed to init Native Windows GUI");
-    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
-
-    let data = app_window::AppWindow::new();
-    let _app = app_window::AppWindow::build_ui(data).expect("Failed to build UI");
-
-    nwg::dispatch_thread_events();
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![windows_subsystem = "windows"]
+
+mod common;
+mod about_dialog;
+mod app_window;
+mod backup_dialog;
+mod bacpac;
+mod cli;
+mod clone_dialog;
+mod connect_dialog;
+mod connect_check_dialog;
+mod copy_dialog;
+mod db_list_dialog;
+mod email_dialog;
+mod export_dialog;
+mod globals_dialog;
+mod import_dialog;
+mod load_dbnames_dialog;
+mod log_viewer_dialog;
+mod migrate_dialog;
+mod mssql;
+mod pitr;
+mod pitr_dialog;
+mod preflight_dialog;
+mod queue_dialog;
+mod restore_dialog;
+mod restore_options_dialog;
+mod schedule_dialog;
+mod share_credentials_dialog;
+
+use nwg::NativeUi;
+
+fn main() {
+    let env_password = common::scrub_password_env();
+    // logging is best-effort, app still works when the logs dir is not writable
+    let _ = common::init_file_logging();
+    log::info!("WiltonDB Backup Tool {} started", env!("CARGO_PKG_VERSION"));
+    let args: Vec<String> = std::env::args().collect();
+    if cli::is_cli_invocation(&args) {
+        std::process::exit(cli::run(&args, env_password));
+    }
+
+    let _instance = match common::SingleInstance::acquire() {
+        Ok(Some(instance)) => Some(instance),
+        Ok(None) => {
+            let file = args.get(1).cloned().unwrap_or_default();
+            match common::forward_to_running_instance(&file) {
+                Ok(_) => log::info!("Passed control to already running instance"),
+                Err(e) => log::error!("{}", e)
+            }
+            // never start a second copy, it could run conflicting restores
+            return;
+        },
+        Err(e) => {
+            log::error!("Error checking for running instance, message: {}", e);
+            None
+        }
+    };
+
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let data = app_window::AppWindow::new(args.get(1).cloned());
+    let _app = app_window::AppWindow::build_ui(data).expect("Failed to build UI");
+
+    nwg::dispatch_thread_events();
+
+    common::close_ssh_tunnels();
 }
\ No newline at end of file
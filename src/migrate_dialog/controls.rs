@@ -0,0 +1,338 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct MigrateDialogControls {
+    layout: MigrateDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) tabs_container: nwg::TabsContainer,
+    pub(super) tools_tab: nwg::Tab,
+    pub(super) source_tab: nwg::Tab,
+    pub(super) target_tab: nwg::Tab,
+
+    pub(super) tools_hint_label: nwg::Label,
+    pub(super) tools_dir_label: nwg::Label,
+    pub(super) tools_dir_input: nwg::TextInput,
+    pub(super) tools_dir_button: nwg::Button,
+    pub(super) tools_dir_chooser: nwg::FileDialog,
+    pub(super) scripter_label: nwg::Label,
+    pub(super) scripter_input: nwg::TextInput,
+    pub(super) scripter_button: nwg::Button,
+    pub(super) scripter_chooser: nwg::FileDialog,
+
+    pub(super) hostname_label: nwg::Label,
+    pub(super) hostname_input: nwg::TextInput,
+    pub(super) port_label: nwg::Label,
+    pub(super) port_input: nwg::TextInput,
+    pub(super) auth_label: nwg::Label,
+    pub(super) auth_combo: nwg::ComboBox<String>,
+    pub(super) username_label: nwg::Label,
+    pub(super) username_input: nwg::TextInput,
+    pub(super) password_label: nwg::Label,
+    pub(super) password_input: nwg::TextInput,
+    pub(super) dbname_label: nwg::Label,
+    pub(super) dbname_input: nwg::TextInput,
+
+    pub(super) target_server_label: nwg::Label,
+    pub(super) target_server_input: nwg::TextInput,
+    pub(super) tds_port_label: nwg::Label,
+    pub(super) tds_port_input: nwg::TextInput,
+    pub(super) target_dbname_label: nwg::Label,
+    pub(super) target_dbname_input: nwg::TextInput,
+    pub(super) work_dir_label: nwg::Label,
+    pub(super) work_dir_input: nwg::TextInput,
+    pub(super) work_dir_button: nwg::Button,
+    pub(super) work_dir_chooser: nwg::FileDialog,
+
+    pub(super) status_label: nwg::Label,
+    pub(super) back_button: nwg::Button,
+    pub(super) next_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+
+    pub(super) check_notice: ui::SyncNotice,
+}
+
+impl ui::Controls for MigrateDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((520, 360))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Migrate from SQL Server")
+            .build(&mut self.window)?;
+
+        nwg::TabsContainer::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.tabs_container)?;
+        nwg::Tab::builder()
+            .text("1. Tools")
+            .parent(&self.tabs_container)
+            .build(&mut self.tools_tab)?;
+        nwg::Tab::builder()
+            .text("2. Source")
+            .parent(&self.tabs_container)
+            .build(&mut self.source_tab)?;
+        nwg::Tab::builder()
+            .text("3. Target")
+            .parent(&self.tabs_container)
+            .build(&mut self.target_tab)?;
+
+        // tools
+        nwg::Label::builder()
+            .text("sqlcmd and bcp utilities are required, mssql-scripter is installed with: pip install mssql-scripter")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.tools_tab)
+            .build(&mut self.tools_hint_label)?;
+        nwg::Label::builder()
+            .text("sqlcmd, bcp dir:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.tools_tab)
+            .build(&mut self.tools_dir_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Found in PATH when empty"))
+            .font(Some(&self.font_normal))
+            .parent(&self.tools_tab)
+            .build(&mut self.tools_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.tools_tab)
+            .build(&mut self.tools_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose directory with sqlcmd and bcp")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.tools_dir_chooser)?;
+        nwg::Label::builder()
+            .text("mssql-scripter:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.tools_tab)
+            .build(&mut self.scripter_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.tools_tab)
+            .build(&mut self.scripter_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.tools_tab)
+            .build(&mut self.scripter_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose mssql-scripter")
+            .action(nwg::FileDialogAction::Open)
+            .filters("Batch(*.bat)|Any(*.*)")
+            .build(&mut self.scripter_chooser)?;
+
+        // source
+        nwg::Label::builder()
+            .text("Server:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.source_tab)
+            .build(&mut self.hostname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.source_tab)
+            .build(&mut self.hostname_input)?;
+        nwg::Label::builder()
+            .text("Port:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.source_tab)
+            .build(&mut self.port_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .font(Some(&self.font_normal))
+            .parent(&self.source_tab)
+            .build(&mut self.port_input)?;
+        nwg::Label::builder()
+            .text("Authentication:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.source_tab)
+            .build(&mut self.auth_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                "SQL Server authentication".to_string(),
+                "Windows authentication".to_string(),
+            ))
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.source_tab)
+            .build(&mut self.auth_combo)?;
+        nwg::Label::builder()
+            .text("Username:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.source_tab)
+            .build(&mut self.username_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.source_tab)
+            .build(&mut self.username_input)?;
+        nwg::Label::builder()
+            .text("Password:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.source_tab)
+            .build(&mut self.password_label)?;
+        nwg::TextInput::builder()
+            .password(Some('*'))
+            .font(Some(&self.font_normal))
+            .parent(&self.source_tab)
+            .build(&mut self.password_input)?;
+        nwg::Label::builder()
+            .text("Database:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.source_tab)
+            .build(&mut self.dbname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.source_tab)
+            .build(&mut self.dbname_input)?;
+
+        // target
+        nwg::Label::builder()
+            .text("WiltonDB:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.target_tab)
+            .build(&mut self.target_server_label)?;
+        nwg::TextInput::builder()
+            .readonly(true)
+            .font(Some(&self.font_normal))
+            .parent(&self.target_tab)
+            .build(&mut self.target_server_input)?;
+        nwg::Label::builder()
+            .text("T-SQL port:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.target_tab)
+            .build(&mut self.tds_port_label)?;
+        nwg::TextInput::builder()
+            .flags(nwg::TextInputFlags::VISIBLE | nwg::TextInputFlags::NUMBER)
+            .font(Some(&self.font_normal))
+            .parent(&self.target_tab)
+            .build(&mut self.tds_port_input)?;
+        nwg::Label::builder()
+            .text("New DB name:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.target_tab)
+            .build(&mut self.target_dbname_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.target_tab)
+            .build(&mut self.target_dbname_input)?;
+        nwg::Label::builder()
+            .text("Work dir:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.target_tab)
+            .build(&mut self.work_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.target_tab)
+            .build(&mut self.work_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.target_tab)
+            .build(&mut self.work_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose work directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.work_dir_chooser)?;
+
+        // buttons
+
+        nwg::Label::builder()
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.status_label)?;
+        nwg::Button::builder()
+            .text("< Back")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.back_button)?;
+        nwg::Button::builder()
+            .text("Next >")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.next_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        ui::notice_builder()
+            .parent(&self.window)
+            .build(&mut self.check_notice)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.tabs_container)
+            .control(&self.tools_dir_input)
+            .control(&self.tools_dir_button)
+            .control(&self.scripter_input)
+            .control(&self.scripter_button)
+            .control(&self.hostname_input)
+            .control(&self.port_input)
+            .control(&self.auth_combo)
+            .control(&self.username_input)
+            .control(&self.password_input)
+            .control(&self.dbname_input)
+            .control(&self.target_server_input)
+            .control(&self.tds_port_input)
+            .control(&self.target_dbname_input)
+            .control(&self.work_dir_input)
+            .control(&self.work_dir_button)
+            .control(&self.back_button)
+            .control(&self.next_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
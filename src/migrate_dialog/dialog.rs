@@ -0,0 +1,270 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+const DEFAULT_MSSQL_PORT: u16 = 1433;
+const TOOLS_TAB: usize = 0;
+const SOURCE_TAB: usize = 1;
+const TARGET_TAB: usize = 2;
+
+#[derive(Default)]
+pub struct MigrateDialog {
+    pub(super) c: MigrateDialogControls,
+
+    args: MigrateDialogArgs,
+    check_join_handle: ui::PopupJoinHandle<MigrateCheckResult>,
+    // source connection is checked before moving to the target step
+    checking_source: Option<MssqlSource>,
+    checked_source: Option<MssqlSource>,
+    result: MigrateDialogResult,
+}
+
+impl MigrateDialog {
+    pub(super) fn on_tab_changed(&mut self, _: nwg::EventData) {
+        self.update_buttons();
+    }
+
+    pub(super) fn on_auth_changed(&mut self, _: nwg::EventData) {
+        let sql_auth = Some(0) == self.c.auth_combo.selection();
+        self.c.username_input.set_enabled(sql_auth);
+        self.c.password_input.set_enabled(sql_auth);
+    }
+
+    pub(super) fn choose_tools_dir(&mut self, _: nwg::EventData) {
+        Self::choose_dir(&self.c.window, &self.c.tools_dir_chooser, &self.c.tools_dir_input);
+    }
+
+    pub(super) fn choose_work_dir(&mut self, _: nwg::EventData) {
+        Self::choose_dir(&self.c.window, &self.c.work_dir_chooser, &self.c.work_dir_input);
+    }
+
+    pub(super) fn choose_scripter(&mut self, _: nwg::EventData) {
+        if self.c.scripter_chooser.run(Some(&self.c.window)) {
+            if let Ok(file) = self.c.scripter_chooser.get_selected_item() {
+                self.c.scripter_input.set_text(&file.to_string_lossy());
+            }
+        }
+    }
+
+    pub(super) fn back(&mut self, _: nwg::EventData) {
+        let idx = self.c.tabs_container.selected_tab();
+        if idx > TOOLS_TAB {
+            self.select_tab(idx - 1);
+        }
+    }
+
+    // Source connection is checked by listing its tables with "sqlcmd", this
+    // also checks that the tools are found. Migration starts from the last step.
+    pub(super) fn next(&mut self, _: nwg::EventData) {
+        match self.c.tabs_container.selected_tab() {
+            TOOLS_TAB => self.select_tab(SOURCE_TAB),
+            SOURCE_TAB => {
+                let source = match self.source_from_input() {
+                    Ok(source) => source,
+                    Err(msg) => {
+                        nwg::modal_error_message(&self.c.window, "Migrate from SQL Server", &msg);
+                        return;
+                    }
+                };
+                if Some(&source) == self.checked_source.as_ref() {
+                    self.select_tab(TARGET_TAB);
+                    return;
+                }
+                self.checking_source = Some(source.clone());
+                let tools = self.tools_from_input();
+                self.c.status_label.set_text("Checking connection ...");
+                self.c.back_button.set_enabled(false);
+                self.c.next_button.set_enabled(false);
+                let sender = self.c.check_notice.sender();
+                let join_handle = thread::spawn(move || {
+                    let res = match mssql::list_tables(&source, &tools) {
+                        Ok(tables) => MigrateCheckResult::success(tables.len()),
+                        Err(e) => MigrateCheckResult::failure(e.to_string())
+                    };
+                    sender.send();
+                    res
+                });
+                self.check_join_handle = ui::PopupJoinHandle::from(join_handle);
+            },
+            _ => self.start()
+        }
+    }
+
+    pub(super) fn on_check_complete(&mut self, _: nwg::EventData) {
+        self.c.check_notice.receive();
+        let res = self.check_join_handle.join();
+        self.update_buttons();
+        if !res.error.is_empty() {
+            self.checking_source = None;
+            self.c.status_label.set_text("Connection failed");
+            nwg::modal_error_message(&self.c.window, "Migrate from SQL Server", &format!(
+                "Error connecting to SQL Server: {}", res.error));
+            return;
+        }
+        self.c.status_label.set_text(&format!("Connected, tables found: {}", res.tables_count));
+        self.checked_source = self.checking_source.take();
+        if self.c.target_dbname_input.text().is_empty() {
+            self.c.target_dbname_input.set_text(self.c.dbname_input.text().trim());
+        }
+        self.select_tab(TARGET_TAB);
+    }
+
+    fn start(&mut self) {
+        let migration = match self.migration_from_input() {
+            Ok(migration) => migration,
+            Err(msg) => {
+                nwg::modal_error_message(&self.c.window, "Migrate from SQL Server", &msg);
+                return;
+            }
+        };
+        self.result = MigrateDialogResult::new(migration);
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn migration_from_input(&self) -> Result<MigrationArgs, String> {
+        let source = self.source_from_input()?;
+        if Some(&source) != self.checked_source.as_ref() {
+            return Err("Source connection must be checked first, please use 'Next' button on the 'Source' step".to_string());
+        }
+        let target_dbname = self.c.target_dbname_input.text().trim().to_string();
+        validate_db_name(&target_dbname)?;
+        let target_tds_port = match self.c.tds_port_input.text().trim().parse::<u16>() {
+            Ok(port) if port > 0 => port,
+            _ => return Err(format!("Invalid T-SQL port: {}", self.c.tds_port_input.text()))
+        };
+        let pcc = &self.args.pg_conn_config;
+        if source.hostname.to_lowercase() == pcc.hostname.to_lowercase() && source.port == target_tds_port {
+            return Err("Source SQL Server must be different from the target WiltonDB server".to_string());
+        }
+        let work_dir = self.c.work_dir_input.text().trim().to_string();
+        if !Path::new(&work_dir).is_dir() {
+            return Err(format!("Work directory not found: {}", &work_dir));
+        }
+        Ok(MigrationArgs {
+            source,
+            tools: self.tools_from_input(),
+            target_dbname,
+            target_tds_port,
+            work_dir,
+        })
+    }
+
+    fn source_from_input(&self) -> Result<MssqlSource, String> {
+        let hostname = self.c.hostname_input.text().trim().to_string();
+        if hostname.is_empty() {
+            return Err("SQL Server host name must be specified".to_string());
+        }
+        let port = match self.c.port_input.text().trim().parse::<u16>() {
+            Ok(port) if port > 0 => port,
+            _ => return Err(format!("Invalid SQL Server port: {}", self.c.port_input.text()))
+        };
+        let trusted = Some(1) == self.c.auth_combo.selection();
+        let username = self.c.username_input.text().trim().to_string();
+        if !trusted && username.is_empty() {
+            return Err("Username must be specified for SQL Server authentication".to_string());
+        }
+        let dbname = self.c.dbname_input.text().trim().to_string();
+        if dbname.is_empty() {
+            return Err("Source database name must be specified".to_string());
+        }
+        Ok(MssqlSource {
+            hostname,
+            port,
+            trusted,
+            username,
+            password: self.c.password_input.text(),
+            dbname,
+        })
+    }
+
+    fn tools_from_input(&self) -> MssqlTools {
+        MssqlTools {
+            tools_dir: self.c.tools_dir_input.text().trim().to_string(),
+            scripter_path: self.c.scripter_input.text().trim().to_string(),
+        }
+    }
+
+    fn select_tab(&mut self, idx: usize) {
+        self.c.tabs_container.set_selected_tab(idx);
+        self.update_buttons();
+    }
+
+    fn update_buttons(&self) {
+        let idx = self.c.tabs_container.selected_tab();
+        self.c.back_button.set_enabled(idx > TOOLS_TAB);
+        self.c.next_button.set_enabled(true);
+        let text = if TARGET_TAB == idx { "Start" } else { "Next >" };
+        self.c.next_button.set_text(text);
+    }
+
+    fn choose_dir(window: &nwg::Window, chooser: &nwg::FileDialog, input: &nwg::TextInput) {
+        let dir = input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = chooser.set_default_folder(&dir);
+        }
+        if chooser.run(Some(window)) {
+            if let Ok(directory) = chooser.get_selected_item() {
+                input.set_text(&directory.to_string_lossy());
+            }
+        }
+    }
+}
+
+impl ui::PopupDialog<MigrateDialogArgs, MigrateDialogResult> for MigrateDialog {
+    fn popup(args: MigrateDialogArgs) -> ui::PopupJoinHandle<MigrateDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.result = MigrateDialogResult::cancelled();
+        let tools = MssqlTools::detect();
+        self.c.tools_dir_input.set_text(&tools.tools_dir);
+        self.c.scripter_input.set_text(&tools.scripter_path);
+        self.c.hostname_input.set_text("localhost");
+        self.c.port_input.set_text(&DEFAULT_MSSQL_PORT.to_string());
+        let pcc = &self.args.pg_conn_config;
+        self.c.target_server_input.set_text(&pcc.hostname);
+        self.c.tds_port_input.set_text(&DEFAULT_MSSQL_PORT.to_string());
+        self.c.work_dir_input.set_text(&self.args.work_dir);
+        self.select_tab(TOOLS_TAB);
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> MigrateDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct MigrateDialogEvents {
+    pub(super) events: Vec<ui::Event<MigrateDialog>>
+}
+
+impl ui::Events<MigrateDialogControls> for MigrateDialogEvents {
+    fn build(&mut self, c: &MigrateDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(MigrateDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(MigrateDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.tabs_container)
+            .event(nwg::Event::TabsContainerChanged)
+            .handler(MigrateDialog::on_tab_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.tools_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(MigrateDialog::choose_tools_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.scripter_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(MigrateDialog::choose_scripter)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.auth_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(MigrateDialog::on_auth_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.work_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(MigrateDialog::choose_work_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.back_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(MigrateDialog::back)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.next_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(MigrateDialog::next)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.cancel_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(MigrateDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.check_notice.notice)
+            .event(nwg::Event::OnNotice)
+            .handler(MigrateDialog::on_check_complete)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
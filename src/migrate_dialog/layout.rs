@@ -0,0 +1,363 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct MigrateDialogLayout {
+    root_layout: nwg::FlexboxLayout,
+    tabs_container_layout: nwg::FlexboxLayout,
+    tools_tab_layout: nwg::FlexboxLayout,
+    tools_hint_layout: nwg::FlexboxLayout,
+    tools_dir_layout: nwg::FlexboxLayout,
+    scripter_layout: nwg::FlexboxLayout,
+    source_tab_layout: nwg::FlexboxLayout,
+    hostname_layout: nwg::FlexboxLayout,
+    port_layout: nwg::FlexboxLayout,
+    auth_layout: nwg::FlexboxLayout,
+    username_layout: nwg::FlexboxLayout,
+    password_layout: nwg::FlexboxLayout,
+    dbname_layout: nwg::FlexboxLayout,
+    target_tab_layout: nwg::FlexboxLayout,
+    target_server_layout: nwg::FlexboxLayout,
+    tds_port_layout: nwg::FlexboxLayout,
+    target_dbname_layout: nwg::FlexboxLayout,
+    work_dir_layout: nwg::FlexboxLayout,
+    buttons_layout: nwg::FlexboxLayout,
+}
+
+impl ui::Layout<MigrateDialogControls> for MigrateDialogLayout {
+    fn build(&self, c: &MigrateDialogControls) -> Result<(), nwg::NwgError> {
+        // tools
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.tools_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.tools_hint_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_input_form_row()
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.tools_hint_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.tools_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.tools_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.tools_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.tools_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.tools_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.tools_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.scripter_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.scripter_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.scripter_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.scripter_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.tools_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.tools_hint_layout)
+            .child_layout(&self.tools_dir_layout)
+            .child_layout(&self.scripter_layout)
+            .build(&self.tools_tab_layout)?;
+
+        // source
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.hostname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.hostname_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.hostname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.port_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.port_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.port_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.auth_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.auth_combo)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.auth_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.username_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.username_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.username_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.password_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.password_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.password_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.dbname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.dbname_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.dbname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.source_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.hostname_layout)
+            .child_layout(&self.port_layout)
+            .child_layout(&self.auth_layout)
+            .child_layout(&self.username_layout)
+            .child_layout(&self.password_layout)
+            .child_layout(&self.dbname_layout)
+            .build(&self.source_tab_layout)?;
+
+        // target
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.target_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.target_server_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.target_server_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.target_server_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.target_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.tds_port_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.tds_port_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.tds_port_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.target_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.target_dbname_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.target_dbname_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .build_partial(&self.target_dbname_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.target_tab)
+            .flex_direction(ui::FlexDirection::Row)
+            .auto_spacing(None)
+            .child(&c.work_dir_label)
+            .child_size(ui::size_builder()
+                .width_label_normal()
+                .height_input_form_row()
+                .build())
+            .child(&c.work_dir_input)
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.work_dir_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.work_dir_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.target_tab)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.target_server_layout)
+            .child_layout(&self.tds_port_layout)
+            .child_layout(&self.target_dbname_layout)
+            .child_layout(&self.work_dir_layout)
+            .build(&self.target_tab_layout)?;
+
+        // window
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Column)
+            .auto_spacing(None)
+            .child(&c.tabs_container)
+            .child_flex_grow(1.0)
+            .build_partial(&self.tabs_container_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Row)
+            .justify_content(ui::JustifyContent::FlexEnd)
+            .auto_spacing(None)
+            .child(&c.status_label)
+            .child_size(ui::size_builder()
+                .width_auto()
+                .height_button()
+                .build())
+            .child_flex_grow(1.0)
+            .child(&c.back_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.next_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .child(&c.cancel_button)
+            .child_size(ui::size_builder()
+                .width_button_normal()
+                .height_button()
+                .build())
+            .child_margin(ui::margin_builder()
+                .start_pt(5)
+                .build())
+            .build_partial(&self.buttons_layout)?;
+
+        nwg::FlexboxLayout::builder()
+            .parent(&c.window)
+            .flex_direction(ui::FlexDirection::Column)
+            .child_layout(&self.tabs_container_layout)
+            .child_flex_grow(1.0)
+            .child_layout(&self.buttons_layout)
+            .build(&self.root_layout)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct MigrateCheckResult {
+    pub(super) tables_count: usize,
+    pub(super) error: String,
+}
+
+impl MigrateCheckResult {
+    pub(super) fn success(tables_count: usize) -> Self {
+        Self {
+            tables_count,
+            error: String::new(),
+        }
+    }
+
+    pub(super) fn failure(error: String) -> Self {
+        Self {
+            error,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct MigrateDialogResult {
+    pub cancelled: bool,
+    pub migration: MigrationArgs,
+}
+
+impl MigrateDialogResult {
+    pub fn new(migration: MigrationArgs) -> Self {
+        Self {
+            cancelled: false,
+            migration,
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self {
+            cancelled: true,
+            ..Default::default()
+        }
+    }
+}
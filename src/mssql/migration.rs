@@ -0,0 +1,192 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::common::PgConnConfig;
+use crate::common::ProgressSender;
+use crate::common::long_path;
+
+use super::*;
+use super::tools::run_tool;
+
+const SQLCMD_PASSWORD: &str = "SQLCMDPASSWORD";
+const SCRIPTER_PASSWORD: &str = "MSSQL_SCRIPTER_PASSWORD";
+const TABLES_QUERY: &str = "set nocount on; \
+    select s.name, t.name from sys.tables t \
+    join sys.schemas s on s.schema_id = t.schema_id \
+    where t.is_ms_shipped = 0 order by 1, 2";
+
+#[derive(Default, Clone)]
+pub struct MigrationArgs {
+    pub source: MssqlSource,
+    pub tools: MssqlTools,
+    pub target_dbname: String,
+    // schema script and data are loaded into WiltonDB over its T-SQL port
+    pub target_tds_port: u16,
+    // schema script and BCP data files are written into a subdirectory
+    pub work_dir: String,
+}
+
+// User tables of the source DB as (schema, table) pairs, this is also
+// used to check the connection and the tools before the migration.
+pub fn list_tables(source: &MssqlSource, tools: &MssqlTools) -> Result<Vec<(String, String)>, io::Error> {
+    let mut args = source.sqlcmd_args();
+    args.extend(["-b", "-l", "15", "-h", "-1", "-W", "-s", "|", "-Q", TABLES_QUERY].iter().map(|st| st.to_string()));
+    let mut lines = Vec::new();
+    let res = run_tool(&tools.sqlcmd_exe(), &args, &source.password_env(SQLCMD_PASSWORD), |ln| lines.push(ln.to_string()));
+    if let Err(e) = res {
+        return Err(io::Error::new(e.kind(), format!("{}, output: {}", e, lines.join(" "))));
+    }
+    let mut tables = Vec::new();
+    for ln in lines.iter().filter(|ln| !ln.is_empty()) {
+        match ln.split_once('|') {
+            Some((schema, table)) => tables.push((schema.to_string(), table.to_string())),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Unexpected sqlcmd output: {}", ln)))
+        }
+    }
+    Ok(tables)
+}
+
+// Schema is scripted with "mssql-scripter" and data is exported with "bcp"
+// in native format, then the target DB is created in WiltonDB, schema script
+// is run with "sqlcmd" and data is loaded with "bcp". Statements of the schema
+// script that are not supported by WiltonDB are reported and skipped.
+pub fn run_migration<P: ProgressSender>(progress: &P, pcc: &PgConnConfig, ma: &MigrationArgs) -> Result<(), String> {
+    progress.send_value(format!("Migrating DB: {}, from: {}, to: {}:{}, as: {} ...", &ma.source.dbname,
+        ma.source.server(), &pcc.hostname, ma.target_tds_port, &ma.target_dbname));
+
+    let ts = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let migration_dir = Path::new(&ma.work_dir).join(format!("wdb_migrate_{}_{}", &ma.source.dbname, ts));
+    progress.send_value(format!("Creating work directory: {} ...", migration_dir.to_string_lossy()));
+    if let Err(e) = fs::create_dir_all(long_path(&migration_dir)) {
+        return Err(format!("Error creating work directory: {}, message: {}", migration_dir.to_string_lossy(), e));
+    }
+
+    let res = migrate_in_dir(progress, pcc, ma, &migration_dir);
+
+    progress.send_value("Cleaning up work directory ...");
+    if let Err(e) = fs::remove_dir_all(long_path(&migration_dir)) {
+        progress.send_value(format!(
+            "Warning: error removing work directory: {}, message: {}", migration_dir.to_string_lossy(), e));
+    }
+    if res.is_ok() {
+        progress.send_value("Migration complete");
+    }
+    res
+}
+
+fn migrate_in_dir<P: ProgressSender>(progress: &P, pcc: &PgConnConfig, ma: &MigrationArgs, dir: &Path) -> Result<(), String> {
+    let src = &ma.source;
+    let tools = &ma.tools;
+
+    // source
+    progress.send_value(format!("Listing tables of DB: {} ...", &src.dbname));
+    let tables = match list_tables(src, tools) {
+        Ok(tables) => tables,
+        Err(e) => return Err(format!("Error listing source tables, message: {}", e))
+    };
+    progress.send_value(format!("Tables found: {}", tables.len()));
+
+    progress.send_value("Scripting schema with mssql-scripter ...");
+    let schema_file = dir.join("schema.sql").to_string_lossy().to_string();
+    let mut args = src.scripter_args();
+    args.extend(["--exclude-use-database", "-f", &schema_file].iter().map(|st| st.to_string()));
+    let res = run_tool(&tools.scripter_exe(), &args, &src.password_env(SCRIPTER_PASSWORD), |ln| progress.send_value(ln));
+    if let Err(e) = res {
+        return Err(format!("Error scripting schema, message: {}", e));
+    }
+
+    let data_files: Vec<String> = (0..tables.len())
+        .map(|idx| dir.join(format!("{:05}.dat", idx)).to_string_lossy().to_string())
+        .collect();
+    for ((schema, table), data_file) in tables.iter().zip(data_files.iter()) {
+        let name = format!("{}.{}", bracket_name(schema), bracket_name(table));
+        progress.send_value(format!("Exporting table: {} ...", &name));
+        let mut args: Vec<String> = vec!(name.clone(), "out".to_string(), data_file.clone(), "-n".to_string());
+        args.extend(src.bcp_args());
+        if let Err(e) = run_tool(&tools.bcp_exe(), &args, &[], |ln| send_bcp_line(progress, ln)) {
+            return Err(format!("Error exporting table: {}, message: {}", &name, e));
+        }
+    }
+
+    // target
+    let target = MssqlSource {
+        hostname: pcc.hostname.clone(),
+        port: ma.target_tds_port,
+        trusted: false,
+        username: pcc.username.clone(),
+        password: pcc.password.clone(),
+        dbname: "master".to_string(),
+    };
+    progress.send_value(format!("Creating DB: {} ...", &ma.target_dbname));
+    let mut args = target.sqlcmd_args();
+    args.extend(vec!("-b".to_string(), "-Q".to_string(), format!("create database {}", bracket_name(&ma.target_dbname))));
+    let res = run_tool(&tools.sqlcmd_exe(), &args, &target.password_env(SQLCMD_PASSWORD), |ln| progress.send_value(ln));
+    if let Err(e) = res {
+        return Err(format!("Error creating DB: {}, message: {}", &ma.target_dbname, e));
+    }
+    let target = MssqlSource {
+        dbname: ma.target_dbname.clone(),
+        ..target
+    };
+
+    progress.send_value("Running schema script ...");
+    let mut args = target.sqlcmd_args();
+    args.extend(["-f", "65001", "-i", &schema_file].iter().map(|st| st.to_string()));
+    let mut script_errors = 0;
+    let res = run_tool(&tools.sqlcmd_exe(), &args, &target.password_env(SQLCMD_PASSWORD), |ln| {
+        if ln.starts_with("Msg ") {
+            script_errors += 1;
+        }
+        progress.send_value(ln);
+    });
+    if let Err(e) = res {
+        return Err(format!("Error running schema script, message: {}", e));
+    }
+    if script_errors > 0 {
+        progress.send_value(format!(
+            "WARNING: schema script statements failed: {}, unsupported objects need to be created manually", script_errors));
+    }
+
+    let mut failed = Vec::new();
+    for ((schema, table), data_file) in tables.iter().zip(data_files.iter()) {
+        let name = format!("{}.{}", bracket_name(schema), bracket_name(table));
+        progress.send_value(format!("Loading table: {} ...", &name));
+        // identity values are kept
+        let mut args: Vec<String> = vec!(name.clone(), "in".to_string(), data_file.clone(),
+            "-n".to_string(), "-E".to_string(), "-b".to_string(), "10000".to_string());
+        args.extend(target.bcp_args());
+        if let Err(e) = run_tool(&tools.bcp_exe(), &args, &[], |ln| send_bcp_line(progress, ln)) {
+            progress.send_value(format!("WARNING: error loading table: {}, message: {}", &name, e));
+            failed.push(name);
+        }
+    }
+    if !failed.is_empty() {
+        return Err(format!("Data was not loaded into tables: {}", failed.join(", ")));
+    }
+    Ok(())
+}
+
+// per-batch row counters are skipped
+fn send_bcp_line<P: ProgressSender>(progress: &P, ln: &str) {
+    if !ln.contains("Total received") && !ln.contains("Total sent") {
+        progress.send_value(ln);
+    }
+}
@@ -0,0 +1,26 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod migration;
+mod source;
+mod tools;
+
+pub use migration::list_tables;
+pub use migration::MigrationArgs;
+pub use migration::run_migration;
+pub use source::bracket_name;
+pub use source::MssqlSource;
+pub use tools::MssqlTools;
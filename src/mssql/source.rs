@@ -0,0 +1,89 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Connection to a SQL Server instance, password is passed to "sqlcmd" and
+// "mssql-scripter" using per-command environment, "bcp" only accepts it
+// as an argument.
+#[derive(Default, Clone, PartialEq)]
+pub struct MssqlSource {
+    pub hostname: String,
+    pub port: u16,
+    // Windows authentication when set, username and password are ignored
+    pub trusted: bool,
+    pub username: String,
+    pub password: String,
+    pub dbname: String,
+}
+
+impl MssqlSource {
+    pub fn server(&self) -> String {
+        format!("{},{}", &self.hostname, self.port)
+    }
+
+    pub(super) fn sqlcmd_args(&self) -> Vec<String> {
+        let mut res = vec!(
+            "-S".to_string(), self.server(),
+            "-d".to_string(), self.dbname.clone(),
+        );
+        if self.trusted {
+            res.push("-E".to_string());
+        } else {
+            res.push("-U".to_string());
+            res.push(self.username.clone());
+        }
+        res
+    }
+
+    pub(super) fn bcp_args(&self) -> Vec<String> {
+        let mut res = vec!(
+            "-S".to_string(), self.server(),
+            "-d".to_string(), self.dbname.clone(),
+        );
+        if self.trusted {
+            res.push("-T".to_string());
+        } else {
+            res.push("-U".to_string());
+            res.push(self.username.clone());
+            res.push("-P".to_string());
+            res.push(self.password.clone());
+        }
+        res
+    }
+
+    pub(super) fn scripter_args(&self) -> Vec<String> {
+        let mut res = vec!(
+            "-S".to_string(), self.server(),
+            "-d".to_string(), self.dbname.clone(),
+        );
+        if !self.trusted {
+            res.push("-U".to_string());
+            res.push(self.username.clone());
+        }
+        res
+    }
+
+    pub(super) fn password_env(&self, name: &str) -> Vec<(String, String)> {
+        if self.trusted {
+            Vec::new()
+        } else {
+            vec!((name.to_string(), self.password.clone()))
+        }
+    }
+}
+
+pub fn bracket_name(name: &str) -> String {
+    format!("[{}]", name.replace(']', "]]"))
+}
@@ -0,0 +1,118 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+// Standard install locations of the SQL Server command line utilities,
+// newer versions first.
+const TOOLS_DIRS: [&str; 4] = [
+    "C:\\Program Files\\Microsoft SQL Server\\Client SDK\\ODBC\\170\\Tools\\Binn",
+    "C:\\Program Files\\Microsoft SQL Server\\Client SDK\\ODBC\\130\\Tools\\Binn",
+    "C:\\Program Files\\Microsoft SQL Server\\Client SDK\\ODBC\\110\\Tools\\Binn",
+    "C:\\Program Files\\Microsoft SQL Server\\110\\Tools\\Binn",
+];
+
+// "sqlcmd" and "bcp" are looked up in the specified directory or in PATH
+// when the directory is empty, "mssql-scripter" is installed with pip
+// as a batch file.
+#[derive(Default, Clone)]
+pub struct MssqlTools {
+    pub tools_dir: String,
+    pub scripter_path: String,
+}
+
+impl MssqlTools {
+    pub fn detect() -> Self {
+        let tools_dir = TOOLS_DIRS.iter()
+            .find(|dir| Path::new(dir).join("bcp.exe").exists())
+            .map(|dir| dir.to_string())
+            .unwrap_or_default();
+        Self {
+            tools_dir,
+            scripter_path: "mssql-scripter.bat".to_string(),
+        }
+    }
+
+    pub fn sqlcmd_exe(&self) -> PathBuf {
+        self.tool_exe("sqlcmd.exe")
+    }
+
+    pub fn bcp_exe(&self) -> PathBuf {
+        self.tool_exe("bcp.exe")
+    }
+
+    pub fn scripter_exe(&self) -> PathBuf {
+        PathBuf::from(&self.scripter_path)
+    }
+
+    fn tool_exe(&self, name: &str) -> PathBuf {
+        if self.tools_dir.is_empty() {
+            PathBuf::from(name)
+        } else {
+            Path::new(&self.tools_dir).join(name)
+        }
+    }
+}
+
+// Output of the tool is passed to the listener line by line, tool is
+// considered failed when it returns non-zero exit code.
+pub(super) fn run_tool<F: FnMut(&str)>(exe: &Path, args: &[String], envs: &[(String, String)], mut listener: F) -> Result<(), io::Error> {
+    let tool = exe.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let mut cmd = duct::cmd(exe, args)
+        .stdin_null()
+        .stderr_to_stdout()
+        .stdout_capture()
+        .before_spawn(|pcmd| {
+            // create no window
+            let _ = pcmd.creation_flags(0x08000000);
+            Ok(())
+        });
+    for (name, value) in envs.iter() {
+        cmd = cmd.env(name, value);
+    }
+    let reader = match cmd.reader() {
+        Ok(reader) => reader,
+        Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+            "{} process spawn failure: {}", tool, e)))
+    };
+    let mut buf_reader = BufReader::new(&reader);
+    loop {
+        let mut buf = vec!();
+        match buf_reader.read_until(b'\n', &mut buf) {
+            Ok(len) => {
+                if 0 == len {
+                    break;
+                }
+                let ln = String::from_utf8_lossy(&buf);
+                listener(ln.trim_end());
+            },
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "{} process failure: {}", tool, e)))
+        };
+    };
+    match reader.try_wait() {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(io::Error::new(io::ErrorKind::Other, format!(
+            "{} process failure", tool))),
+        Err(e) => Err(io::Error::new(io::ErrorKind::Other, format!(
+            "{} process failure: {}", tool, e)))
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::common::long_path;
+
+const BLOCK_SIZE: usize = 512;
+
+// Unlike pg_dump tar archives, pg_basebackup ones contain nested directories,
+// tablespace links are written as symlinks and are reported and skipped.
+// Returns the number of extracted files.
+pub(super) fn extract_base_tar<R, F>(reader: R, dest_dir: &Path, listener: &mut F) -> Result<u64, io::Error>
+where
+    R: Read,
+    F: FnMut(&str)
+{
+    let mut reader = io::BufReader::new(reader);
+    let mut header = [0u8; BLOCK_SIZE];
+    let mut long_name: Option<String> = None;
+    let mut count = 0u64;
+    loop {
+        reader.read_exact(&mut header)?;
+        if header.iter().all(|b| 0 == *b) {
+            break;
+        }
+        let size = header_size(&header)?;
+        let typeflag = header[156];
+        // GNU long name, applies to the next member
+        if b'L' == typeflag {
+            let mut buf = Vec::new();
+            (&mut reader).take(size).read_to_end(&mut buf)?;
+            let name_bytes: Vec<u8> = buf.into_iter().take_while(|b| 0 != *b).collect();
+            long_name = Some(String::from_utf8_lossy(&name_bytes).to_string());
+            skip_padding(&mut reader, size)?;
+            continue;
+        }
+        let name = match long_name.take() {
+            Some(name) => name,
+            None => header_name(&header)
+        };
+        let dest_path = member_path(dest_dir, &name)?;
+        if b'0' == typeflag || 0 == typeflag {
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(long_path(parent))?;
+            }
+            let mut writer = BufWriter::new(File::create(long_path(&dest_path))?);
+            let copied = io::copy(&mut (&mut reader).take(size), &mut writer)?;
+            if copied != size {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!(
+                    "Unexpected end of tar archive, member: {}", name)));
+            }
+            writer.flush()?;
+            count += 1;
+        } else if b'5' == typeflag {
+            listener(&name);
+            fs::create_dir_all(long_path(&dest_path))?;
+        } else {
+            if b'2' == typeflag {
+                listener(&format!("WARNING: link skipped: {}", name));
+            }
+            io::copy(&mut (&mut reader).take(size), &mut io::sink())?;
+        }
+        skip_padding(&mut reader, size)?;
+    }
+    Ok(count)
+}
+
+fn skip_padding<R: Read>(reader: &mut R, size: u64) -> Result<(), io::Error> {
+    let padding = (BLOCK_SIZE as u64 - size % BLOCK_SIZE as u64) % BLOCK_SIZE as u64;
+    io::copy(&mut reader.take(padding), &mut io::sink())?;
+    Ok(())
+}
+
+// ustar names longer than 100 bytes are split into "prefix" and "name" fields
+fn header_name(header: &[u8]) -> String {
+    let field = |start: usize, len: usize| -> String {
+        let bytes: Vec<u8> = header[start..start + len].iter().take_while(|b| 0 != **b).cloned().collect();
+        String::from_utf8_lossy(&bytes).to_string()
+    };
+    let name = field(0, 100);
+    let prefix = if &header[257..262] == b"ustar" { field(345, 155) } else { String::new() };
+    if prefix.is_empty() {
+        name
+    } else {
+        format!("{}/{}", prefix, name)
+    }
+}
+
+// members are only allowed to be written under the destination directory
+fn member_path(dest_dir: &Path, name: &str) -> Result<PathBuf, io::Error> {
+    let rel = Path::new(name.trim_end_matches('/'));
+    let valid = !name.is_empty() && rel.components().all(|comp| match comp {
+        Component::Normal(_) | Component::CurDir => true,
+        _ => false
+    });
+    if !valid {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Unexpected member in tar archive: {}", name)));
+    }
+    Ok(dest_dir.join(rel))
+}
+
+fn header_size(header: &[u8]) -> Result<u64, io::Error> {
+    let field = &header[124..136];
+    // large sizes are stored as base-256 number with the high bit set
+    if 0 != (field[0] & 0x80) {
+        let mut res: u64 = (field[0] & 0x7f) as u64;
+        for b in field[1..].iter() {
+            res = (res << 8) | (*b as u64);
+        }
+        return Ok(res);
+    }
+    let st: String = field.iter()
+        .take_while(|b| 0 != **b)
+        .map(|b| *b as char)
+        .collect();
+    match u64::from_str_radix(st.trim(), 8) {
+        Ok(size) => Ok(size),
+        Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+            "Invalid member size in tar archive: {}", st)))
+    }
+}
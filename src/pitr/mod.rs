@@ -0,0 +1,26 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod base_tar;
+mod recovery;
+mod wal_archive;
+
+pub use recovery::PitrArgs;
+pub use recovery::parse_target_time;
+pub use recovery::run_pitr_restore;
+pub use wal_archive::enable_wal_archiving;
+pub use wal_archive::load_wal_archive_status;
+pub use wal_archive::WalArchiveStatus;
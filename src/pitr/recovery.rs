@@ -0,0 +1,204 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::TimeZone;
+
+use crate::common::ProgressSender;
+use crate::common::long_path;
+
+use super::base_tar::extract_base_tar;
+use super::wal_archive::is_wal_file_name;
+
+const BASE_ARCHIVE: &str = "base.tar.gz";
+const WAL_ARCHIVE: &str = "pg_wal.tar.gz";
+const TARGET_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Default, Clone)]
+pub struct PitrArgs {
+    // zip file or directory written by the physical backup
+    pub backup_path: String,
+    pub wal_dir: String,
+    // new data directory on this machine, must not exist or be empty
+    pub data_dir: String,
+    // local time, all archived WAL is replayed when empty
+    pub target_time: String,
+}
+
+// Target time is entered as local time and is passed to the server with
+// the UTC offset, so it does not depend on the server "TimeZone" setting.
+pub fn parse_target_time(st: &str) -> Result<String, String> {
+    let naive = match chrono::NaiveDateTime::parse_from_str(st.trim(), TARGET_TIME_FORMAT) {
+        Ok(dt) => dt,
+        Err(_) => return Err(format!(
+            "Invalid recovery target time: '{}', expected format: YYYY-MM-DD HH:MM:SS", st))
+    };
+    match chrono::Local.from_local_datetime(&naive).single() {
+        Some(dt) => Ok(dt.format("%Y-%m-%d %H:%M:%S%:z").to_string()),
+        None => Err(format!("Ambiguous recovery target time: '{}'", st))
+    }
+}
+
+// Base backup is laid down into a new data directory together with the WAL
+// written during the backup, recovery settings are added so that the server
+// replays archived WAL up to the target time on its first start.
+pub fn run_pitr_restore<P: ProgressSender>(progress: &P, pa: &PitrArgs) -> Result<(), String> {
+    progress.send_value(format!("Preparing point-in-time restore into data directory: {} ...", &pa.data_dir));
+    let target_time = if pa.target_time.trim().is_empty() {
+        None
+    } else {
+        Some(parse_target_time(&pa.target_time)?)
+    };
+    let wal_dir = Path::new(&pa.wal_dir);
+    match count_wal_files(wal_dir) {
+        Ok(count) => progress.send_value(format!("WAL files in archive folder: {}", count)),
+        Err(e) => return Err(format!(
+            "Error reading WAL archive folder, path: {}, error: {}", &pa.wal_dir, e))
+    }
+
+    let data_dir = Path::new(&pa.data_dir);
+    if let Err(e) = prepare_data_dir(data_dir) {
+        return Err(format!("Error preparing data directory, path: {}, error: {}", &pa.data_dir, e));
+    }
+    if let Err(e) = lay_down_backup(progress, pa) {
+        let _ = fs::remove_dir_all(long_path(data_dir));
+        return Err(format!("Error extracting base backup, path: {}, error: {}", &pa.backup_path, e));
+    }
+    if let Err(e) = write_recovery_settings(data_dir, wal_dir, target_time.as_deref()) {
+        let _ = fs::remove_dir_all(long_path(data_dir));
+        return Err(format!("Error writing recovery settings, path: {}, error: {}", &pa.data_dir, e));
+    }
+
+    match &target_time {
+        Some(tm) => progress.send_value(format!("Recovery target time: {}", tm)),
+        None => progress.send_value("Recovery target: all archived WAL")
+    }
+    progress.send_value("WAL archiving is disabled in the restored instance, it can be re-enabled after checking the restored data");
+    progress.send_value("Stop the original instance (or change the 'port' in 'postgresql.auto.conf') and start the server on the restored data directory:");
+    progress.send_value(format!("pg_ctl start -D \"{}\"", &pa.data_dir));
+    progress.send_value("Server replays archived WAL on start and becomes available for connections when the recovery target is reached");
+    Ok(())
+}
+
+fn count_wal_files(wal_dir: &Path) -> Result<usize, io::Error> {
+    let mut count = 0;
+    for en in fs::read_dir(long_path(wal_dir))? {
+        if is_wal_file_name(&en?.file_name().to_string_lossy()) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn prepare_data_dir(data_dir: &Path) -> Result<(), io::Error> {
+    let dir = long_path(data_dir);
+    if dir.exists() {
+        if fs::read_dir(&dir)?.next().is_some() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, "Data directory is not empty"));
+        }
+    } else {
+        fs::create_dir_all(&dir)?;
+    }
+    Ok(())
+}
+
+fn lay_down_backup<P: ProgressSender>(progress: &P, pa: &PitrArgs) -> Result<(), io::Error> {
+    let data_dir = Path::new(&pa.data_dir);
+    let wal_dest = data_dir.join("pg_wal");
+    let mut listener = |en: &str| progress.send_value(en);
+    let src = Path::new(&pa.backup_path);
+    if long_path(src).is_dir() {
+        progress.send_value(format!("Extracting: {}", BASE_ARCHIVE));
+        let base = File::open(long_path(&src.join(BASE_ARCHIVE)))?;
+        let count = extract_base_tar(flate2::read::GzDecoder::new(base), data_dir, &mut listener)?;
+        progress.send_value(format!("Files extracted: {}", count));
+        let wal_path = src.join(WAL_ARCHIVE);
+        if long_path(&wal_path).is_file() {
+            progress.send_value(format!("Extracting: {}", WAL_ARCHIVE));
+            let wal = File::open(long_path(&wal_path))?;
+            extract_base_tar(flate2::read::GzDecoder::new(wal), &wal_dest, &mut listener)?;
+        }
+        return Ok(());
+    }
+
+    let file = File::open(long_path(src))?;
+    let mut zip = zip::ZipArchive::new(file)?;
+    let names: Vec<String> = zip.file_names().map(|name| name.to_string()).collect();
+    let find = |archive: &str| names.iter()
+        .find(|name| name.as_str() == archive || name.ends_with(&format!("/{}", archive)))
+        .cloned();
+    let base_entry = match find(BASE_ARCHIVE) {
+        Some(name) => name,
+        None => return Err(io::Error::new(io::ErrorKind::NotFound, format!(
+            "'{}' not found in zip file, only backups created on the 'Physical' tab are supported", BASE_ARCHIVE)))
+    };
+    // other tar archives are written by pg_basebackup for tablespaces
+    let tablespaces = names.iter()
+        .filter(|name| name.ends_with(".tar.gz") && !name.ends_with(BASE_ARCHIVE) && !name.ends_with(WAL_ARCHIVE))
+        .count();
+    if tablespaces > 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            "Backups of instances with tablespaces are not supported"));
+    }
+    progress.send_value(format!("Extracting: {}", BASE_ARCHIVE));
+    let count = {
+        let entry = zip.by_name(&base_entry)?;
+        extract_base_tar(flate2::read::GzDecoder::new(entry), data_dir, &mut listener)?
+    };
+    progress.send_value(format!("Files extracted: {}", count));
+    if let Some(wal_entry) = find(WAL_ARCHIVE) {
+        progress.send_value(format!("Extracting: {}", WAL_ARCHIVE));
+        let entry = zip.by_name(&wal_entry)?;
+        extract_base_tar(flate2::read::GzDecoder::new(entry), &wal_dest, &mut listener)?;
+    }
+    Ok(())
+}
+
+// Backslashes are escape characters in config file string values
+fn conf_quote(st: &str) -> String {
+    format!("'{}'", st.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+fn write_recovery_settings(data_dir: &Path, wal_dir: &Path, target_time: Option<&str>) -> Result<(), io::Error> {
+    let dir = long_path(data_dir);
+    File::create(dir.join("recovery.signal"))?;
+    let wal_dir_st = wal_dir.to_string_lossy().replace('/', "\\");
+    let restore_command = format!("copy \"{}\\%f\" \"%p\"", wal_dir_st.trim_end_matches('\\'));
+    let mut lines = vec!(
+        String::new(),
+        "# point-in-time recovery settings added by WiltonDB Backup Tool".to_string(),
+        format!("restore_command = {}", conf_quote(&restore_command)),
+        "recovery_target_action = 'promote'".to_string(),
+        "archive_mode = 'off'".to_string(),
+    );
+    if let Some(tm) = target_time {
+        lines.push(format!("recovery_target_time = {}", conf_quote(tm)));
+    }
+    let mut conf = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("postgresql.auto.conf"))?;
+    let mut text = lines.join("\n");
+    text.push('\n');
+    conf.write_all(text.as_bytes())?;
+    Ok(())
+}
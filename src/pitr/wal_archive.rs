@@ -0,0 +1,157 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use crate::common::PgAccessError;
+use crate::common::PgConnConfig;
+use crate::common::long_path;
+
+#[derive(Default, Clone)]
+pub struct WalArchiveStatus {
+    pub wal_level: String,
+    pub archive_mode: String,
+    pub archive_command: String,
+    // "archive_mode" and "wal_level" changes are applied only after server restart
+    pub pending_restart: bool,
+    pub archived_count: i64,
+    pub last_archived_wal: String,
+    pub last_archived_time: String,
+    pub failed_count: i64,
+    pub last_failed_wal: String,
+    pub last_failed_time: String,
+    // archive folder as seen from this machine, it may not be accessible
+    pub local_wal_count: Option<usize>,
+    pub local_latest_wal: String,
+}
+
+impl WalArchiveStatus {
+    pub fn is_enabled(&self) -> bool {
+        "off" != self.archive_mode && "minimal" != self.wal_level && !self.archive_command.is_empty()
+    }
+
+    pub fn describe(&self) -> String {
+        let mut lines = vec!(
+            format!("WAL archiving: {}", if self.is_enabled() { "enabled" } else { "disabled" }),
+            format!("wal_level: {}", self.wal_level),
+            format!("archive_mode: {}", self.archive_mode),
+            format!("archive_command: {}", self.archive_command),
+        );
+        if self.pending_restart {
+            lines.push("Server restart is required to apply changed settings".to_string());
+        }
+        lines.push(String::new());
+        lines.push(format!("Archived WAL files: {}", self.archived_count));
+        if !self.last_archived_wal.is_empty() {
+            lines.push(format!("Last archived: {} at {}", self.last_archived_wal, self.last_archived_time));
+        }
+        lines.push(format!("Failed attempts: {}", self.failed_count));
+        if !self.last_failed_wal.is_empty() {
+            lines.push(format!("Last failed: {} at {}", self.last_failed_wal, self.last_failed_time));
+        }
+        if let Some(count) = self.local_wal_count {
+            lines.push(String::new());
+            lines.push(format!("WAL files in archive folder: {}", count));
+            if !self.local_latest_wal.is_empty() {
+                lines.push(format!("Latest WAL file in archive folder: {}", self.local_latest_wal));
+            }
+        }
+        lines.join("\r\n")
+    }
+}
+
+// Command is run by the server, so the folder must be writable by the
+// server service account, Windows "copy" is used as suggested in Postgres docs.
+pub(super) fn archive_command(wal_dir: &str) -> String {
+    let dir = wal_dir.trim().replace('/', "\\");
+    format!("copy \"%p\" \"{}\\%f\"", dir.trim_end_matches('\\'))
+}
+
+pub(super) fn is_wal_file_name(name: &str) -> bool {
+    24 == name.len() && name.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+// Returns true if server restart is required for settings to take effect,
+// ALTER SYSTEM requires superuser privileges.
+pub fn enable_wal_archiving(pcc: &PgConnConfig, wal_dir: &str) -> Result<bool, PgAccessError> {
+    if !Path::new(wal_dir.trim()).is_absolute() {
+        return Err(PgAccessError::from_string(format!(
+            "WAL archive folder must be specified as an absolute path: {}", wal_dir)));
+    }
+    let mut client = pcc.open_connection_default()?;
+    let row = client.query_one("select \
+        pg_catalog.current_setting('wal_level') as wal_level, \
+        pg_catalog.current_setting('archive_mode') as archive_mode", &[])?;
+    let wal_level: String = row.get("wal_level");
+    let archive_mode: String = row.get("archive_mode");
+    let mut restart_required = false;
+    if "minimal" == wal_level {
+        client.batch_execute("alter system set wal_level = 'replica'")?;
+        restart_required = true;
+    }
+    if "off" == archive_mode {
+        client.batch_execute("alter system set archive_mode = 'on'")?;
+        restart_required = true;
+    }
+    let command = archive_command(wal_dir);
+    client.batch_execute(&format!("alter system set archive_command = '{}'", command.replace('\'', "''")))?;
+    client.batch_execute("select pg_catalog.pg_reload_conf()")?;
+    client.close()?;
+    Ok(restart_required)
+}
+
+pub fn load_wal_archive_status(pcc: &PgConnConfig, wal_dir: &str) -> Result<WalArchiveStatus, PgAccessError> {
+    let mut client = pcc.open_connection_default()?;
+    let settings = client.query_one("select \
+        pg_catalog.current_setting('wal_level') as wal_level, \
+        pg_catalog.current_setting('archive_mode') as archive_mode, \
+        pg_catalog.current_setting('archive_command') as archive_command, \
+        exists(select 1 from pg_catalog.pg_settings where pending_restart) as pending_restart", &[])?;
+    let stats = client.query_one("select \
+        archived_count, \
+        coalesce(last_archived_wal, '') as last_archived_wal, \
+        coalesce(last_archived_time::text, '') as last_archived_time, \
+        failed_count, \
+        coalesce(last_failed_wal, '') as last_failed_wal, \
+        coalesce(last_failed_time::text, '') as last_failed_time \
+        from pg_catalog.pg_stat_archiver", &[])?;
+    client.close()?;
+    let mut status = WalArchiveStatus {
+        wal_level: settings.get("wal_level"),
+        archive_mode: settings.get("archive_mode"),
+        archive_command: settings.get("archive_command"),
+        pending_restart: settings.get("pending_restart"),
+        archived_count: stats.get("archived_count"),
+        last_archived_wal: stats.get("last_archived_wal"),
+        last_archived_time: stats.get("last_archived_time"),
+        failed_count: stats.get("failed_count"),
+        last_failed_wal: stats.get("last_failed_wal"),
+        last_failed_time: stats.get("last_failed_time"),
+        local_wal_count: None,
+        local_latest_wal: String::new(),
+    };
+    if let Ok(rd) = fs::read_dir(long_path(Path::new(wal_dir.trim()))) {
+        let names: Vec<String> = rd
+            .filter_map(|en| en.ok())
+            .map(|en| en.file_name().to_string_lossy().to_string())
+            .filter(|name| is_wal_file_name(name))
+            .collect();
+        status.local_wal_count = Some(names.len());
+        status.local_latest_wal = names.into_iter().max().unwrap_or_default();
+    }
+    Ok(status)
+}
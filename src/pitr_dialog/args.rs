@@ -0,0 +1,40 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub struct PitrDialogArgs {
+    notice_sender:  ui::SyncNoticeSender,
+    pub(super) wal_dir: String,
+    pub(super) backup_dir: String,
+}
+
+impl PitrDialogArgs {
+    pub fn new(notice: &ui::SyncNotice, wal_dir: &str, backup_dir: &str) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            wal_dir: wal_dir.to_string(),
+            backup_dir: backup_dir.to_string(),
+        }
+    }
+}
+
+impl ui::PopupArgs for PitrDialogArgs {
+    fn notify_parent(&self) {
+        self.notice_sender.send()
+    }
+}
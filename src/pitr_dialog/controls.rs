@@ -0,0 +1,177 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct PitrDialogControls {
+    layout: PitrDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) backup_label: nwg::Label,
+    pub(super) backup_input: nwg::TextInput,
+    pub(super) backup_button: nwg::Button,
+    pub(super) backup_chooser: nwg::FileDialog,
+    pub(super) wal_dir_label: nwg::Label,
+    pub(super) wal_dir_input: nwg::TextInput,
+    pub(super) wal_dir_button: nwg::Button,
+    pub(super) wal_dir_chooser: nwg::FileDialog,
+    pub(super) data_dir_label: nwg::Label,
+    pub(super) data_dir_input: nwg::TextInput,
+    pub(super) data_dir_button: nwg::Button,
+    pub(super) data_dir_chooser: nwg::FileDialog,
+    pub(super) target_time_label: nwg::Label,
+    pub(super) target_time_input: nwg::TextInput,
+    pub(super) hint_label: nwg::Label,
+
+    pub(super) restore_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for PitrDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((520, 260))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Point-in-time restore")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Base backup:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.backup_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.backup_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.backup_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose physical backup file")
+            .action(nwg::FileDialogAction::Open)
+            .filters("Zip(*.zip)|Any(*.*)")
+            .build(&mut self.backup_chooser)?;
+
+        nwg::Label::builder()
+            .text("WAL archive dir.:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.wal_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.wal_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.wal_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose WAL archive directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.wal_dir_chooser)?;
+
+        nwg::Label::builder()
+            .text("New data dir.:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.data_dir_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.data_dir_input)?;
+        nwg::Button::builder()
+            .text("Choose")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.data_dir_button)?;
+        nwg::FileDialog::builder()
+            .title("Choose new data directory")
+            .action(nwg::FileDialogAction::OpenDirectory)
+            .build(&mut self.data_dir_chooser)?;
+
+        nwg::Label::builder()
+            .text("Restore to time:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.target_time_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.target_time_input)?;
+        nwg::Label::builder()
+            .text("Local time as YYYY-MM-DD HH:MM:SS, leave empty to replay all archived WAL")
+            .flags(nwg::LabelFlags::VISIBLE | nwg::LabelFlags::ELIPSIS)
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.hint_label)?;
+
+        nwg::Button::builder()
+            .text("Restore")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.restore_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.backup_input)
+            .control(&self.backup_button)
+            .control(&self.wal_dir_input)
+            .control(&self.wal_dir_button)
+            .control(&self.data_dir_input)
+            .control(&self.data_dir_button)
+            .control(&self.target_time_input)
+            .control(&self.restore_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct PitrDialog {
+    pub(super) c: PitrDialogControls,
+
+    args: PitrDialogArgs,
+    result: PitrDialogResult,
+}
+
+impl PitrDialog {
+    pub(super) fn choose_backup(&mut self, _: nwg::EventData) {
+        if Path::new(&self.args.backup_dir).is_dir() {
+            let _ = self.c.backup_chooser.set_default_folder(&self.args.backup_dir);
+        }
+        if self.c.backup_chooser.run(Some(&self.c.window)) {
+            if let Ok(file) = self.c.backup_chooser.get_selected_item() {
+                self.c.backup_input.set_text(&file.to_string_lossy());
+            }
+        }
+    }
+
+    pub(super) fn choose_wal_dir(&mut self, _: nwg::EventData) {
+        Self::choose_dir(&self.c.window, &self.c.wal_dir_chooser, &self.c.wal_dir_input);
+    }
+
+    pub(super) fn choose_data_dir(&mut self, _: nwg::EventData) {
+        Self::choose_dir(&self.c.window, &self.c.data_dir_chooser, &self.c.data_dir_input);
+    }
+
+    pub(super) fn restore(&mut self, _: nwg::EventData) {
+        let pitr = match self.pitr_from_input() {
+            Ok(pitr) => pitr,
+            Err(msg) => {
+                nwg::modal_error_message(&self.c.window, "Point-in-time restore", &msg);
+                return;
+            }
+        };
+        self.result = PitrDialogResult::new(pitr);
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn pitr_from_input(&self) -> Result<PitrArgs, String> {
+        let backup_path = self.c.backup_input.text().trim().to_string();
+        if !Path::new(&backup_path).exists() {
+            return Err(format!("Base backup not found: {}", &backup_path));
+        }
+        let wal_dir = self.c.wal_dir_input.text().trim().to_string();
+        if !Path::new(&wal_dir).is_dir() {
+            return Err(format!("WAL archive directory not found: {}", &wal_dir));
+        }
+        let data_dir = self.c.data_dir_input.text().trim().to_string();
+        if data_dir.is_empty() {
+            return Err("New data directory must be specified".to_string());
+        }
+        let target_time = self.c.target_time_input.text().trim().to_string();
+        if !target_time.is_empty() {
+            parse_target_time(&target_time)?;
+        }
+        Ok(PitrArgs {
+            backup_path,
+            wal_dir,
+            data_dir,
+            target_time,
+        })
+    }
+
+    fn choose_dir(window: &nwg::Window, chooser: &nwg::FileDialog, input: &nwg::TextInput) {
+        let dir = input.text();
+        if Path::new(&dir).is_dir() {
+            let _ = chooser.set_default_folder(&dir);
+        }
+        if chooser.run(Some(window)) {
+            if let Ok(directory) = chooser.get_selected_item() {
+                input.set_text(&directory.to_string_lossy());
+            }
+        }
+    }
+}
+
+impl ui::PopupDialog<PitrDialogArgs, PitrDialogResult> for PitrDialog {
+    fn popup(args: PitrDialogArgs) -> ui::PopupJoinHandle<PitrDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.c.wal_dir_input.set_text(&self.args.wal_dir);
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.c.target_time_input.set_text(&now);
+        self.result = PitrDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> PitrDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
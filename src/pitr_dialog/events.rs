@@ -0,0 +1,66 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct PitrDialogEvents {
+    pub(super) events: Vec<ui::Event<PitrDialog>>
+}
+
+impl ui::Events<PitrDialogControls> for PitrDialogEvents {
+    fn build(&mut self, c: &PitrDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(PitrDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(PitrDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.backup_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(PitrDialog::choose_backup)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.wal_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(PitrDialog::choose_wal_dir)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.data_dir_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(PitrDialog::choose_data_dir)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.restore_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(PitrDialog::restore)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.cancel_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(PitrDialog::close)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
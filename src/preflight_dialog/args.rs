@@ -0,0 +1,87 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub struct PreflightDialogArgs {
+    pub(super) notice_sender:  ui::SyncNoticeSender,
+    pub(super) pg_conn_config: PgConnConfig,
+    pub(super) kind: RunKind,
+    pub(super) bbf_db: String,
+    // backup destination or directory where the restored archive is extracted
+    pub(super) work_dir: String,
+    // None when the size of the backup cannot be estimated
+    pub(super) required_bytes: Option<u64>,
+    pub(super) forecast: Option<RunForecast>,
+}
+
+impl PreflightDialogArgs {
+    pub fn backup(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig, bbf_db: &str, dest_dir: &str, format: BackupFormat, forecast: Option<RunForecast>) -> Self {
+        // dump directory and zip file exist at the same time until the dump is zipped
+        let factor = if BackupFormat::Zip == format { 2 } else { 1 };
+        let required_bytes = match &forecast {
+            Some(fc) if fc.size_bytes > 0 => Some(fc.size_bytes * factor),
+            _ => None
+        };
+        Self {
+            notice_sender: notice.sender(),
+            pg_conn_config: pg_conn_config.clone(),
+            kind: RunKind::Backup,
+            bbf_db: bbf_db.to_string(),
+            work_dir: dest_dir.to_string(),
+            required_bytes,
+            forecast,
+        }
+    }
+
+    pub fn restore(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig, bbf_db: &str, src_path: &str, forecast: Option<RunForecast>) -> Self {
+        let path = Path::new(src_path);
+        let format = BackupFormat::detect(path);
+        let (work_dir, required_bytes) = if BackupFormat::Directory == format {
+            // unzipped dump is rewritten in place
+            (src_path.to_string(), Some(0))
+        } else {
+            let parent = match path.parent() {
+                Some(dir) => dir.to_string_lossy().to_string(),
+                None => String::new()
+            };
+            // zip and tar are extracted next to the archive and compressed SQL is unpacked,
+            // uncompressed size of the latter is not known, so its file size is a lower bound
+            let required = if BackupFormat::PlainSql == format {
+                Some(0)
+            } else {
+                fs::metadata(path).ok().map(|meta| meta.len())
+            };
+            (parent, required)
+        };
+        Self {
+            notice_sender: notice.sender(),
+            pg_conn_config: pg_conn_config.clone(),
+            kind: RunKind::Restore,
+            bbf_db: bbf_db.to_string(),
+            work_dir,
+            required_bytes,
+            forecast,
+        }
+    }
+}
+
+impl ui::PopupArgs for PreflightDialogArgs {
+    fn notify_parent(&self) {
+        self.notice_sender.send()
+    }
+}
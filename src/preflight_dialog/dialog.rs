@@ -0,0 +1,268 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+use std::os::windows::process::CommandExt;
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct PreflightDialog {
+    pub(super) c: PreflightDialogControls,
+
+    args: PreflightDialogArgs,
+    check_join_handle: ui::PopupJoinHandle<Vec<PreflightCheck>>,
+    result: PreflightDialogResult,
+}
+
+impl PreflightDialog {
+    pub(super) fn on_checks_complete(&mut self, _: nwg::EventData) {
+        self.c.check_notice.receive();
+        let checks = self.check_join_handle.join();
+        let failed = checks.iter().filter(|ch| CheckStatus::Failed == ch.status).count();
+        for ch in checks.iter() {
+            self.c.checks_view.insert_items_row(None, &[ch.name, ch.status.label(), ch.details.as_str()]);
+        }
+        self.stop_progress_bar(0 == failed);
+        let op = match self.args.kind {
+            RunKind::Backup => "backup",
+            RunKind::Restore => "restore",
+        };
+        if 0 == failed {
+            self.c.label.set_text("All checks passed");
+        } else {
+            self.c.label.set_text(&format!("{} check(s) failed, {} is likely to fail", failed, op));
+            self.c.proceed_button.set_text("Proceed anyway");
+        }
+        self.c.proceed_button.set_enabled(true);
+        self.c.proceed_button.set_focus();
+    }
+
+    pub(super) fn proceed(&mut self, _: nwg::EventData) {
+        self.result = PreflightDialogResult::proceed();
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn stop_progress_bar(&self, success: bool) {
+        self.c.progress_bar.set_marquee(false, 0);
+        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_pos(1);
+        if !success {
+            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
+        }
+    }
+
+    fn run_checks(pcc: &PgConnConfig, kind: RunKind, bbf_db: &str, work_dir: &str, required_bytes: Option<u64>, forecast: Option<&RunForecast>) -> Vec<PreflightCheck> {
+        let mut res = Vec::new();
+
+        let tools = match kind {
+            RunKind::Backup => vec!("pg_dump"),
+            RunKind::Restore => vec!("pg_restore", "psql"),
+        };
+        let tool_versions: Result<Vec<(&str, u32)>, String> = tools.iter()
+            .map(|tool| Self::tool_major_version(tool).map(|major| (*tool, major)))
+            .collect();
+        res.push(PreflightCheck::new("Client tools", match &tool_versions {
+            Ok(versions) => Ok(versions.iter()
+                .map(|(tool, major)| format!("{} {}", tool, major))
+                .collect::<Vec<String>>()
+                .join(", ")),
+            Err(e) => Err(e.clone())
+        }));
+
+        let conn = pcc.retry_policy()
+            .run("DB connection check", |en| log::warn!("{}", en), || Self::query_connection(pcc, bbf_db))
+            .map_err(|e| format!("{}", e));
+        let server_major = match &conn {
+            Ok((version, major, _)) => {
+                res.push(PreflightCheck::new("DB connection", Ok(format!("PostgreSQL {}", version))));
+                Some(*major)
+            },
+            Err(e) => {
+                res.push(PreflightCheck::new("DB connection", Err(e.clone())));
+                None
+            }
+        };
+
+        match (&tool_versions, server_major) {
+            (Ok(versions), Some(server)) => {
+                let older: Vec<String> = versions.iter()
+                    .filter(|(_, major)| *major < server)
+                    .map(|(tool, major)| format!("{} {}", tool, major))
+                    .collect();
+                res.push(PreflightCheck::new("Tools version", if older.is_empty() {
+                    Ok(format!("Compatible with DB server {}", server))
+                } else {
+                    Err(format!("{} older than DB server {}", older.join(", "), server))
+                }));
+            },
+            (Err(_), _) => res.push(PreflightCheck::skipped("Tools version", "Client tools not available")),
+            (_, None) => res.push(PreflightCheck::skipped("Tools version", "DB connection failed")),
+        }
+
+        match &conn {
+            Ok((_, _, true)) => res.push(PreflightCheck::new("Sysadmin role", Ok(format!(
+                "User '{}' has sysadmin privileges", &pcc.username)))),
+            Ok((_, _, false)) => res.push(PreflightCheck::new("Sysadmin role", Err(format!(
+                "User '{}' is not a superuser or a member of 'sysadmin'", &pcc.username)))),
+            Err(_) => res.push(PreflightCheck::skipped("Sysadmin role", "DB connection failed")),
+        }
+
+        let writable = Self::check_writable(work_dir);
+        let writable_ok = writable.is_ok();
+        res.push(PreflightCheck::new("Directory writable", writable));
+
+        if writable_ok {
+            res.push(PreflightCheck::new("Free disk space", Self::check_disk_space(work_dir, required_bytes)));
+        } else {
+            res.push(PreflightCheck::skipped("Free disk space", "Directory is not accessible"));
+        }
+
+        res.push(PreflightCheck::new("Estimate", Ok(match forecast {
+            Some(fc) => fc.describe(),
+            None => "No previous runs for this database".to_string()
+        })));
+
+        res
+    }
+
+    // Tools are shipped next to the app executable
+    fn tool_major_version(tool: &str) -> Result<u32, String> {
+        let cur_exe = env::current_exe().map_err(|e| e.to_string())?;
+        let bin_dir = match cur_exe.parent() {
+            Some(path) => path,
+            None => return Err(format!("Parent dir failure, exe path: {}", cur_exe.to_string_lossy()))
+        };
+        let tool_exe = bin_dir.join(format!("{}.exe", tool));
+        if !tool_exe.is_file() {
+            return Err(format!("Not found: {}", tool_exe.to_string_lossy()));
+        }
+        let output = duct::cmd(tool_exe, vec!("--version"))
+            .stdin_null()
+            .stderr_to_stdout()
+            .before_spawn(|pcmd| {
+                // create no window
+                let _ = pcmd.creation_flags(0x08000000);
+                Ok(())
+            })
+            .read();
+        let text = match output {
+            Ok(text) => text,
+            Err(e) => return Err(format!("{} process failure: {}", tool, e))
+        };
+        // "pg_dump (PostgreSQL) 16.2"
+        let major = text.split_whitespace().last()
+            .and_then(|ver| ver.split('.').next())
+            .and_then(|major| major.parse::<u32>().ok());
+        match major {
+            Some(major) => Ok(major),
+            None => Err(format!("Cannot parse {} version: {}", tool, text.trim()))
+        }
+    }
+
+    // server version, major version and whether the user can run backup and restore,
+    // Babelfish DB name is empty for plain Postgres servers
+    fn query_connection(pcc: &PgConnConfig, bbf_db: &str) -> Result<(String, u32, bool), PgAccessError> {
+        let dbname = if bbf_db.is_empty() { pcc.connect_db.as_str() } else { bbf_db };
+        let mut client = pcc.open_connection_to_db(dbname)?;
+        let rs = client.query("
+            select
+                current_setting('server_version') as version,
+                current_setting('server_version_num')::int as version_num,
+                r.rolsuper or exists(
+                    select 1 from pg_roles s
+                    where s.rolname = 'sysadmin'
+                    and pg_has_role(r.oid, s.oid, 'member')
+                ) as is_sysadmin
+            from pg_roles r
+            where r.rolname = current_user", &[])?;
+        let row = &rs[0];
+        let version: String = row.get("version");
+        let version_num: i32 = row.get("version_num");
+        let is_sysadmin: bool = row.get("is_sysadmin");
+        client.close()?;
+        Ok((version, (version_num / 10000) as u32, is_sysadmin))
+    }
+
+    fn check_writable(dir: &str) -> Result<String, String> {
+        match probe_dir_writable(Path::new(dir)) {
+            Ok(_) => Ok(dir.to_string()),
+            Err(e) => Err(e.to_string())
+        }
+    }
+
+    fn check_disk_space(dir: &str, required_bytes: Option<u64>) -> Result<String, String> {
+        let free = match free_disk_space(Path::new(dir)) {
+            Ok(free) => free,
+            Err(e) => return Err(format!("Error checking free space, message: {}", e))
+        };
+        match required_bytes {
+            Some(required) if free < required => Err(format!(
+                "{} free, about {} needed", format_disk_size(free), format_disk_size(required))),
+            Some(0) => Ok(format!("{} free", format_disk_size(free))),
+            Some(required) => Ok(format!(
+                "{} free, about {} needed", format_disk_size(free), format_disk_size(required))),
+            None => Ok(format!("{} free, backup size is not known yet", format_disk_size(free)))
+        }
+    }
+}
+
+impl ui::PopupDialog<PreflightDialogArgs, PreflightDialogResult> for PreflightDialog {
+    fn popup(args: PreflightDialogArgs) -> ui::PopupJoinHandle<PreflightDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.result = PreflightDialogResult::cancelled();
+        let sender = self.c.check_notice.sender();
+        let pcc = self.args.pg_conn_config.clone();
+        let kind = self.args.kind;
+        let bbf_db = self.args.bbf_db.clone();
+        let work_dir = self.args.work_dir.clone();
+        let required_bytes = self.args.required_bytes;
+        let forecast = self.args.forecast.clone();
+        let join_handle = thread::spawn(move || {
+            let res = PreflightDialog::run_checks(&pcc, kind, &bbf_db, &work_dir, required_bytes, forecast.as_ref());
+            sender.send();
+            res
+        });
+        self.check_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn result(&mut self) -> PreflightDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum CheckStatus {
+    Passed,
+    Failed,
+    // not run because an earlier check failed
+    Skipped,
+}
+
+impl CheckStatus {
+    pub(super) fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Passed => "OK",
+            CheckStatus::Failed => "FAILED",
+            CheckStatus::Skipped => "Skipped",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PreflightCheck {
+    pub(super) name: &'static str,
+    pub(super) status: CheckStatus,
+    pub(super) details: String,
+}
+
+impl PreflightCheck {
+    pub(super) fn new(name: &'static str, res: Result<String, String>) -> Self {
+        let (status, details) = match res {
+            Ok(details) => (CheckStatus::Passed, details),
+            Err(details) => (CheckStatus::Failed, details)
+        };
+        Self {
+            name,
+            status,
+            details
+        }
+    }
+
+    pub(super) fn skipped(name: &'static str, reason: &str) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skipped,
+            details: reason.to_string()
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct PreflightDialogResult {
+    pub proceed: bool,
+}
+
+impl PreflightDialogResult {
+    pub fn proceed() -> Self {
+        Self {
+            proceed: true
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self {
+            proceed: false
+        }
+    }
+}
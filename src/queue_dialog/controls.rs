@@ -0,0 +1,111 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct QueueDialogControls {
+    layout: QueueDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) jobs_view: nwg::ListView,
+    pub(super) cancel_job_button: nwg::Button,
+    pub(super) clear_button: nwg::Button,
+    pub(super) close_button: nwg::Button,
+
+    pub(super) refresh_timer: nwg::AnimationTimer,
+}
+
+impl ui::Controls for QueueDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((560, 320))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Job queue")
+            .build(&mut self.window)?;
+
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.window)
+            .build(&mut self.jobs_view)?;
+        self.jobs_view.insert_column("#");
+        self.jobs_view.insert_column("Job");
+        self.jobs_view.insert_column("Status");
+        self.jobs_view.insert_column("Time");
+        self.jobs_view.insert_column("Details");
+        self.jobs_view.set_column_width(0, 40);
+        self.jobs_view.set_column_width(1, 160);
+        self.jobs_view.set_column_width(2, 80);
+        self.jobs_view.set_column_width(3, 60);
+        self.jobs_view.set_column_width(4, 400);
+        self.jobs_view.set_headers_enabled(true);
+
+        nwg::Button::builder()
+            .text("Cancel job")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_job_button)?;
+
+        nwg::Button::builder()
+            .text("Clear finished")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.clear_button)?;
+
+        nwg::Button::builder()
+            .text("Close")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.close_button)?;
+
+        nwg::AnimationTimer::builder()
+            .parent(&self.window)
+            .interval(Duration::from_millis(500))
+            .active(true)
+            .build(&mut self.refresh_timer)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.jobs_view)
+            .control(&self.cancel_job_button)
+            .control(&self.clear_button)
+            .control(&self.close_button)
+            .build();
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct QueueDialog {
+    pub(super) c: QueueDialogControls,
+
+    args: QueueDialogArgs,
+    shown: Vec<QueuedJob>,
+}
+
+impl QueueDialog {
+    pub(super) fn on_refresh_tick(&mut self, _: nwg::EventData) {
+        self.reload_jobs_view();
+    }
+
+    pub(super) fn cancel_selected(&mut self, _: nwg::EventData) {
+        let idx = match self.c.jobs_view.selected_item() {
+            Some(idx) if idx < self.shown.len() => idx,
+            _ => return
+        };
+        let job = &self.shown[idx];
+        if JobStatus::Pending != job.status {
+            nwg::modal_info_message(&self.c.window, "Job queue",
+                &format!("Only pending jobs can be cancelled, job '{}' is {}", job.name, job.status.label().to_lowercase()));
+            return;
+        }
+        self.args.queue.cancel(job.id);
+        self.reload_jobs_view();
+    }
+
+    pub(super) fn clear_finished(&mut self, _: nwg::EventData) {
+        self.args.queue.clear_finished();
+        self.reload_jobs_view();
+    }
+
+    // view is rebuilt only on changes to keep the selection
+    fn reload_jobs_view(&mut self) {
+        let jobs = self.args.queue.jobs();
+        if jobs == self.shown {
+            return;
+        }
+        let selected_id = self.c.jobs_view.selected_item()
+            .and_then(|idx| self.shown.get(idx))
+            .map(|job| job.id);
+        self.c.jobs_view.clear();
+        for job in jobs.iter() {
+            self.c.jobs_view.insert_items_row(None, &[
+                job.id.to_string(), job.name.clone(), job.status.label().to_string(),
+                job.duration_label(), job.details.clone()]);
+        }
+        if let Some(idx) = jobs.iter().position(|job| Some(job.id) == selected_id) {
+            self.c.jobs_view.select_item(idx, true);
+        }
+        self.shown = jobs;
+    }
+}
+
+impl ui::PopupDialog<QueueDialogArgs, ()> for QueueDialog {
+    fn popup(args: QueueDialogArgs) -> ui::PopupJoinHandle<()> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.reload_jobs_view();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> () {
+        ()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.c.refresh_timer.stop();
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
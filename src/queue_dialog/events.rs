@@ -0,0 +1,61 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct QueueDialogEvents {
+    pub(super) events: Vec<ui::Event<QueueDialog>>
+}
+
+impl ui::Events<QueueDialogControls> for QueueDialogEvents {
+    fn build(&mut self, c: &QueueDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(QueueDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(QueueDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.cancel_job_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(QueueDialog::cancel_selected)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.clear_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(QueueDialog::clear_finished)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.close_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(QueueDialog::close)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.refresh_timer)
+            .event(nwg::Event::OnTimerTick)
+            .handler(QueueDialog::on_refresh_tick)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
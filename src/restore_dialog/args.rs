@@ -1,57 +1,144 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use super::*;
-
-
-#[derive(Default, Clone)]
-pub struct PgRestoreArgs {
-    pub(super) zip_file_path: String,
-    pub(super) dest_db_name: String,
-    pub(super) bbf_db_name: String,
-}
-
-#[derive(Default)]
-pub struct RestoreDialogArgs {
-    pub(super) notice_sender:  ui::SyncNoticeSender,
-    pub(super) pg_conn_config: PgConnConfig,
-    pub(super) pg_restore_args: PgRestoreArgs,
-}
-
-impl RestoreDialogArgs {
-    pub fn new(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig,
-               zip_file_path: &str, dest_db_name: &str, bbf_db_name: &str) -> Self {
-        Self {
-            notice_sender: notice.sender(),
-            pg_conn_config: pg_conn_config.clone(),
-            pg_restore_args: PgRestoreArgs {
-                zip_file_path: zip_file_path.to_string(),
-                dest_db_name: dest_db_name.to_string(),
-                bbf_db_name: bbf_db_name.to_string(),
-            }
-        }
-    }
-
-    pub fn send_notice(&self) {
-        self.notice_sender.send()
-    }
-}
-
-impl ui::PopupArgs for RestoreDialogArgs {
-    fn notify_parent(&self) {
-        self.notice_sender.send()
-    }
-}
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+
+#[derive(Default, Clone)]
+pub struct RestoreOptions {
+    pub schema_mapping: Vec<(String, String)>,
+    // owners in the dump mapped to existing roles on the target server
+    pub owner_mapping: Vec<(String, String)>,
+    pub keep_temp_dir: bool,
+    // objects are owned by the connecting user
+    pub no_owner: bool,
+    pub no_privileges: bool,
+    pub terminate_connections: bool,
+    // existing DB with the target name is dropped before restore
+    pub replace_existing: bool,
+    // statistics are collected after restore
+    pub analyze: bool,
+    // row counts from the backup manifest are compared with the restored tables
+    pub verify_row_counts: bool,
+}
+
+#[derive(Default, Clone)]
+pub struct PgRestoreArgs {
+    // zip file or a directory with an already unzipped dump
+    pub(super) src_path: String,
+    pub(super) dest_db_name: String,
+    // empty for plain Postgres servers, target DB is created as a physical DB then
+    pub(super) bbf_db_name: String,
+    pub(super) options: RestoreOptions,
+    // archive and server are checked, nothing is restored
+    pub(super) validate_only: bool,
+    // "globals.sql" from the backup is run before pg_restore
+    pub(super) restore_globals: bool,
+    // schema-only script is written into this file, nothing is restored
+    pub(super) ddl_file: String,
+    // physical backup is laid down into a new data directory for WAL replay
+    pub(super) pitr: Option<PitrArgs>,
+    // unzip and volume joining writes are limited to this rate, zero - no limit
+    pub(super) throttle_mb_per_sec: u64,
+    // migration mode of the target server, multi-db dumps are converted for single-db mode
+    pub(super) migration_mode: String,
+}
+
+impl PgRestoreArgs {
+    pub fn new(src_path: &str, dest_db_name: &str, bbf_db_name: &str, options: &RestoreOptions) -> Self {
+        Self {
+            src_path: src_path.to_string(),
+            dest_db_name: dest_db_name.to_string(),
+            bbf_db_name: bbf_db_name.to_string(),
+            options: options.clone(),
+            validate_only: false,
+            restore_globals: false,
+            ddl_file: String::new(),
+            pitr: None,
+            throttle_mb_per_sec: 0,
+            migration_mode: String::new(),
+        }
+    }
+
+    pub(super) fn is_plain_pg(&self) -> bool {
+        self.bbf_db_name.is_empty()
+    }
+
+    // DB that pg_restore connects to
+    pub(super) fn physical_db(&self) -> &str {
+        if self.is_plain_pg() {
+            &self.dest_db_name
+        } else {
+            &self.bbf_db_name
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RestoreDialogArgs {
+    pub(super) notice_sender:  ui::SyncNoticeSender,
+    pub(super) pg_conn_config: PgConnConfig,
+    pub(super) pg_restore_args: PgRestoreArgs,
+    pub(super) forecast: Option<RunForecast>,
+}
+
+impl RestoreDialogArgs {
+    pub fn new(notice: &ui::SyncNotice, pg_conn_config: &PgConnConfig,
+               src_path: &str, dest_db_name: &str, bbf_db_name: &str, options: &RestoreOptions,
+               forecast: Option<RunForecast>) -> Self {
+        Self {
+            notice_sender: notice.sender(),
+            pg_conn_config: pg_conn_config.clone(),
+            pg_restore_args: PgRestoreArgs::new(src_path, dest_db_name, bbf_db_name, options),
+            forecast,
+        }
+    }
+
+    pub fn validate_only(mut self) -> Self {
+        self.pg_restore_args.validate_only = true;
+        self
+    }
+
+    pub fn export_ddl(mut self, ddl_file: &str) -> Self {
+        self.pg_restore_args.ddl_file = ddl_file.to_string();
+        self
+    }
+
+    pub fn with_globals(mut self, restore_globals: bool) -> Self {
+        self.pg_restore_args.restore_globals = restore_globals;
+        self
+    }
+
+    pub fn point_in_time(mut self, pitr: PitrArgs) -> Self {
+        self.pg_restore_args.pitr = Some(pitr);
+        self
+    }
+
+    pub fn with_throttle(mut self, throttle_mb_per_sec: u64) -> Self {
+        self.pg_restore_args.throttle_mb_per_sec = throttle_mb_per_sec;
+        self
+    }
+
+    pub fn send_notice(&self) {
+        self.notice_sender.send()
+    }
+}
+
+impl ui::PopupArgs for RestoreDialogArgs {
+    fn notify_parent(&self) {
+        self.notice_sender.send()
+    }
+}
@@ -1,356 +1,1575 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-use std::env;
-use std::fs;
-use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::os::windows::process::CommandExt;
-use std::path::Path;
-use std::time;
-
-use pgdump_toc_rewrite;
-
-use super::*;
-use crate::restore_dialog::args::PgRestoreArgs;
-use crate::common::PgAccessError;
-
-#[derive(Default)]
-pub struct RestoreDialog {
-    pub(super) c: RestoreDialogControls,
-
-    args: RestoreDialogArgs,
-    command_join_handle: ui::PopupJoinHandle<RestoreResult>,
-    dialog_result: RestoreDialogResult,
-
-    progress_pending: Vec<String>,
-    progress_last_updated: u128,
-}
-
-impl RestoreDialog {
-
-    pub(super) fn on_progress(&mut self, _: nwg::EventData) {
-        let msg = self.c.progress_notice.receive();
-        self.progress_pending.push(msg);
-        let now = time::SystemTime::now()
-            .duration_since(time::UNIX_EPOCH)
-            .unwrap_or(Duration::from_secs(0))
-            .as_millis();
-        if now - self.progress_last_updated > 100 {
-            let joined = self.progress_pending.join("\r\n");
-            self.progress_pending.clear();
-            self.progress_last_updated = now;
-            self.c.details_box.appendln(&joined);
-        }
-    }
-
-    pub(super) fn on_complete(&mut self, _: nwg::EventData) {
-        self.c.complete_notice.receive();
-        let res = self.command_join_handle.join();
-        let success = res.error.is_empty();
-        self.stop_progress_bar(success.clone());
-        if !success {
-            self.dialog_result = RestoreDialogResult::failure();
-            self.c.label.set_text("Restore failed");
-            self.progress_pending.push(res.error);
-            self.c.copy_clipboard_button.set_enabled(true);
-            self.c.close_button.set_enabled(true);
-        } else {
-            self.dialog_result = RestoreDialogResult::success();
-            self.c.label.set_text("Restore complete");
-            self.c.copy_clipboard_button.set_enabled(true);
-            self.c.close_button.set_enabled(true);
-        }
-        if self.progress_pending.len() > 0 {
-            let joined = self.progress_pending.join("\r\n");
-            self.c.details_box.appendln(&joined);
-            self.progress_pending.clear();
-        }
-    }
-
-    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
-        let text = self.c.details_box.text();
-        let _ = set_clipboard(formats::Unicode, &text);
-    }
-
-    fn stop_progress_bar(&self, success: bool) {
-        self.c.progress_bar.set_marquee(false, 0);
-        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
-        self.c.progress_bar.set_pos(1);
-        if !success {
-            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
-        }
-    }
-
-    fn unzip_file(progress: &ui::SyncNoticeValueSender<String>, zipfile: &str) -> Result<String, io::Error> {
-        let file_path = Path::new(zipfile);
-        let parent_dir = match file_path.parent() {
-            Some(dir) => dir,
-            None => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "Error accessing parent directory")))
-        };
-        let parent_dir_st = match parent_dir.to_str() {
-            Some(st) => st,
-            None => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "Error reading parent directory name")))
-        };
-        let listener = |en: &str| {
-            progress.send_value(en);
-        };
-        match zip_recurse::unzip_directory_listen(zipfile, parent_dir_st, listener) {
-            Ok(dirname) => {
-                let dir_path = parent_dir.join(Path::new(&dirname));
-                match dir_path.to_str() {
-                    Some(st) => Ok(st.to_string()),
-                    None => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                        "Error reading dest directory name")))
-                }
-            },
-            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "Unzip error, file: {}, message: {}", zipfile, e)))
-        }
-    }
-
-    fn check_db_does_not_exist(pg_conn_config: &PgConnConfig, ra: &PgRestoreArgs) -> Result<(), PgAccessError> {
-        let mut client = pg_conn_config.open_connection_to_db(&ra.bbf_db_name)?;
-        let rs = client.query("select name from sys.babelfish_sysdatabases", &[])?;
-        for row in rs.iter() {
-            let name: String = row.get("name");
-            if name.to_lowercase() == ra.dest_db_name.to_lowercase() {
-                return Err(PgAccessError::from_string(format!("Database with name '{}' already exists", &name)))
-            }
-        };
-        client.close()?;
-        Ok(())
-    }
-
-    fn create_role_if_not_exist(client: &mut postgres::Client, dbname: &str, role: &str) -> Result<Option<String>, PgAccessError> {
-        let rolname = format!("{}_{}", dbname, role);
-        let rs = client.query("select (count(1) > 0) as role_exist from pg_catalog.pg_roles where rolname = $1", &[&rolname])?;
-        let exists: bool = rs[0].get(0);
-        if !exists {
-            client.execute(&format!("CREATE ROLE {} WITH NOSUPERUSER INHERIT NOCREATEROLE NOCREATEDB NOLOGIN NOREPLICATION NOBYPASSRLS", rolname), &[])?;
-            // db error: ERROR: must be superuser to alter superuser roles or change superuser attribute
-            // client.execute(&format!("ALTER ROLE {} WITH NOSUPERUSER INHERIT NOCREATEROLE NOCREATEDB NOLOGIN NOREPLICATION NOBYPASSRLS", rolname), &[])?;
-            Ok(Some(rolname))
-        } else {
-            Ok(None)
-        }
-    }
-
-    fn restore_global_data(pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<Vec<String>, PgAccessError> {
-        let mut client = pcc.open_connection_to_db(&ra.bbf_db_name)?;
-        let dbname = &ra.dest_db_name;
-        let mut res = Vec::new();
-        for role in vec!(
-            "db_owner",
-            "dbo",
-            "guest"
-        ) {
-            if let Some(rolename) = Self::create_role_if_not_exist(&mut client, dbname, role)? {
-                res.push(rolename);
-            }
-        }
-        client.execute(&format!("GRANT {}_db_owner TO {}_dbo", dbname, dbname), &[])?;
-        client.execute(&format!("GRANT {}_dbo TO sysadmin", dbname), &[])?;
-        client.execute(&format!("GRANT {}_guest TO sysadmin", dbname), &[])?;
-        client.execute(&format!("GRANT {}_guest TO {}_db_owner", dbname, dbname), &[])?;
-        client.close()?;
-        Ok(res)
-    }
-
-    fn drop_created_roles(pcc: &PgConnConfig, bbf_db: &str, roles: &Vec<String>) -> Result<(), PgAccessError> {
-        let mut client = pcc.open_connection_to_db(bbf_db)?;
-        for rolname in roles {
-            client.execute(&format!("DROP ROLE {}", rolname), &[])?;
-        }
-        client.close()?;
-        Ok(())
-    }
-
-    fn run_pg_restore(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, dir: &str, bbf_db: &str) -> Result<(), io::Error> {
-        let cur_exe = env::current_exe()?;
-        let bin_dir = match cur_exe.parent() {
-            Some(path) => path,
-            None => { // cannot happen
-                let exe_st = cur_exe.to_str().unwrap_or("");
-                return Err(io::Error::new(io::ErrorKind::Other, format!(
-                    "Parent dir failure, exe path: {}", exe_st)))
-            }
-        };
-        let pg_restore_exe = bin_dir.join("pg_restore.exe");
-        let mut cmd = duct::cmd!(
-            pg_restore_exe,
-            "-v",
-            "-h", &pcc.hostname,
-            "-p", &pcc.port.to_string(),
-            "-U", &pcc.username,
-            "-d", bbf_db,
-            "-F", "d",
-            "-j", "1",
-            "--single-transaction",
-            dir
-        )
-            .stdin_null()
-            .stderr_to_stdout()
-            .stdout_capture()
-            .before_spawn(|pcmd| {
-                // create no window
-                let _ = pcmd.creation_flags(0x08000000);
-                Ok(())
-            });
-        if !&pcc.use_pgpass_file {
-            cmd = cmd.env("PGPASSWORD", &pcc.password);
-        }
-        let reader = match cmd.reader() {
-            Ok(reader) => reader,
-            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "pg_restore process spawn failure: {}", e)))
-        };
-        let mut buf_reader = BufReader::new(&reader);
-        loop {
-            let mut buf = vec!();
-            match buf_reader.read_until(b'\n', &mut buf) {
-                Ok(len) => {
-                    if 0 == len {
-                        break;
-                    }
-                    if buf.len() >= 2 {
-                        let ln = String::from_utf8_lossy(&buf[0..buf.len() - 2]);
-                        progress.send_value(ln);
-                    }
-                },
-                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                    "pg_restore process failure: {}", e)))
-            };
-        };
-        match reader.try_wait() {
-            Ok(opt) => match opt {
-                Some(_) => { },
-                None => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                    "pg_restore process failure")))
-            },
-            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
-                "pg_restore process failure: {}", e)))
-        }
-
-        Ok(())
-    }
-
-    fn run_restore(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> RestoreResult {
-        progress.send_value(format!("Running restore into DB: {} ...", ra.dest_db_name));
-
-        // db check
-        if let Err(e) = Self::check_db_does_not_exist(pcc, ra) {
-            return RestoreResult::failure(format!("{}", e))
-        }
-
-        // unzip
-        progress.send_value(format!("Unzipping file: {} ...", &ra.zip_file_path));
-        let dir = match Self::unzip_file(progress, &ra.zip_file_path) {
-            Ok(dir) => dir,
-            Err(e) => return RestoreResult::failure(format!("{}", e))
-        };
-
-        // rewrite
-        progress.send_value("Updating DB name ...");
-        let toc_path = Path::new(&dir).join("toc.dat");
-        if let Err(e) = pgdump_toc_rewrite::rewrite_toc(&toc_path, &ra.dest_db_name) {
-            return RestoreResult::failure(format!("{}", e))
-        }
-
-        // global data
-        progress.send_value("Restoring roles ...");
-        let roles = match Self::restore_global_data(pcc, ra) {
-            Ok(roles) => roles,
-            Err(e) => return RestoreResult::failure(format!("{}", e))
-        };
-
-        // run restore
-        progress.send_value("Running pg_restore ...");
-        if let Err(e) = Self::run_pg_restore(progress, pcc, &dir, &ra.bbf_db_name) {
-            if roles.len() > 0 {
-                progress.send_value(format!(
-                    "Error: restore failed, cleaning up global roles we created: {}", roles.join(", ")));
-                match Self::drop_created_roles(pcc, &ra.bbf_db_name, &roles) {
-                    Ok(_) => progress.send_value("Global roles cleanup complete"),
-                    Err(e) => progress.send_value(format!(
-                        "Error cleaning up global roles: {}", e))
-                }
-            }
-            return RestoreResult::failure(format!("{}", e))
-        };
-
-        // clean up
-        progress.send_value("Cleaning up temp directory ...");
-        if let Err(e) = fs::remove_dir_all(Path::new(&dir)) {
-            progress.send_value(format!(
-                "Warning: error removing tem directory: {}, message: {}", dir, e));
-        };
-
-        progress.send_value("Restore complete");
-        RestoreResult::success()
-    }
-}
-
-impl ui::PopupDialog<RestoreDialogArgs, RestoreDialogResult> for RestoreDialog {
-    fn popup(args: RestoreDialogArgs) -> ui::PopupJoinHandle<RestoreDialogResult> {
-        let join_handle = thread::spawn(move || {
-            let data = Self {
-                args,
-                ..Default::default()
-            };
-            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
-            nwg::dispatch_thread_events();
-            dialog.result()
-        });
-        ui::PopupJoinHandle::from(join_handle)
-    }
-
-    fn init(&mut self) {
-        let complete_sender = self.c.complete_notice.sender();
-        let progress_sender = self.c.progress_notice.sender();
-        let pcc: PgConnConfig = self.args.pg_conn_config.clone();
-        let pra: PgRestoreArgs = self.args.pg_restore_args.clone();
-        let join_handle = thread::spawn(move || {
-            let start = Instant::now();
-            let res = RestoreDialog::run_restore(&progress_sender, &pcc, &pra);
-            let remaining = 1000 - start.elapsed().as_millis() as i64;
-            if remaining > 0 {
-                thread::sleep(Duration::from_millis(remaining as u64));
-            }
-            complete_sender.send();
-            res
-        });
-        self.command_join_handle = ui::PopupJoinHandle::from(join_handle);
-    }
-
-    fn result(&mut self) -> RestoreDialogResult {
-        self.dialog_result.clone()
-    }
-
-    fn close(&mut self, _: nwg::EventData) {
-        self.args.send_notice();
-        self.c.window.set_visible(false);
-        nwg::stop_thread_dispatch();
-    }
-
-    fn on_resize(&mut self, _: nwg::EventData) {
-        self.c.update_tab_order();
-    }
-}
-
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use flate2::read::GzDecoder;
+
+use super::*;
+use crate::restore_dialog::args::PgRestoreArgs;
+use crate::common::PgAccessError;
+
+#[derive(Default)]
+pub struct RestoreDialog {
+    pub(super) c: RestoreDialogControls,
+
+    args: RestoreDialogArgs,
+    command_join_handle: ui::PopupJoinHandle<RestoreResult>,
+    dialog_result: RestoreDialogResult,
+
+    progress_buffer: ProgressBuffer,
+    details_log: DetailsLog,
+    taskbar_progress: Option<TaskbarProgress>,
+    started_at: Option<Instant>,
+    overrun_flagged: bool,
+}
+
+impl RestoreDialog {
+
+    pub(super) fn on_progress(&mut self, _: nwg::EventData) {
+        let msg = self.c.progress_notice.receive();
+        if let Some(text) = self.progress_buffer.push(msg) {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+    }
+
+    pub(super) fn on_progress_tick(&mut self, _: nwg::EventData) {
+        if let Some(text) = self.progress_buffer.flush_if_due() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+        self.check_overrun();
+    }
+
+    fn check_overrun(&mut self) {
+        if self.overrun_flagged {
+            return;
+        }
+        if let (Some(forecast), Some(started)) = (&self.args.forecast, &self.started_at) {
+            if forecast.is_exceeded(started.elapsed()) {
+                self.overrun_flagged = true;
+                self.c.label.set_text(&format!(
+                    "{} taking longer than usual ({})", self.running_label(), forecast.describe()));
+                self.c.progress_bar.set_state(nwg::ProgressBarState::Paused);
+            }
+        }
+    }
+
+    pub(super) fn on_complete(&mut self, _: nwg::EventData) {
+        self.c.complete_notice.receive();
+        let res = self.command_join_handle.join();
+        let success = res.error.is_empty();
+        let duration = match self.started_at {
+            Some(started) => started.elapsed(),
+            None => Duration::from_secs(0)
+        };
+        self.stop_progress_bar(success.clone());
+        let (complete_label, failed_label) = if self.args.pg_restore_args.validate_only {
+            ("Validation complete", "Validation failed")
+        } else if !self.args.pg_restore_args.ddl_file.is_empty() {
+            ("DDL export complete", "DDL export failed")
+        } else if self.args.pg_restore_args.pitr.is_some() {
+            ("Data directory prepared", "Point-in-time restore failed")
+        } else {
+            ("Restore complete", "Restore failed")
+        };
+        if !success {
+            self.dialog_result = RestoreDialogResult::failure(duration);
+            self.c.label.set_text(failed_label);
+            log::error!("{}: {}", failed_label, res.error);
+            if let Some(text) = self.progress_buffer.push(res.error.clone()) {
+                self.details_log.append(&self.c.details_box, &text);
+            }
+            self.c.run_again_button.set_enabled(true);
+            self.c.copy_clipboard_button.set_enabled(true);
+            self.c.close_button.set_enabled(true);
+        } else {
+            self.dialog_result = RestoreDialogResult::success(duration);
+            self.c.label.set_text(complete_label);
+            self.c.run_again_button.set_enabled(true);
+            self.c.copy_clipboard_button.set_enabled(true);
+            self.c.close_button.set_enabled(true);
+        }
+        if window_in_background(&self.c.window) {
+            if success {
+                show_toast(&self.c.tray, &self.c.icon, complete_label, &format!("{} successfully", complete_label));
+            } else {
+                show_toast(&self.c.tray, &self.c.icon, failed_label, &res.error);
+            }
+        }
+        self.c.progress_timer.stop();
+        if let Some(text) = self.progress_buffer.flush() {
+            self.details_log.append(&self.c.details_box, &text);
+        }
+        self.c.copy_command_button.set_enabled(self.details_log.has_commands());
+    }
+
+    pub(super) fn on_autoscroll_changed(&mut self, _: nwg::EventData) {
+        let paused = self.c.autoscroll_checkbox.check_state() != nwg::CheckBoxState::Checked;
+        self.details_log.set_paused(&self.c.details_box, paused);
+    }
+
+    // same arguments are used, output of the previous run is discarded
+    pub(super) fn run_again(&mut self, _: nwg::EventData) {
+        self.c.run_again_button.set_enabled(false);
+        self.c.copy_command_button.set_enabled(false);
+        self.c.copy_clipboard_button.set_enabled(false);
+        self.c.close_button.set_enabled(false);
+        self.c.label.set_text(self.running_label());
+        self.c.details_box.set_text("");
+        self.details_log = DetailsLog::default();
+        self.c.autoscroll_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        self.overrun_flagged = false;
+        self.c.progress_bar.set_state(nwg::ProgressBarState::Normal);
+        self.c.progress_bar.set_pos(0);
+        self.c.progress_bar.add_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_marquee(true, 30);
+        self.c.progress_timer.start();
+        self.start_command();
+    }
+
+    // commands can be re-run from a terminal to reproduce a failure
+    pub(super) fn copy_command_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.commands_text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    pub(super) fn copy_to_clipboard(&mut self, _: nwg::EventData) {
+        let text = self.details_log.text();
+        let _ = set_clipboard(formats::Unicode, &text);
+    }
+
+    fn running_label(&self) -> &'static str {
+        if self.args.pg_restore_args.validate_only {
+            "Validating restore ..."
+        } else if !self.args.pg_restore_args.ddl_file.is_empty() {
+            "Exporting DDL script ..."
+        } else if self.args.pg_restore_args.pitr.is_some() {
+            "Preparing point-in-time restore ..."
+        } else {
+            "Running restore ..."
+        }
+    }
+
+    fn start_command(&mut self) {
+        self.started_at = Some(Instant::now());
+        if let Some(forecast) = &self.args.forecast {
+            self.c.label.set_text(&format!("{} ({})", self.running_label(), forecast.describe()));
+        }
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.set_indeterminate();
+        }
+        let complete_sender = self.c.complete_notice.sender();
+        let progress_sender = self.c.progress_notice.sender();
+        let pcc: PgConnConfig = self.args.pg_conn_config.clone();
+        let pra: PgRestoreArgs = self.args.pg_restore_args.clone();
+        let join_handle = thread::spawn(move || {
+            let start = Instant::now();
+            let res = RestoreDialog::run_restore(&progress_sender, &pcc, &pra);
+            let remaining = 1000 - start.elapsed().as_millis() as i64;
+            if remaining > 0 {
+                thread::sleep(Duration::from_millis(remaining as u64));
+            }
+            complete_sender.send();
+            res
+        });
+        self.command_join_handle = ui::PopupJoinHandle::from(join_handle);
+    }
+
+    fn stop_progress_bar(&self, success: bool) {
+        self.c.progress_bar.set_marquee(false, 0);
+        self.c.progress_bar.remove_flags(nwg::ProgressBarFlags::MARQUEE);
+        self.c.progress_bar.set_pos(1);
+        if !success {
+            self.c.progress_bar.set_state(nwg::ProgressBarState::Error)
+        }
+        if let Some(taskbar) = &self.taskbar_progress {
+            if success {
+                taskbar.set_complete();
+            } else {
+                taskbar.set_error();
+            }
+        }
+    }
+
+    // dump is extracted next to the zip file, TOC and catalog tables are rewritten
+    // before the rest of data files are extracted
+    fn unzip_file(progress: &ui::SyncNoticeValueSender<String>, ra: &PgRestoreArgs) -> Result<String, io::Error> {
+        let zipfile = &ra.src_path;
+        let file_path = Path::new(zipfile);
+        let parent_dir = match file_path.parent() {
+            Some(dir) => dir,
+            None => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Error accessing parent directory")))
+        };
+        progress.send_value("Checking backup file structure ...");
+        pg_toc::check_zip_dump(&long_path(file_path))?;
+        Self::check_disk_space(progress, file_path, parent_dir)?;
+        let listener = |en: &str| {
+            progress.send_value(en);
+        };
+        let mut rewrite_failed = false;
+        let rewrite = |dir: &Path| {
+            let res = Self::merge_chained_data(progress, ra, dir)
+                .and_then(|_| Self::rewrite_dump(progress, ra, dir));
+            rewrite_failed = res.is_err();
+            res
+        };
+        // extracted in extended-length form, pg_restore gets the path as entered by user
+        let throttle = Throttle::new(ra.throttle_mb_per_sec);
+        match pg_toc::extract_zip_dump(&long_path(file_path), &long_path(parent_dir), throttle, listener, rewrite) {
+            Ok(dir_path) => match dir_path.file_name().and_then(|name| parent_dir.join(name).to_str().map(|st| st.to_string())) {
+                Some(st) => Ok(st),
+                None => Err(io::Error::new(io::ErrorKind::Other, format!(
+                    "Error reading dest directory name")))
+            },
+            Err(e) if rewrite_failed => Err(e),
+            Err(e) => Err(io::Error::new(e.kind(), format!(
+                "Unzip error, file: {}, message: {}", zipfile, e)))
+        }
+    }
+
+    // Size errors are reported as warnings, extraction reports the same errors
+    // with a proper message.
+    fn check_disk_space(progress: &ui::SyncNoticeValueSender<String>, zip_path: &Path, dest_dir: &Path) -> Result<(), io::Error> {
+        let required = match pg_toc::extracted_dump_size(zip_path) {
+            Ok(size) => size,
+            Err(e) => {
+                progress.send_value(format!("Warning: error reading extracted size: {}", e));
+                return Ok(());
+            }
+        };
+        let free = match free_disk_space(dest_dir) {
+            Ok(free) => free,
+            Err(e) => {
+                progress.send_value(format!("Warning: error checking free disk space: {}", e));
+                return Ok(());
+            }
+        };
+        progress.send_value(format!("Extracted size: {}, free disk space: {}",
+            format_disk_size(required), format_disk_size(free)));
+        if free < required {
+            return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Not enough free disk space to extract the backup into directory: {}, free: {}, required: {}",
+                dest_dir.to_string_lossy(), format_disk_size(free), format_disk_size(required))));
+        }
+        Ok(())
+    }
+
+    // Incremental and differential backups do not contain data of the tables not changed
+    // since the base backup, it is copied from the earlier backups in the same directory
+    // before the TOC is rewritten. Merging is skipped for tables that already have data in the dump.
+    fn merge_chained_data(progress: &impl ProgressSender, ra: &PgRestoreArgs, dir: &Path) -> Result<(), io::Error> {
+        let src_path = long_path(Path::new(&ra.src_path));
+        let manifest = match BackupManifest::read_path(&src_path)? {
+            Some(manifest) if manifest.is_chained() => manifest,
+            _ => return Ok(())
+        };
+        let backup_dir = match src_path.parent() {
+            Some(dir) => dir,
+            None => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "Error accessing parent directory")))
+        };
+        let tables: Vec<(String, String, PathBuf)> = manifest.table_stamps.iter()
+            .filter_map(|st| manifest.data_sources.get(&st.key())
+                .map(|source| (st.schema.clone(), st.table.clone(), backup_dir.join(source))))
+            .collect();
+        progress.send_value(format!("{} backup, base: {}, unchanged tables: {}",
+            manifest.mode.label(), manifest.base_backup, tables.len()));
+        progress.send_value("Merging table data from earlier backups ...");
+        let throttle = Throttle::new(ra.throttle_mb_per_sec);
+        let count = pg_toc::merge_chain_data(dir, &tables, throttle, |en| progress.send_value(en))?;
+        progress.send_value(format!("Table data merged, tables: {}", count));
+        Ok(())
+    }
+
+    // also used by "toc-rewrite" command line, source path is not used
+    pub fn rewrite_dump(progress: &impl ProgressSender, ra: &PgRestoreArgs, dir: &Path) -> Result<(), io::Error> {
+        // schemas
+        if !ra.options.schema_mapping.is_empty() {
+            progress.send_value("Remapping schema names ...");
+            for (from, to) in ra.options.schema_mapping.iter() {
+                progress.send_value(format!("Schema: {} -> {}", from, to));
+            }
+            pg_toc::remap_schemas(dir, &ra.options.schema_mapping)?;
+        }
+
+        // owners, applied before the DB name rewrite that renames "olddb_dbo" owners
+        if !ra.options.owner_mapping.is_empty() {
+            progress.send_value("Remapping owners ...");
+            for (from, to) in ra.options.owner_mapping.iter() {
+                progress.send_value(format!("Owner: {} -> {}", from, to));
+            }
+            pg_toc::remap_owners(dir, &ra.options.owner_mapping)?;
+        }
+
+        // plain Postgres dumps have no DB name in schema names
+        match pg_toc::dump_migration_mode(dir)? {
+            Some(dump_mode) => Self::rewrite_dbname(progress, ra, dir, dump_mode)?,
+            None => progress.send_value("Dump has no Babelfish schemas, skipping TOC rewrite")
+        }
+        Ok(())
+    }
+
+    // not needed when restoring under the original name into the same migration mode
+    fn rewrite_dbname(progress: &impl ProgressSender, ra: &PgRestoreArgs, dir: &Path, dump_mode: &str) -> Result<(), io::Error> {
+        let to_single_db = pg_toc::MULTI_DB == dump_mode && pg_toc::SINGLE_DB == ra.migration_mode;
+        let mut orig_dbname = None;
+        match pg_toc::DumpSummary::read(dir) {
+            Ok(summary) => {
+                progress.send_value(format!(
+                    "Dump TOC version: {}.{}.{}, entries: {}, data files: {}, original DB name: {}",
+                    summary.header.vmaj, summary.header.vmin, summary.header.vrev, summary.entries.len(),
+                    summary.data_files.len(), summary.original_dbname.as_deref().unwrap_or("unknown")));
+                if Some(&ra.dest_db_name) == summary.original_dbname.as_ref() && !to_single_db {
+                    progress.send_value("DB name is unchanged, skipping TOC rewrite");
+                    return Ok(());
+                }
+                orig_dbname = summary.original_dbname;
+            },
+            Err(e) => progress.send_value(format!("Warning: error reading dump TOC: {}", e))
+        }
+        if to_single_db {
+            progress.send_value("Converting dump taken in multi-db migration mode to single-db mode ...");
+            let prefix = pg_toc::convert_to_single_db(dir)?;
+            progress.send_value(format!("Schema name prefix removed: {}", prefix));
+        }
+        if pg_toc::SINGLE_DB == dump_mode || to_single_db {
+            // schema names are not prefixed, TOC is left as is
+            let orig = match orig_dbname {
+                Some(name) => name,
+                None => return Err(io::Error::new(io::ErrorKind::InvalidData,
+                    "Original DB name not found in a dump for single-db migration mode"))
+            };
+            if orig != ra.dest_db_name {
+                progress.send_value("Updating DB name in Babelfish catalog ...");
+                pg_toc::rename_single_db(dir, &orig, &ra.dest_db_name)?;
+            }
+        } else {
+            progress.send_value("Updating DB name ...");
+            let (orig_prefix, user_schemas) = pg_toc::user_schemas(dir)?;
+            pg_toc::rename_multi_db(dir, &ra.dest_db_name)?;
+
+            // DB name rewrite only covers "dbo" and "guest" schemas
+            if !user_schemas.is_empty() {
+                progress.send_value(format!("Updating user schemas: {} ...", user_schemas.join(", ")));
+                pg_toc::rename_user_schemas(dir, &orig_prefix, &user_schemas)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Directory chosen by user is never modified, TOC and catalog tables are copied
+    // into a work directory next to it and rewritten there, data files are hard-linked
+    fn link_dump_dir(progress: &ui::SyncNoticeValueSender<String>, ra: &PgRestoreArgs) -> Result<String, io::Error> {
+        let src_dir = ra.src_path.trim_end_matches(&['\\', '/'][..]);
+        let dir = format!("{}_restore_{}", src_dir, chrono::Local::now().format("%Y%m%d_%H%M%S"));
+        progress.send_value(format!("Preparing work directory: {} ...", dir));
+        match pg_toc::link_dump_dir(&long_path(Path::new(src_dir)), &long_path(Path::new(&dir))) {
+            Ok(0) => Ok(dir),
+            Ok(bytes) => {
+                progress.send_value(format!("Hard links not supported, data files copied: {}", format_disk_size(bytes)));
+                Ok(dir)
+            },
+            Err(e) => Err(io::Error::new(e.kind(), format!(
+                "Error preparing work directory: {}, message: {}", dir, e)))
+        }
+    }
+
+    // dump is extracted into a directory next to the tar file
+    fn untar_file(progress: &ui::SyncNoticeValueSender<String>, tarfile: &str) -> Result<String, io::Error> {
+        let dir = BackupFormat::strip_extension(tarfile);
+        let listener = |en: &str| {
+            progress.send_value(en);
+        };
+        match pg_toc::extract_tar_dump(&long_path(Path::new(tarfile)), &long_path(Path::new(&dir)), listener) {
+            Ok(_) => Ok(dir),
+            Err(e) => Err(io::Error::new(e.kind(), format!(
+                "Tar extraction error, file: {}, message: {}", tarfile, e)))
+        }
+    }
+
+    // server-level queries go to the Babelfish DB, or to the default DB on plain Postgres
+    fn server_db<'a>(pcc: &'a PgConnConfig, ra: &'a PgRestoreArgs) -> &'a str {
+        if ra.is_plain_pg() {
+            &pcc.connect_db
+        } else {
+            &ra.bbf_db_name
+        }
+    }
+
+    fn find_existing_db(pg_conn_config: &PgConnConfig, ra: &PgRestoreArgs) -> Result<Option<String>, PgAccessError> {
+        let mut client = pg_conn_config.open_connection_to_db(Self::server_db(pg_conn_config, ra))?;
+        if ra.is_plain_pg() {
+            // physical DB names are case-sensitive
+            let rs = client.query("select datname::text as name from pg_catalog.pg_database where datname = $1", &[&ra.dest_db_name])?;
+            let res = rs.iter().next().map(|row| row.get("name"));
+            client.close()?;
+            return Ok(res);
+        }
+        let rs = client.query("select name from sys.babelfish_sysdatabases", &[])?;
+        let mut res = None;
+        for row in rs.iter() {
+            let name: String = row.get("name");
+            if name.to_lowercase() == ra.dest_db_name.to_lowercase() {
+                res = Some(name);
+                break;
+            }
+        };
+        client.close()?;
+        Ok(res)
+    }
+
+    // migration mode of the server and user DBs other than the target one
+    fn read_migration_mode(pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<(String, Vec<String>), PgAccessError> {
+        let mut client = pcc.open_connection_to_db(&ra.bbf_db_name)?;
+        let rs = client.query(
+            "select current_setting('babelfishpg_tsql.migration_mode', true) as migration_mode", &[])?;
+        let mode: Option<String> = rs[0].get("migration_mode");
+        let rs = client.query("
+            select name from sys.babelfish_sysdatabases
+            where name not in ('master', 'tempdb', 'msdb')
+            and lower(name) <> lower($1)
+            order by name", &[&ra.dest_db_name])?;
+        let others = rs.iter().map(|row| row.get("name")).collect();
+        client.close()?;
+        Ok((mode.unwrap_or_default(), others))
+    }
+
+    // Single-db mode allows only one user DB, its schemas are not prefixed with DB name
+    fn check_migration_mode(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<String, PgAccessError> {
+        if ra.is_plain_pg() {
+            return Ok(String::new());
+        }
+        let read = || Self::read_migration_mode(pcc, ra);
+        let (mode, others) = pcc.retry_policy().run("Migration mode check", |en| progress.send_value(en), read)?;
+        if mode.is_empty() {
+            return Ok(mode);
+        }
+        progress.send_value(format!("DB server migration mode: {}", mode));
+        if pg_toc::SINGLE_DB == mode && !others.is_empty() {
+            return Err(PgAccessError::from_string(format!(
+                "DB server is in single-db migration mode and already has user database '{}'.\r\n\r\n\
+                Only one user database can exist in this mode, drop it first or restore\r\n\
+                into a DB server in multi-db mode.", others.join("', '"))));
+        }
+        Ok(mode)
+    }
+
+    // cross-mode restore is not supported by Babelfish, Babelfish dumps
+    // and plain Postgres dumps can only be restored into the same kind of server
+    fn check_dump_migration_mode(progress: &ui::SyncNoticeValueSender<String>, ra: &PgRestoreArgs, dir: &Path) -> Result<(), io::Error> {
+        let server_mode = ra.migration_mode.as_str();
+        let dump_mode = match pg_toc::dump_migration_mode(dir)? {
+            Some(_) if ra.is_plain_pg() => return Err(io::Error::new(io::ErrorKind::InvalidData,
+                "Backup was taken from a Babelfish DB, it cannot be restored into a plain Postgres server")),
+            Some(mode) => mode,
+            None if !ra.is_plain_pg() => return Err(io::Error::new(io::ErrorKind::InvalidData,
+                "Backup was taken from a plain Postgres DB, it cannot be restored into a Babelfish DB")),
+            None => return Ok(())
+        };
+        progress.send_value(format!("Dump migration mode: {}", dump_mode));
+        // multi-db dumps are converted by the TOC rewrite
+        if pg_toc::MULTI_DB == dump_mode && pg_toc::SINGLE_DB == server_mode {
+            return Ok(());
+        }
+        if !server_mode.is_empty() && server_mode != dump_mode {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "Backup was taken in {} migration mode, it cannot be restored into a DB server in {} mode",
+                dump_mode, server_mode)));
+        }
+        Ok(())
+    }
+
+    fn prepare_target_db(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<(), PgAccessError> {
+        let find = || Self::find_existing_db(pcc, ra);
+        let name = match pcc.retry_policy().run("Target DB check", |en| progress.send_value(en), find)? {
+            Some(name) => name,
+            None => return Ok(())
+        };
+        if !ra.options.replace_existing {
+            return Err(PgAccessError::from_string(format!("Database with name '{}' already exists", &name)))
+        }
+        progress.send_value(format!("Dropping existing DB: {} ...", &name));
+        Self::drop_db(pcc, ra, &name)?;
+        progress.send_value("Existing DB dropped");
+        Ok(())
+    }
+
+    // DB is dropped with T-SQL, that also removes its schemas, users and
+    // "{dbname}_*" roles, roles left by previous failed restores are dropped after that
+    fn drop_db(pcc: &PgConnConfig, ra: &PgRestoreArgs, dbname: &str) -> Result<(), PgAccessError> {
+        let mut client = pcc.open_connection_to_db(Self::server_db(pcc, ra))?;
+        if ra.is_plain_pg() {
+            client.batch_execute(&format!("DROP DATABASE \"{}\"", dbname.replace('"', "\"\"")))?;
+            client.close()?;
+            return Ok(());
+        }
+        client.batch_execute("set babelfishpg_tsql.sql_dialect = 'tsql'")?;
+        client.batch_execute(&format!("DROP DATABASE [{}]", dbname.replace(']', "]]")))?;
+        client.batch_execute("reset babelfishpg_tsql.sql_dialect")?;
+        let dbname_lower = dbname.to_lowercase();
+        for role in vec!(
+            "db_owner",
+            "dbo",
+            "guest"
+        ) {
+            let rolname = format!("{}_{}", dbname_lower, role);
+            let rs = client.query("select 1 from pg_catalog.pg_roles where rolname = $1", &[&rolname])?;
+            if !rs.is_empty() {
+                client.execute(&format!("DROP ROLE \"{}\"", rolname.replace('"', "\"\"")), &[])?;
+            }
+        }
+        client.close()?;
+        Ok(())
+    }
+
+    // Sessions that log in with the roles of the target DB or hold locks on its
+    // schemas. Names of other DBs can start with the target DB name ("mydb" and
+    // "mydb_sales"), so schemas are taken from the Babelfish catalog instead of
+    // matching the name prefix. On plain Postgres all sessions connected to
+    // the target DB are listed.
+    fn find_conflicting_sessions(pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<Vec<(i32, String, String)>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(Self::server_db(pcc, ra))?;
+        if ra.is_plain_pg() {
+            let rs = client.query("
+                select a.pid, coalesce(a.usename, '') as usename, coalesce(a.application_name, '') as application_name
+                from pg_catalog.pg_stat_activity a
+                where a.datname = $1
+                and a.pid <> pg_catalog.pg_backend_pid()", &[&ra.dest_db_name])?;
+            let res = rs.iter().map(|row| (row.get("pid"), row.get("usename"), row.get("application_name"))).collect();
+            client.close()?;
+            return Ok(res);
+        }
+        let rs = client.query("select dbid, name from sys.babelfish_sysdatabases", &[])?;
+        let databases: Vec<(i16, String)> = rs.iter().map(|row| (row.get("dbid"), row.get("name"))).collect();
+        let rs = client.query("select dbid, nspname::text as nspname from sys.babelfish_namespace_ext", &[])?;
+        let namespaces: Vec<(i16, String)> = rs.iter().map(|row| (row.get("dbid"), row.get("nspname"))).collect();
+        let (roles, schemas) = match Self::target_db_objects(&ra.dest_db_name, &databases, &namespaces) {
+            Some(objects) => objects,
+            None => {
+                // nothing to conflict with
+                client.close()?;
+                return Ok(Vec::new());
+            }
+        };
+        let rs = client.query("
+            select a.pid, coalesce(a.usename, '') as usename, coalesce(a.application_name, '') as application_name,
+                array(
+                    select distinct n.nspname::text
+                    from pg_catalog.pg_locks l
+                    join pg_catalog.pg_class c on c.oid = l.relation
+                    join pg_catalog.pg_namespace n on n.oid = c.relnamespace
+                    where l.pid = a.pid) as locked_schemas
+            from pg_catalog.pg_stat_activity a
+            where a.datname = current_database()
+            and a.pid <> pg_catalog.pg_backend_pid()", &[])?;
+        let sessions: Vec<(i32, String, String, Vec<String>)> = rs.iter().map(|row| {
+            (row.get("pid"), row.get("usename"), row.get("application_name"), row.get("locked_schemas"))
+        }).collect();
+        client.close()?;
+        Ok(Self::filter_conflicting_sessions(sessions, &roles, &schemas))
+    }
+
+    // roles and schemas of the Babelfish DB, None if the DB does not exist
+    fn target_db_objects(dbname: &str, databases: &[(i16, String)], namespaces: &[(i16, String)]) -> Option<(Vec<String>, Vec<String>)> {
+        let dbname = dbname.to_lowercase();
+        let (dbid, name) = databases.iter().find(|(_, name)| name.to_lowercase() == dbname)?;
+        let roles = vec!(
+            format!("{}_dbo", name),
+            format!("{}_db_owner", name),
+            format!("{}_guest", name),
+        );
+        let schemas = namespaces.iter()
+            .filter(|(id, _)| id == dbid)
+            .map(|(_, nspname)| nspname.clone())
+            .collect();
+        Some((roles, schemas))
+    }
+
+    fn filter_conflicting_sessions(sessions: Vec<(i32, String, String, Vec<String>)>, roles: &[String], schemas: &[String]) -> Vec<(i32, String, String)> {
+        sessions.into_iter()
+            .filter(|(_, usename, _, locked)| {
+                roles.contains(usename) || locked.iter().any(|nspname| schemas.contains(nspname))
+            })
+            .map(|(pid, usename, app, _)| (pid, usename, app))
+            .collect()
+    }
+
+    // errors are reported as warnings, restore may still succeed
+    fn terminate_connections(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) {
+        progress.send_value("Terminating active connections ...");
+        let find = || Self::find_conflicting_sessions(pcc, ra);
+        let sessions = match pcc.retry_policy().run("Listing active connections", |en| progress.send_value(en), find) {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                progress.send_value(format!("Warning: error listing active connections: {}", e));
+                return;
+            }
+        };
+        if sessions.is_empty() {
+            progress.send_value("No conflicting connections found");
+            return;
+        }
+        let mut client = match pcc.open_connection_to_db(Self::server_db(pcc, ra)) {
+            Ok(client) => client,
+            Err(e) => {
+                progress.send_value(format!("Warning: error terminating connections: {}", e));
+                return;
+            }
+        };
+        for (pid, usename, app) in sessions.iter() {
+            progress.send_value(format!("Terminating connection, pid: {}, user: {}, application: {}", pid, usename, app));
+            if let Err(e) = client.execute("select pg_catalog.pg_terminate_backend($1)", &[pid]) {
+                progress.send_value(format!("Warning: error terminating connection, pid: {}, message: {}", pid, e));
+            }
+        }
+        let _ = client.close();
+    }
+
+    fn create_role_if_not_exist(client: &mut postgres::Client, rolname: &str) -> Result<Option<String>, PgAccessError> {
+        let rs = client.query("select (count(1) > 0) as role_exist from pg_catalog.pg_roles where rolname = $1", &[&rolname])?;
+        let exists: bool = rs[0].get(0);
+        if !exists {
+            client.execute(&format!("CREATE ROLE {} WITH NOSUPERUSER INHERIT NOCREATEROLE NOCREATEDB NOLOGIN NOREPLICATION NOBYPASSRLS", rolname), &[])?;
+            // db error: ERROR: must be superuser to alter superuser roles or change superuser attribute
+            // client.execute(&format!("ALTER ROLE {} WITH NOSUPERUSER INHERIT NOCREATEROLE NOCREATEDB NOLOGIN NOREPLICATION NOBYPASSRLS", rolname), &[])?;
+            Ok(Some(rolname.to_string()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // "dbo" and "db_owner" roles are not prefixed with DB name in single-db mode
+    fn physical_role_name(ra: &PgRestoreArgs, role: &str) -> String {
+        if pg_toc::SINGLE_DB == ra.migration_mode && ("dbo" == role || "db_owner" == role) {
+            role.to_string()
+        } else {
+            format!("{}_{}", ra.dest_db_name, role)
+        }
+    }
+
+    fn restore_global_data(pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<Vec<String>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(&ra.bbf_db_name)?;
+        let db_owner = Self::physical_role_name(ra, "db_owner");
+        let dbo = Self::physical_role_name(ra, "dbo");
+        let guest = Self::physical_role_name(ra, "guest");
+        let mut res = Vec::new();
+        for rolname in vec!(&db_owner, &dbo, &guest) {
+            if let Some(rolename) = Self::create_role_if_not_exist(&mut client, rolname)? {
+                res.push(rolename);
+            }
+        }
+        client.execute(&format!("GRANT {} TO {}", db_owner, dbo), &[])?;
+        client.execute(&format!("GRANT {} TO sysadmin", dbo), &[])?;
+        client.execute(&format!("GRANT {} TO sysadmin", guest), &[])?;
+        client.execute(&format!("GRANT {} TO {}", guest, db_owner), &[])?;
+        client.close()?;
+        Ok(res)
+    }
+
+    // Babelfish DB roles are created before the restore, plain Postgres DB is created empty instead
+    fn prepare_global_data(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<Vec<String>, PgAccessError> {
+        if ra.is_plain_pg() {
+            progress.send_value(format!("Creating DB: {} ...", ra.dest_db_name));
+            let mut client = pcc.open_connection_to_db(Self::server_db(pcc, ra))?;
+            client.batch_execute(&format!("CREATE DATABASE \"{}\"", ra.dest_db_name.replace('"', "\"\"")))?;
+            client.close()?;
+            return Ok(Vec::new());
+        }
+        progress.send_value("Restoring roles ...");
+        Self::restore_global_data(pcc, ra)
+    }
+
+    fn drop_created_roles(pcc: &PgConnConfig, bbf_db: &str, roles: &Vec<String>) -> Result<(), PgAccessError> {
+        let mut client = pcc.open_connection_to_db(bbf_db)?;
+        for rolname in roles {
+            client.execute(&format!("DROP ROLE {}", rolname), &[])?;
+        }
+        client.close()?;
+        Ok(())
+    }
+
+    // decompressed file is written next to the source one
+    fn gunzip_file(gzfile: &str) -> Result<String, io::Error> {
+        let dest = format!("{}.restore.sql", BackupFormat::strip_extension(gzfile));
+        let mut decoder = GzDecoder::new(BufReader::new(File::open(long_path(Path::new(gzfile)))?));
+        let mut writer = BufWriter::new(File::create(long_path(Path::new(&dest)))?);
+        if let Err(e) = io::copy(&mut decoder, &mut writer).and_then(|_| writer.flush()) {
+            drop(writer);
+            let _ = fs::remove_file(long_path(Path::new(&dest)));
+            return Err(io::Error::new(e.kind(), format!(
+                "Error decompressing file: {}, message: {}", gzfile, e)))
+        }
+        Ok(dest)
+    }
+
+    fn run_pg_restore(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, dir: &str, ra: &PgRestoreArgs) -> Result<(), io::Error> {
+        let port = pcc.client_port()?.to_string();
+        let mut args = vec!(
+            "-v",
+            "-h", &pcc.hostname,
+            "-p", &port,
+            "-U", &pcc.username,
+            "-d", ra.physical_db(),
+            "-F", "d",
+            "-j", "1",
+            "--single-transaction",
+        );
+        if ra.options.no_owner {
+            args.push("--no-owner");
+        }
+        if ra.options.no_privileges {
+            args.push("--no-privileges");
+        }
+        args.push(dir);
+        Self::run_client_tool(progress, pcc, "pg_restore", args)
+    }
+
+    fn run_psql(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, sql_file: &str, bbf_db: &str) -> Result<(), io::Error> {
+        let port = pcc.client_port()?.to_string();
+        Self::run_client_tool(progress, pcc, "psql", vec!(
+            "-X",
+            "-v", "ON_ERROR_STOP=1",
+            "-h", &pcc.hostname,
+            "-p", &port,
+            "-U", &pcc.username,
+            "-d", bbf_db,
+            "--single-transaction",
+            "-f", sql_file
+        ))
+    }
+
+    // Roles that already exist on the server fail to be created, such errors are
+    // reported in the log and the script continues.
+    fn run_globals_sql(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, sql_file: &str, bbf_db: &str) -> Result<(), io::Error> {
+        let port = pcc.client_port()?.to_string();
+        Self::run_client_tool(progress, pcc, "psql", vec!(
+            "-X",
+            "-h", &pcc.hostname,
+            "-p", &port,
+            "-U", &pcc.username,
+            "-d", bbf_db,
+            "-f", sql_file
+        ))
+    }
+
+    fn run_client_tool(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, tool: &str, args: Vec<&str>) -> Result<(), io::Error> {
+        let cmd = pg_tool_cmd(progress, pcc, tool, args)?;
+        let reader = match cmd.reader() {
+            Ok(reader) => reader,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "{} process spawn failure: {}", tool, e)))
+        };
+        let mut buf_reader = BufReader::new(&reader);
+        loop {
+            let mut buf = vec!();
+            match buf_reader.read_until(b'\n', &mut buf) {
+                Ok(len) => {
+                    if 0 == len {
+                        break;
+                    }
+                    if buf.len() >= 2 {
+                        let ln = String::from_utf8_lossy(&buf[0..buf.len() - 2]);
+                        progress.send_value(ln);
+                    }
+                },
+                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                    "{} process failure: {}", tool, e)))
+            };
+        };
+        match reader.try_wait() {
+            Ok(opt) => match opt {
+                Some(_) => { },
+                None => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                    "{} process failure", tool)))
+            },
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!(
+                "{} process failure: {}", tool, e)))
+        }
+
+        Ok(())
+    }
+
+    // Volumes are joined next to the first one and the joined file is restored
+    // (or validated) as usual, it is removed afterwards like the extracted directory.
+    fn run_volume_set_restore(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, base: &Path) -> RestoreResult {
+        let base_st = base.to_string_lossy().to_string();
+        if long_path(base).exists() {
+            return RestoreResult::failure(format!(
+                "File with the name of the joined volumes already exists, choose it instead of the first volume: {}", base_st));
+        }
+        let required = match volume_set_size(base) {
+            Ok(size) => size,
+            Err(e) => return RestoreResult::failure(format!("{}", e))
+        };
+        if let Some(dir) = base.parent() {
+            match free_disk_space(dir) {
+                Ok(free) if free < required => return RestoreResult::failure(format!(
+                    "Not enough free disk space to join the volumes in directory: {}, free: {}, required: {}",
+                    dir.to_string_lossy(), format_disk_size(free), format_disk_size(required))),
+                Ok(_) => { },
+                Err(e) => progress.send_value(format!("Warning: error checking free disk space: {}", e))
+            }
+        }
+        progress.send_value(format!("Joining volumes into file: {} ...", base_st));
+        if let Err(e) = join_volumes(base, Throttle::new(ra.throttle_mb_per_sec), |en| progress.send_value(en)) {
+            return RestoreResult::failure(format!(
+                "Error joining volumes, file: {}, message: {}", base_st, e));
+        }
+
+        let mut joined = ra.clone();
+        joined.src_path = base_st.clone();
+        let res = Self::run_restore(progress, pcc, &joined);
+
+        if ra.options.keep_temp_dir {
+            progress.send_value(format!("Keeping joined file: {}", base_st));
+        } else if let Err(e) = fs::remove_file(long_path(base)) {
+            progress.send_value(format!(
+                "Warning: error removing joined file: {}, message: {}", base_st, e));
+        }
+        res
+    }
+
+    // also used by copy dialog to restore into the target server
+    pub fn run_restore(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> RestoreResult {
+        if let Some(base) = volume_set_base(Path::new(&ra.src_path)) {
+            return Self::run_volume_set_restore(progress, pcc, ra, &base);
+        }
+        if ra.validate_only {
+            return Self::run_validation(progress, pcc, ra);
+        }
+        if !ra.ddl_file.is_empty() {
+            return Self::run_ddl_export(progress, pcc, ra);
+        }
+        if let Some(pa) = &ra.pitr {
+            return match pitr::run_pitr_restore(progress, pa) {
+                Ok(_) => RestoreResult::success(),
+                Err(e) => RestoreResult::failure(e)
+            };
+        }
+        progress.send_value(format!("Running restore into DB: {} ...", ra.dest_db_name));
+
+        // lingering sessions, terminated before the existing DB is dropped
+        if ra.options.terminate_connections {
+            Self::terminate_connections(progress, pcc, ra);
+        }
+
+        // single-db mode check
+        let migration_mode = match Self::check_migration_mode(progress, pcc, ra) {
+            Ok(mode) => mode,
+            Err(e) => return RestoreResult::failure(format!("{}", e))
+        };
+        // dump rewrite and DB roles depend on the server mode
+        let ra = &PgRestoreArgs {
+            migration_mode: migration_mode.clone(),
+            ..ra.clone()
+        };
+
+        // db check
+        if let Err(e) = Self::prepare_target_db(progress, pcc, ra) {
+            return RestoreResult::failure(format!("{}", e))
+        }
+
+        // plain SQL dumps are restored with psql
+        let src_format = BackupFormat::detect(Path::new(&ra.src_path));
+        if src_format.is_plain_sql() {
+            return Self::run_plain_sql_restore(progress, pcc, ra, src_format);
+        }
+
+        // unzip
+        let src_is_dir = BackupFormat::Directory == src_format;
+        let dir = if src_is_dir {
+            progress.send_value(format!("Using unzipped dump directory: {} ...", &ra.src_path));
+            if !Path::new(&ra.src_path).join("toc.dat").is_file() {
+                return RestoreResult::failure(format!(
+                    "Directory does not contain a dump, 'toc.dat' not found: {}", &ra.src_path))
+            }
+            match Self::link_dump_dir(progress, ra) {
+                Ok(dir) => dir,
+                Err(e) => return RestoreResult::failure(format!("{}", e))
+            }
+        } else if BackupFormat::Tar == src_format {
+            progress.send_value(format!("Extracting tar file: {} ...", &ra.src_path));
+            match Self::untar_file(progress, &ra.src_path) {
+                Ok(dir) => dir,
+                Err(e) => return RestoreResult::failure(format!("{}", e))
+            }
+        } else {
+            // TOC is rewritten during unzip
+            progress.send_value(format!("Unzipping file: {} ...", &ra.src_path));
+            match Self::unzip_file(progress, ra) {
+                Ok(dir) => dir,
+                Err(e) => return RestoreResult::failure(format!("{}", e))
+            }
+        };
+        // cross-mode restore is checked before pg_restore, zip files are already rewritten
+        if let Err(e) = Self::check_dump_migration_mode(progress, ra, &long_path(Path::new(&dir))) {
+            return RestoreResult::failure(format!("{}", e))
+        }
+        if BackupFormat::Zip != src_format {
+            if let Err(e) = Self::merge_chained_data(progress, ra, &long_path(Path::new(&dir))) {
+                return RestoreResult::failure(format!("{}", e))
+            }
+            if let Err(e) = Self::rewrite_dump(progress, ra, &long_path(Path::new(&dir))) {
+                return RestoreResult::failure(format!("{}", e))
+            }
+        }
+
+        // settings differences are only reported
+        Self::compare_server_settings(progress, pcc, ra, &dir);
+
+        // logins from the backup
+        if ra.restore_globals {
+            let globals_file = Path::new(&dir).join(GLOBALS_FILE_NAME);
+            if long_path(&globals_file).is_file() {
+                progress.send_value("Restoring logins ...");
+                if let Err(e) = Self::run_globals_sql(progress, pcc, &globals_file.to_string_lossy(), Self::server_db(pcc, ra)) {
+                    return RestoreResult::failure(format!("{}", e))
+                }
+            } else {
+                progress.send_value(format!("Warning: logins file not found: {}", globals_file.to_string_lossy()));
+            }
+        }
+
+        // global data
+        let roles = match Self::prepare_global_data(progress, pcc, ra) {
+            Ok(roles) => roles,
+            Err(e) => return RestoreResult::failure(format!("{}", e))
+        };
+
+        // run restore
+        progress.send_value("Running pg_restore ...");
+        if let Err(e) = Self::run_pg_restore(progress, pcc, &dir, ra) {
+            Self::cleanup_created_roles(progress, pcc, ra, &roles);
+            Self::cleanup_created_db(progress, pcc, ra);
+            return RestoreResult::failure(format!("{}", e))
+        };
+
+        // verification, manifest is read before the extracted directory is removed
+        if ra.options.verify_row_counts {
+            Self::verify_row_counts(progress, pcc, ra, &dir);
+        }
+
+        // clean up, directory chosen by user is left in place
+        if ra.options.keep_temp_dir {
+            progress.send_value(format!("Keeping extracted directory: {}", dir));
+        } else {
+            progress.send_value("Cleaning up temp directory ...");
+            if let Err(e) = fs::remove_dir_all(long_path(Path::new(&dir))) {
+                progress.send_value(format!(
+                    "Warning: error removing tem directory: {}, message: {}", dir, e));
+            };
+        }
+
+        // statistics
+        if ra.options.analyze {
+            Self::analyze_restored_db(progress, pcc, ra);
+        }
+
+        progress.send_value("Restore complete");
+        RestoreResult::success()
+    }
+
+    fn run_plain_sql_restore(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, format: BackupFormat) -> RestoreResult {
+        // SQL script is passed to psql as is, there is no TOC to rewrite
+        if !ra.options.schema_mapping.is_empty() {
+            return RestoreResult::failure(
+                "Renaming schemas is not supported for plain SQL backups".to_string())
+        }
+        if !ra.options.owner_mapping.is_empty() {
+            return RestoreResult::failure(
+                "Remapping owners is not supported for plain SQL backups".to_string())
+        }
+        if ra.options.no_owner || ra.options.no_privileges {
+            return RestoreResult::failure(
+                "Skipping owners and privileges is not supported for plain SQL backups".to_string())
+        }
+        progress.send_value(
+            "Plain SQL backup, DB name is not updated, objects are restored under their original names");
+
+        // decompress
+        let sql_file = if BackupFormat::PlainSqlGzip == format {
+            progress.send_value(format!("Decompressing file: {} ...", &ra.src_path));
+            match Self::gunzip_file(&ra.src_path) {
+                Ok(file) => file,
+                Err(e) => return RestoreResult::failure(format!("{}", e))
+            }
+        } else {
+            ra.src_path.clone()
+        };
+        let sql_file_is_temp = sql_file != ra.src_path;
+
+        // global data
+        let roles = match Self::prepare_global_data(progress, pcc, ra) {
+            Ok(roles) => roles,
+            Err(e) => return RestoreResult::failure(format!("{}", e))
+        };
+
+        // run restore
+        progress.send_value("Running psql ...");
+        let res = Self::run_psql(progress, pcc, &sql_file, ra.physical_db());
+
+        // clean up, file chosen by user is left in place
+        if sql_file_is_temp {
+            if ra.options.keep_temp_dir {
+                progress.send_value(format!("Keeping decompressed file: {}", sql_file));
+            } else {
+                progress.send_value("Cleaning up temp file ...");
+                if let Err(e) = fs::remove_file(long_path(Path::new(&sql_file))) {
+                    progress.send_value(format!(
+                        "Warning: error removing temp file: {}, message: {}", sql_file, e));
+                }
+            }
+        }
+
+        if let Err(e) = res {
+            Self::cleanup_created_roles(progress, pcc, ra, &roles);
+            Self::cleanup_created_db(progress, pcc, ra);
+            return RestoreResult::failure(format!("{}", e))
+        }
+
+        // statistics
+        if ra.options.analyze {
+            Self::analyze_restored_db(progress, pcc, ra);
+        }
+
+        progress.send_value("Restore complete");
+        RestoreResult::success()
+    }
+
+    // Server is only queried, archive catalog is rewritten in a temp copy,
+    // problems are collected and reported together at the end.
+    fn run_validation(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> RestoreResult {
+        progress.send_value(format!("Validating restore into DB: {} ...", ra.dest_db_name));
+        let mut problems: Vec<String> = Vec::new();
+
+        // db check
+        progress.send_value("Checking target DB ...");
+        let find = || Self::find_existing_db(pcc, ra);
+        match pcc.retry_policy().run("Target DB check", |en| progress.send_value(en), find) {
+            Ok(None) => progress.send_value("Target DB does not exist"),
+            Ok(Some(name)) if ra.options.replace_existing => progress.send_value(format!(
+                "Existing DB will be dropped before restore: {}", name)),
+            Ok(Some(name)) => problems.push(format!("Database with name '{}' already exists", name)),
+            Err(e) => return RestoreResult::failure(format!("{}", e))
+        }
+
+        // migration mode check
+        progress.send_value("Checking migration mode ...");
+        let migration_mode = match Self::check_migration_mode(progress, pcc, ra) {
+            Ok(mode) => mode,
+            Err(e) => {
+                problems.push(format!("{}", e));
+                String::new()
+            }
+        };
+        let ra = &PgRestoreArgs {
+            migration_mode,
+            ..ra.clone()
+        };
+
+        // role check
+        progress.send_value("Checking roles ...");
+        if let Err(e) = Self::check_roles(progress, pcc, ra, &mut problems) {
+            return RestoreResult::failure(format!("{}", e))
+        }
+
+        // archive check
+        let src_format = BackupFormat::detect(Path::new(&ra.src_path));
+        if src_format.is_plain_sql() {
+            if !ra.options.schema_mapping.is_empty() || !ra.options.owner_mapping.is_empty() {
+                problems.push("Remapping schemas or owners is not supported for plain SQL backups".to_string());
+            }
+            if ra.options.no_owner || ra.options.no_privileges {
+                problems.push("Skipping owners and privileges is not supported for plain SQL backups".to_string());
+            }
+            progress.send_value("Plain SQL backup, script contents are not validated");
+        } else if let Err(e) = Self::validate_dump(progress, pcc, ra, src_format) {
+            problems.push(format!("{}", e));
+        }
+
+        // earlier backups with table data of an incremental or differential backup
+        match BackupManifest::read_path(&long_path(Path::new(&ra.src_path))) {
+            Ok(Some(manifest)) if manifest.is_chained() => {
+                progress.send_value(format!("{} backup, checking earlier backups, base: {} ...",
+                    manifest.mode.label(), manifest.base_backup));
+                let backup_dir = long_path(Path::new(&ra.src_path)).parent().map(|dir| dir.to_path_buf()).unwrap_or_default();
+                let sources: BTreeSet<&String> = manifest.data_sources.values().collect();
+                for name in sources {
+                    if !backup_dir.join(name).exists() {
+                        problems.push(format!("Backup with table data of the {} backup not found: {}",
+                            manifest.mode.label().to_lowercase(), name));
+                    }
+                }
+            },
+            Ok(_) => { },
+            Err(e) => progress.send_value(format!("Warning: error reading backup manifest: {}", e))
+        }
+
+        if !problems.is_empty() {
+            for pr in problems.iter() {
+                progress.send_value(format!("ERROR: {}", pr));
+            }
+            return RestoreResult::failure(format!(
+                "Validation found problems: {}, restore would likely fail", problems.len()))
+        }
+        progress.send_value("Validation complete, no problems found, restore would likely succeed");
+        RestoreResult::success()
+    }
+
+    // connecting user needs to create "{dbname}_*" roles, mapped owners must exist
+    fn check_roles(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, problems: &mut Vec<String>) -> Result<(), PgAccessError> {
+        let mut client = pcc.open_connection_to_db(Self::server_db(pcc, ra))?;
+        if ra.is_plain_pg() {
+            // no roles are created, target DB is created instead
+            let rs = client.query("select rolsuper or rolcreatedb as can_create from pg_catalog.pg_roles where rolname = current_user", &[])?;
+            let can_create = rs.iter().next().map(|row| row.get::<_, bool>("can_create")).unwrap_or(false);
+            if !can_create {
+                problems.push(format!("User '{}' is not allowed to create databases", pcc.username));
+            }
+        } else {
+            let rs = client.query("select rolsuper or rolcreaterole as can_create from pg_catalog.pg_roles where rolname = current_user", &[])?;
+            let can_create = rs.iter().next().map(|row| row.get::<_, bool>("can_create")).unwrap_or(false);
+            if !can_create {
+                problems.push(format!("User '{}' is not allowed to create roles", pcc.username));
+            }
+            for role in vec!(
+                "db_owner",
+                "dbo",
+                "guest"
+            ) {
+                let rolename = Self::physical_role_name(ra, role);
+                let rs = client.query("select 1 from pg_catalog.pg_roles where rolname = $1", &[&rolename])?;
+                if !rs.is_empty() {
+                    progress.send_value(format!("Role already exists and will be reused: {}", rolename));
+                }
+            }
+        }
+        for (_, to) in ra.options.owner_mapping.iter() {
+            let rs = client.query("select 1 from pg_catalog.pg_roles where rolname = $1", &[to])?;
+            if rs.is_empty() {
+                problems.push(format!("Mapped owner role does not exist: {}", to));
+            }
+        }
+        client.close()?;
+        Ok(())
+    }
+
+    // only TOC and catalog data files are copied into a temp dir, rewritten copy is checked with pg_restore
+    fn validate_dump(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, src_format: BackupFormat) -> Result<(), io::Error> {
+        Self::in_temp_dir(progress, "wdb_validate", |work_dir| {
+            Self::validate_dump_in_dir(progress, pcc, ra, src_format, work_dir)
+        })
+    }
+
+    fn in_temp_dir<F>(progress: &ui::SyncNoticeValueSender<String>, prefix: &str, fun: F) -> Result<(), io::Error>
+    where
+        F: FnOnce(&Path) -> Result<(), io::Error>
+    {
+        let work_dir = env::temp_dir().join(format!("{}_{}", prefix, chrono::Local::now().format("%Y%m%d_%H%M%S")));
+        fs::create_dir_all(&work_dir)?;
+        let res = fun(&work_dir);
+        if let Err(e) = fs::remove_dir_all(long_path(&work_dir)) {
+            progress.send_value(format!(
+                "Warning: error removing temp directory: {}, message: {}", work_dir.to_string_lossy(), e));
+        }
+        res
+    }
+
+    // Data files are not needed to read the TOC, catalog tables are
+    // extracted too, so the copy can be rewritten.
+    fn extract_dump_catalog(progress: &ui::SyncNoticeValueSender<String>, ra: &PgRestoreArgs, src_format: BackupFormat, work_dir: &Path) -> Result<PathBuf, io::Error> {
+        let listener = |en: &str| {
+            progress.send_value(en);
+        };
+        let src_path = long_path(Path::new(&ra.src_path));
+        if BackupFormat::Directory == src_format {
+            progress.send_value(format!("Copying dump catalog: {} ...", &ra.src_path));
+            if !src_path.join("toc.dat").is_file() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                    "Directory does not contain a dump, 'toc.dat' not found: {}", &ra.src_path)))
+            }
+            let dir = work_dir.join("dump");
+            pg_toc::copy_dump_catalog(&src_path, &long_path(&dir))?;
+            Ok(dir)
+        } else if BackupFormat::Tar == src_format {
+            progress.send_value(format!("Extracting tar file: {} ...", &ra.src_path));
+            let dir = work_dir.join("dump");
+            pg_toc::extract_tar_dump(&src_path, &long_path(&dir), listener)?;
+            Ok(dir)
+        } else {
+            pg_toc::check_zip_dump(&src_path)?;
+            progress.send_value(format!("Extracting dump catalog: {} ...", &ra.src_path));
+            pg_toc::extract_zip_catalog(&src_path, &long_path(work_dir), listener)
+        }
+    }
+
+    fn validate_dump_in_dir(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, src_format: BackupFormat, work_dir: &Path) -> Result<(), io::Error> {
+        let dir = Self::extract_dump_catalog(progress, ra, src_format, work_dir)?;
+        Self::check_dump_migration_mode(progress, ra, &long_path(&dir))?;
+        Self::rewrite_dump(progress, ra, &long_path(&dir))?;
+
+        // TOC is read by pg_restore, list is written to file to keep the log short
+        progress.send_value("Running pg_restore --list ...");
+        let list_file = work_dir.join("toc_list.txt");
+        let dir_st = dir.to_string_lossy().to_string();
+        let list_st = list_file.to_string_lossy().to_string();
+        Self::run_client_tool(progress, pcc, "pg_restore", vec!(
+            "--list",
+            "-F", "d",
+            "-f", &list_st,
+            &dir_st
+        ))?;
+        let list = fs::read_to_string(long_path(&list_file))?;
+        let count = list.lines()
+            .filter(|ln| !ln.trim().is_empty() && !ln.starts_with(';'))
+            .count();
+        progress.send_value(format!("Dump TOC is readable, entries to restore: {}", count));
+        Ok(())
+    }
+
+    // pg_restore prints object definitions from the TOC without connecting to a server
+    fn run_ddl_export(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) -> RestoreResult {
+        progress.send_value(format!("Exporting schema DDL into file: {} ...", ra.ddl_file));
+        let src_format = BackupFormat::detect(Path::new(&ra.src_path));
+        if src_format.is_plain_sql() {
+            return RestoreResult::failure(
+                "Plain SQL backup is already a script, DDL export is supported for zip, directory and tar backups".to_string());
+        }
+        let res = Self::in_temp_dir(progress, "wdb_ddl", |work_dir| {
+            let dir = Self::extract_dump_catalog(progress, ra, src_format, work_dir)?;
+            progress.send_value("Running pg_restore --schema-only ...");
+            let dir_st = dir.to_string_lossy().to_string();
+            Self::run_client_tool(progress, pcc, "pg_restore", vec!(
+                "--schema-only",
+                "-F", "d",
+                "-f", &ra.ddl_file,
+                &dir_st
+            ))
+        });
+        if let Err(e) = res {
+            return RestoreResult::failure(format!("{}", e));
+        }
+        if !Path::new(&ra.ddl_file).is_file() {
+            return RestoreResult::failure(format!(
+                "DDL script was not written by pg_restore: {}", ra.ddl_file));
+        }
+        progress.send_value(format!("DDL script written: {}", ra.ddl_file));
+        RestoreResult::success()
+    }
+
+    // whole DB is restored on plain Postgres, only system schemas are skipped
+    fn list_restored_tables(pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<Vec<(String, String)>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(ra.physical_db())?;
+        let rs = if ra.is_plain_pg() {
+            client.query("
+                select n.nspname::text as nspname, c.relname::text as relname
+                from pg_catalog.pg_class c
+                join pg_catalog.pg_namespace n on n.oid = c.relnamespace
+                where n.nspname not in ('pg_catalog', 'information_schema')
+                and n.nspname not like 'pg\\_%'
+                and c.relkind in ('r', 'p', 'm')
+                order by n.nspname, c.relname", &[])?
+        } else {
+            client.query("
+                select n.nspname::text as nspname, c.relname::text as relname
+                from pg_catalog.pg_class c
+                join pg_catalog.pg_namespace n on n.oid = c.relnamespace
+                join sys.babelfish_namespace_ext ne on ne.nspname = n.nspname
+                join sys.babelfish_sysdatabases d on d.dbid = ne.dbid
+                where lower(d.name) = lower($1)
+                and c.relkind in ('r', 'p', 'm')
+                order by n.nspname, c.relname", &[&ra.dest_db_name])?
+        };
+        let res = rs.iter().map(|row| (row.get("nspname"), row.get("relname"))).collect();
+        client.close()?;
+        Ok(res)
+    }
+
+    // Same as "sp_updatestats", errors are reported as warnings because
+    // the data is already restored.
+    fn analyze_restored_db(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) {
+        progress.send_value("Updating statistics ...");
+        let tables = match Self::list_restored_tables(pcc, ra) {
+            Ok(tables) => tables,
+            Err(e) => {
+                progress.send_value(format!("Warning: error listing restored tables: {}", e));
+                return;
+            }
+        };
+        if tables.is_empty() {
+            progress.send_value("No tables found to analyze");
+            return;
+        }
+        let mut client = match pcc.open_connection_to_db(ra.physical_db()) {
+            Ok(client) => client,
+            Err(e) => {
+                progress.send_value(format!("Warning: error updating statistics: {}", e));
+                return;
+            }
+        };
+        let mut failed = 0;
+        for (idx, (schema, table)) in tables.iter().enumerate() {
+            progress.send_value(format!("Analyzing table: {}.{} ({}/{})", schema, table, idx + 1, tables.len()));
+            let sql = format!("ANALYZE \"{}\".\"{}\"", schema.replace('"', "\"\""), table.replace('"', "\"\""));
+            if let Err(e) = client.batch_execute(&sql) {
+                failed += 1;
+                progress.send_value(format!("Warning: error analyzing table: {}.{}, message: {}", schema, table, e));
+            }
+        }
+        let _ = client.close();
+        progress.send_value(format!("Statistics updated, tables: {}, failed: {}", tables.len() - failed, failed));
+    }
+
+    fn read_server_settings(pcc: &PgConnConfig, ra: &PgRestoreArgs) -> Result<BTreeMap<String, String>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(Self::server_db(pcc, ra))?;
+        let settings = babelfish_settings(&mut client)?;
+        client.close()?;
+        Ok(settings)
+    }
+
+    // Escape hatches and other Babelfish settings change T-SQL behaviour,
+    // differences with the source server are reported as warnings.
+    fn compare_server_settings(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, dir: &str) {
+        let manifest = match BackupManifest::read(&long_path(Path::new(dir))) {
+            Ok(Some(manifest)) if !manifest.settings.is_empty() => manifest,
+            Ok(_) => return,
+            Err(e) => {
+                progress.send_value(format!("Warning: {}", e));
+                return;
+            }
+        };
+        progress.send_value("Comparing server settings ...");
+        let server = match Self::read_server_settings(pcc, ra) {
+            Ok(settings) => settings,
+            Err(e) => {
+                progress.send_value(format!("Warning: error reading server settings: {}", e));
+                return;
+            }
+        };
+        let diff = manifest.settings_diff(&server);
+        for (name, backup_value, server_value) in diff.iter() {
+            progress.send_value(format!("WARNING: server setting differs: {}, backup: '{}', this server: '{}'",
+                name, backup_value, server_value));
+        }
+        if diff.is_empty() {
+            progress.send_value("Server settings match the source server");
+        } else {
+            progress.send_value(format!("Server settings differ from the source server: {}, T-SQL behaviour may change", diff.len()));
+        }
+    }
+
+    fn count_restored_rows(pcc: &PgConnConfig, ra: &PgRestoreArgs, tables: &Vec<(String, String)>) -> Result<Vec<Result<i64, String>>, PgAccessError> {
+        let mut client = pcc.open_connection_to_db(ra.physical_db())?;
+        let mut res = Vec::new();
+        for (schema, table) in tables.iter() {
+            let sql = format!("select count(*) as cnt from \"{}\".\"{}\"", schema.replace('"', "\"\""), table.replace('"', "\"\""));
+            match client.query_one(&sql, &[]) {
+                Ok(row) => res.push(Ok(row.get("cnt"))),
+                Err(e) => res.push(Err(e.to_string()))
+            }
+        }
+        client.close()?;
+        Ok(res)
+    }
+
+    // Tables are located in the rewritten TOC by dump ID, mismatches are
+    // reported in the details, restore itself is not failed.
+    fn verify_row_counts(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, dir: &str) {
+        progress.send_value("Verifying row counts ...");
+        let dir_path = long_path(Path::new(dir));
+        let manifest = match BackupManifest::read(&dir_path) {
+            Ok(Some(manifest)) => manifest,
+            Ok(None) => {
+                progress.send_value("Backup does not contain row counts, verification skipped");
+                return;
+            },
+            Err(e) => {
+                progress.send_value(format!("Warning: {}", e));
+                return;
+            }
+        };
+        let toc = match pg_toc::TocArchive::read_file(&dir_path.join("toc.dat")) {
+            Ok(toc) => toc,
+            Err(e) => {
+                progress.send_value(format!("Warning: error reading dump TOC: {}", e));
+                return;
+            }
+        };
+        let mut expected = Vec::new();
+        let mut tables = Vec::new();
+        for tc in manifest.tables.iter() {
+            let en = toc.entries.iter().find(|en| en.dump_id == tc.dump_id);
+            if let Some((Some(schema), Some(table))) = en.map(|en| (en.namespace.clone(), en.tag.clone())) {
+                expected.push(tc.rows);
+                tables.push((schema, table));
+            } else {
+                progress.send_value(format!("WARNING: table not found in dump TOC: {}.{}", tc.schema, tc.table));
+            }
+        }
+        let actual = match Self::count_restored_rows(pcc, ra, &tables) {
+            Ok(counts) => counts,
+            Err(e) => {
+                progress.send_value(format!("Warning: error counting restored rows: {}", e));
+                return;
+            }
+        };
+        let mut mismatches = 0;
+        for (((schema, table), exp), act) in tables.iter().zip(expected.iter()).zip(actual.iter()) {
+            match act {
+                Ok(count) if count == exp => { },
+                Ok(count) => {
+                    mismatches += 1;
+                    progress.send_value(format!("WARNING: row count mismatch, table: {}.{}, backup: {}, restored: {}",
+                        schema, table, exp, count));
+                },
+                Err(e) => {
+                    mismatches += 1;
+                    progress.send_value(format!("WARNING: error counting rows, table: {}.{}, message: {}", schema, table, e));
+                }
+            }
+        }
+        progress.send_value(format!("Row counts verified, tables: {}, mismatches: {}", tables.len(), mismatches));
+    }
+
+    // empty DB is left by a failed restore into plain Postgres
+    fn cleanup_created_db(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs) {
+        if !ra.is_plain_pg() {
+            return;
+        }
+        progress.send_value(format!("Error: restore failed, dropping DB we created: {}", ra.dest_db_name));
+        match Self::drop_db(pcc, ra, &ra.dest_db_name) {
+            Ok(_) => progress.send_value("DB cleanup complete"),
+            Err(e) => progress.send_value(format!(
+                "Error dropping DB: {}", e))
+        }
+    }
+
+    fn cleanup_created_roles(progress: &ui::SyncNoticeValueSender<String>, pcc: &PgConnConfig, ra: &PgRestoreArgs, roles: &Vec<String>) {
+        if roles.len() > 0 {
+            progress.send_value(format!(
+                "Error: restore failed, cleaning up global roles we created: {}", roles.join(", ")));
+            match Self::drop_created_roles(pcc, &ra.bbf_db_name, roles) {
+                Ok(_) => progress.send_value("Global roles cleanup complete"),
+                Err(e) => progress.send_value(format!(
+                    "Error cleaning up global roles: {}", e))
+            }
+        }
+    }
+}
+
+impl ui::PopupDialog<RestoreDialogArgs, RestoreDialogResult> for RestoreDialog {
+    fn popup(args: RestoreDialogArgs) -> ui::PopupJoinHandle<RestoreDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        if self.args.pg_restore_args.validate_only {
+            self.c.window.set_text("Validate restore");
+            self.c.label.set_text(self.running_label());
+        } else if !self.args.pg_restore_args.ddl_file.is_empty() {
+            self.c.window.set_text("Export DDL script");
+            self.c.label.set_text(self.running_label());
+        } else if self.args.pg_restore_args.pitr.is_some() {
+            self.c.window.set_text("Point-in-time restore");
+            self.c.label.set_text(self.running_label());
+        }
+        self.taskbar_progress = TaskbarProgress::new(&self.c.window);
+        self.start_command();
+    }
+
+    fn result(&mut self) -> RestoreDialogResult {
+        self.dialog_result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.c.tray.set_visibility(false);
+        if let Some(taskbar) = &self.taskbar_progress {
+            taskbar.clear();
+        }
+        self.args.send_notice();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: nwg::EventData) {
+        self.c.update_tab_order();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(pid: i32, usename: &str, locked: &[&str]) -> (i32, String, String, Vec<String>) {
+        (pid, usename.to_string(), "ssms".to_string(), locked.iter().map(|st| st.to_string()).collect())
+    }
+
+    #[test]
+    fn conflicting_sessions_of_db_with_prefix_sibling() {
+        let databases = vec!((1, "master".to_string()), (5, "mydb".to_string()), (6, "mydb_sales".to_string()));
+        let namespaces = vec!(
+            (1, "master_dbo".to_string()),
+            (5, "mydb_dbo".to_string()),
+            // T-SQL schema "sales" of "mydb"
+            (5, "mydb_sales".to_string()),
+            (6, "mydb_sales_dbo".to_string()),
+        );
+        let (roles, schemas) = RestoreDialog::target_db_objects("MyDb", &databases, &namespaces).unwrap();
+        assert_eq!(roles, vec!("mydb_dbo", "mydb_db_owner", "mydb_guest"));
+        assert_eq!(schemas, vec!("mydb_dbo", "mydb_sales"));
+
+        let sessions = vec!(
+            session(101, "mydb_dbo", &[]),
+            session(102, "mydb_sales_dbo", &[]),
+            session(103, "mydb_sales_db_owner", &["mydb_sales_dbo"]),
+            session(104, "jdoe", &["mydb_sales"]),
+            session(105, "jdoe", &["master_dbo"]),
+        );
+        let pids: Vec<i32> = RestoreDialog::filter_conflicting_sessions(sessions, &roles, &schemas).iter()
+            .map(|(pid, _, _)| *pid)
+            .collect();
+        assert_eq!(pids, vec!(101, 104));
+    }
+
+    #[test]
+    fn no_conflicting_sessions_of_missing_db() {
+        let databases = vec!((1, "master".to_string()), (6, "mydb_sales".to_string()));
+        let namespaces = vec!((6, "mydb_sales_dbo".to_string()));
+        assert!(RestoreDialog::target_db_objects("mydb", &databases, &namespaces).is_none());
+    }
+}
@@ -1,47 +1,69 @@
-/*
- * Copyright 2023, WiltonDB Software
- *
- * Licensed under the Apache License, Version 2.0 (the "License");
- * you may not use this file except in compliance with the License.
- * You may obtain a copy of the License at
- *
- * http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
-
-mod args;
-mod controls;
-mod dialog;
-mod events;
-mod layout;
-mod nui;
-mod result;
-
-use std::thread;
-use std::time::Duration;
-use std::time::Instant;
-
-use clipboard_win::formats;
-use clipboard_win::set_clipboard;
-use nwg::NativeUi;
-
-use crate::*;
-use common::PgConnConfig;
-use nwg_ui as ui;
-use ui::Controls;
-use ui::Events;
-use ui::Layout;
-use ui::PopupDialog;
-
-pub use args::RestoreDialogArgs;
-pub(self) use controls::RestoreDialogControls;
-pub use dialog::RestoreDialog;
-use events::RestoreDialogEvents;
-use layout::RestoreDialogLayout;
-pub use result::RestoreDialogResult;
-use result::RestoreResult;
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod args;
+mod controls;
+mod dialog;
+mod events;
+mod layout;
+mod nui;
+mod result;
+
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use clipboard_win::formats;
+use clipboard_win::set_clipboard;
+use nwg::NativeUi;
+
+use crate::*;
+use common::babelfish_settings;
+use common::BackupFormat;
+use common::BackupManifest;
+use common::DetailsLog;
+use common::format_disk_size;
+use common::free_disk_space;
+use common::GLOBALS_FILE_NAME;
+use common::join_volumes;
+use common::long_path;
+use common::PgConnConfig;
+use common::pg_tool_cmd;
+use common::ProgressBuffer;
+use common::ProgressSender;
+use common::RunForecast;
+use common::show_toast;
+use common::TaskbarProgress;
+use pg_toc::Throttle;
+use common::volume_set_base;
+use common::volume_set_size;
+use common::window_in_background;
+use pitr::PitrArgs;
+use nwg_ui as ui;
+use ui::Controls;
+use ui::Events;
+use ui::Layout;
+use ui::PopupDialog;
+
+pub use args::PgRestoreArgs;
+pub use args::RestoreDialogArgs;
+pub use args::RestoreOptions;
+pub(self) use controls::RestoreDialogControls;
+pub use dialog::RestoreDialog;
+use events::RestoreDialogEvents;
+use layout::RestoreDialogLayout;
+pub use result::RestoreDialogResult;
+pub use result::RestoreResult;
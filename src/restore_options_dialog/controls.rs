@@ -0,0 +1,227 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct RestoreOptionsDialogControls {
+    layout: RestoreOptionsDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) schema_mapping_label: nwg::Label,
+    pub(super) schema_mapping_view: nwg::ListView,
+    pub(super) schema_from_input: nwg::TextInput,
+    pub(super) schema_to_input: nwg::TextInput,
+    pub(super) schema_add_button: nwg::Button,
+    pub(super) schema_remove_button: nwg::Button,
+    pub(super) owner_mapping_label: nwg::Label,
+    pub(super) owner_mapping_view: nwg::ListView,
+    pub(super) owner_from_input: nwg::TextInput,
+    pub(super) owner_to_input: nwg::TextInput,
+    pub(super) owner_add_button: nwg::Button,
+    pub(super) owner_remove_button: nwg::Button,
+    pub(super) no_owner_checkbox: nwg::CheckBox,
+    pub(super) no_privileges_checkbox: nwg::CheckBox,
+    pub(super) terminate_connections_checkbox: nwg::CheckBox,
+    pub(super) replace_existing_checkbox: nwg::CheckBox,
+    pub(super) analyze_checkbox: nwg::CheckBox,
+    pub(super) verify_row_counts_checkbox: nwg::CheckBox,
+    pub(super) keep_temp_dir_checkbox: nwg::CheckBox,
+
+    pub(super) ok_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for RestoreOptionsDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((480, 700))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Restore options")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Rename schemas:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.schema_mapping_label)?;
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.window)
+            .build(&mut self.schema_mapping_view)?;
+        self.schema_mapping_view.insert_column("Schema");
+        self.schema_mapping_view.insert_column("Rename to");
+        self.schema_mapping_view.set_column_width(0, 200);
+        self.schema_mapping_view.set_column_width(1, 200);
+        self.schema_mapping_view.set_headers_enabled(true);
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Schema"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.schema_from_input)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Rename to"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.schema_to_input)?;
+        nwg::Button::builder()
+            .text("Add")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.schema_add_button)?;
+        nwg::Button::builder()
+            .text("Remove")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.schema_remove_button)?;
+        nwg::Label::builder()
+            .text("Remap owners to existing roles:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.owner_mapping_label)?;
+        nwg::ListView::builder()
+            .list_style(nwg::ListViewStyle::Detailed)
+            .ex_flags(nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT)
+            .parent(&self.window)
+            .build(&mut self.owner_mapping_view)?;
+        self.owner_mapping_view.insert_column("Owner");
+        self.owner_mapping_view.insert_column("Target role");
+        self.owner_mapping_view.set_column_width(0, 200);
+        self.owner_mapping_view.set_column_width(1, 200);
+        self.owner_mapping_view.set_headers_enabled(true);
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Owner"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.owner_from_input)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("Target role"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.owner_to_input)?;
+        nwg::Button::builder()
+            .text("Add")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.owner_add_button)?;
+        nwg::Button::builder()
+            .text("Remove")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.owner_remove_button)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Do not restore owners, objects are owned by the connecting user")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.no_owner_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Do not restore privileges (GRANT/REVOKE)")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.no_privileges_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Terminate connections using target DB roles and schemas")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.terminate_connections_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Drop and replace existing database with the same name")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.replace_existing_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Update statistics (ANALYZE) after restore")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.analyze_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Verify row counts against the backup after restore")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.verify_row_counts_checkbox)?;
+        nwg::CheckBox::builder()
+            .check_state(nwg::CheckBoxState::Unchecked)
+            .text("Keep extracted files after restore (for debugging)")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.keep_temp_dir_checkbox)?;
+
+        nwg::Button::builder()
+            .text("OK")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.ok_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.schema_mapping_view)
+            .control(&self.schema_from_input)
+            .control(&self.schema_to_input)
+            .control(&self.schema_add_button)
+            .control(&self.schema_remove_button)
+            .control(&self.owner_mapping_view)
+            .control(&self.owner_from_input)
+            .control(&self.owner_to_input)
+            .control(&self.owner_add_button)
+            .control(&self.owner_remove_button)
+            .control(&self.no_owner_checkbox)
+            .control(&self.no_privileges_checkbox)
+            .control(&self.terminate_connections_checkbox)
+            .control(&self.replace_existing_checkbox)
+            .control(&self.analyze_checkbox)
+            .control(&self.verify_row_counts_checkbox)
+            .control(&self.keep_temp_dir_checkbox)
+            .control(&self.ok_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct RestoreOptionsDialog {
+    pub(super) c: RestoreOptionsDialogControls,
+
+    args: RestoreOptionsDialogArgs,
+    schema_mapping: Vec<(String, String)>,
+    owner_mapping: Vec<(String, String)>,
+    result: RestoreOptionsDialogResult,
+}
+
+impl RestoreOptionsDialog {
+    pub(super) fn add_schema_mapping(&mut self, _: nwg::EventData) {
+        let from = self.c.schema_from_input.text().trim().to_string();
+        let to = self.c.schema_to_input.text().trim().to_string();
+        if from.is_empty() || to.is_empty() {
+            return;
+        }
+        if let Err(msg) = self.check_schema_mapping(&from, &to) {
+            nwg::modal_error_message(&self.c.window, "Restore options", &msg);
+            return;
+        }
+        self.schema_mapping.push((from, to));
+        self.c.schema_from_input.set_text("");
+        self.c.schema_to_input.set_text("");
+        self.reload_schema_mapping_view();
+    }
+
+    pub(super) fn remove_schema_mapping(&mut self, _: nwg::EventData) {
+        if let Some(idx) = self.c.schema_mapping_view.selected_item() {
+            if idx < self.schema_mapping.len() {
+                self.schema_mapping.remove(idx);
+                self.reload_schema_mapping_view();
+            }
+        }
+    }
+
+    pub(super) fn add_owner_mapping(&mut self, _: nwg::EventData) {
+        let from = self.c.owner_from_input.text().trim().to_string();
+        let to = self.c.owner_to_input.text().trim().to_string();
+        if from.is_empty() || to.is_empty() {
+            return;
+        }
+        if let Err(msg) = self.check_owner_mapping(&from, &to) {
+            nwg::modal_error_message(&self.c.window, "Restore options", &msg);
+            return;
+        }
+        self.owner_mapping.push((from, to));
+        self.c.owner_from_input.set_text("");
+        self.c.owner_to_input.set_text("");
+        self.reload_owner_mapping_view();
+    }
+
+    pub(super) fn remove_owner_mapping(&mut self, _: nwg::EventData) {
+        if let Some(idx) = self.c.owner_mapping_view.selected_item() {
+            if idx < self.owner_mapping.len() {
+                self.owner_mapping.remove(idx);
+                self.reload_owner_mapping_view();
+            }
+        }
+    }
+
+    pub(super) fn apply(&mut self, _: nwg::EventData) {
+        let options = RestoreOptions {
+            schema_mapping: self.schema_mapping.clone(),
+            owner_mapping: self.owner_mapping.clone(),
+            keep_temp_dir: self.c.keep_temp_dir_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            no_owner: self.c.no_owner_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            no_privileges: self.c.no_privileges_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            terminate_connections: self.c.terminate_connections_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            replace_existing: self.c.replace_existing_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            analyze: self.c.analyze_checkbox.check_state() == nwg::CheckBoxState::Checked,
+            verify_row_counts: self.c.verify_row_counts_checkbox.check_state() == nwg::CheckBoxState::Checked,
+        };
+        self.result = RestoreOptionsDialogResult::new(options);
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn check_schema_mapping(&self, from: &str, to: &str) -> Result<(), String> {
+        for name in vec!(from, to) {
+            let lower = name.to_lowercase();
+            if "dbo" == lower || "guest" == lower {
+                return Err(format!("Schema '{}' cannot be remapped", name));
+            }
+        }
+        if from.to_lowercase() == to.to_lowercase() {
+            return Err(format!("Schema '{}' is mapped to itself", from));
+        }
+        for (existing_from, existing_to) in self.schema_mapping.iter() {
+            if existing_from.to_lowercase() == from.to_lowercase() {
+                return Err(format!("Schema '{}' is already mapped", from));
+            }
+            if existing_to.to_lowercase() == to.to_lowercase() {
+                return Err(format!("Schema '{}' is already a mapping target", to));
+            }
+        }
+        Ok(())
+    }
+
+    // role names are case-sensitive, several owners can be mapped to the same role
+    fn check_owner_mapping(&self, from: &str, to: &str) -> Result<(), String> {
+        if from == to {
+            return Err(format!("Owner '{}' is mapped to itself", from));
+        }
+        if self.owner_mapping.iter().any(|(existing_from, _)| existing_from == from) {
+            return Err(format!("Owner '{}' is already mapped", from));
+        }
+        Ok(())
+    }
+
+    fn reload_owner_mapping_view(&self) {
+        self.c.owner_mapping_view.clear();
+        for (from, to) in self.owner_mapping.iter() {
+            self.c.owner_mapping_view.insert_items_row(None, &[from, to]);
+        }
+    }
+
+    fn reload_schema_mapping_view(&self) {
+        self.c.schema_mapping_view.clear();
+        for (from, to) in self.schema_mapping.iter() {
+            self.c.schema_mapping_view.insert_items_row(None, &[from, to]);
+        }
+    }
+}
+
+impl ui::PopupDialog<RestoreOptionsDialogArgs, RestoreOptionsDialogResult> for RestoreOptionsDialog {
+    fn popup(args: RestoreOptionsDialogArgs) -> ui::PopupJoinHandle<RestoreOptionsDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.schema_mapping = self.args.options.schema_mapping.clone();
+        self.reload_schema_mapping_view();
+        self.owner_mapping = self.args.options.owner_mapping.clone();
+        self.reload_owner_mapping_view();
+        if self.args.options.no_owner {
+            self.c.no_owner_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        if self.args.options.no_privileges {
+            self.c.no_privileges_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        if self.args.options.terminate_connections {
+            self.c.terminate_connections_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        if self.args.options.replace_existing {
+            self.c.replace_existing_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        if self.args.options.analyze {
+            self.c.analyze_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        if self.args.options.verify_row_counts {
+            self.c.verify_row_counts_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        if self.args.options.keep_temp_dir {
+            self.c.keep_temp_dir_checkbox.set_check_state(nwg::CheckBoxState::Checked);
+        }
+        self.result = RestoreOptionsDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> RestoreOptionsDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct RestoreOptionsDialogEvents {
+    pub(super) events: Vec<ui::Event<RestoreOptionsDialog>>
+}
+
+impl ui::Events<RestoreOptionsDialogControls> for RestoreOptionsDialogEvents {
+    fn build(&mut self, c: &RestoreOptionsDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(RestoreOptionsDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(RestoreOptionsDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.schema_add_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(RestoreOptionsDialog::add_schema_mapping)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.schema_remove_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(RestoreOptionsDialog::remove_schema_mapping)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.owner_add_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(RestoreOptionsDialog::add_owner_mapping)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.owner_remove_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(RestoreOptionsDialog::remove_owner_mapping)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.ok_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(RestoreOptionsDialog::apply)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.cancel_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(RestoreOptionsDialog::close)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ScheduleDialogControls {
+    layout: ScheduleDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) name_label: nwg::Label,
+    pub(super) name_input: nwg::TextInput,
+    pub(super) scheduler_label: nwg::Label,
+    pub(super) scheduler_combo: nwg::ComboBox<String>,
+    pub(super) frequency_label: nwg::Label,
+    pub(super) frequency_combo: nwg::ComboBox<String>,
+    pub(super) day_label: nwg::Label,
+    pub(super) day_combo: nwg::ComboBox<String>,
+    pub(super) time_label: nwg::Label,
+    pub(super) time_input: nwg::TextInput,
+    pub(super) cron_label: nwg::Label,
+    pub(super) cron_input: nwg::TextInput,
+    pub(super) note_label: nwg::Label,
+
+    pub(super) create_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for ScheduleDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((420, 320))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Schedule backup")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Task name:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.name_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.name_input)?;
+        nwg::Label::builder()
+            .text("Run with:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.scheduler_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                "Windows Task Scheduler".to_string(),
+                "Built-in scheduler".to_string(),
+            ))
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.scheduler_combo)?;
+        nwg::Label::builder()
+            .text("Frequency:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.frequency_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                "Daily".to_string(),
+                "Weekly".to_string(),
+            ))
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.frequency_combo)?;
+        nwg::Label::builder()
+            .text("Day of week:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.day_label)?;
+        nwg::ComboBox::builder()
+            .collection(vec!(
+                "Monday".to_string(),
+                "Tuesday".to_string(),
+                "Wednesday".to_string(),
+                "Thursday".to_string(),
+                "Friday".to_string(),
+                "Saturday".to_string(),
+                "Sunday".to_string(),
+            ))
+            .selected_index(Some(0))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.day_combo)?;
+        nwg::Label::builder()
+            .text("Start time:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.time_label)?;
+        nwg::TextInput::builder()
+            .text("02:00")
+            .placeholder_text(Some("HH:MM"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.time_input)?;
+        nwg::Label::builder()
+            .text("Cron expression:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.cron_label)?;
+        nwg::TextInput::builder()
+            .text("0 2 * * *")
+            .placeholder_text(Some("MIN HOUR DAY MONTH WEEKDAY"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cron_input)?;
+        nwg::Label::builder()
+            .text("")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.note_label)?;
+
+        nwg::Button::builder()
+            .text("Schedule")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.create_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.name_input)
+            .control(&self.scheduler_combo)
+            .control(&self.frequency_combo)
+            .control(&self.day_combo)
+            .control(&self.time_input)
+            .control(&self.cron_input)
+            .control(&self.create_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+
+use super::*;
+use nwg::EventData;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+const DAYS: [&str; 7] = ["MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+#[derive(Default)]
+pub struct ScheduleDialog {
+    pub(super) c: ScheduleDialogControls,
+
+    args: ScheduleDialogArgs,
+    result: ScheduleDialogResult,
+}
+
+impl ScheduleDialog {
+    pub(super) fn on_scheduler_changed(&mut self, _: nwg::EventData) {
+        let builtin = self.is_builtin_scheduler();
+        self.c.frequency_combo.set_enabled(!builtin);
+        self.c.time_input.set_enabled(!builtin);
+        self.c.cron_input.set_enabled(builtin);
+        self.on_frequency_changed(nwg::EventData::NoData);
+        if builtin {
+            self.c.note_label.set_text("Backup runs only while the application is running.");
+        } else {
+            self.c.note_label.set_text("Scheduled backups read DB password from pgpass file.");
+        }
+    }
+
+    pub(super) fn on_frequency_changed(&mut self, _: nwg::EventData) {
+        let weekly = Some(1) == self.c.frequency_combo.selection();
+        self.c.day_combo.set_enabled(weekly && !self.is_builtin_scheduler());
+    }
+
+    pub(super) fn create_task(&mut self, _: nwg::EventData) {
+        let name = self.c.name_input.text().trim().to_string();
+        if let Err(msg) = Self::check_name(&name) {
+            nwg::modal_error_message(&self.c.window, "Schedule backup", &msg);
+            return;
+        }
+        let mut job = self.args.job.clone();
+        job.name = name;
+
+        if self.is_builtin_scheduler() {
+            let cron = self.c.cron_input.text().trim().to_string();
+            if let Err(msg) = CronSchedule::parse(&cron) {
+                nwg::modal_error_message(&self.c.window, "Schedule backup", &msg);
+                return;
+            }
+            job.cron = cron;
+            self.result = ScheduleDialogResult::new(job);
+            self.close(nwg::EventData::NoData);
+            return;
+        }
+
+        let time = self.c.time_input.text().trim().to_string();
+        if let Err(msg) = Self::check_time(&time) {
+            nwg::modal_error_message(&self.c.window, "Schedule backup", &msg);
+            return;
+        }
+        let day = match self.c.frequency_combo.selection() {
+            Some(1) => Some(DAYS[self.c.day_combo.selection().unwrap_or(0)]),
+            _ => None
+        };
+        if let Err(msg) = Self::run_schtasks(&job, &time, day) {
+            nwg::modal_error_message(&self.c.window, "Schedule backup", &msg);
+            return;
+        }
+        self.result = ScheduleDialogResult::new(job);
+        self.close(nwg::EventData::NoData);
+    }
+
+    fn is_builtin_scheduler(&self) -> bool {
+        Some(1) == self.c.scheduler_combo.selection()
+    }
+
+    fn check_name(name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Task name must be specified".to_string());
+        }
+        if name.chars().any(|ch| "\\/:*?\"<>|".contains(ch)) {
+            return Err(format!("Task name contains invalid characters: {}", name));
+        }
+        Ok(())
+    }
+
+    fn check_time(time: &str) -> Result<(), String> {
+        let parts: Vec<&str> = time.split(':').collect();
+        let valid_time = 2 == parts.len() &&
+            parts.iter().all(|p| 2 == p.len() && p.chars().all(|ch| ch.is_ascii_digit())) &&
+            parts[0].parse::<u32>().unwrap_or(24) < 24 &&
+            parts[1].parse::<u32>().unwrap_or(60) < 60;
+        if !valid_time {
+            return Err(format!("Invalid start time: {}, expected format: HH:MM", time));
+        }
+        Ok(())
+    }
+
+    fn run_schtasks(job: &BackupJob, time: &str, day: Option<&str>) -> Result<(), String> {
+        let cur_exe = match env::current_exe() {
+            Ok(path) => path,
+            Err(e) => return Err(format!("Error resolving executable path: {}", e))
+        };
+        let task_cmd = format!("\"{}\" backup --job \"{}\"", cur_exe.to_string_lossy(), &job.name);
+        let mut cmd = Command::new("schtasks");
+        cmd.arg("/Create")
+            .arg("/F")
+            .arg("/TN").arg(format!("WiltonDB\\{}", &job.name))
+            .arg("/TR").arg(&task_cmd)
+            .arg("/ST").arg(time)
+            .creation_flags(CREATE_NO_WINDOW);
+        match day {
+            Some(day) => cmd.arg("/SC").arg("WEEKLY").arg("/D").arg(day),
+            None => cmd.arg("/SC").arg("DAILY")
+        };
+        let output = match cmd.output() {
+            Ok(output) => output,
+            Err(e) => return Err(format!("schtasks process spawn failure: {}", e))
+        };
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(format!("Error creating scheduled task:\r\n{}{}", stdout.trim(), stderr.trim()));
+        }
+        Ok(())
+    }
+}
+
+impl ui::PopupDialog<ScheduleDialogArgs, ScheduleDialogResult> for ScheduleDialog {
+    fn popup(args: ScheduleDialogArgs) -> ui::PopupJoinHandle<ScheduleDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.c.name_input.set_text(&format!("Backup {}", &self.args.job.dbname));
+        self.on_scheduler_changed(nwg::EventData::NoData);
+        self.result = ScheduleDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> ScheduleDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
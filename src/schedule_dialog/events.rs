@@ -0,0 +1,61 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ScheduleDialogEvents {
+    pub(super) events: Vec<ui::Event<ScheduleDialog>>
+}
+
+impl ui::Events<ScheduleDialogControls> for ScheduleDialogEvents {
+    fn build(&mut self, c: &ScheduleDialogControls) -> Result<(), nwg::NwgError> {
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnWindowClose)
+            .handler(ScheduleDialog::close)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.window)
+            .event(nwg::Event::OnResizeEnd)
+            .handler(ScheduleDialog::on_resize)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.scheduler_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(ScheduleDialog::on_scheduler_changed)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.frequency_combo)
+            .event(nwg::Event::OnComboxBoxSelection)
+            .handler(ScheduleDialog::on_frequency_changed)
+            .build(&mut self.events)?;
+
+        ui::event_builder()
+            .control(&c.create_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(ScheduleDialog::create_task)
+            .build(&mut self.events)?;
+        ui::event_builder()
+            .control(&c.cancel_button)
+            .event(nwg::Event::OnButtonClick)
+            .handler(ScheduleDialog::close)
+            .build(&mut self.events)?;
+
+        Ok(())
+    }
+}
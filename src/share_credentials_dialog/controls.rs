@@ -0,0 +1,118 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[derive(Default)]
+pub(super) struct ShareCredentialsDialogControls {
+    layout: ShareCredentialsDialogLayout,
+
+    pub(super) font_normal: nwg::Font,
+
+    pub(super) icon: nwg::Icon,
+    pub(super) window: nwg::Window,
+
+    pub(super) share_label: nwg::Label,
+    pub(super) share_input: nwg::TextInput,
+    pub(super) username_label: nwg::Label,
+    pub(super) username_input: nwg::TextInput,
+    pub(super) password_label: nwg::Label,
+    pub(super) password_input: nwg::TextInput,
+
+    pub(super) connect_button: nwg::Button,
+    pub(super) cancel_button: nwg::Button,
+}
+
+impl ui::Controls for ShareCredentialsDialogControls {
+    fn build(&mut self) -> Result<(), nwg::NwgError> {
+        nwg::Font::builder()
+            .size(ui::font_size_builder()
+                .normal()
+                .build())
+            .build(&mut self.font_normal)?;
+
+        nwg::Icon::builder()
+            .source_embed(Some(&nwg::EmbedResource::load(None)
+                .expect("Error loading embedded resource")))
+            .source_embed_id(2)
+            .build(&mut self.icon)?;
+
+        nwg::Window::builder()
+            .size((400, 160))
+            .icon(Some(&self.icon))
+            .center(true)
+            .title("Network share credentials")
+            .build(&mut self.window)?;
+
+        nwg::Label::builder()
+            .text("Share:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.share_label)?;
+        nwg::TextInput::builder()
+            .font(Some(&self.font_normal))
+            .readonly(true)
+            .parent(&self.window)
+            .build(&mut self.share_input)?;
+        nwg::Label::builder()
+            .text("Username:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.username_label)?;
+        nwg::TextInput::builder()
+            .placeholder_text(Some("DOMAIN\\user"))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.username_input)?;
+        nwg::Label::builder()
+            .text("Password:")
+            .font(Some(&self.font_normal))
+            .h_align(nwg::HTextAlign::Left)
+            .parent(&self.window)
+            .build(&mut self.password_label)?;
+        nwg::TextInput::builder()
+            .password(Some('*'))
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.password_input)?;
+
+        nwg::Button::builder()
+            .text("Connect")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.connect_button)?;
+        nwg::Button::builder()
+            .text("Cancel")
+            .font(Some(&self.font_normal))
+            .parent(&self.window)
+            .build(&mut self.cancel_button)?;
+
+        self.layout.build(&self)?;
+
+        Ok(())
+    }
+
+    fn update_tab_order(&self) {
+        ui::tab_order_builder()
+            .control(&self.username_input)
+            .control(&self.password_input)
+            .control(&self.connect_button)
+            .control(&self.cancel_button)
+            .build();
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use nwg::EventData;
+
+#[derive(Default)]
+pub struct ShareCredentialsDialog {
+    pub(super) c: ShareCredentialsDialogControls,
+
+    args: ShareCredentialsDialogArgs,
+    result: ShareCredentialsDialogResult,
+}
+
+impl ShareCredentialsDialog {
+    pub(super) fn connect(&mut self, _: nwg::EventData) {
+        let username = self.c.username_input.text().trim().to_string();
+        let password = SecretString::from(self.c.password_input.text());
+        if let Err(e) = connect_share(&self.args.share, &username, password.expose()) {
+            nwg::modal_error_message(&self.c.window, "Network share", &format!(
+                "Error connecting to network share: {}, message: {}", &self.args.share, e));
+            return;
+        }
+        self.result = ShareCredentialsDialogResult::connected();
+        self.close(nwg::EventData::NoData);
+    }
+}
+
+impl ui::PopupDialog<ShareCredentialsDialogArgs, ShareCredentialsDialogResult> for ShareCredentialsDialog {
+    fn popup(args: ShareCredentialsDialogArgs) -> ui::PopupJoinHandle<ShareCredentialsDialogResult> {
+        let join_handle = thread::spawn(move || {
+            let data = Self {
+                args,
+                ..Default::default()
+            };
+            let mut dialog = Self::build_ui(data).expect("Failed to build UI");
+            nwg::dispatch_thread_events();
+            dialog.result()
+        });
+        ui::PopupJoinHandle::from(join_handle)
+    }
+
+    fn init(&mut self) {
+        self.c.share_input.set_text(&self.args.share);
+        self.result = ShareCredentialsDialogResult::cancelled();
+        ui::shake_window(&self.c.window);
+    }
+
+    fn result(&mut self) -> ShareCredentialsDialogResult {
+        self.result.clone()
+    }
+
+    fn close(&mut self, _: nwg::EventData) {
+        self.args.notify_parent();
+        self.c.window.set_visible(false);
+        nwg::stop_thread_dispatch();
+    }
+
+    fn on_resize(&mut self, _: EventData) {
+        self.c.update_tab_order();
+    }
+}
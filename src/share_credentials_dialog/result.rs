@@ -0,0 +1,35 @@
+/*
+ * Copyright 2023, WiltonDB Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// credentials are used only to connect the share and are not returned
+#[derive(Default, Clone)]
+pub struct ShareCredentialsDialogResult {
+    pub cancelled: bool,
+}
+
+impl ShareCredentialsDialogResult {
+    pub fn connected() -> Self {
+        Self {
+            cancelled: false
+        }
+    }
+
+    pub fn cancelled() -> Self {
+        Self {
+            cancelled: true
+        }
+    }
+}